@@ -59,6 +59,21 @@ impl ChartConfig {
         builder.location(ChartLocation::sys_inline());
         builder
     }
+
+    /// The sys chart bundled with the installer, to be installed in `namespace`
+    ///
+    /// Unlike [`sys_builder`](ChartConfig::sys_builder) this can't fail, it
+    /// sets nothing but the required fields.
+    pub fn sys_inline(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            location: ChartLocation::sys_inline(),
+            version: None,
+            name: SYS_CHART_NAME.to_string(),
+            values: vec![],
+            string_values: vec![],
+        }
+    }
 }
 
 impl ChartConfigBuilder {
@@ -1,14 +1,21 @@
+#[cfg(feature = "helm-checks")]
 mod chart;
+#[cfg(feature = "helm-checks")]
 mod location;
 
+#[cfg(feature = "helm-checks")]
 pub use chart::*;
+#[cfg(feature = "helm-checks")]
 pub use error::*;
+#[cfg(feature = "helm-checks")]
 pub use location::*;
 
 pub(crate) const SYS_CHART_NAME: &str = "fluvio-sys";
 pub(crate) const APP_CHART_NAME: &str = "fluvio";
+#[cfg(feature = "helm-checks")]
 pub(crate) const DEFAULT_HELM_VERSION: &str = "3.3.4";
 
+#[cfg(feature = "helm-checks")]
 mod error {
 
     use std::io::Error as IoError;
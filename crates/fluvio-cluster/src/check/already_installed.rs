@@ -0,0 +1,265 @@
+//! Checks that Fluvio is not already installed where it would conflict
+//!
+//! Every release of the Fluvio app chart counts as an installation, in any
+//! namespace. Installing next to one fails unless it is reused or multiple
+//! installations are allowed.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use semver::Version;
+
+use crate::charts::APP_CHART_NAME;
+use crate::render::ProgressRenderer;
+
+use super::chart;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::{
+    format_installations, CheckResult, CheckStatus, ClusterCheck, FluvioClusterComponent,
+    FluvioInstallation, UnrecoverableCheckStatus,
+};
+
+#[derive(Debug)]
+pub(crate) struct AlreadyInstalled {
+    namespace: String,
+    reuse: bool,
+    allow_multiple: bool,
+    /// the version being installed, suggests upgrading older installations
+    platform_version: Option<Version>,
+    /// helm is run against this context, `None` for the current one
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+impl AlreadyInstalled {
+    /// check for existing installations before installing into `namespace`
+    pub(crate) fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            reuse: false,
+            allow_multiple: false,
+            platform_version: None,
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+
+    /// the platform version the installer is going to install
+    pub(crate) fn with_platform_version(mut self, version: Version) -> Self {
+        self.platform_version = Some(version);
+        self
+    }
+
+    /// an existing installation in the target namespace is going to be upgraded or reused
+    pub(crate) fn with_reuse(mut self, reuse: bool) -> Self {
+        self.reuse = reuse;
+        self
+    }
+
+    /// installations in other namespaces are deliberate and should not block
+    pub(crate) fn with_allow_multiple(mut self, allow_multiple: bool) -> Self {
+        self.allow_multiple = allow_multiple;
+        self
+    }
+
+    fn evaluate(&self, installations: Vec<FluvioInstallation>) -> CheckStatus {
+        if installations.is_empty() {
+            return CheckStatus::pass("Previous fluvio installation not found");
+        }
+
+        let (same, others): (Vec<_>, Vec<_>) = installations
+            .iter()
+            .partition(|installation| installation.namespace == self.namespace);
+
+        if !(others.is_empty() || self.allow_multiple) {
+            return CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::ConflictingInstallations {
+                    namespace: self.namespace.clone(),
+                    installations,
+                },
+            );
+        }
+        if let (Some(existing), false) = (same.first(), self.reuse) {
+            return CheckStatus::Unrecoverable(UnrecoverableCheckStatus::AlreadyInstalled {
+                name: existing.name.clone(),
+                namespace: existing.namespace.clone(),
+                version: existing.version.clone(),
+                target: self
+                    .platform_version
+                    .as_ref()
+                    .map(|version| version.to_string()),
+            });
+        }
+
+        match same.first() {
+            Some(existing) => CheckStatus::pass(format!(
+                "Fluvio {} found in namespace {}, reusing it",
+                existing.version, existing.namespace
+            )),
+            None => CheckStatus::pass(format!(
+                "Fluvio installed in other namespaces: {}",
+                format_installations(&installations)
+            )),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for AlreadyInstalled {
+    /// Checks that Fluvio is not already installed in a conflicting namespace
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        // no namespace filter lists releases across all namespaces
+        let app_charts = chart::installed_releases(
+            &*self.env,
+            APP_CHART_NAME,
+            None,
+            self.kube_context.as_deref(),
+        )
+        .await?;
+        let installations = app_charts
+            .into_iter()
+            .map(|chart| FluvioInstallation {
+                name: chart.name,
+                namespace: chart.namespace,
+                version: chart.app_version,
+            })
+            .collect();
+        Ok(self.evaluate(installations))
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![
+            FluvioClusterComponent::Helm,
+            FluvioClusterComponent::Kubernetes,
+        ]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::kubectl_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "already-installed"
+    }
+
+    fn label(&self) -> &str {
+        "Fluvio installation"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = namespace.to_string();
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+
+    fn description(&self) -> &str {
+        "Fluvio is not already installed in the Kubernetes cluster"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installation(namespace: &str, version: &str) -> FluvioInstallation {
+        FluvioInstallation {
+            name: "fluvio".to_string(),
+            namespace: namespace.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_already_installed_none() {
+        let check = AlreadyInstalled::new("fluvio");
+        assert!(matches!(check.evaluate(vec![]), CheckStatus::Pass(_)));
+    }
+
+    #[test]
+    fn test_already_installed_same_namespace() {
+        let installed = vec![installation("fluvio", "0.10.0")];
+
+        let check = AlreadyInstalled::new("fluvio");
+        let status = match check.evaluate(installed.clone()) {
+            CheckStatus::Unrecoverable(status) => status,
+            other => panic!("expected failure, got {other:?}"),
+        };
+        assert_eq!(
+            status.to_string(),
+            "Fluvio 0.10.0 is already installed as release fluvio in namespace fluvio"
+        );
+        assert!(status
+            .suggestion()
+            .unwrap()
+            .contains("fluvio cluster delete"));
+
+        let check = AlreadyInstalled::new("fluvio").with_platform_version(Version::new(0, 11, 0));
+        let status = match check.evaluate(installed.clone()) {
+            CheckStatus::Unrecoverable(status) => status,
+            other => panic!("expected failure, got {other:?}"),
+        };
+        assert_eq!(
+            status.suggestion().unwrap(),
+            "Upgrade it to 0.11.0 with 'fluvio cluster upgrade'"
+        );
+
+        let check = AlreadyInstalled::new("fluvio").with_reuse(true);
+        match check.evaluate(installed) {
+            CheckStatus::Pass(msg) => assert!(msg.contains("0.10.0")),
+            other => panic!("expected pass, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_already_installed_other_namespace() {
+        let installed = vec![installation("team-a", "0.10.0")];
+
+        let check = AlreadyInstalled::new("fluvio").with_reuse(true);
+        assert!(matches!(
+            check.evaluate(installed.clone()),
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::ConflictingInstallations { .. })
+        ));
+
+        let check = AlreadyInstalled::new("fluvio").with_allow_multiple(true);
+        assert!(matches!(check.evaluate(installed), CheckStatus::Pass(_)));
+    }
+
+    #[test]
+    fn test_already_installed_lists_every_release() {
+        let installed = vec![
+            installation("fluvio", "0.10.0"),
+            installation("team-a", "0.9.33"),
+        ];
+
+        let check = AlreadyInstalled::new("fluvio").with_reuse(true);
+        let status = match check.evaluate(installed.clone()) {
+            CheckStatus::Unrecoverable(status) => status,
+            other => panic!("expected failure, got {other:?}"),
+        };
+        assert_eq!(
+            status.to_string(),
+            "Fluvio is already installed (0.10.0 in fluvio, 0.9.33 in team-a), \
+             cannot install into namespace fluvio"
+        );
+        assert!(status
+            .suggestion()
+            .unwrap()
+            .contains("--allow-multiple-installs"));
+
+        let check = AlreadyInstalled::new("fluvio")
+            .with_reuse(true)
+            .with_allow_multiple(true);
+        assert!(matches!(check.evaluate(installed), CheckStatus::Pass(_)));
+    }
+}
@@ -0,0 +1,241 @@
+//! Checks that the kubectl and helm binaries are installed
+//!
+//! A missing binary otherwise shows up in every check that runs it, as an
+//! IO error or an opaque helm client error. Checking for it once, before the
+//! other checks, lets [`ClusterChecker::run`] skip the checks that need it.
+//!
+//! [`ClusterChecker::run`]: super::ClusterChecker::run
+
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use crate::render::ProgressRenderer;
+
+use super::environment::{CheckEnvironment, Environment};
+use super::{CheckResult, CheckStatus, ClusterCheck, FluvioClusterComponent, UnrecoverableCheckStatus};
+
+const KUBECTL: &str = "kubectl";
+const HELM: &str = "helm";
+
+/// Fails if kubectl is neither in `PATH` nor at the given path
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ClusterChecker, KubectlBinary};
+/// let check = KubectlBinary::at("/opt/kubernetes/bin/kubectl");
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct KubectlBinary {
+    /// kubectl to look for instead of searching `PATH`
+    pub path: Option<PathBuf>,
+    env: Environment,
+}
+
+impl KubectlBinary {
+    /// Looks for kubectl at `path` instead of searching `PATH`
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for KubectlBinary {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        Ok(binary_status(&*self.env, KUBECTL, self.path.as_deref()))
+    }
+
+    fn component(&self) -> Option<FluvioClusterComponent> {
+        Some(FluvioClusterComponent::KubectlBinary)
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "kubectl-binary"
+    }
+
+    fn label(&self) -> &str {
+        "Kubectl binary"
+    }
+
+    fn description(&self) -> &str {
+        "kubectl is installed"
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Fails if helm is neither in `PATH` nor at the given path
+#[derive(Debug, Clone, Default)]
+pub struct HelmBinary {
+    /// helm to look for instead of searching `PATH`
+    pub path: Option<PathBuf>,
+    env: Environment,
+}
+
+impl HelmBinary {
+    /// Looks for helm at `path` instead of searching `PATH`
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..Default::default()
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for HelmBinary {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        Ok(binary_status(&*self.env, HELM, self.path.as_deref()))
+    }
+
+    fn component(&self) -> Option<FluvioClusterComponent> {
+        Some(FluvioClusterComponent::HelmBinary)
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "helm-binary"
+    }
+
+    fn label(&self) -> &str {
+        "Helm binary"
+    }
+
+    fn description(&self) -> &str {
+        "helm is installed"
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Passes if `binary` is at `path`, or in `PATH` when no path is given
+fn binary_status(env: &dyn CheckEnvironment, binary: &str, path: Option<&Path>) -> CheckStatus {
+    let (found, searched) = match path {
+        Some(path) => (
+            path.is_file().then(|| path.to_path_buf()),
+            path.display().to_string(),
+        ),
+        None => (env.find_binary(binary), "PATH".to_string()),
+    };
+    match found {
+        Some(found) => CheckStatus::pass(format!("{binary} found at {}", found.display())),
+        None => {
+            debug!(binary, searched, "binary not found");
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::BinaryNotFound {
+                binary: binary.to_string(),
+                searched,
+            })
+        }
+    }
+}
+
+/// First executable named `binary` in `PATH`
+pub(crate) fn find_in_path(binary: &str) -> Option<PathBuf> {
+    env::var_os("PATH").and_then(|paths| find_in_paths(binary, &paths))
+}
+
+/// First executable named `binary` in the directories of a `PATH` like list
+fn find_in_paths(binary: &str, paths: &OsStr) -> Option<PathBuf> {
+    let cwd = env::current_dir().ok()?;
+    which::which_in(binary, Some(paths), cwd).ok()
+}
+
+/// Command that installs `binary` on this platform
+pub(crate) fn install_command(binary: &str) -> Option<&'static str> {
+    let command = match (binary, env::consts::OS) {
+        (KUBECTL, "macos") => "brew install kubectl",
+        (KUBECTL, "windows") => "winget install -e --id Kubernetes.kubectl",
+        (KUBECTL, _) => "sudo snap install kubectl --classic",
+        (HELM, "macos") => "brew install helm",
+        (HELM, "windows") => "winget install Helm.Helm",
+        (HELM, _) => {
+            "curl -fsSL https://raw.githubusercontent.com/helm/helm/main/scripts/get-helm-3 | bash"
+        }
+        _ => return None,
+    };
+    Some(command)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::check::CheckSuggestion;
+
+    use super::*;
+
+    #[test]
+    fn test_find_in_paths() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let empty = tempfile::tempdir().expect("temp dir");
+        let kubectl = dir
+            .path()
+            .join(format!("kubectl{}", env::consts::EXE_SUFFIX));
+        fs::write(&kubectl, "").expect("write");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&kubectl, fs::Permissions::from_mode(0o755)).expect("chmod");
+        }
+        let paths = env::join_paths([empty.path(), dir.path()]).expect("paths");
+
+        assert_eq!(find_in_paths(KUBECTL, &paths), Some(kubectl));
+        assert_eq!(find_in_paths(HELM, &paths), None);
+    }
+
+    #[fluvio_future::test]
+    async fn test_missing_binary_path() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let check = HelmBinary::at(dir.path().join("helm"));
+
+        let status = check
+            .perform_check(&ProgressRenderer::default())
+            .await
+            .expect("status");
+
+        let CheckStatus::Unrecoverable(failure) = status else {
+            panic!("helm is not in {}", dir.path().display());
+        };
+        assert!(matches!(
+            &failure,
+            UnrecoverableCheckStatus::BinaryNotFound { binary, .. } if binary == HELM
+        ));
+        let suggestion = failure.suggestion().expect("suggestion");
+        assert!(suggestion.contains(install_command(HELM).expect("command")));
+    }
+
+    #[fluvio_future::test]
+    async fn test_binary_path() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let kubectl = dir.path().join("kubectl");
+        fs::write(&kubectl, "").expect("write");
+        let check = KubectlBinary::at(kubectl);
+
+        let status = check
+            .perform_check(&ProgressRenderer::default())
+            .await
+            .expect("status");
+
+        assert!(matches!(status, CheckStatus::Pass(_)), "{status:?}");
+    }
+}
@@ -0,0 +1,415 @@
+//! Checks that the chart version to install is in its helm repository
+//!
+//! A version missing from a remote repository, or a repository that can't be
+//! reached, otherwise only fails `helm install` after the other checks passed
+//! and the system chart was installed. Asking the repository first reports
+//! the versions it does have.
+//!
+//! Also lists installed releases for the other helm checks, against the
+//! context set with [`ClusterChecker::with_kube_context`](super::ClusterChecker::with_kube_context).
+
+use std::io::ErrorKind;
+use std::process::{Command, Output};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use semver::Version;
+use serde::Deserialize;
+use serde_json::Error as JsonError;
+use tracing::debug;
+
+use crate::render::ProgressRenderer;
+
+use super::command::{self, CommandOutput};
+use super::environment::{CheckEnvironment, Environment};
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+/// How many of the available versions a failure lists
+const NEAREST_VERSIONS: usize = 4;
+
+/// Fails if `version` of `chart` is not in the helm repository at `repo_url`
+///
+/// The repository is added under the name of the chart, the same way the
+/// installer adds it before installing from a remote location.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ChartAvailability, ClusterChecker};
+/// let check = ChartAvailability::new(
+///     "fluvio",
+///     "https://charts.fluvio.io",
+///     semver::Version::new(0, 11, 0),
+/// );
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ChartAvailability {
+    /// Name of the chart, such as `fluvio`
+    pub chart: String,
+    /// URL of the helm repository
+    pub repo_url: String,
+    /// The chart version that will be installed
+    pub version: Version,
+    env: Environment,
+}
+
+impl ChartAvailability {
+    /// Looks for `version` of `chart` in the helm repository at `repo_url`
+    pub fn new(chart: impl Into<String>, repo_url: impl Into<String>, version: Version) -> Self {
+        Self {
+            chart: chart.into(),
+            repo_url: repo_url.into(),
+            version,
+            env: Environment::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for ChartAvailability {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let mut add = Command::new("helm");
+        add.args(["repo", "add", &self.chart, &self.repo_url, "--force-update"]);
+        let description = CommandOutput::from_command(&add);
+        let output = match helm_output(&*self.env, add).await {
+            Ok(output) if output.status.success() => output,
+            Ok(output) => {
+                return Ok(repo_unreachable(
+                    &self.repo_url,
+                    description.with_output(&output).stderr_summary(),
+                ))
+            }
+            Err(ClusterCheckError::CommandTimedOut { timeout, .. }) => {
+                return Ok(repo_unreachable(
+                    &self.repo_url,
+                    format!("no answer within {}s", timeout.as_secs_f32()),
+                ))
+            }
+            Err(err) => return Err(err),
+        };
+        debug!(stdout = %String::from_utf8_lossy(&output.stdout), "added chart repository");
+
+        let mut search = Command::new("helm");
+        search
+            .args(["search", "repo"])
+            .arg(format!("{0}/{0}", self.chart))
+            .args(["--versions", "--devel", "--output", "json"]);
+        let description = CommandOutput::from_command(&search);
+        let output = helm_output(&*self.env, search).await?;
+        if !output.status.success() {
+            return Err(ClusterCheckError::HelmFailed(
+                description.with_output(&output),
+            ));
+        }
+        let available = chart_versions(&self.chart, &output.stdout).map_err(|err| {
+            ClusterCheckError::Other(format!("Unable to read helm search output: {err}"))
+        })?;
+        debug!(?available, "chart versions");
+        Ok(chart_availability_status(
+            &self.chart,
+            &self.version,
+            &available,
+        ))
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::HelmBinary]
+    }
+
+    fn name(&self) -> &str {
+        "chart-availability"
+    }
+
+    fn label(&self) -> &str {
+        "Chart availability"
+    }
+
+    fn description(&self) -> &str {
+        "The chart version to install is in the helm repository"
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+fn repo_unreachable(url: &str, reason: String) -> CheckStatus {
+    CheckStatus::Unrecoverable(UnrecoverableCheckStatus::ChartRepoUnreachable {
+        url: url.to_string(),
+        reason,
+    })
+}
+
+/// Runs helm in `env` without blocking the executor, killing it after [`command::COMMAND_TIMEOUT`]
+async fn helm_output(
+    env: &dyn CheckEnvironment,
+    command: Command,
+) -> Result<Output, ClusterCheckError> {
+    let description = CommandOutput::from_command(&command);
+    env.command_output(command, command::COMMAND_TIMEOUT)
+        .await
+        .map_err(|err| match err.kind() {
+            ErrorKind::TimedOut => ClusterCheckError::CommandTimedOut {
+                command: "helm".to_string(),
+                timeout: command::COMMAND_TIMEOUT,
+            },
+            _ => ClusterCheckError::Other(format!("Unable to run {description}: {err}")),
+        })
+}
+
+/// Version of the helm client without the leading `v`, such as `3.12.0+gc9f554d`
+pub(crate) async fn helm_version(env: &dyn CheckEnvironment) -> Result<String, ClusterCheckError> {
+    let mut version = Command::new("helm");
+    version.args(["version", "--short"]);
+    let description = CommandOutput::from_command(&version);
+    let output = helm_output(env, version).await?;
+    if !output.status.success() {
+        return Err(ClusterCheckError::HelmFailed(
+            description.with_output(&output),
+        ));
+    }
+    let version = String::from_utf8_lossy(&output.stdout);
+    let version = version.trim();
+    Ok(version.strip_prefix('v').unwrap_or(version).to_string())
+}
+
+/// A release listed by `helm list --output json`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct HelmRelease {
+    pub name: String,
+    pub namespace: String,
+    pub app_version: String,
+}
+
+/// Releases named `chart` in `namespace`, or in every namespace for `None`
+///
+/// helm is run with `--kube-context` when `kube_context` is set, it otherwise
+/// lists the releases of the current context.
+pub(crate) async fn installed_releases(
+    env: &dyn CheckEnvironment,
+    chart: &str,
+    namespace: Option<&str>,
+    kube_context: Option<&str>,
+) -> Result<Vec<HelmRelease>, ClusterCheckError> {
+    let list = list_command(chart, namespace, kube_context);
+    let description = CommandOutput::from_command(&list);
+    let output = helm_output(env, list).await?;
+    if !output.status.success() {
+        return Err(ClusterCheckError::HelmFailed(
+            description.with_output(&output),
+        ));
+    }
+    serde_json::from_slice(&output.stdout)
+        .map_err(|err| ClusterCheckError::Other(format!("Unable to read helm list output: {err}")))
+}
+
+fn list_command(chart: &str, namespace: Option<&str>, kube_context: Option<&str>) -> Command {
+    let mut list = Command::new("helm");
+    list.args(["list", "--filter"])
+        .arg(format!("^{chart}$"))
+        .args(["--output", "json"]);
+    match namespace {
+        Some(namespace) => list.args(["--namespace", namespace]),
+        None => list.arg("--all-namespaces"),
+    };
+    if let Some(context) = kube_context {
+        list.args(["--kube-context", context]);
+    }
+    list
+}
+
+/// One line of `helm search repo --output json`
+#[derive(Debug, Deserialize)]
+struct SearchResult {
+    name: String,
+    version: String,
+}
+
+/// Versions of `chart` in the output of `helm search repo --output json`
+///
+/// The search matches on substrings, results for other charts of the
+/// repository are left out. So are versions that are not semver.
+fn chart_versions(chart: &str, output: &[u8]) -> Result<Vec<Version>, JsonError> {
+    let results: Vec<SearchResult> = serde_json::from_slice(output)?;
+    let name = format!("{chart}/{chart}");
+    Ok(results
+        .into_iter()
+        .filter(|result| result.name == name)
+        .filter_map(|result| Version::parse(result.version.trim_start_matches('v')).ok())
+        .collect())
+}
+
+fn chart_availability_status(chart: &str, version: &Version, available: &[Version]) -> CheckStatus {
+    if available.contains(version) {
+        return CheckStatus::pass(format!("{chart} {version} is in the repository"));
+    }
+    CheckStatus::Unrecoverable(UnrecoverableCheckStatus::ChartVersionNotFound {
+        chart: chart.to_string(),
+        version: version.to_string(),
+        available: nearest_versions(version, available)
+            .iter()
+            .map(Version::to_string)
+            .collect(),
+    })
+}
+
+/// Up to [`NEAREST_VERSIONS`] of `available` around `version`, in ascending order
+fn nearest_versions(version: &Version, available: &[Version]) -> Vec<Version> {
+    let mut sorted = available.to_vec();
+    sorted.sort();
+    sorted.dedup();
+    let position = sorted.partition_point(|available| available < version);
+    let start = position
+        .saturating_sub(NEAREST_VERSIONS / 2)
+        .min(sorted.len().saturating_sub(NEAREST_VERSIONS));
+    sorted
+        .into_iter()
+        .skip(start)
+        .take(NEAREST_VERSIONS)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::CheckSuggestion;
+
+    use super::*;
+
+    fn versions(versions: &[&str]) -> Vec<Version> {
+        versions
+            .iter()
+            .map(|version| Version::parse(version).expect("version"))
+            .collect()
+    }
+
+    #[test]
+    fn test_chart_versions() {
+        let output = br#"[
+            {"name":"fluvio/fluvio","version":"0.11.1","app_version":"0.11.1","description":""},
+            {"name":"fluvio/fluvio","version":"v0.11.0","app_version":"0.11.0","description":""},
+            {"name":"fluvio/fluvio","version":"latest","app_version":"","description":""},
+            {"name":"fluvio/fluvio-sys","version":"0.12.0","app_version":"0.12.0","description":""}
+        ]"#;
+
+        assert_eq!(
+            chart_versions("fluvio", output).expect("json"),
+            versions(&["0.11.1", "0.11.0"])
+        );
+        assert!(chart_versions("fluvio", b"[]").expect("json").is_empty());
+    }
+
+    #[test]
+    fn test_list_command() {
+        let args = |command: Command| -> Vec<String> {
+            command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect()
+        };
+
+        assert_eq!(
+            args(list_command("fluvio-sys", None, None)),
+            [
+                "list",
+                "--filter",
+                "^fluvio-sys$",
+                "--output",
+                "json",
+                "--all-namespaces"
+            ]
+        );
+        assert_eq!(
+            args(list_command("fluvio", Some("fluvio"), Some("kind-fluvio"))),
+            [
+                "list",
+                "--filter",
+                "^fluvio$",
+                "--output",
+                "json",
+                "--namespace",
+                "fluvio",
+                "--kube-context",
+                "kind-fluvio"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_helm_release() {
+        let output = br#"[{"name":"fluvio","namespace":"fluvio","revision":"2","updated":"2026-10-01 10:00:00.0 +0000 UTC","status":"deployed","chart":"fluvio-app-0.11.0","app_version":"0.11.0"}]"#;
+
+        let releases: Vec<HelmRelease> = serde_json::from_slice(output).expect("json");
+        assert_eq!(
+            releases,
+            [HelmRelease {
+                name: "fluvio".to_string(),
+                namespace: "fluvio".to_string(),
+                app_version: "0.11.0".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_nearest_versions() {
+        let available = versions(&[
+            "0.9.0", "0.10.0", "0.10.1", "0.10.2", "0.11.0", "0.11.1", "0.12.0",
+        ]);
+
+        assert_eq!(
+            nearest_versions(&Version::new(0, 10, 5), &available),
+            versions(&["0.10.1", "0.10.2", "0.11.0", "0.11.1"])
+        );
+        assert_eq!(
+            nearest_versions(&Version::new(1, 0, 0), &available),
+            versions(&["0.10.2", "0.11.0", "0.11.1", "0.12.0"])
+        );
+        assert_eq!(
+            nearest_versions(&Version::new(0, 1, 0), &available),
+            versions(&["0.9.0", "0.10.0", "0.10.1", "0.10.2"])
+        );
+        assert_eq!(
+            nearest_versions(&Version::new(0, 1, 0), &versions(&["0.2.0"])),
+            versions(&["0.2.0"])
+        );
+    }
+
+    #[test]
+    fn test_chart_version_available() {
+        let available = versions(&["0.11.0", "0.11.1"]);
+
+        assert!(matches!(
+            chart_availability_status("fluvio", &Version::new(0, 11, 1), &available),
+            CheckStatus::Pass(_)
+        ));
+    }
+
+    #[test]
+    fn test_chart_version_not_found() {
+        let available = versions(&["0.11.0", "0.11.1", "0.12.0"]);
+
+        let CheckStatus::Unrecoverable(failure) =
+            chart_availability_status("fluvio", &Version::new(0, 11, 2), &available)
+        else {
+            panic!("0.11.2 is not in the repository");
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Chart fluvio 0.11.2 is not in the repository. Nearest versions: 0.11.0, 0.11.1, 0.12.0"
+        );
+        assert!(failure.suggestion().is_some());
+
+        let CheckStatus::Unrecoverable(failure) =
+            chart_availability_status("fluvio", &Version::new(0, 11, 2), &[])
+        else {
+            panic!("the repository is empty");
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Chart fluvio 0.11.2 is not in the repository, it has no versions of fluvio"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Runs external commands such as kubectl from async checks
+//!
+//! [`Command::output`] blocks the calling thread until the child exits, which
+//! stalls every other check on the executor and hangs forever on a wedged
+//! kubectl. [`command_output`] waits on a blocking thread instead and kills
+//! the child once its timeout expires.
+
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind, Read};
+use std::process::{Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use fluvio_future::task::spawn_blocking;
+use tracing::debug;
+
+#[cfg(feature = "kubectl-checks")]
+use super::environment::CheckEnvironment;
+#[cfg(feature = "kubectl-checks")]
+use super::ClusterCheckError;
+
+/// How long a single external command may run
+pub(crate) const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often a running command is polled for completion
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// How much of stderr an error message shows, the rest is only rendered below it
+const STDERR_SUMMARY_LEN: usize = 200;
+
+/// An external command that failed, and what it reported
+///
+/// Its `Display` shows the command line, the exit code and the first line
+/// of stderr, shortened to fit in an error message. The complete stderr is
+/// kept in `stderr`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandOutput {
+    /// The program that was run, such as `kubectl`
+    pub program: String,
+    /// The arguments it was run with
+    pub args: Vec<String>,
+    /// Exit code, `None` if the command could not be started or was killed by a signal
+    pub status: Option<i32>,
+    /// What the command wrote to stderr
+    pub stderr: String,
+}
+
+impl CommandOutput {
+    /// Describes `command` before it runs
+    pub(crate) fn from_command(command: &Command) -> Self {
+        Self {
+            program: command.get_program().to_string_lossy().to_string(),
+            args: command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect(),
+            status: None,
+            stderr: String::new(),
+        }
+    }
+
+    /// Adds the exit code and stderr of `output`
+    pub(crate) fn with_output(self, output: &Output) -> Self {
+        Self {
+            status: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            ..self
+        }
+    }
+
+    /// First line of stderr, shortened to 200 characters
+    pub fn stderr_summary(&self) -> String {
+        let first_line = self.stderr.lines().next().unwrap_or_default();
+        match first_line.char_indices().nth(STDERR_SUMMARY_LEN) {
+            Some((end, _)) => format!("{}...", &first_line[..end]),
+            None => first_line.to_string(),
+        }
+    }
+
+    /// Whether [`stderr_summary`](Self::stderr_summary) leaves part of stderr out
+    pub fn is_stderr_truncated(&self) -> bool {
+        self.stderr_summary() != self.stderr
+    }
+}
+
+impl fmt::Display for CommandOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}", self.program)?;
+        for arg in &self.args {
+            write!(f, " {arg}")?;
+        }
+        write!(f, "`")?;
+        if let Some(code) = self.status {
+            write!(f, " exited with status {code}")?;
+        }
+        if !self.stderr.is_empty() {
+            write!(f, ": {}", self.stderr_summary())?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `command` to completion and captures stdout and stderr
+///
+/// If the command is still running after `timeout` it is killed and an error
+/// of kind [`ErrorKind::TimedOut`] is returned.
+pub(crate) async fn command_output(command: Command, timeout: Duration) -> Result<Output, IoError> {
+    spawn_blocking(move || output_within(command, timeout)).await
+}
+
+/// Runs kubectl in `env` without blocking the executor, killing it after [`COMMAND_TIMEOUT`]
+#[cfg(feature = "kubectl-checks")]
+pub(crate) async fn kubectl_output(
+    env: &dyn CheckEnvironment,
+    command: Command,
+) -> Result<Output, ClusterCheckError> {
+    let description = CommandOutput::from_command(&command);
+    env.command_output(command, COMMAND_TIMEOUT)
+        .await
+        .map_err(|err| match err.kind() {
+            ErrorKind::TimedOut => ClusterCheckError::CommandTimedOut {
+                command: "kubectl".to_string(),
+                timeout: COMMAND_TIMEOUT,
+            },
+            _ => ClusterCheckError::KubectlNotFoundError {
+                command: description,
+                source: err,
+            },
+        })
+}
+
+fn output_within(mut command: Command, timeout: Duration) -> Result<Output, IoError> {
+    let program = command.get_program().to_string_lossy().to_string();
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // drain both pipes while waiting, a child blocked on a full pipe never exits
+    let stdout = read_to_end(child.stdout.take());
+    let stderr = read_to_end(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            debug!(program, ?timeout, "killing command");
+            // the child may have exited just now, in that case there is nothing to kill
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(IoError::new(
+                ErrorKind::TimedOut,
+                format!("{program} did not finish within {}s", timeout.as_secs_f32()),
+            ));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let output = Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    };
+    if !output.stderr.is_empty() {
+        debug!(
+            program,
+            stderr = %String::from_utf8_lossy(&output.stderr),
+            "command wrote to stderr"
+        );
+    }
+    Ok(output)
+}
+
+fn read_to_end<R: Read + Send + 'static>(pipe: Option<R>) -> JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = vec![];
+        if let Some(mut pipe) = pipe {
+            if let Err(err) = pipe.read_to_end(&mut buffer) {
+                debug!(%err, "unable to read command output");
+            }
+        }
+        buffer
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn shell(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(script);
+        command
+    }
+
+    #[fluvio_future::test]
+    async fn test_command_output() {
+        let output = command_output(shell("echo out; echo err >&2; exit 3"), COMMAND_TIMEOUT)
+            .await
+            .expect("output");
+
+        assert_eq!(output.status.code(), Some(3));
+        assert_eq!(output.stdout, b"out\n");
+        assert_eq!(output.stderr, b"err\n");
+    }
+
+    #[fluvio_future::test]
+    async fn test_command_output_kills_on_timeout() {
+        let started = Instant::now();
+        let err = command_output(shell("sleep 30"), Duration::from_millis(100))
+            .await
+            .expect_err("timed out");
+
+        assert_eq!(err.kind(), ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[fluvio_future::test]
+    async fn test_failed_command_output() {
+        let command = shell("echo 'error: unknown flag' >&2; echo usage >&2; exit 1");
+        let failed = CommandOutput::from_command(&command);
+        let output = command_output(command, COMMAND_TIMEOUT)
+            .await
+            .expect("output");
+        let failed = failed.with_output(&output);
+
+        assert_eq!(failed.program, "sh");
+        assert_eq!(failed.status, Some(1));
+        assert_eq!(failed.stderr, "error: unknown flag\nusage");
+        assert!(failed.is_stderr_truncated());
+        assert_eq!(
+            failed.to_string(),
+            "`sh -c echo 'error: unknown flag' >&2; echo usage >&2; exit 1` \
+             exited with status 1: error: unknown flag"
+        );
+    }
+
+    #[test]
+    fn test_stderr_summary_is_shortened() {
+        let failed = CommandOutput {
+            program: "kubectl".to_string(),
+            stderr: "x".repeat(STDERR_SUMMARY_LEN + 1),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            failed.stderr_summary(),
+            format!("{}...", "x".repeat(STDERR_SUMMARY_LEN))
+        );
+        assert!(failed.is_stderr_truncated());
+    }
+
+    #[fluvio_future::test]
+    async fn test_command_output_missing_binary() {
+        let err = command_output(Command::new("fluvio-no-such-binary"), COMMAND_TIMEOUT)
+            .await
+            .expect_err("not found");
+
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+}
@@ -0,0 +1,200 @@
+//! Checks that a client can reach the SC of an installed cluster
+//!
+//! Clients only report "connection refused" when the SC can't be reached.
+//! [`ScConnectivity`] goes through the same steps a client does, resolving
+//! the address, opening a TCP connection and negotiating API versions, and
+//! reports which of them failed.
+
+use std::io::Error as IoError;
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use fluvio::{Fluvio, FluvioConfig};
+use fluvio_future::task::spawn_blocking;
+use fluvio_future::timer::sleep;
+use futures_util::future::{select, Either};
+use tracing::debug;
+
+use crate::render::ProgressRenderer;
+
+use super::environment::{CheckEnvironment, Environment};
+use super::{CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, UnrecoverableCheckStatus};
+
+/// How long each step of reaching the SC may take
+const SC_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connects to the SC the way a client would
+///
+/// By default the cluster of the current profile is used, which the
+/// installer points at the load balancer, node port or `localhost` address
+/// of the SC it started.
+#[derive(Debug, Default)]
+pub struct ScConnectivity {
+    config: Option<FluvioConfig>,
+    env: Environment,
+}
+
+impl ScConnectivity {
+    /// Connects to the SC in `config` instead of the current profile
+    pub fn new(config: FluvioConfig) -> Self {
+        Self {
+            config: Some(config),
+            env: Environment::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for ScConnectivity {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let config = match &self.config {
+            Some(config) => config.clone(),
+            None => self.env.profile()?,
+        };
+        let address = config.endpoint.clone();
+
+        let socket_addrs = match resolve(&address).await {
+            Ok(socket_addrs) => socket_addrs,
+            Err(err) => {
+                debug!(%address, %err, "unable to resolve SC address");
+                return Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::ScAddressUnresolved { address },
+                ));
+            }
+        };
+
+        if let Err(err) = connect(socket_addrs).await {
+            debug!(%address, %err, "unable to connect to SC");
+            return Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::ScConnectionRefused { address },
+            ));
+        }
+
+        match select(
+            Box::pin(Fluvio::connect_with_config(&config)),
+            Box::pin(sleep(SC_CONNECT_TIMEOUT)),
+        )
+        .await
+        {
+            Either::Left((Ok(_), _)) => {
+                Ok(CheckStatus::pass(format!("SC is reachable at {address}")))
+            }
+            Either::Left((Err(err), _)) => {
+                Ok(CheckStatus::Unrecoverable(UnrecoverableCheckStatus::Other(
+                    format!("Unable to talk to SC at {address}: {err}"),
+                )))
+            }
+            Either::Right(_) => Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::ScHandshakeTimeout {
+                    address,
+                    timeout: SC_CONNECT_TIMEOUT,
+                },
+            )),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "sc-connectivity"
+    }
+
+    fn label(&self) -> &str {
+        "SC connectivity"
+    }
+
+    fn description(&self) -> &str {
+        "A client can connect to the SC and negotiate API versions"
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// The cluster of the current profile
+pub(crate) fn profile_config() -> Result<FluvioConfig, ClusterCheckError> {
+    FluvioConfig::load()
+        .map_err(|err| ClusterCheckError::Other(format!("no cluster profile: {err}")))
+}
+
+/// Resolves `host:port` on a blocking thread, system DNS lookups block
+async fn resolve(address: &str) -> Result<Vec<SocketAddr>, IoError> {
+    let address = address.to_string();
+    spawn_blocking(move || {
+        address
+            .to_socket_addrs()
+            .map(|socket_addrs| socket_addrs.collect())
+    })
+    .await
+}
+
+/// Opens a TCP connection to the first address that accepts one
+async fn connect(socket_addrs: Vec<SocketAddr>) -> Result<(), IoError> {
+    spawn_blocking(move || {
+        let mut last_err = IoError::other("no addresses to connect to");
+        for socket_addr in socket_addrs {
+            match TcpStream::connect_timeout(&socket_addr, SC_CONNECT_TIMEOUT) {
+                Ok(_) => return Ok(()),
+                Err(err) => last_err = err,
+            }
+        }
+        Err(last_err)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+
+    use super::*;
+
+    async fn check(endpoint: String) -> CheckStatus {
+        ScConnectivity::new(FluvioConfig::new(endpoint))
+            .perform_check(&ProgressRenderer::default())
+            .await
+            .expect("status")
+    }
+
+    #[fluvio_future::test]
+    async fn test_sc_address_unresolved() {
+        let status = check("fluvio-sc.invalid:9003".to_string()).await;
+
+        assert!(matches!(
+            status,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::ScAddressUnresolved { address })
+                if address == "fluvio-sc.invalid:9003"
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_sc_connection_refused() {
+        let port = {
+            let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+            listener.local_addr().expect("addr").port()
+        };
+
+        let status = check(format!("127.0.0.1:{port}")).await;
+
+        assert!(matches!(
+            status,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::ScConnectionRefused { .. })
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_sc_handshake_timeout() {
+        // accepts connections but never answers
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+
+        let status = check(format!("127.0.0.1:{port}")).await;
+
+        assert!(matches!(
+            status,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::ScHandshakeTimeout { .. })
+        ));
+        drop(listener);
+    }
+}
@@ -0,0 +1,221 @@
+//! Points checks at a kubeconfig context other than the current one
+//!
+//! The Kubernetes client and kubectl both use the current context, so with
+//! several clusters in the kubeconfig it is easy to check one cluster and
+//! install into another. [`ClusterChecker::with_kube_context`] names the
+//! context to use instead, and the helpers here resolve it for the client,
+//! for kubectl and for the identity a check reports.
+//!
+//! [`ClusterChecker::with_kube_context`]: super::ClusterChecker::with_kube_context
+
+use std::env;
+#[cfg(feature = "kubectl-checks")]
+use std::process::Command;
+
+use k8_config::{K8Config, KubeConfig, KubeContext};
+use k8_types::MetaStatus;
+
+use super::environment::CheckEnvironment;
+use super::load_balancer::ServiceSource;
+use super::namespace::NamespaceSource;
+use super::nodes::NodeSource;
+use super::permission::AccessReviewer;
+use super::tls::SecretSource;
+use super::{ClusterCheckError, ServerVersionSource};
+
+/// Everything the checks read through the Kubernetes client
+///
+/// Implemented by every type that implements each of the sources, such as
+/// [`K8Client`](k8_client::K8Client) or a fake cluster in tests. The `as_*`
+/// methods hand the client to helpers that take a single source.
+pub trait ClusterApi:
+    AccessReviewer + ServiceSource + SecretSource + NodeSource + NamespaceSource + ServerVersionSource
+{
+    fn as_access_reviewer(&self) -> &dyn AccessReviewer;
+
+    fn as_service_source(&self) -> &dyn ServiceSource;
+
+    fn as_secret_source(&self) -> &dyn SecretSource;
+}
+
+impl<T> ClusterApi for T
+where
+    T: AccessReviewer
+        + ServiceSource
+        + SecretSource
+        + NodeSource
+        + NamespaceSource
+        + ServerVersionSource,
+{
+    fn as_access_reviewer(&self) -> &dyn AccessReviewer {
+        self
+    }
+
+    fn as_service_source(&self) -> &dyn ServiceSource {
+        self
+    }
+
+    fn as_secret_source(&self) -> &dyn SecretSource {
+        self
+    }
+}
+
+/// The kubeconfig kubectl reads, the first file of `KUBECONFIG` or the one in the home directory
+pub(crate) fn load_kubeconfig() -> Option<KubeConfig> {
+    match env::var("KUBECONFIG") {
+        Ok(paths) => env::split_paths(&paths)
+            .next()
+            .and_then(|path| KubeConfig::from_file(path).ok()),
+        Err(_) => KubeConfig::from_home().ok(),
+    }
+}
+
+/// Whether the kubeconfig of `env` has a context named `name`
+pub(crate) fn context_exists(env: &dyn CheckEnvironment, name: &str) -> bool {
+    env.kubeconfig()
+        .is_some_and(|config| has_context(&config, name))
+}
+
+fn has_context(config: &KubeConfig, name: &str) -> bool {
+    config.contexts.iter().any(|context| context.name == name)
+}
+
+/// Client configuration for `context`, or the default one if `None`
+pub(crate) fn load_config(
+    env: &dyn CheckEnvironment,
+    context: Option<&str>,
+) -> Result<K8Config, ClusterCheckError> {
+    let Some(name) = context else {
+        return env.default_config();
+    };
+    let config = env.kubeconfig().ok_or_else(|| context_not_found(name))?;
+    Ok(K8Config::KubeConfig(select_context(config, name)?))
+}
+
+/// `config` with `name` as its current context
+pub(crate) fn select_context(
+    mut config: KubeConfig,
+    name: &str,
+) -> Result<KubeContext, ClusterCheckError> {
+    if !has_context(&config, name) {
+        return Err(context_not_found(name));
+    }
+    config.current_context = name.to_string();
+    let namespace = config
+        .current_context()
+        .map(|context| context.context.namespace().to_string())
+        .unwrap_or_default();
+    let api_path = config
+        .current_cluster()
+        .map(|cluster| cluster.cluster.server.clone())
+        .ok_or_else(|| context_not_found(name))?;
+    Ok(KubeContext {
+        namespace,
+        api_path,
+        config,
+    })
+}
+
+/// The error of a failed Kubernetes client request
+///
+/// A request the API server answered with 403 Forbidden is a
+/// [`ClusterCheckError::K8ApiForbidden`], checks skip what the user may not see.
+pub(crate) fn k8_api_error(err: anyhow::Error) -> ClusterCheckError {
+    match err.downcast_ref::<MetaStatus>() {
+        Some(status) if status.code == Some(403) => {
+            ClusterCheckError::K8ApiForbidden(err.to_string())
+        }
+        _ => ClusterCheckError::K8ApiError(err.to_string()),
+    }
+}
+
+/// A kubectl command that talks to `context`, or to the current context if `None`
+#[cfg(feature = "kubectl-checks")]
+pub(crate) fn kubectl_command(context: Option<&str>) -> Command {
+    let mut command = Command::new("kubectl");
+    if let Some(context) = context {
+        command.arg("--context").arg(context);
+    }
+    command
+}
+
+fn context_not_found(name: &str) -> ClusterCheckError {
+    ClusterCheckError::Other(format!("Kubernetes context {name} not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KUBECONFIG: &str = r#"
+apiVersion: v1
+kind: Config
+current-context: kind-dev
+clusters:
+- name: kind-dev
+  cluster:
+    server: https://127.0.0.1:40000
+- name: prod
+  cluster:
+    server: https://prod.example.com:6443
+contexts:
+- name: kind-dev
+  context:
+    cluster: kind-dev
+    user: kind-dev
+- name: prod
+  context:
+    cluster: prod
+    user: admin
+    namespace: streaming
+users:
+- name: kind-dev
+  user:
+    token: abc
+- name: admin
+  user:
+    token: def
+"#;
+
+    fn kubeconfig() -> KubeConfig {
+        serde_yaml::from_str(KUBECONFIG).expect("kubeconfig")
+    }
+
+    #[test]
+    fn test_select_context() {
+        let context = select_context(kubeconfig(), "prod").expect("context");
+
+        assert_eq!(context.config.current_context, "prod");
+        assert_eq!(context.api_path, "https://prod.example.com:6443");
+        assert_eq!(context.namespace, "streaming");
+        assert_eq!(
+            context.config.current_user().map(|user| user.name.as_str()),
+            Some("admin")
+        );
+    }
+
+    #[test]
+    fn test_select_missing_context() {
+        assert!(has_context(&kubeconfig(), "kind-dev"));
+        assert!(!has_context(&kubeconfig(), "kind-gone"));
+        assert!(matches!(
+            select_context(kubeconfig(), "kind-gone"),
+            Err(ClusterCheckError::Other(message)) if message == "Kubernetes context kind-gone not found"
+        ));
+    }
+
+    #[cfg(feature = "kubectl-checks")]
+    #[test]
+    fn test_kubectl_command_context() {
+        let args = |context| {
+            kubectl_command(context)
+                .arg("version")
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(args(None), vec!["version"]);
+        assert_eq!(args(Some("prod")), vec!["--context", "prod", "version"]);
+    }
+}
@@ -0,0 +1,194 @@
+//! Checks that the local SPU has room for its storage
+//!
+//! An SPU that runs out of disk stops accepting records, long after the
+//! install reported success. Checking the free space of the data directory
+//! up front surfaces a full disk before anything is started.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sysinfo::{DiskExt, System, SystemExt};
+use tracing::debug;
+
+use crate::render::ProgressRenderer;
+use crate::start::local::DEFAULT_DATA_DIR;
+
+use super::environment::{CheckEnvironment, Environment};
+use super::{CheckResult, CheckStatus, ClusterCheck, UnrecoverableCheckStatus};
+
+/// Free space required by default, 1 GiB
+const DEFAULT_MIN_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Fails if the disk holding `path` has less than `min_bytes` available
+///
+/// By default `path` is the data directory of a local cluster. It does not
+/// have to exist yet, the disk it would be created on is checked.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ClusterChecker, LocalDiskSpace};
+/// let check = LocalDiskSpace::new(10 * 1024 * 1024 * 1024, "/var/lib/fluvio");
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LocalDiskSpace {
+    /// Bytes that must be available
+    pub min_bytes: u64,
+    /// Directory the SPU stores its data in
+    pub path: PathBuf,
+    env: Environment,
+}
+
+impl LocalDiskSpace {
+    /// Requires `min_bytes` to be available on the disk holding `path`
+    pub fn new(min_bytes: u64, path: impl Into<PathBuf>) -> Self {
+        Self {
+            min_bytes,
+            path: path.into(),
+            env: Environment::default(),
+        }
+    }
+}
+
+impl Default for LocalDiskSpace {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_MIN_BYTES,
+            DEFAULT_DATA_DIR.clone().unwrap_or_else(env::temp_dir),
+        )
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for LocalDiskSpace {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let disks = self.env.disks();
+        let path = existing_ancestor(&self.path);
+        let Some(available) = available_space(&path, &disks) else {
+            debug!(path = %path.display(), "no disk found for path");
+            return Ok(CheckStatus::skipped(format!(
+                "Unable to find the disk of {}",
+                self.path.display()
+            )));
+        };
+        debug!(path = %path.display(), available, "available disk space");
+
+        if available < self.min_bytes {
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::InsufficientDiskSpace {
+                    available,
+                    required: self.min_bytes,
+                },
+            ))
+        } else {
+            Ok(CheckStatus::pass(format!(
+                "{} MiB available for {}",
+                available / (1024 * 1024),
+                self.path.display()
+            )))
+        }
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "local-disk-space"
+    }
+
+    fn label(&self) -> &str {
+        "Disk space"
+    }
+
+    fn description(&self) -> &str {
+        "The disk holding the local data directory has enough free space"
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Mount point and available bytes of each disk
+pub(crate) fn mounted_disks() -> Vec<(PathBuf, u64)> {
+    let mut sys = System::new();
+    sys.refresh_disks_list();
+    sys.disks()
+        .iter()
+        .map(|disk| (disk.mount_point().to_path_buf(), disk.available_space()))
+        .collect()
+}
+
+/// `path` itself or its closest parent that exists, with symlinks resolved
+fn existing_ancestor(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find_map(|ancestor| ancestor.canonicalize().ok())
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+/// Space available on the disk mounted closest to `path`
+fn available_space(path: &Path, disks: &[(PathBuf, u64)]) -> Option<u64> {
+    disks
+        .iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, available)| *available)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_available_space_picks_closest_mount() {
+        let disks = vec![
+            (PathBuf::from("/"), 100),
+            (PathBuf::from("/home"), 50),
+            (PathBuf::from("/home/fluvio/other"), 10),
+        ];
+
+        assert_eq!(
+            available_space(Path::new("/home/fluvio/.fluvio/data"), &disks),
+            Some(50)
+        );
+        assert_eq!(available_space(Path::new("/var/lib"), &disks), Some(100));
+        assert_eq!(available_space(Path::new("/var/lib"), &[]), None);
+    }
+
+    #[test]
+    fn test_existing_ancestor() {
+        let dir = env::temp_dir();
+        let missing = dir.join("fluvio-disk-check").join("not-created");
+
+        assert_eq!(
+            existing_ancestor(&missing),
+            dir.canonicalize().expect("temp dir")
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_insufficient_disk_space() {
+        let check = LocalDiskSpace::new(u64::MAX, env::temp_dir());
+
+        let status = check
+            .perform_check(&ProgressRenderer::default())
+            .await
+            .expect("status");
+
+        // containers may not expose any disk, which skips the check
+        assert!(
+            matches!(
+                status,
+                CheckStatus::Unrecoverable(UnrecoverableCheckStatus::InsufficientDiskSpace {
+                    required: u64::MAX,
+                    ..
+                }) | CheckStatus::Skipped(_)
+            ),
+            "{status:?}"
+        );
+    }
+}
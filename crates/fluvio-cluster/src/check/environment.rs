@@ -0,0 +1,174 @@
+//! Where the checks find out about the machine and the cluster
+//!
+//! Checks don't reach the outside world on their own. The kubeconfig, the
+//! Kubernetes client, the commands they run, the binaries in `PATH`, and the
+//! local ports, disks and processes all come from the [`CheckEnvironment`]
+//! given to them with [`ClusterChecker::with_environment`]. Unless told
+//! otherwise that is the [`SystemEnvironment`], tests give them a fake
+//! cluster instead.
+//!
+//! [`ClusterChecker::with_environment`]: super::ClusterChecker::with_environment
+
+use std::fmt::Debug;
+use std::io::Error as IoError;
+use std::ops::Deref;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use fluvio::FluvioConfig;
+use k8_client::{load_and_share, K8Client};
+use k8_config::{ConfigError as K8ConfigError, K8Config, KubeConfig};
+
+#[cfg(feature = "helm-checks")]
+use crate::charts::{ChartConfig, ChartInstallError, ChartInstaller};
+
+use super::context::{self, ClusterApi};
+use super::local::{self, RunningProcess};
+#[cfg(feature = "helm-checks")]
+use super::sys_chart::SysChartInstaller;
+use super::{binary, command, connectivity, disk, port, ClusterCheckError};
+
+/// The machine the checks run on and the cluster they look at
+///
+/// [`SystemEnvironment`] is the real one. Implement this to run the checks
+/// against something else, such as a fake cluster in tests, and pass it to
+/// [`ClusterChecker::with_environment`].
+///
+/// [`ClusterChecker::with_environment`]: super::ClusterChecker::with_environment
+#[async_trait]
+pub trait CheckEnvironment: Debug + Send + Sync + 'static {
+    /// The kubeconfig kubectl reads, `None` if there is none
+    fn kubeconfig(&self) -> Option<KubeConfig>;
+
+    /// Client configuration of the current kubeconfig context
+    ///
+    /// The [`SystemEnvironment`] also falls back to the service account of
+    /// the pod it runs in.
+    fn default_config(&self) -> Result<K8Config, ClusterCheckError> {
+        let config = self.kubeconfig().ok_or(K8ConfigError::NoCurrentContext)?;
+        let name = config.current_context.clone();
+        Ok(K8Config::KubeConfig(context::select_context(
+            config, &name,
+        )?))
+    }
+
+    /// Kubernetes client for kubeconfig `context`, or for the default configuration if `None`
+    ///
+    /// Fails with the reason no client could be built, checks then fall back to kubectl.
+    fn client(&self, context: Option<&str>) -> Result<Arc<dyn ClusterApi>, String>;
+
+    /// Runs `command` to completion and captures stdout and stderr
+    ///
+    /// A command still running after `timeout` fails with an error of kind
+    /// [`ErrorKind::TimedOut`](std::io::ErrorKind::TimedOut).
+    async fn command_output(&self, command: Command, timeout: Duration) -> Result<Output, IoError>;
+
+    /// First executable named `binary` in `PATH`
+    fn find_binary(&self, binary: &str) -> Option<PathBuf>;
+
+    /// Whether a listener can be bound to `port` on every interface
+    fn is_port_free(&self, port: u16) -> bool;
+
+    /// Mount point and available bytes of each disk
+    fn disks(&self) -> Vec<(PathBuf, u64)>;
+
+    /// The processes running on this machine
+    fn processes(&self) -> Vec<RunningProcess>;
+
+    /// The cluster of the current profile
+    fn profile(&self) -> Result<FluvioConfig, ClusterCheckError>;
+
+    /// Installer of the system chart described by `config`
+    #[cfg(feature = "helm-checks")]
+    fn sys_chart_installer(
+        &self,
+        config: &ChartConfig,
+    ) -> Result<Box<dyn SysChartInstaller>, ChartInstallError>;
+}
+
+/// The machine the checks run on, and the cluster of its kubeconfig
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemEnvironment;
+
+#[async_trait]
+impl CheckEnvironment for SystemEnvironment {
+    fn kubeconfig(&self) -> Option<KubeConfig> {
+        context::load_kubeconfig()
+    }
+
+    fn default_config(&self) -> Result<K8Config, ClusterCheckError> {
+        Ok(K8Config::load()?)
+    }
+
+    fn client(&self, context: Option<&str>) -> Result<Arc<dyn ClusterApi>, String> {
+        let client = match context {
+            Some(_) => {
+                let config = context::load_config(self, context).map_err(|err| err.to_string())?;
+                K8Client::new(config).map(Arc::new)
+            }
+            None => load_and_share(),
+        };
+        client
+            .map(|client| client as Arc<dyn ClusterApi>)
+            .map_err(|err| err.to_string())
+    }
+
+    async fn command_output(&self, command: Command, timeout: Duration) -> Result<Output, IoError> {
+        command::command_output(command, timeout).await
+    }
+
+    fn find_binary(&self, binary: &str) -> Option<PathBuf> {
+        binary::find_in_path(binary)
+    }
+
+    fn is_port_free(&self, port: u16) -> bool {
+        port::is_port_free(port)
+    }
+
+    fn disks(&self) -> Vec<(PathBuf, u64)> {
+        disk::mounted_disks()
+    }
+
+    fn processes(&self) -> Vec<RunningProcess> {
+        local::running_processes()
+    }
+
+    fn profile(&self) -> Result<FluvioConfig, ClusterCheckError> {
+        connectivity::profile_config()
+    }
+
+    #[cfg(feature = "helm-checks")]
+    fn sys_chart_installer(
+        &self,
+        config: &ChartConfig,
+    ) -> Result<Box<dyn SysChartInstaller>, ChartInstallError> {
+        Ok(Box::new(ChartInstaller::from_config(config.clone())?))
+    }
+}
+
+/// The environment of a check, the [`SystemEnvironment`] until it is given another one
+#[derive(Debug, Clone)]
+pub(crate) struct Environment(Arc<dyn CheckEnvironment>);
+
+impl Default for Environment {
+    fn default() -> Self {
+        Self(Arc::new(SystemEnvironment))
+    }
+}
+
+impl From<&Arc<dyn CheckEnvironment>> for Environment {
+    fn from(env: &Arc<dyn CheckEnvironment>) -> Self {
+        Self(env.clone())
+    }
+}
+
+impl Deref for Environment {
+    type Target = dyn CheckEnvironment;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
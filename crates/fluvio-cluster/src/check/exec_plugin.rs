@@ -0,0 +1,223 @@
+//! Diagnostics for kubeconfig exec credential plugins
+//!
+//! Managed clusters such as EKS and GKE hand out credentials through an
+//! external binary declared in the `exec` section of the kubeconfig user.
+//! When that binary is missing or its session expired, the Kubernetes client
+//! only reports a generic error, so we run the plugin the same way the client
+//! does and translate the outcome into something actionable.
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use tracing::debug;
+
+use super::command::COMMAND_TIMEOUT;
+use super::environment::CheckEnvironment;
+
+/// stderr fragments that plugins print when the cached session is no longer valid
+const EXPIRED_SIGNATURES: &[&str] = &[
+    "expired",
+    "ExpiredToken",
+    "sso session",
+    "reauthenticat",
+    "re-authenticat",
+    "invalid_grant",
+];
+
+/// A problem with the exec credential plugin of the active kubeconfig user
+#[derive(thiserror::Error, Debug, PartialEq, Eq)]
+pub enum ExecPluginError {
+    /// The plugin binary could not be spawned
+    #[error("Kubernetes credential plugin `{plugin}` is not installed")]
+    NotFound { plugin: String },
+
+    /// The plugin ran but reported that its credentials have expired
+    #[error("Kubernetes credentials from `{plugin}` have expired")]
+    Expired { plugin: String, stderr: String },
+
+    /// The plugin exited with an error
+    #[error("Kubernetes credential plugin `{plugin}` failed: {stderr}")]
+    Failed { plugin: String, stderr: String },
+}
+
+impl ExecPluginError {
+    fn plugin(&self) -> &str {
+        match self {
+            Self::NotFound { plugin } => plugin,
+            Self::Expired { plugin, .. } => plugin,
+            Self::Failed { plugin, .. } => plugin,
+        }
+    }
+
+    /// Provider specific hint on how to get the plugin working again
+    pub fn suggestion(&self) -> Option<String> {
+        let suggestion = match (self, self.plugin()) {
+            (Self::NotFound { .. }, "aws") => "Install the AWS CLI: https://aws.amazon.com/cli/",
+            (Self::NotFound { .. }, "aws-iam-authenticator") => {
+                "Install aws-iam-authenticator or switch the kubeconfig to `aws eks get-token`"
+            }
+            (Self::NotFound { .. }, "gke-gcloud-auth-plugin") => {
+                "Install gke-gcloud-auth-plugin with `gcloud components install gke-gcloud-auth-plugin`"
+            }
+            (Self::Expired { .. }, "aws") | (Self::Expired { .. }, "aws-iam-authenticator") => {
+                "Run `aws sso login` to refresh your credentials"
+            }
+            (Self::Expired { .. }, "gke-gcloud-auth-plugin") => {
+                "Run `gcloud auth login` to refresh your credentials"
+            }
+            (Self::NotFound { .. }, _) => {
+                "Install the credential plugin referenced by your kubeconfig"
+            }
+            (Self::Expired { .. }, _) => "Log in again with your cloud provider's CLI",
+            (Self::Failed { .. }, _) => return None,
+        };
+        Some(suggestion.to_string())
+    }
+}
+
+/// Runs the credential plugin the same way the Kubernetes client would
+pub(crate) async fn check_exec_plugin(
+    env: &dyn CheckEnvironment,
+    command: &str,
+    args: &[String],
+) -> Result<(), ExecPluginError> {
+    debug!(command, ?args, "running exec credential plugin");
+    let mut plugin = Command::new(command);
+    plugin.args(args);
+    let output = env.command_output(plugin, COMMAND_TIMEOUT).await;
+    classify_exec_output(command, output)
+}
+
+/// Maps the outcome of running a plugin to an [`ExecPluginError`]
+pub(crate) fn classify_exec_output(
+    command: &str,
+    output: std::io::Result<Output>,
+) -> Result<(), ExecPluginError> {
+    let plugin = plugin_name(command);
+    let output = match output {
+        Ok(output) => output,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            return Err(ExecPluginError::NotFound { plugin })
+        }
+        Err(err) => {
+            return Err(ExecPluginError::Failed {
+                plugin,
+                stderr: err.to_string(),
+            })
+        }
+    };
+
+    if output.status.success() {
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let lowercase = stderr.to_lowercase();
+    if EXPIRED_SIGNATURES
+        .iter()
+        .any(|signature| lowercase.contains(&signature.to_lowercase()))
+    {
+        Err(ExecPluginError::Expired { plugin, stderr })
+    } else {
+        Err(ExecPluginError::Failed { plugin, stderr })
+    }
+}
+
+/// `/usr/local/bin/aws` => `aws`
+fn plugin_name(command: &str) -> String {
+    Path::new(command)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| command.to_string())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::io::Error as IoError;
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    use super::*;
+
+    const AWS_SSO_EXPIRED: &str =
+        "Error when retrieving token from sso: Token has expired and refresh failed";
+    const GKE_EXPIRED: &str =
+        "Reauthentication failed. cannot prompt during non-interactive execution.";
+    const GKE_NOT_LOGGED_IN: &str = "ERROR: (gcloud.config.config-helper) You do not currently have an active account selected.";
+
+    fn failed_output(stderr: &str) -> std::io::Result<Output> {
+        Ok(Output {
+            status: ExitStatus::from_raw(1 << 8),
+            stdout: vec![],
+            stderr: stderr.as_bytes().to_vec(),
+        })
+    }
+
+    #[test]
+    fn test_aws_plugin_missing() {
+        let err = classify_exec_output(
+            "/usr/local/bin/aws",
+            Err(IoError::from(ErrorKind::NotFound)),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ExecPluginError::NotFound {
+                plugin: "aws".to_string()
+            }
+        );
+        assert!(err.suggestion().unwrap().contains("AWS CLI"));
+    }
+
+    #[test]
+    fn test_aws_sso_expired() {
+        let err = classify_exec_output("aws", failed_output(AWS_SSO_EXPIRED)).unwrap_err();
+        assert!(matches!(err, ExecPluginError::Expired { .. }));
+        assert_eq!(
+            err.suggestion().as_deref(),
+            Some("Run `aws sso login` to refresh your credentials")
+        );
+    }
+
+    #[test]
+    fn test_gke_plugin_missing() {
+        let err = classify_exec_output(
+            "gke-gcloud-auth-plugin",
+            Err(IoError::from(ErrorKind::NotFound)),
+        )
+        .unwrap_err();
+        assert!(err
+            .suggestion()
+            .unwrap()
+            .contains("gcloud components install gke-gcloud-auth-plugin"));
+    }
+
+    #[test]
+    fn test_gke_expired_and_failed() {
+        let err =
+            classify_exec_output("gke-gcloud-auth-plugin", failed_output(GKE_EXPIRED)).unwrap_err();
+        assert!(matches!(err, ExecPluginError::Expired { .. }));
+
+        let err = classify_exec_output("gke-gcloud-auth-plugin", failed_output(GKE_NOT_LOGGED_IN))
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ExecPluginError::Failed {
+                plugin: "gke-gcloud-auth-plugin".to_string(),
+                stderr: GKE_NOT_LOGGED_IN.to_string(),
+            }
+        );
+        assert!(err.suggestion().is_none());
+    }
+
+    #[test]
+    fn test_plugin_success() {
+        let output = Ok(Output {
+            status: ExitStatus::from_raw(0),
+            stdout: b"{}".to_vec(),
+            stderr: vec![],
+        });
+        assert!(classify_exec_output("aws", output).is_ok());
+    }
+}
@@ -0,0 +1,160 @@
+//! Checks that a supported version of helm is installed
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use semver::{Comparator, Op, Version, VersionReq};
+
+use fluvio_helm::HelmClient;
+
+use crate::charts::DEFAULT_HELM_VERSION;
+use crate::render::ProgressRenderer;
+
+use super::chart;
+use super::environment::{CheckEnvironment, Environment};
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+#[derive(Debug)]
+pub(crate) struct HelmVersion {
+    required: VersionReq,
+    env: Environment,
+}
+
+impl Default for HelmVersion {
+    fn default() -> Self {
+        Self {
+            required: parse_version_requirement(DEFAULT_HELM_VERSION).expect("valid helm version"),
+            env: Environment::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for HelmVersion {
+    /// Checks that the installed helm version is compatible with the installer requirements
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let helm_version = match chart::helm_version(&*self.env).await {
+            Ok(version) => version,
+            // helm could not be started at all
+            Err(ClusterCheckError::Other(err)) => {
+                return Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::NoHelmClient(format!("Unable to find helm: {err}")),
+                ))
+            }
+            Err(err) => return Err(err),
+        };
+
+        helm_version_status(helm_version, &self.required)
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::HelmBinary]
+    }
+
+    fn component(&self) -> Option<FluvioClusterComponent> {
+        Some(FluvioClusterComponent::Helm)
+    }
+
+    fn name(&self) -> &str {
+        "helm-version"
+    }
+
+    fn label(&self) -> &str {
+        "Helm"
+    }
+
+    fn description(&self) -> &str {
+        "A supported version of helm is installed"
+    }
+
+    fn set_required_version(&mut self, component: &FluvioClusterComponent, version: &Version) {
+        if *component == FluvioClusterComponent::Helm {
+            self.required = at_least(version);
+        }
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Checks that the installed helm satisfies `requirement`
+///
+/// `requirement` is a semver requirement such as `>=3.0, <4.0`. A plain
+/// version such as `3.3.4` requires that version or later.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fluvio_cluster::{check_helm_version, CheckStatus};
+/// # use fluvio_helm::HelmClient;
+/// let helm = HelmClient::new().expect("helm");
+/// let status = check_helm_version(&helm, ">=3.0, <4.0").expect("helm version");
+/// assert!(matches!(status, CheckStatus::Pass(_)));
+/// ```
+pub fn check_helm_version(helm: &HelmClient, requirement: &str) -> CheckResult {
+    let requirement = parse_version_requirement(requirement)?;
+    let helm_version = helm
+        .get_helm_version()
+        .map_err(ClusterCheckError::HelmError)?;
+    helm_version_status(helm_version, &requirement)
+}
+
+fn helm_version_status(helm_version: String, requirement: &VersionReq) -> CheckResult {
+    if !requirement.matches(&Version::parse(&helm_version)?) {
+        return Ok(CheckStatus::Unrecoverable(
+            UnrecoverableCheckStatus::IncompatibleHelmVersion {
+                installed: helm_version,
+                required: requirement.to_string(),
+            },
+        ));
+    }
+    Ok(CheckStatus::pass(format!(
+        "Supported helm version {helm_version} is installed"
+    )))
+}
+
+/// Parses a semver requirement, treating a plain version as that version or later
+///
+/// `VersionReq` alone would read `3.3.4` as `^3.3.4` and reject helm 4.
+fn parse_version_requirement(requirement: &str) -> Result<VersionReq, semver::Error> {
+    match Version::parse(requirement.trim()) {
+        Ok(version) => Ok(at_least(&version)),
+        Err(_) => VersionReq::parse(requirement),
+    }
+}
+
+/// Requirement matching `version` and everything after it
+fn at_least(version: &Version) -> VersionReq {
+    VersionReq {
+        comparators: vec![Comparator {
+            op: Op::GreaterEq,
+            major: version.major,
+            minor: Some(version.minor),
+            patch: Some(version.patch),
+            pre: version.pre.clone(),
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version_requirement() {
+        let requirement = parse_version_requirement("3.3.4").expect("plain version");
+        assert_eq!(requirement.to_string(), ">=3.3.4");
+        assert!(requirement.matches(&Version::new(4, 0, 0)));
+        assert!(!requirement.matches(&Version::new(3, 3, 3)));
+
+        let requirement = parse_version_requirement(">=3.0, <4.0").expect("range");
+        assert!(requirement.matches(&Version::new(3, 12, 1)));
+        assert!(!requirement.matches(&Version::new(4, 0, 0)));
+
+        assert!(parse_version_requirement("three").is_err());
+    }
+}
@@ -0,0 +1,202 @@
+//! Which identity a check authenticated with against Kubernetes
+//!
+//! kubectl and helm read the kubeconfig, while the Kubernetes client prefers
+//! mounted service account credentials when running inside a pod. If the two
+//! disagree, a passing permission check says nothing about the install, so
+//! the checker warns when checks in the same run used different identities.
+
+use std::fmt;
+use std::path::Path;
+
+use k8_config::{K8Config, KubeConfig};
+
+use super::context;
+use super::environment::CheckEnvironment;
+
+const SERVICE_ACCOUNT_TOKEN: &str = "/var/run/secrets/kubernetes.io/serviceaccount/token";
+
+/// Identity and mechanism used to authenticate with the Kubernetes API
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ClusterIdentity {
+    /// kubeconfig user with credentials stored in the kubeconfig
+    KubeConfigUser { user: String },
+    /// kubeconfig user whose credentials come from an exec plugin
+    ExecPlugin { user: String, command: String },
+    /// service account token mounted into the pod
+    ServiceAccount,
+}
+
+impl fmt::Display for ClusterIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KubeConfigUser { user } => write!(f, "kubeconfig user {user}"),
+            Self::ExecPlugin { user, command } => {
+                write!(f, "kubeconfig user {user} via exec plugin {command}")
+            }
+            Self::ServiceAccount => write!(f, "in-pod service account"),
+        }
+    }
+}
+
+/// Identity of the current user of a kubeconfig
+pub(crate) fn identity_from_kubeconfig(config: &KubeConfig) -> Option<ClusterIdentity> {
+    let user = config.current_user()?;
+    let identity = match &user.user.exec {
+        Some(exec) => ClusterIdentity::ExecPlugin {
+            user: user.name.clone(),
+            command: exec.command.clone(),
+        },
+        None => ClusterIdentity::KubeConfigUser {
+            user: user.name.clone(),
+        },
+    };
+    Some(identity)
+}
+
+/// Identity picked by the Kubernetes client configuration
+pub(crate) fn identity_from_config(config: &K8Config) -> Option<ClusterIdentity> {
+    match config {
+        K8Config::Pod(_) => Some(ClusterIdentity::ServiceAccount),
+        K8Config::KubeConfig(context) => identity_from_kubeconfig(&context.config),
+    }
+}
+
+/// Identity used by checks going through the Kubernetes client
+///
+/// `context` is the kubeconfig context the client uses, `None` for the default.
+pub(crate) fn client_identity(
+    env: &dyn CheckEnvironment,
+    context: Option<&str>,
+) -> Option<ClusterIdentity> {
+    context::load_config(env, context)
+        .ok()
+        .and_then(|config| identity_from_config(&config))
+}
+
+/// Identity used by checks shelling out to kubectl or helm
+///
+/// Both only fall back to the mounted service account when there is no kubeconfig.
+/// `context` is passed to them with `--context`, `None` for the current one.
+#[cfg_attr(
+    not(any(feature = "helm-checks", feature = "kubectl-checks")),
+    allow(dead_code)
+)]
+pub(crate) fn kubectl_identity(
+    env: &dyn CheckEnvironment,
+    context: Option<&str>,
+) -> Option<ClusterIdentity> {
+    match env.kubeconfig() {
+        Some(mut config) => {
+            if let Some(context) = context {
+                config.current_context = context.to_string();
+            }
+            identity_from_kubeconfig(&config)
+        }
+        None if Path::new(SERVICE_ACCOUNT_TOKEN).exists() => Some(ClusterIdentity::ServiceAccount),
+        None => None,
+    }
+}
+
+/// Warning message if checks authenticated as more than one identity
+///
+/// `identities` pairs a check label with the identity it used.
+pub(crate) fn identity_mismatch(identities: &[(String, ClusterIdentity)]) -> Option<String> {
+    let (_, first) = identities.first()?;
+    if identities.iter().all(|(_, identity)| identity == first) {
+        return None;
+    }
+
+    let used = identities
+        .iter()
+        .map(|(check, identity)| format!("{check}: {identity}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "Checks authenticated as different identities ({used}), results may not reflect the install"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kubeconfig(user: &str) -> KubeConfig {
+        let yaml = format!(
+            r#"
+apiVersion: v1
+kind: Config
+current-context: test
+clusters:
+- name: test
+  cluster:
+    server: https://127.0.0.1:6443
+contexts:
+- name: test
+  context:
+    cluster: test
+    user: {user}
+users:
+- name: admin
+  user:
+    token: abc
+- name: eks-admin
+  user:
+    exec:
+      apiVersion: client.authentication.k8s.io/v1beta1
+      command: aws
+      args: ["eks", "get-token", "--cluster-name", "test"]
+"#
+        );
+        serde_yaml::from_str(&yaml).expect("kubeconfig")
+    }
+
+    #[test]
+    fn test_kubeconfig_user_identity() {
+        assert_eq!(
+            identity_from_kubeconfig(&kubeconfig("admin")),
+            Some(ClusterIdentity::KubeConfigUser {
+                user: "admin".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_exec_plugin_identity() {
+        assert_eq!(
+            identity_from_kubeconfig(&kubeconfig("eks-admin")),
+            Some(ClusterIdentity::ExecPlugin {
+                user: "eks-admin".to_string(),
+                command: "aws".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_unknown_user_identity() {
+        assert_eq!(identity_from_kubeconfig(&kubeconfig("nobody")), None);
+    }
+
+    #[test]
+    fn test_identity_mismatch() {
+        let admin = ClusterIdentity::KubeConfigUser {
+            user: "admin".to_string(),
+        };
+        let same = vec![
+            ("Kubernetes config".to_string(), admin.clone()),
+            ("Helm".to_string(), admin.clone()),
+        ];
+        assert_eq!(identity_mismatch(&same), None);
+        assert_eq!(identity_mismatch(&[]), None);
+
+        let mixed = vec![
+            (
+                "Kubernetes config".to_string(),
+                ClusterIdentity::ServiceAccount,
+            ),
+            ("Kubernetes Service Permission".to_string(), admin),
+        ];
+        let warning = identity_mismatch(&mixed).expect("warning");
+        assert!(warning.contains("Kubernetes config: in-pod service account"));
+        assert!(warning.contains("Kubernetes Service Permission: kubeconfig user admin"));
+    }
+}
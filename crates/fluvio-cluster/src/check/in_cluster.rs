@@ -0,0 +1,246 @@
+//! Checks for running the checker from inside a pod
+//!
+//! In a pod, such as an operator or a CI job, the Kubernetes client
+//! authenticates with the service account token mounted into the pod and
+//! kubectl is usually not installed. The kubeconfig checks have nothing to
+//! look at there, so [`ClusterChecker::for_in_cluster`] asks the API server
+//! whether the service account may do what an install needs, through the
+//! in-cluster client.
+//!
+//! [`ClusterChecker::for_in_cluster`]: super::ClusterChecker::for_in_cluster
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use tracing::debug;
+
+use crate::render::ProgressRenderer;
+use crate::DEFAULT_NAMESPACE;
+
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::permission::{self, AccessResource, AccessReviewer};
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    ServerVersionSource, UnrecoverableCheckStatus,
+};
+
+/// Something the service account must be allowed to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Access {
+    verb: &'static str,
+    resource: AccessResource,
+    /// reviewed in the install namespace, otherwise cluster-wide
+    namespaced: bool,
+}
+
+const SERVICE_ACCOUNT_ACCESS: &[Access] = &[
+    // the installer finds the SC through its service
+    Access {
+        verb: "list",
+        resource: permission::SERVICES,
+        namespaced: true,
+    },
+    Access {
+        verb: "create",
+        resource: permission::SERVICES,
+        namespaced: true,
+    },
+    Access {
+        verb: "create",
+        resource: permission::SECRETS,
+        namespaced: true,
+    },
+    Access {
+        verb: "create",
+        resource: permission::CUSTOM_RESOURCE_DEFINITIONS,
+        namespaced: false,
+    },
+];
+
+/// Status of the in-cluster config found by the Kubernetes client
+///
+/// There is no kubeconfig to look at, so the API server must answer a request
+/// made with the token of the service account.
+pub(crate) async fn service_account_status(
+    env: &dyn CheckEnvironment,
+    context: Option<&str>,
+) -> CheckStatus {
+    let version = match env.client(context) {
+        Ok(client) => client
+            .server_git_version()
+            .await
+            .map_err(|err| err.to_string()),
+        Err(err) => Err(err),
+    };
+    match version {
+        Ok(git_version) => CheckStatus::pass(format!(
+            "In-cluster service account config found, Kubernetes {git_version} is reachable"
+        )),
+        Err(err) => {
+            debug!(%err, "unable to reach kubernetes with the service account");
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::CannotConnectToKubernetes)
+        }
+    }
+}
+
+/// Checks that the service account of the pod can list services and create what Fluvio needs
+#[derive(Debug)]
+pub(crate) struct InClusterPermission {
+    namespace: String,
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+impl Default for InClusterPermission {
+    fn default() -> Self {
+        Self {
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for InClusterPermission {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let client = self
+            .env
+            .client(self.kube_context.as_deref())
+            .map_err(ClusterCheckError::K8ApiError)?;
+        review_service_account(client.as_access_reviewer(), &self.namespace).await
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "in-cluster-permission"
+    }
+
+    fn label(&self) -> &str {
+        "Service account permissions"
+    }
+
+    fn description(&self) -> &str {
+        "The service account of the pod can list services and create every resource Fluvio needs"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = namespace.to_string();
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Reviews every [`SERVICE_ACCOUNT_ACCESS`] at once, reporting the first one that is denied
+async fn review_service_account(reviewer: &dyn AccessReviewer, namespace: &str) -> CheckResult {
+    let reviews = SERVICE_ACCOUNT_ACCESS.iter().map(|access| async move {
+        let namespace = access.namespaced.then_some(namespace);
+        permission::review_access(reviewer, access.verb, access.resource, namespace)
+            .await
+            .map(|allowed| (access, allowed))
+    });
+    for review in join_all(reviews).await {
+        let (access, allowed) = review?;
+        if !allowed {
+            return Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::ServiceAccountPermissionError {
+                    verb: access.verb.to_string(),
+                    resource: access.resource.resource.to_string(),
+                },
+            ));
+        }
+    }
+    let allowed = SERVICE_ACCOUNT_ACCESS
+        .iter()
+        .map(|access| format!("{} {}", access.verb, access.resource.resource))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(CheckStatus::pass(format!("Service account can {allowed}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::permission::{SelfSubjectAccessReviewSpec, SubjectAccessReviewStatus};
+
+    use super::*;
+
+    /// Allows everything but one verb on one resource
+    struct DenyingReviewer {
+        verb: &'static str,
+        resource: &'static str,
+    }
+
+    #[async_trait]
+    impl AccessReviewer for DenyingReviewer {
+        async fn review(
+            &self,
+            spec: SelfSubjectAccessReviewSpec,
+        ) -> Result<SubjectAccessReviewStatus, ClusterCheckError> {
+            let attributes = spec.resource_attributes;
+            // custom resource definitions are cluster-wide, the rest is in the namespace
+            let cluster_wide = attributes.resource == "customresourcedefinitions";
+            assert_eq!(attributes.namespace.is_empty(), cluster_wide);
+            Ok(SubjectAccessReviewStatus {
+                allowed: attributes.verb != self.verb || attributes.resource != self.resource,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_service_account_allowed() {
+        let reviewer = DenyingReviewer {
+            verb: "delete",
+            resource: "services",
+        };
+
+        let status = review_service_account(&reviewer, "fluvio")
+            .await
+            .expect("status");
+
+        assert!(matches!(
+            status,
+            CheckStatus::Pass(message) if message == "Service account can list services, \
+                create services, create secrets, create customresourcedefinitions"
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_service_account_cannot_list_services() {
+        let reviewer = DenyingReviewer {
+            verb: "list",
+            resource: "services",
+        };
+
+        let status = review_service_account(&reviewer, "fluvio")
+            .await
+            .expect("status");
+
+        let CheckStatus::Unrecoverable(failure) = status else {
+            panic!("listing services is denied");
+        };
+        assert_eq!(
+            failure.to_string(),
+            "The service account of the pod is not allowed to list services"
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Guesses which Kubernetes distribution a kubeconfig context points to
+//!
+//! Some failures only have a fix on a given distribution, such as a
+//! LoadBalancer service that never gets an address on minikube or kind.
+//! Context and user names can be renamed freely (`minikube -p dev` creates a
+//! context named `dev`), so [`InfraDetector`] looks at the cluster name and
+//! server address as well, and asks minikube for its profiles as a last resort.
+
+use std::fmt;
+use std::process::Command;
+use std::time::Duration;
+
+use k8_config::{K8Config, KubeConfig};
+use serde::Deserialize;
+use tracing::debug;
+use url::Url;
+
+use super::environment::{CheckEnvironment, SystemEnvironment};
+use super::k8::k8_server_git_version;
+
+/// Port the minikube VM drivers serve the API on
+const MINIKUBE_API_PORT: u16 = 8443;
+
+/// How long `minikube profile list` may take
+const MINIKUBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Kubernetes distribution a cluster runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KubernetesInfra {
+    Minikube,
+    Kind,
+    K3d,
+    /// k3s outside of k3d, which only gives itself away through its server version
+    K3s,
+    DockerDesktop,
+    /// A managed cluster such as EKS, GKE or AKS
+    CloudProvider,
+    Unknown,
+}
+
+impl fmt::Display for KubernetesInfra {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let infra = match self {
+            Self::Minikube => "minikube",
+            Self::Kind => "kind",
+            Self::K3d => "k3d",
+            Self::K3s => "k3s",
+            Self::DockerDesktop => "Docker Desktop",
+            Self::CloudProvider => "cloud provider",
+            Self::Unknown => "unknown",
+        };
+        write!(f, "{infra}")
+    }
+}
+
+/// Detects the [`KubernetesInfra`] of the current kubeconfig context
+///
+/// # Example
+///
+/// ```no_run
+/// # use fluvio_cluster::{InfraDetector, KubernetesInfra};
+/// # async fn example() {
+/// if InfraDetector::detect().await == KubernetesInfra::Minikube {
+///     println!("run 'minikube tunnel' to get LoadBalancer addresses");
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct InfraDetector;
+
+impl InfraDetector {
+    /// Detects the infra of the current context
+    ///
+    /// When the kubeconfig alone is not conclusive, asks the server for its
+    /// version, and runs `minikube profile list` if the binary is installed.
+    pub async fn detect() -> KubernetesInfra {
+        Self::detect_context(None).await
+    }
+
+    /// Detects the infra of the kubeconfig context `context`, or of the current one if `None`
+    pub async fn detect_context(context: Option<&str>) -> KubernetesInfra {
+        Self::detect_in(&SystemEnvironment, context).await
+    }
+
+    /// Detects the infra of kubeconfig `context` as seen from `env`
+    pub async fn detect_in(env: &dyn CheckEnvironment, context: Option<&str>) -> KubernetesInfra {
+        let config = match super::context::load_config(env, context) {
+            Ok(K8Config::KubeConfig(context)) => context.config,
+            Ok(K8Config::Pod(_)) => return KubernetesInfra::Unknown,
+            Err(err) => {
+                debug!(%err, "no kubeconfig to detect infra from");
+                return KubernetesInfra::Unknown;
+            }
+        };
+
+        let infra = Self::from_kubeconfig(&config);
+        if infra != KubernetesInfra::Unknown {
+            return infra;
+        }
+        match k8_server_git_version(env, context).await {
+            Ok(Some(git_version)) if is_k3s_version(&git_version) => return KubernetesInfra::K3s,
+            Ok(_) => {}
+            Err(err) => debug!(%err, "unable to get server version"),
+        }
+        let profiles = minikube_profiles(env).await;
+        if cluster_names(&config)
+            .iter()
+            .any(|name| profiles.iter().any(|profile| profile == name))
+        {
+            KubernetesInfra::Minikube
+        } else {
+            KubernetesInfra::Unknown
+        }
+    }
+
+    /// Detects the infra of the current context from the kubeconfig alone
+    pub fn from_kubeconfig(config: &KubeConfig) -> KubernetesInfra {
+        let server = config
+            .current_cluster()
+            .map(|cluster| cluster.cluster.server.as_str())
+            .unwrap_or_default();
+        detect_infra(&cluster_names(config), server)
+    }
+}
+
+/// Names the current cluster goes by, its context name and its cluster name
+fn cluster_names(config: &KubeConfig) -> Vec<&str> {
+    let mut names = vec![config.current_context.as_str()];
+    if let Some(cluster) = config.current_cluster() {
+        names.push(cluster.name.as_str());
+    }
+    names
+}
+
+fn detect_infra(names: &[&str], server: &str) -> KubernetesInfra {
+    let is_named = |matches: &dyn Fn(&str) -> bool| names.iter().any(|&name| matches(name));
+    let server = Url::parse(server).ok();
+    let host = server
+        .as_ref()
+        .and_then(|url| url.host_str())
+        .unwrap_or_default();
+
+    if is_named(&|name| name == "minikube") {
+        KubernetesInfra::Minikube
+    } else if is_named(&|name| name.starts_with("kind-")) {
+        KubernetesInfra::Kind
+    } else if is_named(&|name| name.starts_with("k3d-")) {
+        KubernetesInfra::K3d
+    } else if is_named(&|name| name == "docker-desktop" || name == "docker-for-desktop") {
+        KubernetesInfra::DockerDesktop
+    } else if is_named(&|name| name.starts_with("gke_") || name.starts_with("arn:aws:eks:"))
+        || host.ends_with(".eks.amazonaws.com")
+        || host.ends_with(".azmk8s.io")
+    {
+        KubernetesInfra::CloudProvider
+    } else if host.starts_with("192.168.")
+        && server.as_ref().and_then(|url| url.port()) == Some(MINIKUBE_API_PORT)
+    {
+        KubernetesInfra::Minikube
+    } else {
+        KubernetesInfra::Unknown
+    }
+}
+
+/// Whether a server `gitVersion` carries k3s build metadata, such as `v1.27.4+k3s1`
+fn is_k3s_version(git_version: &str) -> bool {
+    git_version
+        .split_once('+')
+        .is_some_and(|(_, build)| build.starts_with("k3s"))
+}
+
+/// Names of the valid minikube profiles, empty if minikube is not installed
+async fn minikube_profiles(env: &dyn CheckEnvironment) -> Vec<String> {
+    let mut command = Command::new("minikube");
+    command.arg("profile").arg("list").arg("-o").arg("json");
+    match env.command_output(command, MINIKUBE_TIMEOUT).await {
+        Ok(output) => parse_minikube_profiles(&output.stdout),
+        Err(err) => {
+            debug!(%err, "unable to list minikube profiles");
+            vec![]
+        }
+    }
+}
+
+fn parse_minikube_profiles(output: &[u8]) -> Vec<String> {
+    #[derive(Debug, Deserialize)]
+    struct Profile {
+        #[serde(rename = "Name")]
+        name: String,
+    }
+
+    #[derive(Debug, Deserialize)]
+    struct Profiles {
+        #[serde(default)]
+        valid: Vec<Profile>,
+    }
+
+    match serde_json::from_slice::<Profiles>(output) {
+        Ok(profiles) => profiles
+            .valid
+            .into_iter()
+            .map(|profile| profile.name)
+            .collect(),
+        Err(err) => {
+            debug!(%err, "unable to parse minikube profiles");
+            vec![]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_infra_by_name() {
+        assert_eq!(
+            detect_infra(&["minikube", "minikube"], "https://127.0.0.1:32769"),
+            KubernetesInfra::Minikube
+        );
+        assert_eq!(
+            detect_infra(&["kind-fluvio", "kind-fluvio"], "https://127.0.0.1:40000"),
+            KubernetesInfra::Kind
+        );
+        assert_eq!(
+            detect_infra(&["k3d-dev", "k3d-dev"], "https://0.0.0.0:6550"),
+            KubernetesInfra::K3d
+        );
+        assert_eq!(
+            detect_infra(
+                &["docker-desktop"],
+                "https://kubernetes.docker.internal:6443"
+            ),
+            KubernetesInfra::DockerDesktop
+        );
+        assert_eq!(
+            detect_infra(
+                &[
+                    "gke_project_us-central1_prod",
+                    "gke_project_us-central1_prod"
+                ],
+                "https://34.66.1.2"
+            ),
+            KubernetesInfra::CloudProvider
+        );
+    }
+
+    #[test]
+    fn test_detect_renamed_infra() {
+        // `minikube -p dev` with a VM driver
+        assert_eq!(
+            detect_infra(&["dev", "dev"], "https://192.168.49.2:8443"),
+            KubernetesInfra::Minikube
+        );
+        assert_eq!(
+            detect_infra(
+                &["prod", "prod"],
+                "https://ABCDEF.gr7.us-east-1.eks.amazonaws.com"
+            ),
+            KubernetesInfra::CloudProvider
+        );
+        assert_eq!(
+            detect_infra(&["dev", "dev"], "https://10.0.0.1:6443"),
+            KubernetesInfra::Unknown
+        );
+    }
+
+    #[test]
+    fn test_k3s_version() {
+        assert!(is_k3s_version("v1.27.4+k3s1"));
+        assert!(!is_k3s_version("v1.27.4"));
+        assert!(!is_k3s_version("v1.27.4-eks-2d98532"));
+        assert!(!is_k3s_version("v1.27.4+rke2r1"));
+    }
+
+    #[test]
+    fn test_parse_minikube_profiles() {
+        let output = br#"{
+            "invalid": [{ "Name": "broken" }],
+            "valid": [{ "Name": "minikube", "Status": "Running" }, { "Name": "dev" }]
+        }"#;
+        assert_eq!(parse_minikube_profiles(output), vec!["minikube", "dev"]);
+        assert!(parse_minikube_profiles(b"").is_empty());
+    }
+}
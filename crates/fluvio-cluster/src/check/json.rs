@@ -0,0 +1,620 @@
+//! Machine-readable check results
+//!
+//! Every check result becomes an object with a `status` of `pass`, `fail`,
+//! `error` or `skipped`, a `message` and an optional `suggestion`. A check
+//! that could not be performed also carries an `error` object whose `kind`
+//! names the [`ClusterCheckError`] variant. Errors wrapping a foreign error,
+//! such as a helm or Kubernetes config error, add its text as `source`.
+
+use std::error::Error;
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::{json, Value};
+
+use super::{
+    CheckData, CheckOutcome, CheckResults, CheckStatus, CheckSuggestion, ClusterCheckError,
+    ClusterChecker, RecoverableCheck, UnrecoverableCheckStatus,
+};
+
+impl ClusterChecker {
+    /// Converts `results` into a JSON array, one object per check
+    pub fn serialize_results(results: &CheckResults) -> Value {
+        json!(results)
+    }
+}
+
+impl CheckResults {
+    /// The results as a JSON array, see [`ClusterChecker::serialize_results`]
+    pub fn to_json(&self) -> String {
+        ClusterChecker::serialize_results(self).to_string()
+    }
+}
+
+impl Serialize for CheckResults {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl Serialize for CheckOutcome {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut entry = match &self.result {
+            Ok(status) => json!(status),
+            Err(err) => json!({
+                "status": "error",
+                "message": err.to_string(),
+                "suggestion": null,
+                "error": err,
+            }),
+        };
+        entry["check"] = Value::String(self.name.clone());
+        entry.serialize(serializer)
+    }
+}
+
+impl Serialize for CheckStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            Self::Pass(message) => {
+                map.serialize_entry("status", "pass")?;
+                map.serialize_entry("message", message)?;
+                map.serialize_entry("suggestion", &None::<String>)?;
+            }
+            Self::PassWithData(message, data) => {
+                map.serialize_entry("status", "pass")?;
+                map.serialize_entry("message", message)?;
+                map.serialize_entry("suggestion", &None::<String>)?;
+                map.serialize_entry("data", data)?;
+            }
+            Self::AutoFixableError { message, .. } => {
+                map.serialize_entry("status", "fail")?;
+                map.serialize_entry("message", message)?;
+                map.serialize_entry("suggestion", "Use `--fix` to fix it automatically")?;
+                map.serialize_entry("fixable", &true)?;
+            }
+            Self::Unrecoverable(failure) => {
+                map.serialize_entry("status", "fail")?;
+                map.serialize_entry("message", &failure.to_string())?;
+                map.serialize_entry("suggestion", &failure.suggestion())?;
+                map.serialize_entry("kind", failure_kind(failure))?;
+            }
+            Self::Skipped(reason) => {
+                map.serialize_entry("status", "skipped")?;
+                map.serialize_entry("message", reason)?;
+                map.serialize_entry("suggestion", &None::<String>)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for CheckData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            Self::LoadBalancerAddress(address) => {
+                map.serialize_entry("kind", "load_balancer_address")?;
+                map.serialize_entry("address", address)?;
+            }
+            Self::NodePortRecommended => {
+                map.serialize_entry("kind", "node_port_recommended")?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for RecoverableCheck {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let kind = match self {
+            Self::MissingSystemChart => "missing_system_chart",
+            Self::UpgradeSystemChart => "upgrade_system_chart",
+            Self::OutdatedSystemChart { .. } => "outdated_system_chart",
+        };
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("kind", kind)?;
+        map.serialize_entry("message", &self.to_string())?;
+        map.serialize_entry("suggestion", &self.suggestion())?;
+        map.end()
+    }
+}
+
+impl Serialize for UnrecoverableCheckStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("kind", failure_kind(self))?;
+        map.serialize_entry("message", &self.to_string())?;
+        map.serialize_entry("suggestion", &self.suggestion())?;
+        map.end()
+    }
+}
+
+impl Serialize for ClusterCheckError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", error_kind(self))?;
+        map.serialize_entry("message", &self.to_string())?;
+        if let Some(source) = self.source() {
+            map.serialize_entry("source", &source.to_string())?;
+        }
+        map.end()
+    }
+}
+
+fn failure_kind(failure: &UnrecoverableCheckStatus) -> &'static str {
+    use UnrecoverableCheckStatus::*;
+
+    match failure {
+        PermissionError { .. } => "permission_error",
+        IncompatibleHelmVersion { .. } => "incompatible_helm_version",
+        IncompatibleKubectlVersion { .. } => "incompatible_kubectl_version",
+        IncompatibleKubectlVersionTooNew { .. } => "incompatible_kubectl_version_too_new",
+        NoActiveKubernetesContext => "no_active_kubernetes_context",
+        KubeContextNotFound { .. } => "kube_context_not_found",
+        CannotConnectToKubernetes => "cannot_connect_to_kubernetes",
+        MultipleSystemCharts => "multiple_system_charts",
+        SysChartNeedsCurrentContext { .. } => "sys_chart_needs_current_context",
+        IncompatibleSystemChart { .. } => "incompatible_system_chart",
+        AlreadyInstalled { .. } => "already_installed",
+        NoInstallationToUpgrade { .. } => "no_installation_to_upgrade",
+        UpgradeIsDowngrade { .. } => "upgrade_is_downgrade",
+        BreakingUpgrade { .. } => "breaking_upgrade",
+        ConflictingInstallations { .. } => "conflicting_installations",
+        MissingKubernetesServerHost => "missing_kubernetes_server_host",
+        LoadBalancerServiceNotAvailable => "load_balancer_service_not_available",
+        DockerDesktopLoadBalancerNotAvailable => "docker_desktop_load_balancer_not_available",
+        MinikubeTunnelNotFound => "minikube_tunnel_not_found",
+        NoHelmClient(_) => "no_helm_client",
+        NodesNotReady { .. } => "nodes_not_ready",
+        ChartVersionNotFound { .. } => "chart_version_not_found",
+        ChartRepoUnreachable { .. } => "chart_repo_unreachable",
+        BinaryNotFound { .. } => "binary_not_found",
+        UnhandledK8ClientError(_) => "unhandled_k8_client_error",
+        ExistingLocalCluster => "existing_local_cluster",
+        ScAddressUnresolved { .. } => "sc_address_unresolved",
+        ScConnectionRefused { .. } => "sc_connection_refused",
+        ScHandshakeTimeout { .. } => "sc_handshake_timeout",
+        PortInUse { .. } => "port_in_use",
+        InsufficientDiskSpace { .. } => "insufficient_disk_space",
+        MissingOpenShiftScc { .. } => "missing_openshift_scc",
+        NamespacePermissionError { .. } => "namespace_permission_error",
+        ServiceAccountPermissionError { .. } => "service_account_permission_error",
+        InvalidTlsSecret { .. } => "invalid_tls_secret",
+        HelmClientError => "helm_client_error",
+        ExecCredentialPlugin(_) => "exec_credential_plugin",
+        FailedRecovery { .. } => "failed_recovery",
+        Other(_) => "other",
+    }
+}
+
+fn error_kind(err: &ClusterCheckError) -> &'static str {
+    use ClusterCheckError::*;
+
+    match err {
+        #[cfg(feature = "helm-checks")]
+        HelmError(_) => "helm_error",
+        K8ConfigError(_) => "k8_config_error",
+        BadKubernetesServerUrl(_) => "bad_kubernetes_server_url",
+        #[cfg(feature = "kubectl-checks")]
+        KubectlNotFoundError { .. } => "kubectl_not_found",
+        #[cfg(feature = "kubectl-checks")]
+        FetchPermissionError => "fetch_permission_error",
+        #[cfg(feature = "kubectl-checks")]
+        KubectlFailed(_) => "kubectl_failed",
+        K8ApiError(_) => "k8_api_error",
+        K8ApiForbidden(_) => "k8_api_forbidden",
+        #[cfg(feature = "kubectl-checks")]
+        KubectlVersionJsonError(_) => "kubectl_version_json_error",
+        CommandTimedOut { .. } => "command_timed_out",
+        InvalidServerVersion(_) => "invalid_server_version",
+        ServiceCreateError(_) => "service_create_error",
+        ServiceDeleteError(_) => "service_delete_error",
+        VersionError(_) => "version_error",
+        LocalClusterExists => "local_cluster_exists",
+        Other(_) => "other",
+        PreCheckFlightFailure => "pre_check_flight_failure",
+        TimedOut { .. } => "timed_out",
+        Cancelled => "cancelled",
+        EnvironmentChanged(_) => "environment_changed",
+        ProgressError(_) => "progress_error",
+        UnknownCheck { .. } => "unknown_check",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::check::{CheckOutcome, CheckResult, CommandOutput};
+
+    use super::*;
+
+    fn outcome(name: &str, result: CheckResult) -> CheckOutcome {
+        CheckOutcome {
+            name: name.to_string(),
+            result,
+        }
+    }
+
+    #[test]
+    fn test_serialize_results() {
+        let results: CheckResults = vec![
+            outcome(
+                "helm-version",
+                Ok(CheckStatus::pass("Supported helm version 3.9.0")),
+            ),
+            outcome(
+                "kubernetes-cluster",
+                Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::NoActiveKubernetesContext,
+                )),
+            ),
+            outcome(
+                "local-disk",
+                Ok(CheckStatus::skipped("not a local install")),
+            ),
+            outcome(
+                "sys-chart",
+                Err(ClusterCheckError::TimedOut {
+                    check: "sys-chart".to_string(),
+                    timeout: Duration::from_secs(2),
+                }),
+            ),
+        ]
+        .into();
+
+        assert_eq!(
+            ClusterChecker::serialize_results(&results),
+            json!([
+                {
+                    "check": "helm-version",
+                    "status": "pass",
+                    "message": "Supported helm version 3.9.0",
+                    "suggestion": null,
+                },
+                {
+                    "check": "kubernetes-cluster",
+                    "status": "fail",
+                    "kind": "no_active_kubernetes_context",
+                    "message": "There is no active Kubernetes context",
+                    "suggestion": null,
+                },
+                {
+                    "check": "local-disk",
+                    "status": "skipped",
+                    "message": "not a local install",
+                    "suggestion": null,
+                },
+                {
+                    "check": "sys-chart",
+                    "status": "error",
+                    "message": "Check sys-chart timed out after 2s",
+                    "suggestion": null,
+                    "error": {
+                        "kind": "timed_out",
+                        "message": "Check sys-chart timed out after 2s",
+                    },
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_serialize_failure_suggestion() {
+        let failure = UnrecoverableCheckStatus::ConflictingInstallations {
+            namespace: "dev".to_string(),
+            installations: vec![],
+        };
+        let value = json!(CheckStatus::Unrecoverable(failure));
+
+        assert_eq!(value["status"], "fail");
+        assert_eq!(value["kind"], "conflicting_installations");
+        assert!(value["suggestion"]
+            .as_str()
+            .unwrap()
+            .contains("fluvio cluster upgrade"));
+    }
+
+    #[test]
+    fn test_serialize_pass_with_data() {
+        let value = json!(CheckStatus::pass_with_data(
+            "Load balancer fluvio-sc-public has address 10.0.0.7",
+            CheckData::LoadBalancerAddress("10.0.0.7".to_string()),
+        ));
+
+        assert_eq!(
+            value,
+            json!({
+                "status": "pass",
+                "message": "Load balancer fluvio-sc-public has address 10.0.0.7",
+                "suggestion": null,
+                "data": {
+                    "kind": "load_balancer_address",
+                    "address": "10.0.0.7",
+                },
+            })
+        );
+    }
+
+    fn every_failure() -> Vec<UnrecoverableCheckStatus> {
+        use UnrecoverableCheckStatus::*;
+
+        vec![
+            PermissionError {
+                resource: "service".to_string(),
+            },
+            IncompatibleHelmVersion {
+                installed: "2.16.1".to_string(),
+                required: "3.0.0".to_string(),
+            },
+            IncompatibleKubectlVersion {
+                installed: "1.6.0".to_string(),
+                required: "1.7.0".to_string(),
+            },
+            IncompatibleKubectlVersionTooNew {
+                installed: "1.30.0".to_string(),
+                max_supported: "1.29.99".to_string(),
+            },
+            NoActiveKubernetesContext,
+            KubeContextNotFound {
+                name: "kind-gone".to_string(),
+            },
+            CannotConnectToKubernetes,
+            MultipleSystemCharts,
+            SysChartNeedsCurrentContext {
+                context: "kind-fluvio".to_string(),
+                reason: "System chart not installed, installing version 0.11.0".to_string(),
+            },
+            IncompatibleSystemChart {
+                installed: "0.9.0".to_string(),
+                required: "0.10.0".to_string(),
+            },
+            AlreadyInstalled {
+                name: "fluvio".to_string(),
+                namespace: "default".to_string(),
+                version: "0.10.0".to_string(),
+                target: Some("0.11.0".to_string()),
+            },
+            NoInstallationToUpgrade {
+                namespace: "dev".to_string(),
+            },
+            UpgradeIsDowngrade {
+                installed: "0.11.3".to_string(),
+                target: "0.11.2".to_string(),
+            },
+            BreakingUpgrade {
+                installed: "0.9.33".to_string(),
+                target: "0.11.2".to_string(),
+                breaking: "0.10.0".to_string(),
+                reason: "clusters from 0.9 and earlier are not compatible with it".to_string(),
+            },
+            ConflictingInstallations {
+                namespace: "dev".to_string(),
+                installations: vec![],
+            },
+            MissingKubernetesServerHost,
+            LoadBalancerServiceNotAvailable,
+            DockerDesktopLoadBalancerNotAvailable,
+            MinikubeTunnelNotFound,
+            NoHelmClient("helm not found".to_string()),
+            NodesNotReady {
+                ready: 0,
+                required: 1,
+                not_ready: vec!["minikube (Ready=False)".to_string()],
+            },
+            ChartVersionNotFound {
+                chart: "fluvio".to_string(),
+                version: "0.11.2".to_string(),
+                available: vec!["0.11.1".to_string(), "0.12.0".to_string()],
+            },
+            ChartRepoUnreachable {
+                url: "https://charts.fluvio.io".to_string(),
+                reason: "connection refused".to_string(),
+            },
+            BinaryNotFound {
+                binary: "helm".to_string(),
+                searched: "PATH".to_string(),
+            },
+            UnhandledK8ClientError("connection refused".to_string()),
+            ExistingLocalCluster,
+            ScAddressUnresolved {
+                address: "fluvio-sc.invalid:9003".to_string(),
+            },
+            ScConnectionRefused {
+                address: "127.0.0.1:9003".to_string(),
+            },
+            ScHandshakeTimeout {
+                address: "127.0.0.1:9003".to_string(),
+                timeout: Duration::from_secs(5),
+            },
+            PortInUse {
+                port: 9003,
+                process: Some("fluvio-run (pid 4242)".to_string()),
+            },
+            InsufficientDiskSpace {
+                available: 512 * 1024 * 1024,
+                required: 1024 * 1024 * 1024,
+            },
+            MissingOpenShiftScc {
+                service_account: "fluvio".to_string(),
+                namespace: "fluvio".to_string(),
+            },
+            NamespacePermissionError {
+                namespace: "fluvio".to_string(),
+            },
+            ServiceAccountPermissionError {
+                verb: "list".to_string(),
+                resource: "services".to_string(),
+            },
+            InvalidTlsSecret {
+                secret: "fluvio-tls".to_string(),
+                namespace: "fluvio".to_string(),
+                reason: "secret not found".to_string(),
+            },
+            HelmClientError,
+            ExecCredentialPlugin(crate::check::ExecPluginError::NotFound {
+                plugin: "aws".to_string(),
+            }),
+            FailedRecovery {
+                check: "sys-chart".to_string(),
+                error: "Chart Install error".to_string(),
+            },
+            Other("unexpected".to_string()),
+        ]
+    }
+
+    fn kubectl(args: &[&str], status: Option<i32>, stderr: &str) -> CommandOutput {
+        CommandOutput {
+            program: "kubectl".to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            status,
+            stderr: stderr.to_string(),
+        }
+    }
+
+    fn every_error() -> Vec<ClusterCheckError> {
+        use ClusterCheckError::*;
+
+        vec![
+            K8ConfigError(k8_config::ConfigError::NoCurrentContext),
+            BadKubernetesServerUrl(url::Url::parse("not a url").unwrap_err()),
+            #[cfg(feature = "kubectl-checks")]
+            KubectlNotFoundError {
+                command: kubectl(&["version", "-o=json"], None, ""),
+                source: std::io::ErrorKind::NotFound.into(),
+            },
+            #[cfg(feature = "kubectl-checks")]
+            FetchPermissionError,
+            #[cfg(feature = "kubectl-checks")]
+            KubectlFailed(kubectl(
+                &["auth", "can-i", "create", "pods"],
+                Some(1),
+                "error: You must be logged in to the server",
+            )),
+            K8ApiError("connection refused".to_string()),
+            K8ApiForbidden("services is forbidden".to_string()),
+            #[cfg(feature = "kubectl-checks")]
+            KubectlVersionJsonError(serde_json::from_str::<Value>("{").unwrap_err()),
+            CommandTimedOut {
+                command: "kubectl".to_string(),
+                timeout: Duration::from_secs(30),
+            },
+            InvalidServerVersion("v1.25".to_string()),
+            ServiceCreateError(kubectl(&["create", "service"], Some(1), "error: forbidden")),
+            ServiceDeleteError(kubectl(&["delete", "service"], Some(1), "")),
+            VersionError(semver::Version::parse("one").unwrap_err()),
+            LocalClusterExists,
+            Other("unexpected".to_string()),
+            PreCheckFlightFailure,
+            TimedOut {
+                check: "sys-chart".to_string(),
+                timeout: Duration::from_secs(2),
+            },
+            Cancelled,
+            EnvironmentChanged(Default::default()),
+            UnknownCheck {
+                name: "helm".to_string(),
+                known: vec!["helm-version".to_string()],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_serialize_every_failure() {
+        let failures = every_failure();
+        let mut kinds = vec![];
+        for failure in &failures {
+            let value = json!(failure);
+            assert_eq!(value["message"], failure.to_string());
+            assert_eq!(value["suggestion"], json!(failure.suggestion()));
+            kinds.push(value["kind"].as_str().unwrap().to_string());
+        }
+        kinds.sort();
+        kinds.dedup();
+        assert_eq!(kinds.len(), failures.len(), "kinds are unique");
+    }
+
+    #[test]
+    fn test_serialize_every_recoverable_check() {
+        for check in [
+            RecoverableCheck::MissingSystemChart,
+            RecoverableCheck::UpgradeSystemChart,
+            RecoverableCheck::OutdatedSystemChart {
+                installed: "0.10.0".to_string(),
+                required: "0.11.0".to_string(),
+            },
+        ] {
+            let value = json!(check);
+            assert_eq!(value["message"], check.to_string());
+            assert_eq!(value["suggestion"], json!(check.suggestion()));
+        }
+    }
+
+    #[test]
+    fn test_serialize_every_error() {
+        let errors = every_error();
+        let mut kinds = vec![];
+        for err in &errors {
+            let value = json!(err);
+            assert_eq!(value["message"], err.to_string());
+            match err.source() {
+                Some(source) => assert_eq!(value["source"], source.to_string()),
+                None => assert!(value.get("source").is_none()),
+            }
+            kinds.push(value["kind"].as_str().unwrap().to_string());
+        }
+        kinds.sort();
+        kinds.dedup();
+        assert_eq!(kinds.len(), errors.len(), "kinds are unique");
+    }
+
+    #[test]
+    fn test_serialize_wrapped_error_source() {
+        let value = json!(ClusterCheckError::K8ConfigError(
+            k8_config::ConfigError::NoCurrentContext
+        ));
+
+        assert_eq!(value["kind"], "k8_config_error");
+        assert_eq!(value["message"], "Kubernetes config error");
+        assert_eq!(
+            value["source"],
+            k8_config::ConfigError::NoCurrentContext.to_string()
+        );
+    }
+
+    #[test]
+    fn test_to_json_round_trip() {
+        let results: CheckResults = every_failure()
+            .into_iter()
+            .map(|failure| Ok(CheckStatus::Unrecoverable(failure)))
+            .chain(every_error().into_iter().map(Err))
+            .chain([
+                Ok(CheckStatus::pass("ok")),
+                Ok(CheckStatus::pass_with_data(
+                    "found",
+                    CheckData::LoadBalancerAddress("10.0.0.7".to_string()),
+                )),
+                Ok(CheckStatus::skipped("not needed")),
+            ])
+            .enumerate()
+            .map(|(idx, result)| outcome(&format!("check-{idx}"), result))
+            .collect();
+
+        let parsed: Value = serde_json::from_str(&results.to_json()).unwrap();
+
+        assert_eq!(parsed, ClusterChecker::serialize_results(&results));
+        let entries = parsed.as_array().unwrap();
+        assert_eq!(entries.len(), results.len());
+        for (entry, outcome) in entries.iter().zip(results.iter()) {
+            assert_eq!(entry["check"], outcome.name);
+            let status = match &outcome.result {
+                Ok(CheckStatus::Pass(_) | CheckStatus::PassWithData(..)) => "pass",
+                Ok(CheckStatus::Skipped(_)) => "skipped",
+                Ok(_) => "fail",
+                Err(_) => "error",
+            };
+            assert_eq!(entry["status"], status);
+        }
+    }
+}
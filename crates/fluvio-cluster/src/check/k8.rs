@@ -0,0 +1,606 @@
+//! Checks that a Kubernetes cluster can be reached and runs a supported version
+//!
+//! The server version is read through the Kubernetes client, falling back to
+//! `kubectl version` when no client can be built and the `kubectl-checks`
+//! feature is enabled.
+
+#[cfg(feature = "kubectl-checks")]
+use std::process::Command;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use semver::Version;
+use tracing::debug;
+
+use k8_client::K8Client;
+use k8_config::{ConfigError as K8ConfigError, K8Config};
+
+use crate::render::ProgressRenderer;
+
+#[cfg(feature = "kubectl-checks")]
+use super::command::kubectl_output;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::{context, exec_plugin, in_cluster};
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+const KUBE_VERSION: &str = "1.7.0";
+
+/// Check for loading
+#[derive(Debug, Default)]
+pub(crate) struct ActiveKubernetesCluster {
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+#[async_trait]
+impl ClusterCheck for ActiveKubernetesCluster {
+    /// Checks that we can connect to Kubernetes via the active context, or the
+    /// service account when running in a pod
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let config = match context::load_config(&*self.env, self.kube_context.as_deref()) {
+            Ok(config) => config,
+            Err(ClusterCheckError::K8ConfigError(K8ConfigError::NoCurrentContext)) => {
+                return Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::NoActiveKubernetesContext,
+                ))
+            }
+
+            Err(err) => {
+                return Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::UnhandledK8ClientError(format!("K8 Error: {err:#?}")),
+                ))
+            }
+        };
+
+        let context = match config {
+            K8Config::Pod(_) => {
+                return Ok(in_cluster::service_account_status(
+                    &*self.env,
+                    self.kube_context.as_deref(),
+                )
+                .await)
+            }
+            K8Config::KubeConfig(context) => context,
+        };
+
+        let cluster = match context.config.current_cluster() {
+            Some(cluster) => cluster,
+            None => {
+                return Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::NoActiveKubernetesContext,
+                ))
+            }
+        };
+
+        // EKS, GKE and friends authenticate through an external binary; surface
+        // its failures here instead of as an opaque client error later on
+        if let Some(exec) = context
+            .config
+            .current_user()
+            .and_then(|user| user.user.exec.as_ref())
+        {
+            if let Err(err) =
+                exec_plugin::check_exec_plugin(&*self.env, &exec.command, &exec.args).await
+            {
+                debug!(%err, "exec credential plugin failed");
+                return Ok(CheckStatus::Unrecoverable(err.into()));
+            }
+        }
+
+        Ok(CheckStatus::pass(format!(
+            "Kubectl active cluster {} at: {} found",
+            context.config.current_context, cluster.cluster.server
+        )))
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![]
+    }
+
+    fn component(&self) -> Option<FluvioClusterComponent> {
+        Some(FluvioClusterComponent::Kubernetes)
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "kubernetes-cluster"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes config"
+    }
+
+    fn description(&self) -> &str {
+        "The active kubeconfig context or in-pod service account reaches a Kubernetes cluster"
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct K8Version {
+    required: Version,
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+impl Default for K8Version {
+    fn default() -> Self {
+        Self {
+            required: Version::parse(KUBE_VERSION).expect("valid kubernetes version"),
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for K8Version {
+    /// Check that the Kubernetes server is recent enough
+    async fn perform_check(&self, _: &ProgressRenderer) -> CheckResult {
+        match k8_server_git_version(&*self.env, self.kube_context.as_deref()).await? {
+            Some(git_version) => k8_version_status(&git_version, &self.required),
+            None => Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::CannotConnectToKubernetes,
+            )),
+        }
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn component(&self) -> Option<FluvioClusterComponent> {
+        Some(FluvioClusterComponent::K8Version)
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "kubernetes-version"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes version"
+    }
+
+    fn description(&self) -> &str {
+        "The Kubernetes server is the required version or later"
+    }
+
+    fn set_required_version(&mut self, component: &FluvioClusterComponent, version: &Version) {
+        if *component == FluvioClusterComponent::K8Version {
+            self.required = version.clone();
+        }
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Checks that the Kubernetes server version is between `min` and `max`, both included
+///
+/// Unlike the default Kubernetes version check, this also fails on servers
+/// newer than Fluvio has been validated against.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ClusterChecker, K8VersionRange};
+/// let check = K8VersionRange::new("1.21.0", "1.29.99");
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+#[derive(Debug, Clone)]
+pub struct K8VersionRange {
+    /// The oldest supported version
+    pub min: String,
+    /// The newest supported version
+    pub max: String,
+    /// Kubeconfig context of the cluster, `None` for the current one
+    pub kube_context: Option<String>,
+    env: Environment,
+}
+
+impl K8VersionRange {
+    /// Requires a server version between `min` and `max`, in the current context
+    pub fn new(min: impl Into<String>, max: impl Into<String>) -> Self {
+        Self {
+            min: min.into(),
+            max: max.into(),
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for K8VersionRange {
+    async fn perform_check(&self, _: &ProgressRenderer) -> CheckResult {
+        let min = Version::parse(&self.min)?;
+        let max = Version::parse(&self.max)?;
+        match k8_server_git_version(&*self.env, self.kube_context.as_deref()).await? {
+            Some(git_version) => k8_version_range_status(&git_version, &min, &max),
+            None => Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::CannotConnectToKubernetes,
+            )),
+        }
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn component(&self) -> Option<FluvioClusterComponent> {
+        Some(FluvioClusterComponent::K8Version)
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "kubernetes-version-range"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes version"
+    }
+
+    fn description(&self) -> &str {
+        "The Kubernetes server is a supported version, neither too old nor too new"
+    }
+
+    fn set_required_version(&mut self, component: &FluvioClusterComponent, version: &Version) {
+        if *component == FluvioClusterComponent::K8Version {
+            self.min = version.to_string();
+        }
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Checks a Kubernetes `gitVersion` such as `v1.25.3` against `required`
+fn k8_version_status(git_version: &str, required: &Version) -> CheckResult {
+    let installed = parse_server_version(git_version)?;
+    if installed < *required {
+        Ok(CheckStatus::Unrecoverable(
+            UnrecoverableCheckStatus::IncompatibleKubectlVersion {
+                installed: installed.to_string(),
+                required: required.to_string(),
+            },
+        ))
+    } else {
+        Ok(CheckStatus::pass(format!(
+            "Supported Kubernetes server {installed} found"
+        )))
+    }
+}
+
+fn k8_version_range_status(git_version: &str, min: &Version, max: &Version) -> CheckResult {
+    let installed = parse_server_version(git_version)?;
+    if installed > *max {
+        return Ok(CheckStatus::Unrecoverable(
+            UnrecoverableCheckStatus::IncompatibleKubectlVersionTooNew {
+                installed: installed.to_string(),
+                max_supported: max.to_string(),
+            },
+        ));
+    }
+    k8_version_status(git_version, min)
+}
+
+/// The version of the Kubernetes API server, from the client or a fake cluster in tests
+#[async_trait]
+pub trait ServerVersionSource: Send + Sync {
+    /// `gitVersion` of the server, such as `v1.25.3`
+    async fn server_git_version(&self) -> Result<String, ClusterCheckError>;
+}
+
+#[async_trait]
+impl ServerVersionSource for K8Client {
+    async fn server_git_version(&self) -> Result<String, ClusterCheckError> {
+        let version = self.server_version().await.map_err(context::k8_api_error)?;
+        Ok(version.git_version)
+    }
+}
+
+/// `gitVersion` of the Kubernetes server of kubeconfig `context`, `None` if it can't be reached
+pub(crate) async fn k8_server_git_version(
+    env: &dyn CheckEnvironment,
+    context: Option<&str>,
+) -> Result<Option<String>, ClusterCheckError> {
+    match env.client(context) {
+        Ok(client) => client.server_git_version().await.map(Some),
+        #[cfg(feature = "kubectl-checks")]
+        Err(err) => {
+            debug!(%err, "no kubernetes client, asking kubectl instead");
+            kubectl_server_version(env, context).await
+        }
+        #[cfg(not(feature = "kubectl-checks"))]
+        Err(err) => Err(ClusterCheckError::K8ApiError(err)),
+    }
+}
+
+/// Parses a `gitVersion`, ignoring the leading `v` and distro build metadata
+/// such as the `+k3s1` in `v1.25.3+k3s1`
+fn parse_server_version(git_version: &str) -> Result<Version, ClusterCheckError> {
+    let version = git_version.trim();
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let version = version.split('+').next().unwrap_or_default();
+    Version::parse(version)
+        .map_err(|_| ClusterCheckError::InvalidServerVersion(git_version.to_string()))
+}
+
+/// How long kubectl waits for the server when asked for its version a second time
+#[cfg(feature = "kubectl-checks")]
+const KUBECTL_VERSION_RETRY_TIMEOUT: &str = "20s";
+
+/// Server version reported by `kubectl version`, `None` if kubectl can't reach the server
+#[cfg(feature = "kubectl-checks")]
+async fn kubectl_server_version(
+    env: &dyn CheckEnvironment,
+    context: Option<&str>,
+) -> Result<Option<String>, ClusterCheckError> {
+    let output = kubectl_output(env, kubectl_version_command(context, None)).await?;
+    let version = parse_kubectl_version(&output.stdout)?;
+    // kubectl exits with an error when it can't reach the server; succeeding
+    // without a server version means the server was reachable but didn't answer
+    // in time, so ask once more and give it longer
+    if version.is_none() && output.status.success() {
+        debug!("kubectl printed no server version, retrying");
+        let command = kubectl_version_command(context, Some(KUBECTL_VERSION_RETRY_TIMEOUT));
+        let output = kubectl_output(env, command).await?;
+        return parse_kubectl_version(&output.stdout);
+    }
+    Ok(version)
+}
+
+#[cfg(feature = "kubectl-checks")]
+fn kubectl_version_command(context: Option<&str>, request_timeout: Option<&str>) -> Command {
+    let mut command = context::kubectl_command(context);
+    command.arg("version").arg("-o=json");
+    if let Some(timeout) = request_timeout {
+        command.arg(format!("--request-timeout={timeout}"));
+    }
+    command
+}
+
+/// Server `gitVersion` from the JSON output of `kubectl version`
+///
+/// Lines before the JSON, such as the version skew warning of recent kubectl
+/// releases when stderr is merged into stdout, are skipped, and so is anything
+/// after it. `None` when the output has no server version, as with `--client`.
+#[cfg(feature = "kubectl-checks")]
+fn parse_kubectl_version(output: &[u8]) -> Result<Option<String>, ClusterCheckError> {
+    use serde::Deserialize;
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ComponentVersion {
+        git_version: String,
+    }
+
+    #[derive(Debug, serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct KubernetesVersion {
+        server_version: Option<ComponentVersion>,
+    }
+
+    let json = &output[json_start(output)..];
+    let versions = KubernetesVersion::deserialize(&mut serde_json::Deserializer::from_slice(json))
+        .map_err(ClusterCheckError::KubectlVersionJsonError)?;
+    Ok(versions.server_version.map(|version| version.git_version))
+}
+
+/// Offset of the first line of `output` that opens a JSON object, 0 if there is none
+#[cfg(feature = "kubectl-checks")]
+fn json_start(output: &[u8]) -> usize {
+    let mut offset = 0;
+    for line in output.split_inclusive(|byte| *byte == b'\n') {
+        if line.iter().find(|byte| !byte.is_ascii_whitespace()) == Some(&b'{') {
+            return offset;
+        }
+        offset += line.len();
+    }
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::ClusterChecker;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_server_version() {
+        let parse = |version| parse_server_version(version).map(|version| version.to_string());
+
+        assert_eq!(parse("v1.25.3").unwrap(), "1.25.3");
+        assert_eq!(parse("v1.25.3+k3s1").unwrap(), "1.25.3");
+        assert_eq!(parse("1.27.4").unwrap(), "1.27.4");
+        assert_eq!(parse("v1.27.4-eks-2d98532").unwrap(), "1.27.4-eks-2d98532");
+        for invalid in ["", "v", "vé1.2", "v1.25"] {
+            assert!(
+                matches!(
+                    parse_server_version(invalid),
+                    Err(ClusterCheckError::InvalidServerVersion(version)) if version == invalid
+                ),
+                "{invalid:?} is not a version"
+            );
+        }
+    }
+
+    #[cfg(feature = "kubectl-checks")]
+    #[test]
+    fn test_parse_kubectl_version_k3s() {
+        let output = include_bytes!("../../test-data/kubectl-version-k3s.json");
+
+        let git_version = parse_kubectl_version(output)
+            .expect("kubectl version")
+            .expect("server version");
+
+        assert_eq!(git_version, "v1.27.4+k3s1");
+        assert!(matches!(
+            k8_version_status(&git_version, &Version::parse(KUBE_VERSION).unwrap()),
+            Ok(CheckStatus::Pass(message)) if message == "Supported Kubernetes server 1.27.4 found"
+        ));
+        assert!(matches!(
+            parse_kubectl_version(br#"{ "clientVersion": { "gitVersion": "v1.27.4" } }"#),
+            Ok(None)
+        ));
+    }
+
+    #[cfg(feature = "kubectl-checks")]
+    #[test]
+    fn test_parse_kubectl_version_releases() {
+        let outputs: [(&[u8], &str); 3] = [
+            (
+                include_bytes!("../../test-data/kubectl-version-1.20.json"),
+                "v1.20.15",
+            ),
+            (
+                include_bytes!("../../test-data/kubectl-version-1.25.json"),
+                "v1.25.3",
+            ),
+            (
+                include_bytes!("../../test-data/kubectl-version-1.28-warning.txt"),
+                "v1.26.9-eks-f8587cb",
+            ),
+        ];
+        for (output, expected) in outputs {
+            let git_version = parse_kubectl_version(output)
+                .expect("kubectl version")
+                .expect("server version");
+            assert_eq!(git_version, expected);
+            assert!(matches!(
+                k8_version_status(&git_version, &Version::parse(KUBE_VERSION).unwrap()),
+                Ok(CheckStatus::Pass(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "kubectl-checks")]
+    #[test]
+    fn test_parse_kubectl_version_without_server() {
+        let output = include_bytes!("../../test-data/kubectl-version-client-only.json");
+        assert!(matches!(parse_kubectl_version(output), Ok(None)));
+
+        // the warning kubectl prints to stderr when it can't reach the server, merged into stdout
+        let mut output = output.to_vec();
+        output.extend_from_slice(b"The connection to the server localhost:8080 was refused\n");
+        assert!(matches!(parse_kubectl_version(&output), Ok(None)));
+
+        assert!(matches!(
+            parse_kubectl_version(b"error: unknown flag: --output\n"),
+            Err(ClusterCheckError::KubectlVersionJsonError(_))
+        ));
+    }
+
+    #[cfg(feature = "kubectl-checks")]
+    #[test]
+    fn test_kubectl_version_command() {
+        let command = kubectl_version_command(None, Some("20s"));
+        let args: Vec<_> = command.get_args().collect();
+        assert_eq!(args, ["version", "-o=json", "--request-timeout=20s"]);
+    }
+
+    #[test]
+    fn test_k8_version_status() {
+        let required = Version::parse(KUBE_VERSION).unwrap();
+        assert!(matches!(
+            k8_version_status("v1.25.3+k3s1", &required),
+            Ok(CheckStatus::Pass(message)) if message == "Supported Kubernetes server 1.25.3 found"
+        ));
+        assert!(matches!(
+            k8_version_status("v1.6.0", &required),
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::IncompatibleKubectlVersion { installed, .. }
+            )) if installed == "1.6.0"
+        ));
+    }
+
+    #[test]
+    fn test_required_k8_version() {
+        let mut check = K8Version::default();
+        let checker = ClusterChecker::empty()
+            .with_required_k8_version("1.21.0")
+            .expect("valid version");
+        for (component, version) in &checker.required_versions {
+            check.set_required_version(component, version);
+        }
+        assert_eq!(check.required, Version::new(1, 21, 0));
+        assert_eq!(
+            K8Version::default().required.to_string(),
+            KUBE_VERSION,
+            "defaults are unchanged"
+        );
+
+        assert!(matches!(
+            k8_version_status("v1.20.7", &check.required),
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::IncompatibleKubectlVersion { installed, required }
+            )) if installed == "1.20.7" && required == "1.21.0"
+        ));
+    }
+
+    #[test]
+    fn test_k8_version_range() {
+        let min = Version::new(1, 21, 0);
+        let max = Version::new(1, 29, 99);
+
+        assert!(matches!(
+            k8_version_range_status("v1.25.3+k3s1", &min, &max),
+            Ok(CheckStatus::Pass(_))
+        ));
+        assert!(matches!(
+            k8_version_range_status("v1.20.7", &min, &max),
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::IncompatibleKubectlVersion { .. }
+            ))
+        ));
+        assert!(matches!(
+            k8_version_range_status("v1.30.0", &min, &max),
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::IncompatibleKubectlVersionTooNew { installed, max_supported }
+            )) if installed == "1.30.0" && max_supported == "1.29.99"
+        ));
+    }
+}
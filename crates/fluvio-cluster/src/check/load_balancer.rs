@@ -0,0 +1,718 @@
+//! Checks that the cluster gives LoadBalancer services an external address
+//!
+//! A cloud provider assigns the address some time after the service is
+//! created, and on clusters without a load balancer controller it never
+//! comes. Instead of polling at a fixed interval, the check watches the
+//! services of the namespace so it passes as soon as an address shows up,
+//! and fails once its deadline is over. Clients that can't watch fall back
+//! to listing the services with an exponential backoff.
+//!
+//! Before an install there is no LoadBalancer service to look at yet. The
+//! check then creates `fluvio-dummy-service` with kubectl, waits for it to
+//! get an address and deletes it again, on every way out of the check.
+//!
+//! Some local clusters never give LoadBalancer services an address, such as
+//! kind without MetalLB. There the check recommends NodePort services
+//! instead of failing, see `infra_status`. minikube only gives them one
+//! while `minikube tunnel` runs, the check then suggests starting it.
+//! Docker Desktop serves load balancers on `localhost`, which passes but is
+//! only reachable locally.
+
+#[cfg(feature = "kubectl-checks")]
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+#[cfg(feature = "kubectl-checks")]
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use futures_util::future::{select, Either};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use tracing::debug;
+
+use k8_client::meta_client::MetadataClient;
+use k8_client::K8Client;
+use k8_types::core::service::{LoadBalancerType, ServiceSpec};
+use k8_types::{K8Obj, K8Watch};
+
+use crate::render::ProgressRenderer;
+use crate::DEFAULT_NAMESPACE;
+
+#[cfg(feature = "kubectl-checks")]
+use super::command::{kubectl_output, CommandOutput};
+use super::command::COMMAND_TIMEOUT;
+#[cfg(feature = "kubectl-checks")]
+use super::context::kubectl_command;
+use super::context::{k8_api_error, ClusterApi};
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::infra::{InfraDetector, KubernetesInfra};
+#[cfg(feature = "kubectl-checks")]
+use super::namespace::NamespaceSource;
+use super::{
+    CheckData, CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+/// The LoadBalancer service created when the namespace has none to look at
+#[cfg(feature = "kubectl-checks")]
+const PROBE_SERVICE: &str = "fluvio-dummy-service";
+
+const DEFAULT_DEADLINE: Duration = Duration::from_secs(10);
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Checks that the LoadBalancer services of the namespace get an external address
+///
+/// The address is reported as [`CheckData::LoadBalancerAddress`]. Without
+/// any LoadBalancer service in the namespace the check creates one to wait
+/// for, in the `default` namespace if the namespace does not exist yet.
+/// Without kubectl that can't be done and the check is skipped as
+/// unverified. On kind, where no address comes without MetalLB, the check
+/// passes with [`CheckData::NodePortRecommended`].
+///
+/// # Example
+///
+/// ```
+/// # use std::time::Duration;
+/// # use fluvio_cluster::{ClusterChecker, LoadBalancerCheck};
+/// let check = LoadBalancerCheck::new(
+///     Duration::from_secs(30),
+///     Duration::from_millis(200),
+///     Duration::from_secs(5),
+/// );
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LoadBalancerCheck {
+    namespace: String,
+    kube_context: Option<String>,
+    deadline: Duration,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    env: Environment,
+}
+
+impl LoadBalancerCheck {
+    /// Waits up to `deadline` for an address
+    ///
+    /// When the services can't be watched, they are listed again after
+    /// `initial_backoff`, doubling the wait each time up to `max_backoff`.
+    pub fn new(deadline: Duration, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self {
+            namespace: DEFAULT_NAMESPACE.to_string(),
+            kube_context: None,
+            deadline,
+            initial_backoff,
+            max_backoff,
+            env: Environment::default(),
+        }
+    }
+}
+
+impl Default for LoadBalancerCheck {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_DEADLINE,
+            DEFAULT_INITIAL_BACKOFF,
+            DEFAULT_MAX_BACKOFF,
+        )
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for LoadBalancerCheck {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let client = self
+            .env
+            .client(self.kube_context.as_deref())
+            .map_err(ClusterCheckError::K8ApiError)?;
+        let services = client.as_service_source();
+        let load_balancers = match services.load_balancers(&self.namespace).await {
+            Ok(load_balancers) => load_balancers,
+            Err(ClusterCheckError::K8ApiForbidden(message)) => {
+                debug!(%message, "not allowed to list services");
+                return Ok(CheckStatus::skipped(format!(
+                    "not allowed to list services in {}",
+                    self.namespace
+                )));
+            }
+            Err(err) => return Err(err),
+        };
+        let status = if load_balancers.is_empty() {
+            self.probe(client.as_ref()).await?
+        } else {
+            self.wait_for_address(services, &self.namespace, load_balancers)
+                .await?
+        };
+        if !matches!(status, CheckStatus::Unrecoverable(_)) {
+            return Ok(status);
+        }
+        let infra = InfraDetector::detect_in(&*self.env, self.kube_context.as_deref()).await;
+        debug!(%infra, "no load balancer address");
+        Ok(infra_status(infra, status))
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    /// leaves room to create and delete the probe service, and to list the
+    /// services once more after the deadline
+    fn timeout(&self) -> Option<Duration> {
+        Some(self.deadline + self.max_backoff.max(Duration::from_secs(5)) + 2 * COMMAND_TIMEOUT)
+    }
+
+    fn name(&self) -> &str {
+        "load-balancer"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes load balancers"
+    }
+
+    fn description(&self) -> &str {
+        "The LoadBalancer services of the namespace have an external address"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = namespace.to_string();
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+impl LoadBalancerCheck {
+    /// Creates the probe service, waits for its address and deletes it again
+    #[cfg(feature = "kubectl-checks")]
+    async fn probe(&self, client: &dyn ClusterApi) -> CheckResult {
+        // the namespace of an install may not be created yet
+        let namespace = match client.namespace_exists(&self.namespace).await {
+            Ok(false) => DEFAULT_NAMESPACE,
+            _ => self.namespace.as_str(),
+        };
+        let probe =
+            ProbeService::create(self.env.clone(), self.kube_context.clone(), namespace).await?;
+        let services = client.as_service_source();
+        let status = match services.load_balancers(namespace).await {
+            Ok(load_balancers) => {
+                self.wait_for_address(services, namespace, load_balancers)
+                    .await
+            }
+            Err(err) => Err(err),
+        };
+        let deleted = probe.delete().await;
+        let status = status?;
+        deleted?;
+        Ok(status)
+    }
+
+    #[cfg(not(feature = "kubectl-checks"))]
+    async fn probe(&self, _client: &dyn ClusterApi) -> CheckResult {
+        Ok(CheckStatus::skipped(format!(
+            "unverified, there are no LoadBalancer services in {} and creating one needs kubectl",
+            self.namespace
+        )))
+    }
+
+    /// Waits up to the deadline for any of `load_balancers` in `namespace` to get an address
+    async fn wait_for_address(
+        &self,
+        services: &dyn ServiceSource,
+        namespace: &str,
+        load_balancers: Vec<LoadBalancer>,
+    ) -> CheckResult {
+        debug!(?load_balancers, "load balancers");
+        let status = load_balancer_status(namespace, &load_balancers);
+        if !matches!(status, CheckStatus::Unrecoverable(_)) {
+            return Ok(status);
+        }
+
+        let next_address = Box::pin(self.next_address(services, namespace));
+        match select(next_address, Box::pin(sleep(self.deadline))).await {
+            Either::Left((load_balancer, _)) => {
+                Ok(load_balancer_status(namespace, &[load_balancer?]))
+            }
+            // an address given before the watch started was not seen, look once more
+            Either::Right(_) => {
+                debug!(deadline = ?self.deadline, "no load balancer address in time");
+                let load_balancers = services.load_balancers(namespace).await?;
+                Ok(load_balancer_status(namespace, &load_balancers))
+            }
+        }
+    }
+
+    /// The first load balancer given an address, runs until there is one
+    async fn next_address(
+        &self,
+        services: &dyn ServiceSource,
+        namespace: &str,
+    ) -> Result<LoadBalancer, ClusterCheckError> {
+        let mut updates = services.watch_load_balancers(namespace);
+        while let Some(update) = updates.next().await {
+            match update {
+                Ok(load_balancer) if load_balancer.address.is_some() => return Ok(load_balancer),
+                Ok(load_balancer) => debug!(?load_balancer, "load balancer without address"),
+                Err(err) => {
+                    debug!(%err, "unable to watch services, polling instead");
+                    break;
+                }
+            }
+        }
+
+        let mut backoff = self.initial_backoff;
+        loop {
+            sleep(backoff).await;
+            let load_balancers = services.load_balancers(namespace).await?;
+            if let Some(load_balancer) = load_balancers
+                .into_iter()
+                .find(|load_balancer| load_balancer.address.is_some())
+            {
+                return Ok(load_balancer);
+            }
+            backoff = (backoff * 2).min(self.max_backoff);
+        }
+    }
+}
+
+/// The service [`LoadBalancerCheck`] creates to wait for, deleted even if the check is cancelled
+///
+/// [`delete`](ProbeService::delete) reports whether the delete succeeded.
+/// Dropped without it, when the check times out, is cancelled or panics,
+/// the service is deleted on a task of its own.
+#[cfg(feature = "kubectl-checks")]
+#[derive(Debug)]
+struct ProbeService {
+    env: Environment,
+    kube_context: Option<String>,
+    namespace: String,
+    deleted: bool,
+}
+
+#[cfg(feature = "kubectl-checks")]
+impl ProbeService {
+    async fn create(
+        env: Environment,
+        kube_context: Option<String>,
+        namespace: &str,
+    ) -> Result<Self, ClusterCheckError> {
+        let mut command = kubectl_command(kube_context.as_deref());
+        command
+            .args(["create", "service", "loadbalancer", PROBE_SERVICE])
+            .args(["--tcp=5555:5555", "--namespace", namespace]);
+        let description = CommandOutput::from_command(&command);
+        let output = kubectl_output(&*env, command).await?;
+        if !output.status.success() {
+            return Err(ClusterCheckError::ServiceCreateError(
+                description.with_output(&output),
+            ));
+        }
+        debug!(%namespace, "created probe service");
+        Ok(Self {
+            env,
+            kube_context,
+            namespace: namespace.to_string(),
+            deleted: false,
+        })
+    }
+
+    fn delete_command(&self) -> Command {
+        let mut command = kubectl_command(self.kube_context.as_deref());
+        command.args(["delete", "service", PROBE_SERVICE]).args([
+            "--namespace",
+            self.namespace.as_str(),
+            "--ignore-not-found",
+        ]);
+        command
+    }
+
+    async fn delete(mut self) -> Result<(), ClusterCheckError> {
+        self.deleted = true;
+        let command = self.delete_command();
+        let description = CommandOutput::from_command(&command);
+        let output = kubectl_output(&*self.env, command).await?;
+        if !output.status.success() {
+            return Err(ClusterCheckError::ServiceDeleteError(
+                description.with_output(&output),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "kubectl-checks")]
+impl Drop for ProbeService {
+    fn drop(&mut self) {
+        if self.deleted {
+            return;
+        }
+        let env = self.env.clone();
+        let command = self.delete_command();
+        spawn(async move {
+            if let Err(err) = kubectl_output(&*env, command).await {
+                debug!(%err, "unable to delete the probe service");
+            }
+        });
+    }
+}
+
+/// A LoadBalancer service and the address it was given, if any
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadBalancer {
+    pub name: String,
+    pub address: Option<String>,
+}
+
+impl LoadBalancer {
+    /// `None` for services of another type
+    fn from_service(service: K8Obj<ServiceSpec>) -> Option<Self> {
+        if !matches!(service.spec.r#type, Some(LoadBalancerType::LoadBalancer)) {
+            return None;
+        }
+        let address = service
+            .status
+            .load_balancer
+            .ingress
+            .iter()
+            .find_map(|ingress| ingress.host_or_ip())
+            .map(|host| host.to_string());
+        Some(Self {
+            name: service.metadata.name,
+            address,
+        })
+    }
+}
+
+/// The services of a namespace, from the Kubernetes client or a fake in tests
+#[async_trait]
+pub trait ServiceSource: Send + Sync {
+    /// The LoadBalancer services of `namespace`
+    async fn load_balancers(&self, namespace: &str)
+        -> Result<Vec<LoadBalancer>, ClusterCheckError>;
+
+    /// The LoadBalancer services of `namespace` as they are added or modified
+    fn watch_load_balancers(
+        &self,
+        namespace: &str,
+    ) -> BoxStream<'_, Result<LoadBalancer, ClusterCheckError>>;
+}
+
+#[async_trait]
+impl ServiceSource for K8Client {
+    async fn load_balancers(
+        &self,
+        namespace: &str,
+    ) -> Result<Vec<LoadBalancer>, ClusterCheckError> {
+        let services = self
+            .retrieve_items::<ServiceSpec, _>(namespace)
+            .await
+            .map_err(k8_api_error)?;
+        Ok(services
+            .items
+            .into_iter()
+            .filter_map(LoadBalancer::from_service)
+            .collect())
+    }
+
+    fn watch_load_balancers(
+        &self,
+        namespace: &str,
+    ) -> BoxStream<'_, Result<LoadBalancer, ClusterCheckError>> {
+        self.watch_stream_now::<ServiceSpec>(namespace.to_string())
+            .flat_map(|watches| {
+                let updates: Vec<_> = match watches {
+                    Ok(watches) => watches
+                        .into_iter()
+                        .filter_map(|watch| match watch {
+                            Ok(K8Watch::ADDED(service)) | Ok(K8Watch::MODIFIED(service)) => {
+                                LoadBalancer::from_service(service).map(Ok)
+                            }
+                            Ok(K8Watch::DELETED(_)) => None,
+                            Err(err) => Some(Err(ClusterCheckError::K8ApiError(err.to_string()))),
+                        })
+                        .collect(),
+                    Err(err) => vec![Err(k8_api_error(err))],
+                };
+                stream::iter(updates)
+            })
+            .boxed()
+    }
+}
+
+/// Passes once any load balancer has an address, the cluster can provision them
+fn load_balancer_status(namespace: &str, load_balancers: &[LoadBalancer]) -> CheckStatus {
+    if load_balancers.is_empty() {
+        return CheckStatus::skipped(format!("no LoadBalancer services in {namespace}"));
+    }
+    match load_balancers
+        .iter()
+        .find_map(|lb| Some((&lb.name, lb.address.as_ref()?)))
+    {
+        Some((name, address)) if address == "localhost" => CheckStatus::pass_with_data(
+            format!(
+                "Load balancer {name} is served on localhost, as Docker Desktop does, \
+                 and is only reachable from this machine"
+            ),
+            CheckData::LoadBalancerAddress(address.clone()),
+        ),
+        Some((name, address)) => CheckStatus::pass_with_data(
+            format!("Load balancer {name} has address {address}"),
+            CheckData::LoadBalancerAddress(address.clone()),
+        ),
+        None => {
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::LoadBalancerServiceNotAvailable)
+        }
+    }
+}
+
+/// Adapts a failure to get an address to the distribution of the cluster
+fn infra_status(infra: KubernetesInfra, status: CheckStatus) -> CheckStatus {
+    match (infra, status) {
+        (
+            KubernetesInfra::Kind,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::LoadBalancerServiceNotAvailable),
+        ) => CheckStatus::pass_with_data(
+            "kind gives LoadBalancer services no address without MetalLB, \
+             use NodePort services instead",
+            CheckData::NodePortRecommended,
+        ),
+        (
+            KubernetesInfra::DockerDesktop,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::LoadBalancerServiceNotAvailable),
+        ) => CheckStatus::Unrecoverable(
+            UnrecoverableCheckStatus::DockerDesktopLoadBalancerNotAvailable,
+        ),
+        (
+            KubernetesInfra::Minikube,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::LoadBalancerServiceNotAvailable),
+        ) => CheckStatus::Unrecoverable(UnrecoverableCheckStatus::MinikubeTunnelNotFound),
+        (_, status) => status,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    use super::*;
+
+    fn load_balancer(name: &str, address: Option<&str>) -> LoadBalancer {
+        LoadBalancer {
+            name: name.to_string(),
+            address: address.map(str::to_owned),
+        }
+    }
+
+    /// Lists the given load balancers in turn, repeating the last list
+    struct FakeServices {
+        lists: Mutex<Vec<Vec<LoadBalancer>>>,
+        listed: Mutex<usize>,
+        /// `None` for a client that can't watch
+        updates: Option<Vec<LoadBalancer>>,
+    }
+
+    impl FakeServices {
+        fn new(lists: Vec<Vec<LoadBalancer>>, updates: Option<Vec<LoadBalancer>>) -> Self {
+            Self {
+                lists: Mutex::new(lists),
+                listed: Mutex::default(),
+                updates,
+            }
+        }
+
+        fn listed(&self) -> usize {
+            *self.listed.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl ServiceSource for FakeServices {
+        async fn load_balancers(
+            &self,
+            _namespace: &str,
+        ) -> Result<Vec<LoadBalancer>, ClusterCheckError> {
+            *self.listed.lock().unwrap() += 1;
+            let mut lists = self.lists.lock().unwrap();
+            if lists.len() > 1 {
+                Ok(lists.remove(0))
+            } else {
+                Ok(lists[0].clone())
+            }
+        }
+
+        fn watch_load_balancers(
+            &self,
+            _namespace: &str,
+        ) -> BoxStream<'_, Result<LoadBalancer, ClusterCheckError>> {
+            match &self.updates {
+                // a watch stays open until the deadline
+                Some(updates) => stream::iter(updates.clone().into_iter().map(Ok))
+                    .chain(stream::pending())
+                    .boxed(),
+                None => stream::once(async {
+                    Err(ClusterCheckError::K8ApiError(
+                        "watch not supported".to_string(),
+                    ))
+                })
+                .boxed(),
+            }
+        }
+    }
+
+    fn check(deadline: Duration) -> LoadBalancerCheck {
+        LoadBalancerCheck::new(
+            deadline,
+            Duration::from_millis(10),
+            Duration::from_millis(40),
+        )
+    }
+
+    /// Lists the services and waits for an address, as the check does
+    async fn wait_for_address(check: LoadBalancerCheck, services: &FakeServices) -> CheckStatus {
+        let load_balancers = services.load_balancers("fluvio").await.expect("services");
+        check
+            .wait_for_address(services, "fluvio", load_balancers)
+            .await
+            .expect("status")
+    }
+
+    #[fluvio_future::test]
+    async fn test_address_from_watch() {
+        let services = FakeServices::new(
+            vec![vec![load_balancer("fluvio-sc-public", None)]],
+            Some(vec![
+                load_balancer("fluvio-sc-public", None),
+                load_balancer("fluvio-sc-public", Some("10.0.0.7")),
+            ]),
+        );
+
+        let started = Instant::now();
+        let status = wait_for_address(check(Duration::from_secs(10)), &services).await;
+
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert!(matches!(
+            status,
+            CheckStatus::PassWithData(message, CheckData::LoadBalancerAddress(address))
+                if message == "Load balancer fluvio-sc-public has address 10.0.0.7"
+                    && address == "10.0.0.7"
+        ));
+        assert_eq!(services.listed(), 1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_address_by_polling() {
+        let pending = vec![load_balancer("fluvio-sc-public", None)];
+        let services = FakeServices::new(
+            vec![
+                pending.clone(),
+                pending.clone(),
+                pending,
+                vec![load_balancer("fluvio-sc-public", Some("lb.example.com"))],
+            ],
+            None,
+        );
+
+        let started = Instant::now();
+        let status = wait_for_address(check(Duration::from_secs(10)), &services).await;
+
+        // 10 + 20 + 40 ms of backoff
+        assert!(started.elapsed() < Duration::from_millis(500));
+        assert_eq!(
+            status.data(),
+            Some(&CheckData::LoadBalancerAddress(
+                "lb.example.com".to_string()
+            ))
+        );
+        assert_eq!(services.listed(), 4);
+    }
+
+    #[fluvio_future::test]
+    async fn test_no_address_before_deadline() {
+        let services = FakeServices::new(
+            vec![vec![load_balancer("fluvio-sc-public", None)]],
+            Some(vec![]),
+        );
+
+        let status = wait_for_address(check(Duration::from_millis(50)), &services).await;
+
+        assert!(matches!(
+            status,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::LoadBalancerServiceNotAvailable)
+        ));
+        assert_eq!(services.listed(), 2);
+    }
+
+    #[test]
+    fn test_infra_status() {
+        let not_available = || {
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::LoadBalancerServiceNotAvailable)
+        };
+
+        assert!(matches!(
+            infra_status(KubernetesInfra::Kind, not_available()),
+            CheckStatus::PassWithData(_, CheckData::NodePortRecommended)
+        ));
+        assert!(matches!(
+            infra_status(KubernetesInfra::DockerDesktop, not_available()),
+            CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::DockerDesktopLoadBalancerNotAvailable
+            )
+        ));
+        assert!(matches!(
+            infra_status(KubernetesInfra::Minikube, not_available()),
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::MinikubeTunnelNotFound)
+        ));
+        assert!(matches!(
+            infra_status(KubernetesInfra::CloudProvider, not_available()),
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::LoadBalancerServiceNotAvailable)
+        ));
+        assert!(matches!(
+            infra_status(KubernetesInfra::Kind, CheckStatus::skipped("no services")),
+            CheckStatus::Skipped(_)
+        ));
+    }
+
+    #[test]
+    fn test_load_balancer_status() {
+        assert!(matches!(
+            load_balancer_status("fluvio", &[]),
+            CheckStatus::Skipped(reason) if reason == "no LoadBalancer services in fluvio"
+        ));
+        assert!(matches!(
+            load_balancer_status(
+                "fluvio",
+                &[
+                    load_balancer("pending", None),
+                    load_balancer("fluvio-sc-public", Some("10.0.0.7")),
+                ]
+            ),
+            CheckStatus::PassWithData(message, CheckData::LoadBalancerAddress(address))
+                if message == "Load balancer fluvio-sc-public has address 10.0.0.7"
+                    && address == "10.0.0.7"
+        ));
+        assert!(matches!(
+            load_balancer_status(
+                "fluvio",
+                &[load_balancer("fluvio-sc-public", Some("localhost"))]
+            ),
+            CheckStatus::PassWithData(message, CheckData::LoadBalancerAddress(address))
+                if message.contains("only reachable from this machine") && address == "localhost"
+        ));
+        assert!(matches!(
+            load_balancer_status("fluvio", &[load_balancer("fluvio-sc-public", None)]),
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::LoadBalancerServiceNotAvailable)
+        ));
+    }
+}
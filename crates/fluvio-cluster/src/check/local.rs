@@ -0,0 +1,137 @@
+//! Checks that no local cluster is running
+//!
+//! `fluvio cluster start --local` runs the SC and SPUs as processes of the
+//! fluvio binaries, which a second local cluster would collide with.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sysinfo::{ProcessExt, System, SystemExt};
+use tracing::debug;
+
+use crate::render::ProgressRenderer;
+
+use super::environment::{CheckEnvironment, Environment};
+use super::{CheckResult, CheckStatus, ClusterCheck, UnrecoverableCheckStatus};
+
+/// check if local cluster is running
+///
+/// Helm releases are covered by [`AlreadyInstalled`](super::AlreadyInstalled),
+/// this looks for the SC and SPU processes that `fluvio cluster start --local`
+/// leaves running.
+#[derive(Debug, Default)]
+pub(crate) struct LocalClusterCheck {
+    env: Environment,
+}
+
+#[async_trait]
+impl ClusterCheck for LocalClusterCheck {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let proc_count = self
+            .env
+            .processes()
+            .iter()
+            .filter(|process| is_local_cluster_process(&process.name, &process.cmd))
+            .map(|x| debug!("Found existing {} process. pid: {}", x.name, x.pid))
+            .count();
+        if proc_count > 0 {
+            return Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::ExistingLocalCluster,
+            ));
+        }
+        Ok(CheckStatus::pass("Local Fluvio is not installed"))
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "local-cluster"
+    }
+
+    fn label(&self) -> &str {
+        "Fluvio Local Installation"
+    }
+
+    fn description(&self) -> &str {
+        "No local Fluvio cluster is running"
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// A running process, as much of it as the local cluster check looks at
+#[derive(Debug, Clone)]
+pub struct RunningProcess {
+    pub pid: String,
+    pub name: String,
+    /// the executable and its arguments
+    pub cmd: Vec<String>,
+}
+
+pub(crate) fn running_processes() -> Vec<RunningProcess> {
+    let mut sys = System::new();
+    sys.refresh_processes(); // Only load what we need.
+    sys.processes()
+        .values()
+        .map(|process| RunningProcess {
+            pid: process.pid().to_string(),
+            name: process.name().to_string(),
+            cmd: process.cmd().to_vec(),
+        })
+        .collect()
+}
+
+/// Whether a process is part of a local cluster, the same ones `fluvio cluster shutdown` stops
+fn is_local_cluster_process(name: &str, cmd: &[String]) -> bool {
+    // First command is the executable so cut that out.
+    let args: Vec<&str> = cmd.iter().skip(1).map(String::as_str).collect();
+    match name {
+        "fluvio-run" => true,
+        "fluvio" => args.starts_with(&["run"]) || args.starts_with(&["cluster", "run"]),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_cluster_process() {
+        let cmd = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+
+        assert!(is_local_cluster_process(
+            "fluvio-run",
+            &cmd(&["fluvio-run", "sc"])
+        ));
+        assert!(is_local_cluster_process(
+            "fluvio",
+            &cmd(&["fluvio", "run", "spu"])
+        ));
+        assert!(is_local_cluster_process(
+            "fluvio",
+            &cmd(&["fluvio", "cluster", "run", "sc"])
+        ));
+        assert!(!is_local_cluster_process(
+            "fluvio",
+            &cmd(&["fluvio", "cluster", "start", "--local"])
+        ));
+        assert!(!is_local_cluster_process("fluvio", &[]));
+        assert!(!is_local_cluster_process("fluvio-sc", &cmd(&["fluvio-sc"])));
+
+        assert_ne!(
+            UnrecoverableCheckStatus::ExistingLocalCluster.to_string(),
+            UnrecoverableCheckStatus::AlreadyInstalled {
+                name: "fluvio".to_string(),
+                namespace: "default".to_string(),
+                version: "0.11.0".to_string(),
+                target: None,
+            }
+            .to_string()
+        );
+    }
+}
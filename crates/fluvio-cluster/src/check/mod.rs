@@ -1,34 +1,109 @@
-use std::cmp::Ordering;
-use std::collections::HashSet;
-use std::io::Error as IoError;
-use std::fmt::Debug;
-use std::process::Command;
+use std::fmt::{self, Debug};
+use std::ops::Deref;
+use std::sync::Arc;
 use std::time::Duration;
+#[cfg(feature = "kubectl-checks")]
+use std::io::Error as IoError;
 
 pub mod render;
-
+#[cfg(feature = "helm-checks")]
+mod already_installed;
+mod binary;
+#[cfg(feature = "helm-checks")]
+mod chart;
+mod command;
+mod connectivity;
+mod context;
+mod disk;
+mod environment;
+mod exec_plugin;
+#[cfg(feature = "helm-checks")]
+mod helm;
+mod identity;
+mod in_cluster;
+mod infra;
+mod json;
+mod k8;
+mod load_balancer;
+mod local;
+mod namespace;
+mod nodes;
+#[cfg(feature = "kubectl-checks")]
+mod openshift;
+mod permission;
+mod port;
+mod presets;
+mod revalidate;
+mod run;
+mod selection;
+#[cfg(feature = "helm-checks")]
+mod sys_chart;
+#[cfg(test)]
+mod testing;
+mod tls;
+#[cfg(feature = "helm-checks")]
+mod upgrade;
+
+#[cfg(feature = "helm-checks")]
+pub(crate) use already_installed::AlreadyInstalled;
+pub use binary::{HelmBinary, KubectlBinary};
+#[cfg(feature = "helm-checks")]
+pub use chart::ChartAvailability;
+#[cfg(feature = "helm-checks")]
+pub use helm::check_helm_version;
+#[cfg(feature = "helm-checks")]
+pub(crate) use helm::HelmVersion;
+#[cfg(feature = "helm-checks")]
+pub use sys_chart::{SysChartCheck, SysChartInstaller};
+#[cfg(feature = "helm-checks")]
+pub(crate) use upgrade::UpgradePathCheck;
+pub use command::CommandOutput;
+pub use connectivity::ScConnectivity;
+pub use context::ClusterApi;
+pub use disk::LocalDiskSpace;
+pub use environment::{CheckEnvironment, SystemEnvironment};
+pub use exec_plugin::ExecPluginError;
+pub use identity::ClusterIdentity;
+pub use infra::{InfraDetector, KubernetesInfra};
+pub use k8::K8VersionRange;
+pub use k8::ServerVersionSource;
+pub(crate) use k8::{ActiveKubernetesCluster, K8Version};
+pub use load_balancer::{LoadBalancer, LoadBalancerCheck, ServiceSource};
+pub use local::RunningProcess;
+pub use namespace::{NamespaceCheck, NamespaceSource};
+pub use nodes::{NodeCondition, NodeReadiness, NodeSource, NodeState};
+pub use permission::{AccessReviewer, ResourceAttributes};
+pub use permission::{SelfSubjectAccessReviewSpec, SubjectAccessReviewStatus};
+pub use port::PortAvailabilityCheck;
+pub use revalidate::{CheckDrift, RevalidationReport};
+pub use selection::{CheckPreset, CheckRegistry, CheckSelection};
+pub use tls::{SecretSource, TlsSecrets};
+use selection::SkippedCheck;
+use in_cluster::InClusterPermission;
+use local::LocalClusterCheck;
+use permission::{CreateBatchPermission, CreateCrdPermission};
+use permission::{CreateServiceAccountPermission, CreateServicePermission};
+
+use async_channel::{Receiver, Sender};
 use colored::Colorize;
-use fluvio_future::timer::sleep;
+use futures_util::{stream, Stream};
 use indicatif::style::TemplateError;
-use tracing::{error, debug};
+use tracing::debug;
 use async_trait::async_trait;
 use url::ParseError;
 use semver::Version;
+#[cfg(feature = "kubectl-checks")]
 use serde_json::Error as JsonError;
-use sysinfo::{ProcessExt, System, SystemExt};
 
-use fluvio_helm::{HelmClient, HelmError};
-use k8_config::{ConfigError as K8ConfigError, K8Config};
+#[cfg(feature = "helm-checks")]
+use fluvio_helm::HelmError;
+use fluvio::config::TlsPolicy;
+use fluvio_types::event::StickyEvent;
+use k8_config::ConfigError as K8ConfigError;
 
-use crate::charts::{DEFAULT_HELM_VERSION, APP_CHART_NAME};
-use crate::progress::ProgressBarFactory;
 use crate::render::ProgressRenderer;
-use crate::charts::{ChartConfig, ChartInstaller, ChartInstallError, SYS_CHART_NAME};
-
-const KUBE_VERSION: &str = "1.7.0";
-const RESOURCE_SERVICE: &str = "service";
-const RESOURCE_CRD: &str = "customresourcedefinitions";
-const RESOURCE_SERVICE_ACCOUNT: &str = "secret";
+#[cfg(feature = "helm-checks")]
+use crate::charts::ChartInstallError;
 
 /// The outcome of a check: it was either successfully performed, or it errored
 ///
@@ -38,16 +113,236 @@ const RESOURCE_SERVICE_ACCOUNT: &str = "secret";
 /// an `Err`.
 pub type CheckResult = std::result::Result<CheckStatus, ClusterCheckError>;
 
+/// The result of a check, together with the name of the check it belongs to
+#[derive(Debug)]
+pub struct CheckOutcome {
+    /// [`ClusterCheck::name`] of the check
+    pub name: String,
+    pub result: CheckResult,
+}
+
+impl CheckOutcome {
+    /// Whether the check errored or failed in a way no fixer can recover from
+    pub fn is_blocking(&self) -> bool {
+        matches!(self.result, Ok(CheckStatus::Unrecoverable(_)) | Err(_))
+    }
+}
+
 /// A collection of the successes, failures, and errors of running checks
-pub type CheckResults = Vec<CheckResult>;
+///
+/// Outcomes are in the order the checks were added to the [`ClusterChecker`].
+#[derive(Debug, Default)]
+pub struct CheckResults(Vec<CheckOutcome>);
+
+impl CheckResults {
+    /// Result of the check named `name`, see [`ClusterCheck::name`]
+    pub fn result_for(&self, name: &str) -> Option<&CheckResult> {
+        self.0
+            .iter()
+            .find(|outcome| outcome.name == name)
+            .map(|outcome| &outcome.result)
+    }
+
+    /// Outcomes of the checks that were skipped
+    pub fn skipped(&self) -> impl Iterator<Item = &CheckOutcome> {
+        self.0
+            .iter()
+            .filter(|outcome| matches!(outcome.result, Ok(CheckStatus::Skipped(_))))
+    }
+
+    /// Outcomes of the checks that passed
+    pub fn passed(&self) -> impl Iterator<Item = &CheckOutcome> {
+        self.0.iter().filter(|outcome| {
+            matches!(
+                outcome.result,
+                Ok(CheckStatus::Pass(_) | CheckStatus::PassWithData(..))
+            )
+        })
+    }
+
+    /// What the checks that passed found, such as the address of a load balancer
+    pub fn data(&self) -> impl Iterator<Item = &CheckData> {
+        self.0.iter().filter_map(|outcome| match &outcome.result {
+            Ok(status) => status.data(),
+            Err(_) => None,
+        })
+    }
+
+    /// Outcomes of the checks that completed and reported a problem, fixable or not
+    pub fn failures(&self) -> impl Iterator<Item = &CheckOutcome> {
+        self.0.iter().filter(|outcome| {
+            matches!(
+                outcome.result,
+                Ok(CheckStatus::AutoFixableError { .. } | CheckStatus::Unrecoverable(_))
+            )
+        })
+    }
+
+    /// Outcomes of the checks that failed with a fixer attached
+    pub fn recoverable_failures(&self) -> impl Iterator<Item = &CheckOutcome> {
+        self.0
+            .iter()
+            .filter(|outcome| matches!(outcome.result, Ok(CheckStatus::AutoFixableError { .. })))
+    }
+
+    /// Outcomes of the checks that could not be performed
+    pub fn errors(&self) -> impl Iterator<Item = &CheckOutcome> {
+        self.0.iter().filter(|outcome| outcome.result.is_err())
+    }
+
+    /// Whether every check passed or was skipped
+    pub fn all_passed(&self) -> bool {
+        self.failures().next().is_none() && self.errors().next().is_none()
+    }
+
+    /// Counts the outcomes and keeps the first one that blocks the install
+    pub fn into_report(self) -> CheckReport {
+        let summary = self.summary();
+        let first_blocking = self.0.into_iter().find(CheckOutcome::is_blocking);
+        CheckReport {
+            summary,
+            first_blocking,
+        }
+    }
+
+    /// The outcomes, in the order the checks were added
+    pub fn into_inner(self) -> Vec<CheckOutcome> {
+        self.0
+    }
+}
+
+/// What a run amounts to, see [`CheckResults::into_report`]
+#[derive(Debug)]
+pub struct CheckReport {
+    pub summary: CheckResultsSummary,
+    /// The first check that errored or failed unrecoverably, in the order the checks were added
+    pub first_blocking: Option<CheckOutcome>,
+}
+
+impl CheckReport {
+    /// Whether the install can go ahead, possibly after fixing recoverable failures
+    pub fn can_proceed(&self) -> bool {
+        self.first_blocking.is_none()
+    }
+}
+
+impl Deref for CheckResults {
+    type Target = [CheckOutcome];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<Vec<CheckOutcome>> for CheckResults {
+    fn from(outcomes: Vec<CheckOutcome>) -> Self {
+        Self(outcomes)
+    }
+}
+
+impl FromIterator<CheckOutcome> for CheckResults {
+    fn from_iter<I: IntoIterator<Item = CheckOutcome>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for CheckResults {
+    type Item = CheckOutcome;
+    type IntoIter = std::vec::IntoIter<CheckOutcome>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a CheckResults {
+    type Item = &'a CheckOutcome;
+    type IntoIter = std::slice::Iter<'a, CheckOutcome>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// How many checks passed, failed, errored or were skipped
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CheckResultsSummary {
+    pub passed: usize,
+    /// checks that completed and reported a problem, fixable or not
+    pub failed: usize,
+    /// checks that could not be performed
+    pub errors: usize,
+    pub skipped: usize,
+}
+
+impl CheckResultsSummary {
+    fn count(&mut self, result: &CheckResult) {
+        match result {
+            Ok(CheckStatus::Pass(_) | CheckStatus::PassWithData(..)) => self.passed += 1,
+            Ok(CheckStatus::AutoFixableError { .. } | CheckStatus::Unrecoverable(_)) => {
+                self.failed += 1
+            }
+            Ok(CheckStatus::Skipped(_)) => self.skipped += 1,
+            Err(_) => self.errors += 1,
+        }
+    }
+}
+
+impl fmt::Display for CheckResultsSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} errors, {} skipped",
+            self.passed, self.failed, self.errors, self.skipped
+        )
+    }
+}
+
+/// Summary statistics over the results of a run
+pub trait CheckResultsExt {
+    /// Counts the results by outcome
+    fn summary(&self) -> CheckResultsSummary;
+
+    /// Whether any check failed or could not be performed
+    fn has_failures(&self) -> bool {
+        let summary = self.summary();
+        summary.failed > 0 || summary.errors > 0
+    }
+}
+
+impl CheckResultsExt for [CheckResult] {
+    fn summary(&self) -> CheckResultsSummary {
+        let mut summary = CheckResultsSummary::default();
+        for result in self {
+            summary.count(result);
+        }
+        summary
+    }
+}
+
+impl CheckResultsExt for CheckResults {
+    fn summary(&self) -> CheckResultsSummary {
+        let mut summary = CheckResultsSummary::default();
+        for outcome in self {
+            summary.count(&outcome.result);
+        }
+        summary
+    }
+}
 
 /// An error occurred during the checking process
 #[derive(thiserror::Error, Debug)]
 pub enum ClusterCheckError {
     /// There was a problem with the helm client during pre-check
+    #[cfg(feature = "helm-checks")]
     #[error("Helm client error")]
     HelmError(#[from] HelmError),
 
+    /// helm ran but exited with an error
+    #[cfg(feature = "helm-checks")]
+    #[error("helm failed: {0}")]
+    HelmFailed(CommandOutput),
+
     /// There was a problem fetching kubernetes configuration
     #[error("Kubernetes config error")]
     K8ConfigError(#[from] K8ConfigError),
@@ -57,24 +352,59 @@ pub enum ClusterCheckError {
     BadKubernetesServerUrl(#[from] ParseError),
 
     /// Kubectl not found
-    #[error("Kubectl not found")]
-    KubectlNotFoundError(IoError),
+    #[cfg(feature = "kubectl-checks")]
+    #[error("Kubectl not found, unable to run {command}")]
+    KubectlNotFoundError {
+        /// The kubectl command that could not be started
+        command: CommandOutput,
+        /// Why it could not be started
+        #[source]
+        source: IoError,
+    },
 
     /// Error while fetching create permissions for a resource
+    #[cfg(feature = "kubectl-checks")]
     #[error("Unable to fetch permissions")]
     FetchPermissionError,
 
+    /// kubectl ran but exited with an error
+    #[cfg(feature = "kubectl-checks")]
+    #[error("kubectl failed: {0}")]
+    KubectlFailed(CommandOutput),
+
+    /// The Kubernetes API rejected a request
+    #[error("Kubernetes API error: {0}")]
+    K8ApiError(String),
+
+    /// The Kubernetes API server answered a request with 403 Forbidden
+    #[error("Kubernetes API request forbidden: {0}")]
+    K8ApiForbidden(String),
+
     /// Unable to parse kubectl version
+    #[cfg(feature = "kubectl-checks")]
     #[error("Unable to parse kubectl version from JSON")]
     KubectlVersionJsonError(JsonError),
 
+    /// An external command was killed after running for too long
+    #[error("Command {command} did not finish within {}s", timeout.as_secs_f32())]
+    CommandTimedOut {
+        /// The program that was run
+        command: String,
+        /// How long it was given
+        timeout: Duration,
+    },
+
+    /// The Kubernetes server reported a version that is not semver
+    #[error("Unrecognized Kubernetes server version: {0}")]
+    InvalidServerVersion(String),
+
     /// Could not create dummy service
-    #[error("Could not create service")]
-    ServiceCreateError,
+    #[error("Could not create service: {0}")]
+    ServiceCreateError(CommandOutput),
 
     /// Could not delete dummy service
-    #[error("Could not delete service")]
-    ServiceDeleteError,
+    #[error("Could not delete service: {0}")]
+    ServiceDeleteError(CommandOutput),
 
     /// Unable to parse Error
     #[error("Could not parse Version")]
@@ -91,14 +421,57 @@ pub enum ClusterCheckError {
     #[error("Preflight check failed")]
     PreCheckFlightFailure,
 
+    /// A check did not finish in time
+    #[error("Check {check} timed out after {}s", timeout.as_secs_f32())]
+    TimedOut {
+        /// Name of the check
+        check: String,
+        /// How long the check was given
+        timeout: Duration,
+    },
+
+    /// The run was cancelled through [`ClusterChecker::with_cancellation_token`]
+    #[error("Check run was cancelled")]
+    Cancelled,
+
+    /// The cluster changed after the checks passed
+    #[error("{0}")]
+    EnvironmentChanged(RevalidationReport),
+
     #[error("Progress Error")]
     ProgressError(#[from] TemplateError),
+
+    /// A check selection referenced a check that is not registered
+    #[error("Unknown check {name}, known checks: {}", known.join(", "))]
+    UnknownCheck {
+        /// The name that could not be resolved
+        name: String,
+        /// All registered check names
+        known: Vec<String>,
+    },
+}
+
+impl ClusterCheckError {
+    /// The external command behind this error, if it failed running one
+    pub fn command_output(&self) -> Option<&CommandOutput> {
+        match self {
+            #[cfg(feature = "kubectl-checks")]
+            Self::KubectlNotFoundError { command, .. } | Self::KubectlFailed(command) => {
+                Some(command)
+            }
+            #[cfg(feature = "helm-checks")]
+            Self::HelmFailed(command) => Some(command),
+            Self::ServiceCreateError(command) | Self::ServiceDeleteError(command) => Some(command),
+            _ => None,
+        }
+    }
 }
 
 /// An error occurred during the checking process
 #[derive(thiserror::Error, Debug)]
 pub enum ClusterAutoFixError {
     /// There was a problem with the helm client during pre-check
+    #[cfg(feature = "helm-checks")]
     #[error("Helm client error")]
     Helm(#[from] HelmError),
 
@@ -106,8 +479,18 @@ pub enum ClusterAutoFixError {
     #[error("Kubernetes config error")]
     K8Config(#[from] K8ConfigError),
 
+    #[cfg(feature = "helm-checks")]
     #[error("Chart Install error")]
     ChartInstall(#[from] ChartInstallError),
+
+    /// The fix ran but the check it was fixing still does not pass
+    #[error("{check} check still fails after the fix: {reason}")]
+    NotFixed {
+        /// Name of the check that was re-run
+        check: String,
+        /// What the check reported
+        reason: String,
+    },
 }
 
 /// Allows checks to suggest further action
@@ -127,6 +510,8 @@ pub type CheckStatuses = Vec<CheckStatus>;
 pub enum CheckStatus {
     /// This check has passed and has the given success message
     Pass(CheckSucceeded),
+    /// This check has passed with the given success message and details of what it found
+    PassWithData(CheckSucceeded, CheckData),
     /// This check has failed but can be recovered
     AutoFixableError {
         message: String,
@@ -134,6 +519,11 @@ pub enum CheckStatus {
     },
     /// check that cannot be recovered
     Unrecoverable(UnrecoverableCheckStatus),
+    /// This check does not apply to this environment, for the given reason
+    ///
+    /// A skipped check neither passes nor fails; components it provides are
+    /// not registered.
+    Skipped(String),
 }
 
 impl CheckStatus {
@@ -141,6 +531,174 @@ impl CheckStatus {
     pub(crate) fn pass(msg: impl Into<String>) -> Self {
         Self::Pass(msg.into())
     }
+
+    /// Creates a passing check status with a success message and what the check found
+    pub(crate) fn pass_with_data(msg: impl Into<String>, data: CheckData) -> Self {
+        Self::PassWithData(msg.into(), data)
+    }
+
+    /// Creates a skipped check status with the reason it was skipped
+    pub(crate) fn skipped(reason: impl Into<String>) -> Self {
+        Self::Skipped(reason.into())
+    }
+
+    /// What the check found, if it passed with data
+    pub fn data(&self) -> Option<&CheckData> {
+        match self {
+            Self::PassWithData(_, data) => Some(data),
+            _ => None,
+        }
+    }
+}
+
+/// Structured details found by a check that passed, see [`CheckStatus::PassWithData`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckData {
+    /// External address of a LoadBalancer service, an IP or a hostname
+    LoadBalancerAddress(String),
+    /// LoadBalancer services get no address on this cluster, such as kind
+    /// without MetalLB, and the install should use NodePort services instead
+    NodePortRecommended,
+}
+
+/// Renders the status as one terminal line, plus the suggestion of a failure
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::CheckStatus;
+/// let status = CheckStatus::Pass("Kubernetes config is loadable".to_string());
+/// assert!(status.to_string().ends_with(" Kubernetes config is loadable"));
+/// ```
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass(message) | Self::PassWithData(message, _) => {
+                write!(f, "{} {message}", "✓".green())
+            }
+            Self::AutoFixableError { message, .. } => {
+                write!(f, "{} {message} (can be fixed automatically)", "✗".yellow())
+            }
+            Self::Unrecoverable(err) => {
+                write!(f, "{} {err}", "✗".red())?;
+                if let Some(suggestion) = err.suggestion() {
+                    write!(f, "\n  💡 {suggestion}")?;
+                }
+                Ok(())
+            }
+            Self::Skipped(reason) => write!(f, "{} skipped: {reason}", "-".dimmed()),
+        }
+    }
+}
+
+/// Renders the check name followed by its status, or by the error that kept it from running
+impl fmt::Display for CheckOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.result {
+            Ok(status) => write!(f, "{}: {status}", self.name),
+            Err(err) => write!(
+                f,
+                "{}: {} could not be performed: {err}",
+                self.name,
+                "✗".red()
+            ),
+        }
+    }
+}
+
+/// What [`ClusterChecker::run_with_progress`] reports while it runs
+///
+/// `index` is the position of the check in the run, starting at 0, out of
+/// `total` checks. Every `Started` is followed by the `Completed` of the
+/// same check before the next one starts.
+#[derive(Debug)]
+pub enum CheckEvent {
+    /// The check is being performed
+    Started {
+        /// [`ClusterCheck::name`] of the check
+        name: String,
+        index: usize,
+        total: usize,
+    },
+    /// The check finished, or could not be performed
+    Completed {
+        /// [`ClusterCheck::name`] of the check
+        name: String,
+        index: usize,
+        total: usize,
+        result: CheckResult,
+    },
+}
+
+impl CheckEvent {
+    /// The outcome of a `Completed` event
+    pub fn into_outcome(self) -> Option<CheckOutcome> {
+        match self {
+            Self::Started { .. } => None,
+            Self::Completed { name, result, .. } => Some(CheckOutcome { name, result }),
+        }
+    }
+}
+
+/// A run started with [`ClusterChecker::run_with_progress`]
+///
+/// Dropping it aborts the run, as [`abort`] does.
+///
+/// [`abort`]: CheckProgress::abort
+#[derive(Debug)]
+pub struct CheckProgress {
+    events: Receiver<CheckEvent>,
+    abort: CheckAbortHandle,
+}
+
+impl CheckProgress {
+    /// The next event, `None` once the run is over
+    pub async fn next_event(&self) -> Option<CheckEvent> {
+        self.events.recv().await.ok()
+    }
+
+    /// Stops the run, see [`CheckAbortHandle::abort`]
+    pub fn abort(&self) {
+        self.abort.abort();
+    }
+
+    /// A handle that aborts the run from elsewhere, such as a Ctrl-C handler
+    pub fn abort_handle(&self) -> CheckAbortHandle {
+        self.abort.clone()
+    }
+
+    /// The events as a stream
+    pub fn into_stream(self) -> impl Stream<Item = CheckEvent> {
+        stream::unfold(self, |progress| async move {
+            let event = progress.next_event().await?;
+            Some((event, progress))
+        })
+    }
+}
+
+impl Drop for CheckProgress {
+    fn drop(&mut self) {
+        self.abort.abort();
+    }
+}
+
+/// Aborts a run started with [`ClusterChecker::run_with_progress`]
+#[derive(Debug, Clone)]
+pub struct CheckAbortHandle(Arc<StickyEvent>);
+
+impl CheckAbortHandle {
+    /// Interrupts the check being performed and starts no further check
+    ///
+    /// The interrupted check completes with [`ClusterCheckError::Cancelled`]
+    /// and the run ends.
+    pub fn abort(&self) {
+        self.0.notify();
+    }
+
+    /// Whether the run was aborted
+    pub fn is_aborted(&self) -> bool {
+        self.0.is_set()
+    }
 }
 
 /// A successful check yields a success message
@@ -155,6 +713,15 @@ pub enum RecoverableCheck {
 
     #[error("Fluvio system charts are not up to date.")]
     UpgradeSystemChart,
+
+    /// The installed fluvio-sys chart is older than the install requires
+    #[error("Fluvio system chart {installed} is older than the required {required}")]
+    OutdatedSystemChart {
+        /// The currently-installed system chart version
+        installed: String,
+        /// The system chart version the install requires
+        required: String,
+    },
 }
 
 impl CheckSuggestion for RecoverableCheck {
@@ -162,6 +729,7 @@ impl CheckSuggestion for RecoverableCheck {
         let suggestion = match self {
             Self::MissingSystemChart => "Run 'fluvio cluster start --sys'",
             Self::UpgradeSystemChart => "Run 'fluvio cluster start --sys'",
+            Self::OutdatedSystemChart { .. } => "Run 'fluvio cluster upgrade --sys-only'",
         };
         Some(suggestion.to_string())
     }
@@ -178,11 +746,11 @@ pub enum UnrecoverableCheckStatus {
     },
 
     /// The installed version of helm is incompatible
-    #[error("Must have helm version {required} or later. You have {installed}")]
+    #[error("Must have helm version {required}. You have {installed}")]
     IncompatibleHelmVersion {
         /// The currently-installed helm version
         installed: String,
-        /// The minimum required helm version
+        /// The requirement helm must satisfy, such as `>=3.3.4`
         required: String,
     },
 
@@ -195,10 +763,26 @@ pub enum UnrecoverableCheckStatus {
         required: String,
     },
 
+    /// The Kubernetes server is newer than Fluvio has been validated against
+    #[error("Must have Kubernetes version {max_supported} or earlier. You have {installed}")]
+    IncompatibleKubectlVersionTooNew {
+        /// The version of the Kubernetes server
+        installed: String,
+        /// The newest supported Kubernetes version
+        max_supported: String,
+    },
+
     /// There is no current Kubernetes context
     #[error("There is no active Kubernetes context")]
     NoActiveKubernetesContext,
 
+    /// The context set with [`ClusterChecker::with_kube_context`] is not in the kubeconfig
+    #[error("Kubernetes context {name} not found")]
+    KubeContextNotFound {
+        /// Name of the missing context
+        name: String,
+    },
+
     /// Unable to connect to the active context
     #[error("Failed to connect to Kubernetes via the active context")]
     CannotConnectToKubernetes,
@@ -207,8 +791,79 @@ pub enum UnrecoverableCheckStatus {
     #[error("Cannot have multiple versions of fluvio-sys installed")]
     MultipleSystemCharts,
 
-    #[error("Fluvio chart is already installed")]
-    AlreadyInstalled,
+    /// The system chart needs installing or upgrading in a context that is not the current one
+    #[error(
+        "{reason}. The system chart can only be installed in the current context, not {context}"
+    )]
+    SysChartNeedsCurrentContext {
+        /// The context set with [`ClusterChecker::with_kube_context`]
+        context: String,
+        /// What the system chart needs
+        reason: String,
+    },
+
+    /// The installed system chart is older than the installer supports
+    #[error("Must have system chart version {required} or later. You have {installed}")]
+    IncompatibleSystemChart {
+        /// The currently-installed system chart version
+        installed: String,
+        /// The minimum required system chart version
+        required: String,
+    },
+
+    /// Fluvio is already installed in the namespace of the install
+    #[error("Fluvio {version} is already installed as release {name} in namespace {namespace}")]
+    AlreadyInstalled {
+        /// Name of the helm release
+        name: String,
+        /// Namespace of the release
+        namespace: String,
+        /// Installed platform version
+        version: String,
+        /// The platform version being installed, if known
+        target: Option<String>,
+    },
+
+    /// An upgrade was asked for, but there is no release to upgrade
+    #[error("There is no Fluvio installation to upgrade in namespace {namespace}")]
+    NoInstallationToUpgrade {
+        /// The namespace the upgrade targets
+        namespace: String,
+    },
+
+    /// The upgrade target is older than the installed version
+    #[error("Cannot upgrade Fluvio {installed} to the older version {target}")]
+    UpgradeIsDowngrade {
+        /// The installed platform version
+        installed: String,
+        /// The version the upgrade installs
+        target: String,
+    },
+
+    /// The upgrade crosses a version that can only be installed fresh
+    #[error("Cannot upgrade Fluvio {installed} to {target} in place across {breaking}: {reason}")]
+    BreakingUpgrade {
+        /// The installed platform version
+        installed: String,
+        /// The version the upgrade installs
+        target: String,
+        /// The version in between that breaks the upgrade
+        breaking: String,
+        /// What changed in that version
+        reason: String,
+    },
+
+    /// Fluvio app releases exist that conflict with the requested install
+    #[error(
+        "Fluvio is already installed ({}), cannot install into namespace {namespace}",
+        format_installations(installations)
+    )]
+    ConflictingInstallations {
+        /// The namespace the install targets
+        namespace: String,
+        /// Every existing Fluvio release
+        installations: Vec<FluvioInstallation>,
+    },
 
     /// The current kubernetes cluster must have a server hostname
     #[error("Missing Kubernetes server host")]
@@ -218,20 +873,174 @@ pub enum UnrecoverableCheckStatus {
     #[error("Load balancer service is not available")]
     LoadBalancerServiceNotAvailable,
 
+    /// Docker Desktop gave no address to a LoadBalancer service
+    #[error("Docker Desktop gave no address to the load balancer service")]
+    DockerDesktopLoadBalancerNotAvailable,
+
+    /// minikube gave no address to a LoadBalancer service, which it only does while `minikube tunnel` runs
+    #[error(
+        "minikube gave no address to the load balancer service, 'minikube tunnel' is not running"
+    )]
+    MinikubeTunnelNotFound,
+
     /// No Helm client
     #[error("No Helm client: {0}")]
     NoHelmClient(String),
 
+    /// Fewer Kubernetes nodes are ready than the install needs
+    #[error(
+        "{ready} Kubernetes nodes ready, {required} required{}",
+        format_not_ready_nodes(not_ready)
+    )]
+    NodesNotReady {
+        /// Nodes that are ready and schedulable
+        ready: usize,
+        /// Nodes the install needs, one per SPU replica
+        required: usize,
+        /// Each other node with what keeps pods off it
+        not_ready: Vec<String>,
+    },
+
+    /// The chart version to install is not in the helm repository
+    #[error(
+        "Chart {chart} {version} is not in the repository{}",
+        format_available_versions(chart, available)
+    )]
+    ChartVersionNotFound {
+        /// Name of the chart
+        chart: String,
+        /// The version that was asked for
+        version: String,
+        /// The versions of the repository closest to it
+        available: Vec<String>,
+    },
+
+    /// The helm repository to install from could not be reached
+    #[error("Could not reach chart repository {url}: {reason}")]
+    ChartRepoUnreachable {
+        /// URL of the repository
+        url: String,
+        /// What helm reported
+        reason: String,
+    },
+
+    /// kubectl or helm is not installed
+    #[error("{binary} was not found in {searched}")]
+    BinaryNotFound {
+        /// Name of the binary
+        binary: String,
+        /// Where it was looked for, `PATH` or the path given to the check
+        searched: String,
+    },
+
     /// Default unhandled K8 client error
     #[error("Unhandled K8 client error: {0}")]
     UnhandledK8ClientError(String),
 
-    #[error("Local Fluvio component still exists")]
+    /// SC or SPU processes of a local cluster are running on this machine
+    #[error("A local Fluvio cluster is already running")]
     ExistingLocalCluster,
 
+    /// The SC address of the cluster could not be resolved
+    #[error("Could not resolve SC address {address}")]
+    ScAddressUnresolved {
+        /// The address clients connect to
+        address: String,
+    },
+
+    /// Nothing accepted a TCP connection at the SC address
+    #[error("Could not connect to SC at {address}")]
+    ScConnectionRefused {
+        /// The address clients connect to
+        address: String,
+    },
+
+    /// The SC accepted the connection but did not negotiate API versions in time
+    #[error("SC at {address} did not respond within {}s", timeout.as_secs_f32())]
+    ScHandshakeTimeout {
+        /// The address clients connect to
+        address: String,
+        /// How long the SC was given
+        timeout: Duration,
+    },
+
+    /// A port the local cluster needs is taken
+    #[error(
+        "Port {port} is already in use{}",
+        process.as_ref().map(|process| format!(" by {process}")).unwrap_or_default()
+    )]
+    PortInUse {
+        /// The occupied port
+        port: u16,
+        /// Name and pid of the process listening on it, if known
+        process: Option<String>,
+    },
+
+    /// The disk holding the local data directory is too full
+    #[error(
+        "Only {} MiB of disk space available, {} MiB required",
+        available / (1024 * 1024),
+        required / (1024 * 1024)
+    )]
+    InsufficientDiskSpace {
+        /// Available bytes
+        available: u64,
+        /// Required bytes
+        required: u64,
+    },
+
+    /// No SecurityContextConstraints let the Fluvio pods run on OpenShift
+    #[error("Service account {service_account} in namespace {namespace} may not run as any user")]
+    MissingOpenShiftScc {
+        /// Service account of the Fluvio pods
+        service_account: String,
+        /// Namespace Fluvio is installed into
+        namespace: String,
+    },
+
+    /// The namespace to install into does not exist and can't be created
+    #[error("Namespace {namespace} does not exist and the current user can't create it")]
+    NamespacePermissionError {
+        /// Namespace Fluvio is installed into
+        namespace: String,
+    },
+
+    /// The service account of the pod the checks run in lacks a permission
+    #[error("The service account of the pod is not allowed to {verb} {resource}")]
+    ServiceAccountPermissionError {
+        /// What it must be allowed to do, such as `list` or `create`
+        verb: String,
+        /// Name of the resource
+        resource: String,
+    },
+
+    /// A TLS secret the SC mounts is missing or holds no valid certificate
+    #[error("TLS secret {secret} is invalid: {reason}")]
+    InvalidTlsSecret {
+        /// Name of the secret
+        secret: String,
+        /// Namespace Fluvio is installed into
+        namespace: String,
+        /// What is wrong with it
+        reason: String,
+    },
+
     #[error("Helm client error")]
     HelmClientError,
 
+    /// The exec credential plugin of the current kubeconfig user is not working
+    #[error(transparent)]
+    ExecCredentialPlugin(#[from] ExecPluginError),
+
+    /// The auto-fix of a recoverable failure did not succeed
+    #[error("Auto fix for {check} failed: {error}")]
+    FailedRecovery {
+        /// Name of the check whose failure was being fixed
+        check: String,
+        /// Why the fix failed
+        error: String,
+    },
+
     /// Other misc
     #[error("Other failure: {0}")]
     Other(String),
@@ -239,24 +1048,243 @@ pub enum UnrecoverableCheckStatus {
 
 impl CheckSuggestion for UnrecoverableCheckStatus {
     fn suggestion(&self) -> Option<String> {
-        None
+        match self {
+            Self::ExecCredentialPlugin(err) => err.suggestion(),
+            Self::ConflictingInstallations { .. } => Some(
+                "Upgrade the existing installation with 'fluvio cluster upgrade', remove it with \
+                 'fluvio cluster delete', or pass '--allow-multiple-installs' to run \
+                 several Fluvio clusters in separate namespaces"
+                    .to_string(),
+            ),
+            Self::AlreadyInstalled {
+                version, target, ..
+            } => Some(match target {
+                Some(target) if is_older_version(version, target) => {
+                    format!("Upgrade it to {target} with 'fluvio cluster upgrade'")
+                }
+                _ => "Use the existing installation, or remove it with 'fluvio cluster delete'"
+                    .to_string(),
+            }),
+            Self::NoInstallationToUpgrade { .. } => Some(
+                "Install Fluvio with 'fluvio cluster start', or upgrade the namespace Fluvio is \
+                 installed in"
+                    .to_string(),
+            ),
+            Self::UpgradeIsDowngrade { installed, target } => Some(format!(
+                "Upgrade to {installed} or later, or delete the cluster with \
+                 'fluvio cluster delete' and install {target} with 'fluvio cluster start'"
+            )),
+            Self::BreakingUpgrade { target, .. } => Some(format!(
+                "Delete the cluster with 'fluvio cluster delete' and install {target} with \
+                 'fluvio cluster start'"
+            )),
+            Self::ExistingLocalCluster => Some(
+                "Stop the running cluster with 'fluvio cluster shutdown' or remove it with \
+                 'fluvio cluster delete'"
+                    .to_string(),
+            ),
+            Self::ScAddressUnresolved { address } => Some(format!(
+                "Make sure {address} resolves from this machine, or update the cluster \
+                 address of the current profile"
+            )),
+            Self::ScConnectionRefused { address } => Some(format!(
+                "Make sure the SC is running and that the load balancer or node port \
+                 behind {address} is reachable from this machine"
+            )),
+            Self::ScHandshakeTimeout { .. } => Some(
+                "Something accepts connections at the SC address but does not answer; check \
+                 that it is the Fluvio SC, that the SC pod is ready and that the TLS \
+                 settings of the profile match the cluster"
+                    .to_string(),
+            ),
+            Self::PortInUse { port, .. } => Some(format!(
+                "Stop the process listening on {port}, or start the cluster on other ports"
+            )),
+            Self::MissingOpenShiftScc {
+                service_account,
+                namespace,
+            } => Some(format!(
+                "Allow the service account to run as any user with \
+                 'oc adm policy add-scc-to-user anyuid -z {service_account} -n {namespace}'"
+            )),
+            Self::NamespacePermissionError { namespace } => Some(format!(
+                "Ask a cluster admin to run 'kubectl create namespace {namespace}', \
+                 or install into an existing namespace with '--namespace'"
+            )),
+            Self::ServiceAccountPermissionError { verb, resource } => Some(format!(
+                "Bind the service account of the pod to a Role, or a ClusterRole for \
+                 cluster-wide resources, that allows to {verb} {resource}"
+            )),
+            Self::InvalidTlsSecret {
+                secret, namespace, ..
+            } => Some(format!(
+                "Recreate {secret} with a valid certificate using \
+                 'kubectl create secret -n {namespace}', or install with the TLS certificate \
+                 files so the installer uploads them"
+            )),
+            Self::LoadBalancerServiceNotAvailable => Some(
+                "Make sure the cluster can provision load balancers, on minikube by running \
+                 'minikube tunnel', or install with '--use-cluster-ip' to connect through the \
+                 cluster IP from inside the cluster"
+                    .to_string(),
+            ),
+            Self::DockerDesktopLoadBalancerNotAvailable => Some(
+                "Make sure Kubernetes is enabled and running in the Docker Desktop settings, \
+                 Docker Desktop serves load balancers on localhost"
+                    .to_string(),
+            ),
+            Self::MinikubeTunnelNotFound => Some(
+                "Run 'minikube tunnel' in another terminal and keep it running while Fluvio \
+                 is installed and used, or install with NodePort services"
+                    .to_string(),
+            ),
+            Self::NodesNotReady { required, .. } => Some(if *required > 1 {
+                "Look into the nodes with 'kubectl describe nodes', add nodes or install \
+                 with fewer SPU replicas"
+                    .to_string()
+            } else {
+                "Look into the nodes with 'kubectl describe nodes'".to_string()
+            }),
+            Self::ChartVersionNotFound { available, .. } => Some(if available.is_empty() {
+                "Install from a local chart with '--chart-location <path>'".to_string()
+            } else {
+                "Install one of the available versions, or install from a local chart with \
+                 '--chart-location <path>'"
+                    .to_string()
+            }),
+            Self::ChartRepoUnreachable { .. } => Some(
+                "Check the repository URL and the network connection, or install from a \
+                 local chart with '--chart-location <path>'"
+                    .to_string(),
+            ),
+            Self::BinaryNotFound { binary, .. } => Some(match binary::install_command(binary) {
+                Some(command) => format!("Install {binary} with '{command}'"),
+                None => format!("Install {binary} and make sure it is in your PATH"),
+            }),
+            Self::KubeContextNotFound { .. } => {
+                Some("List the available contexts with 'kubectl config get-contexts'".to_string())
+            }
+            Self::SysChartNeedsCurrentContext { context, .. } => Some(format!(
+                "Switch to it with 'kubectl config use-context {context}' and run 'fluvio cluster start --sys'"
+            )),
+            Self::InsufficientDiskSpace { .. } => Some(
+                "Free up disk space, or set the data directory to a disk with more space"
+                    .to_string(),
+            ),
+            Self::IncompatibleKubectlVersionTooNew { max_supported, .. } => Some(format!(
+                "Use a Kubernetes cluster running {max_supported} or earlier"
+            )),
+            Self::IncompatibleSystemChart { required, .. } => Some(format!(
+                "Upgrade the cluster to {required} or later with 'fluvio cluster upgrade'"
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// A Fluvio app release found in the Kubernetes cluster
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FluvioInstallation {
+    /// Name of the helm release
+    pub name: String,
+    /// Namespace of the release
+    pub namespace: String,
+    /// Installed platform version
+    pub version: String,
+}
+
+fn format_installations(installations: &[FluvioInstallation]) -> String {
+    installations
+        .iter()
+        .map(|installation| format!("{} in {}", installation.version, installation.namespace))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn format_available_versions(chart: &str, versions: &[String]) -> String {
+    if versions.is_empty() {
+        format!(", it has no versions of {chart}")
+    } else {
+        format!(". Nearest versions: {}", versions.join(", "))
+    }
+}
+
+/// Whether `installed` is an older version than `target`, false if either is not semver
+fn is_older_version(installed: &str, target: &str) -> bool {
+    matches!(
+        (Version::parse(installed), Version::parse(target)),
+        (Ok(installed), Ok(target)) if installed < target
+    )
+}
+
+fn format_not_ready_nodes(nodes: &[String]) -> String {
+    if nodes.is_empty() {
+        String::new()
+    } else {
+        format!(". Not ready: {}", nodes.join(", "))
+    }
+}
+
+/// How much a failed check matters, from least to most severe
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CheckSeverity {
+    /// Worth reporting, nothing is expected to break
+    Info,
+    /// Fluvio may work, but something should be looked at
+    Warning,
+    /// Fluvio can't be installed or run
+    #[default]
+    Critical,
+}
+
+impl fmt::Display for CheckSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self {
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Critical => "critical",
+        };
+        write!(f, "{severity}")
     }
 }
 
 /// Fluvio Cluster component
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
 pub enum FluvioClusterComponent {
     Helm,
     Kubernetes,
     K8Version,
     SysChart,
+    KubectlBinary,
+    HelmBinary,
 }
 
-#[async_trait]
-pub trait ClusterCheck: Debug + 'static + Send + Sync {
-    /// Returns label that can be used
+impl FluvioClusterComponent {
+    /// Name of the binary this component stands for
+    fn binary(&self) -> Option<&'static str> {
+        match self {
+            Self::KubectlBinary => Some("kubectl"),
+            Self::HelmBinary => Some("helm"),
+            _ => None,
+        }
+    }
+}
+
+#[async_trait]
+pub trait ClusterCheck: Debug + 'static + Send + Sync {
+    /// Stable identifier used to select checks by name, e.g. `helm-version`
+    fn name(&self) -> &str;
+
+    /// Returns label that can be used
     fn label(&self) -> &str;
 
+    /// One sentence on what the check verifies, defaults to the [`label`]
+    ///
+    /// [`label`]: ClusterCheck::label
+    fn description(&self) -> &str {
+        self.label()
+    }
+
     /// can register as component that other checker can depend on
     fn component(&self) -> Option<FluvioClusterComponent> {
         None
@@ -267,701 +1295,906 @@ pub trait ClusterCheck: Debug + 'static + Send + Sync {
         vec![]
     }
 
-    /// perform check, if successful return success message, if fail, return
-    async fn perform_check(&self, pb: &ProgressRenderer) -> Result<CheckStatus, ClusterCheckError>;
-}
-
-#[async_trait]
-pub trait ClusterAutoFix: Debug + 'static + Send + Sync {
-    /// Attempt to fix a recoverable error. return string
-    async fn attempt_fix(&self, render: &ProgressRenderer) -> Result<String, ClusterAutoFixError>;
-}
-
-/// Check for loading
-#[derive(Debug)]
-pub(crate) struct ActiveKubernetesCluster;
-
-#[async_trait]
-impl ClusterCheck for ActiveKubernetesCluster {
-    /// Checks that we can connect to Kubernetes via the active context
-    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
-        let config = match K8Config::load() {
-            Ok(config) => config,
-            Err(K8ConfigError::NoCurrentContext) => {
-                return Ok(CheckStatus::Unrecoverable(
-                    UnrecoverableCheckStatus::NoActiveKubernetesContext,
-                ))
-            }
-
-            Err(err) => {
-                return Ok(CheckStatus::Unrecoverable(
-                    UnrecoverableCheckStatus::UnhandledK8ClientError(format!("K8 Error: {err:#?}")),
-                ))
-            }
-        };
-
-        let context = match config {
-            K8Config::Pod(_) => {
-                return Ok(CheckStatus::Unrecoverable(UnrecoverableCheckStatus::Other(
-                    "Pod config found".to_owned(),
-                )))
-            }
-            K8Config::KubeConfig(context) => context,
-        };
+    /// identity used to talk to the Kubernetes API, `None` if the check doesn't touch the cluster
+    fn identity(&self) -> Option<ClusterIdentity> {
+        None
+    }
 
-        match context.config.current_cluster() {
-            Some(cluster) => Ok(CheckStatus::pass(format!(
-                "Kubectl active cluster {} at: {} found",
-                context.config.current_context, cluster.cluster.server
-            ))),
-            None => Ok(CheckStatus::Unrecoverable(
-                UnrecoverableCheckStatus::NoActiveKubernetesContext,
-            )),
-        }
+    /// cheap and never changes the cluster, so it can be re-run to detect drift,
+    /// see [`CheckResults::revalidate_quick`]
+    fn is_quick(&self) -> bool {
+        false
     }
 
-    fn required_components(&self) -> Vec<FluvioClusterComponent> {
-        vec![]
+    /// how long the check may take, `None` to use the checker's default
+    fn timeout(&self) -> Option<Duration> {
+        None
     }
 
-    fn component(&self) -> Option<FluvioClusterComponent> {
-        Some(FluvioClusterComponent::Kubernetes)
+    /// changes cluster state, so it must not run at the same time as other checks
+    fn exclusive(&self) -> bool {
+        false
     }
 
-    fn label(&self) -> &str {
-        "Kubernetes config"
+    /// how much a failure of this check matters, see [`ClusterChecker::with_minimum_severity`]
+    fn severity(&self) -> CheckSeverity {
+        CheckSeverity::Critical
     }
-}
 
-#[derive(Debug)]
-pub(crate) struct K8Version;
+    /// look at `namespace` instead of the check's default, see [`ClusterChecker::with_namespace`]
+    fn set_namespace(&mut self, _namespace: &str) {}
 
-#[async_trait]
-impl ClusterCheck for K8Version {
-    /// Check if required kubectl version is installed
-    async fn perform_check(&self, _: &ProgressRenderer) -> CheckResult {
-        let kube_version = Command::new("kubectl")
-            .arg("version")
-            .arg("-o=json")
-            .output()
-            .map_err(ClusterCheckError::KubectlNotFoundError)?;
-
-        #[derive(Debug, serde::Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct ComponentVersion {
-            git_version: String,
-        }
-
-        #[derive(Debug, serde::Deserialize)]
-        #[serde(rename_all = "camelCase")]
-        struct KubernetesVersion {
-            #[allow(dead_code)]
-            client_version: ComponentVersion,
-            server_version: Option<ComponentVersion>,
-        }
-
-        let kube_versions: KubernetesVersion = serde_json::from_slice(&kube_version.stdout)
-            .map_err(ClusterCheckError::KubectlVersionJsonError)?;
-
-        let server_version = match kube_versions.server_version {
-            Some(version) => version.git_version,
-            None => {
-                return Ok(CheckStatus::Unrecoverable(
-                    UnrecoverableCheckStatus::CannotConnectToKubernetes,
-                ))
-            }
-        };
+    /// talk to the cluster of kubeconfig `context` instead of the current one,
+    /// see [`ClusterChecker::with_kube_context`]
+    fn set_kube_context(&mut self, _context: &str) {}
 
-        // Trim off the `v` in v0.1.2 to get just "0.1.2"
-        let server_version = &server_version[1..];
-        if Version::parse(server_version)? < Version::parse(KUBE_VERSION)? {
-            Ok(CheckStatus::Unrecoverable(
-                UnrecoverableCheckStatus::IncompatibleKubectlVersion {
-                    installed: server_version.to_string(),
-                    required: KUBE_VERSION.to_string(),
-                },
-            ))
-        } else {
-            Ok(CheckStatus::pass(format!(
-                "Supported Kubernetes server {server_version} found"
-            )))
-        }
-    }
+    /// find out about the machine and the cluster through `env`,
+    /// see [`ClusterChecker::with_environment`]
+    fn set_environment(&mut self, _env: &Arc<dyn CheckEnvironment>) {}
 
-    fn required_components(&self) -> Vec<FluvioClusterComponent> {
-        vec![FluvioClusterComponent::Kubernetes]
-    }
+    /// require at least `version` of `component`, see [`ClusterChecker::with_required_k8_version`]
+    fn set_required_version(&mut self, _component: &FluvioClusterComponent, _version: &Version) {}
 
-    fn component(&self) -> Option<FluvioClusterComponent> {
-        Some(FluvioClusterComponent::K8Version)
-    }
+    /// require `count` ready Kubernetes nodes, see [`ClusterChecker::with_min_ready_nodes`]
+    fn set_min_ready_nodes(&mut self, _count: usize) {}
 
-    fn label(&self) -> &str {
-        "Kubernetes version"
-    }
+    /// perform check, if successful return success message, if fail, return
+    async fn perform_check(&self, pb: &ProgressRenderer) -> Result<CheckStatus, ClusterCheckError>;
 }
 
-#[derive(Debug)]
-pub(crate) struct HelmVersion;
-
 #[async_trait]
-impl ClusterCheck for HelmVersion {
-    /// Checks that the installed helm version is compatible with the installer requirements
-    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
-        let helm = match HelmClient::new() {
-            Ok(client) => client,
-            Err(err) => {
-                return Ok(CheckStatus::Unrecoverable(
-                    UnrecoverableCheckStatus::NoHelmClient(format!(
-                        "Unable to find helm: {err:#?}"
-                    )),
-                ))
-            }
-        };
-
-        let helm_version = helm
-            .get_helm_version()
-            .map_err(ClusterCheckError::HelmError)?;
-        let required = DEFAULT_HELM_VERSION;
-        if Version::parse(&helm_version)? < Version::parse(required)? {
-            return Ok(CheckStatus::Unrecoverable(
-                UnrecoverableCheckStatus::IncompatibleHelmVersion {
-                    installed: helm_version,
-                    required: required.to_string(),
-                },
-            ));
-        }
-        Ok(CheckStatus::pass(format!(
-            "Supported helm version {helm_version} is installed"
-        )))
-    }
-
-    fn component(&self) -> Option<FluvioClusterComponent> {
-        Some(FluvioClusterComponent::Helm)
-    }
-
-    fn label(&self) -> &str {
-        "Helm"
-    }
+pub trait ClusterAutoFix: Debug + 'static + Send + Sync {
+    /// Attempt to fix a recoverable error. return string
+    ///
+    /// Long running fixes should report their steps through `progress`.
+    async fn attempt_fix(
+        &self,
+        progress: &FixProgressReporter<'_>,
+    ) -> Result<String, ClusterAutoFixError>;
 }
 
-#[derive(Debug)]
-pub(crate) struct SysChartCheck {
-    config: ChartConfig,
-    platform_version: Version,
+/// A step reported by a fixer while it is running
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixProgress {
+    /// Human readable description of the current step
+    pub step: String,
+    /// Completion percentage, if the fixer can estimate it
+    pub percent: Option<u8>,
 }
 
-impl SysChartCheck {
-    pub(crate) fn new(config: ChartConfig, platform_version: Version) -> Self {
+impl FixProgress {
+    pub fn new(step: impl Into<String>, percent: Option<u8>) -> Self {
         Self {
-            config,
-            platform_version,
+            step: step.into(),
+            percent,
         }
     }
 }
 
-#[async_trait]
-impl ClusterCheck for SysChartCheck {
-    /// Check that the system chart is installed
-    /// This uses whatever namespace it is being called
-    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
-        debug!("performing sys chart check");
-
-        let helm = HelmClient::new()?;
-        // check installed system chart version
-        let sys_charts = match helm
-            .get_installed_chart_by_name(SYS_CHART_NAME, None)
-            .map_err(ClusterCheckError::HelmError)
-        {
-            Ok(charts) => charts,
-            Err(helm_error) => {
-                debug!(?helm_error, "helm client error");
-                return Ok(CheckStatus::Unrecoverable(
-                    UnrecoverableCheckStatus::HelmClientError,
-                ));
-            }
-        };
-        debug!(charts = sys_charts.len(), "sys charts count");
-        if sys_charts.is_empty() {
-            Ok(CheckStatus::AutoFixableError {
-                message: format!(
-                    "System chart not installed, installing version {}",
-                    self.platform_version
-                ),
-                fixer: Box::new(InstallSysChart {
-                    config: self.config.clone(),
-                    platform_version: self.platform_version.clone(),
-                }),
-            })
-        } else if sys_charts.len() > 1 {
-            Ok(CheckStatus::Unrecoverable(
-                UnrecoverableCheckStatus::MultipleSystemCharts,
-            ))
-        } else {
-            let install_chart = sys_charts.get(0).unwrap();
-            debug!(app_version = %install_chart.app_version,"Sys Chart Version");
-            let existing_platform_version = Version::parse(&install_chart.app_version)?;
-            if existing_platform_version == self.platform_version {
-                Ok(CheckStatus::pass("Fluvio system charts are installed"))
-            } else {
-                Ok(CheckStatus::AutoFixableError {
-                    message: format!(
-                        "System chart version {} installed, upgrading to version {}",
-                        existing_platform_version, self.platform_version
-                    ),
-                    fixer: Box::new(UpgradeSysChart {
-                        config: self.config.clone(),
-                        platform_version: self.platform_version.clone(),
-                    }),
-                })
-            }
-        }
-    }
+/// Renders fix progress as a nested spinner under the check being fixed
+/// and forwards it to the channel registered with [`ClusterChecker::with_fix_progress`]
+#[derive(Debug)]
+pub struct FixProgressReporter<'a> {
+    render: &'a ProgressRenderer,
+    sender: Option<Sender<FixProgress>>,
+}
 
-    fn required_components(&self) -> Vec<FluvioClusterComponent> {
-        vec![
-            FluvioClusterComponent::Helm,
-            FluvioClusterComponent::Kubernetes,
-        ]
+impl<'a> FixProgressReporter<'a> {
+    pub fn new(render: &'a ProgressRenderer, sender: Option<Sender<FixProgress>>) -> Self {
+        Self { render, sender }
     }
 
-    fn component(&self) -> Option<FluvioClusterComponent> {
-        Some(FluvioClusterComponent::SysChart)
+    /// Report a step with an optional completion percentage
+    pub fn report(&self, progress: FixProgress) {
+        let percent = progress
+            .percent
+            .map(|percent| format!(" ({percent}%)"))
+            .unwrap_or_default();
+        self.render
+            .set_message(format!("{:>6} ⏳ {}{}", "", progress.step, percent));
+
+        if let Some(sender) = &self.sender {
+            if let Err(err) = sender.try_send(progress) {
+                debug!(%err, "unable to forward fix progress");
+            }
+        }
     }
 
-    fn label(&self) -> &str {
-        "Fluvio Sys Chart"
+    /// Report a step without completion percentage
+    pub fn step(&self, step: impl Into<String>) {
+        self.report(FixProgress::new(step, None));
     }
 }
 
+/// Manages all cluster check operations
+///
+/// A `ClusterChecker` can be configured with different sets of checks to run.
+/// Checks are run with the [`run`] method.
+///
+/// [`run`]: ClusterChecker::run
 #[derive(Debug)]
-pub(crate) struct InstallSysChart {
-    config: ChartConfig,
-    platform_version: Version,
+#[non_exhaustive]
+pub struct ClusterChecker {
+    checks: Vec<Box<dyn ClusterCheck>>,
+    fix_progress: Option<Sender<FixProgress>>,
+    concurrency_limit: Option<usize>,
+    timeouts: CheckTimeouts,
+    minimum_severity: CheckSeverity,
+    namespace: Option<String>,
+    kube_context: Option<String>,
+    environment: Option<Arc<dyn CheckEnvironment>>,
+    retry: CheckRetry,
+    required_versions: Vec<(FluvioClusterComponent, Version)>,
+    min_ready_nodes: Option<usize>,
+    cancellation: Option<Arc<StickyEvent>>,
 }
 
-#[async_trait]
-impl ClusterAutoFix for InstallSysChart {
-    async fn attempt_fix(&self, _render: &ProgressRenderer) -> Result<String, ClusterAutoFixError> {
-        debug!(
-            "Fixing by installing Fluvio sys chart with config: {:#?}",
-            &self.config
-        );
-        let sys_installer = ChartInstaller::from_config(self.config.clone())?;
-        sys_installer.install()?;
+/// How often a check that errors is performed again
+#[derive(Debug, Clone, Copy)]
+struct CheckRetry {
+    /// attempts in total, including the first one
+    max_attempts: u32,
+    /// grows linearly with every attempt
+    backoff: Duration,
+}
 
-        Ok(format!(
-            "Fluvio Sys chart {} is installed",
-            self.platform_version
-        ))
+impl Default for CheckRetry {
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            backoff: Duration::ZERO,
+        }
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct UpgradeSysChart {
-    config: ChartConfig,
-    platform_version: Version,
+/// Time limits applied to each check
+#[derive(Debug, Clone, Copy, Default)]
+struct CheckTimeouts {
+    /// for checks that don't advertise a timeout
+    default: Option<Duration>,
+    /// upper bound for every check
+    limit: Option<Duration>,
 }
 
-#[async_trait]
-impl ClusterAutoFix for UpgradeSysChart {
-    async fn attempt_fix(&self, _render: &ProgressRenderer) -> Result<String, ClusterAutoFixError> {
-        debug!(
-            "Fixing by updating Fluvio sys chart with config: {:#?}",
-            &self.config
-        );
-
-        let sys_installer = ChartInstaller::from_config(self.config.clone())?;
-        sys_installer.upgrade()?;
-
-        Ok(format!(
-            "Fluvio sys chart is upgraded to: {}",
-            self.platform_version
-        ))
+impl CheckTimeouts {
+    fn for_check(&self, check: &dyn ClusterCheck) -> Option<Duration> {
+        match (check.timeout().or(self.default), self.limit) {
+            (Some(timeout), Some(limit)) => Some(timeout.min(limit)),
+            (timeout, limit) => timeout.or(limit),
+        }
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct AlreadyInstalled;
-
-#[async_trait]
-impl ClusterCheck for AlreadyInstalled {
-    /// Checks that Fluvio is not already installed
-    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
-        let helm = HelmClient::new()?;
-        let app_charts = helm.get_installed_chart_by_name(APP_CHART_NAME, None)?;
-        if !app_charts.is_empty() {
-            return Ok(CheckStatus::Unrecoverable(
-                UnrecoverableCheckStatus::AlreadyInstalled,
-            ));
+impl ClusterChecker {
+    /// Creates an empty checker with no checks to be run.
+    ///
+    /// Be sure to use methods like [`with_check`] to add checks before
+    /// calling the `run` method, or it will do nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::ClusterChecker;
+    /// let checker: ClusterChecker = ClusterChecker::empty();
+    /// ```
+    ///
+    /// [`with_check`]: ClusterChecker::with_check
+    pub fn empty() -> Self {
+        ClusterChecker {
+            checks: vec![],
+            fix_progress: None,
+            concurrency_limit: None,
+            timeouts: CheckTimeouts::default(),
+            minimum_severity: CheckSeverity::Info,
+            namespace: None,
+            kube_context: None,
+            environment: None,
+            retry: CheckRetry::default(),
+            required_versions: vec![],
+            min_ready_nodes: None,
+            cancellation: None,
         }
-        Ok(CheckStatus::pass("Previous fluvio installation not found"))
     }
 
-    fn required_components(&self) -> Vec<FluvioClusterComponent> {
-        vec![
-            FluvioClusterComponent::Helm,
-            FluvioClusterComponent::Kubernetes,
-        ]
+    /// Adds a check to this `ClusterChecker`
+    pub fn with_check<C: ClusterCheck>(mut self, check: impl Into<Box<C>>) -> Self {
+        self.push_check(check.into());
+        self
     }
 
-    fn label(&self) -> &str {
-        "Fluvio installation"
+    /// Adds a [`TlsSecrets`] check of `secrets` if `tls` enables TLS on the SC
+    pub fn with_tls_checks(self, tls: &TlsPolicy, secrets: TlsSecrets) -> Self {
+        match tls {
+            TlsPolicy::Disabled => self,
+            TlsPolicy::Anonymous | TlsPolicy::Verified(_) => self.with_check(secrets),
+        }
     }
-}
 
-#[derive(Debug)]
-struct CreateServicePermission;
-
-#[async_trait]
-impl ClusterCheck for CreateServicePermission {
-    async fn perform_check(&self, pb: &ProgressRenderer) -> CheckResult {
-        check_permission(RESOURCE_SERVICE, pb)
+    fn push_check(&mut self, mut check: Box<dyn ClusterCheck>) {
+        if let Some(namespace) = &self.namespace {
+            check.set_namespace(namespace);
+        }
+        if let Some(context) = &self.kube_context {
+            check.set_kube_context(context);
+        }
+        if let Some(env) = &self.environment {
+            check.set_environment(env);
+        }
+        for (component, version) in &self.required_versions {
+            check.set_required_version(component, version);
+        }
+        if let Some(count) = self.min_ready_nodes {
+            check.set_min_ready_nodes(count);
+        }
+        self.checks.push(check);
     }
 
-    fn required_components(&self) -> Vec<FluvioClusterComponent> {
-        vec![FluvioClusterComponent::Kubernetes]
+    /// Forwards the steps reported by auto-fixes to `sender`
+    ///
+    /// Steps are always rendered on the progress bar; this is for callers
+    /// that want to consume them programmatically.
+    pub fn with_fix_progress(mut self, sender: Sender<FixProgress>) -> Self {
+        self.fix_progress = Some(sender);
+        self
     }
 
-    fn label(&self) -> &str {
-        "Kubernetes Service Permission"
+    /// Limits how many checks [`run_parallel`] performs at the same time
+    ///
+    /// [`run_parallel`]: ClusterChecker::run_parallel
+    pub fn with_concurrency_limit(mut self, limit: usize) -> Self {
+        self.concurrency_limit = Some(limit.max(1));
+        self
     }
-}
 
-#[derive(Debug)]
-struct CreateCrdPermission;
-
-#[async_trait]
-impl ClusterCheck for CreateCrdPermission {
-    async fn perform_check(&self, pb: &ProgressRenderer) -> CheckResult {
-        check_permission(RESOURCE_CRD, pb)
+    /// Gives up on checks that take longer than `timeout`
+    ///
+    /// Checks that advertise their own [`timeout`] use that instead. A check
+    /// that runs out of time fails with [`ClusterCheckError::TimedOut`].
+    ///
+    /// [`timeout`]: ClusterCheck::timeout
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.default = Some(timeout);
+        self
     }
 
-    fn required_components(&self) -> Vec<FluvioClusterComponent> {
-        vec![FluvioClusterComponent::Kubernetes]
+    /// Gives up on any check that takes longer than `timeout`
+    ///
+    /// Unlike [`with_default_timeout`] this also caps checks that advertise a
+    /// longer [`timeout`] of their own, so a hung kubectl can't stall the run.
+    ///
+    /// [`with_default_timeout`]: ClusterChecker::with_default_timeout
+    /// [`timeout`]: ClusterCheck::timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.limit = Some(timeout);
+        self
     }
 
-    fn label(&self) -> &str {
-        "Kubernetes Crd Permission"
+    /// Only fails [`run`] for checks at least as severe as `severity`
+    ///
+    /// Failures of less severe checks are still reported, but the run
+    /// continues as if they passed, [`run_until_first_failure`] included.
+    /// The other run modes report every outcome and stop on none of them.
+    /// By default every failure counts.
+    ///
+    /// [`run`]: ClusterChecker::run
+    /// [`run_until_first_failure`]: ClusterChecker::run_until_first_failure
+    pub fn with_minimum_severity(mut self, severity: CheckSeverity) -> Self {
+        self.minimum_severity = severity;
+        self
     }
-}
-
-#[derive(Debug)]
-struct CreateServiceAccountPermission;
 
-#[async_trait]
-impl ClusterCheck for CreateServiceAccountPermission {
-    async fn perform_check(&self, pb: &ProgressRenderer) -> CheckResult {
-        check_permission(RESOURCE_SERVICE_ACCOUNT, pb)
+    /// Performs a check that errors up to `max_attempts` times in total
+    ///
+    /// Before attempt `n + 1` the checker waits `backoff * n`. Only errors,
+    /// including timeouts, are retried: a check that fails or passes is
+    /// reported right away. This rides out short hiccups of the Kubernetes
+    /// API server. By default every check is performed once.
+    pub fn with_retry(mut self, max_attempts: u32, backoff: Duration) -> Self {
+        self.retry = CheckRetry {
+            max_attempts: max_attempts.max(1),
+            backoff,
+        };
+        self
     }
 
-    fn required_components(&self) -> Vec<FluvioClusterComponent> {
-        vec![FluvioClusterComponent::Kubernetes]
+    /// Stops performing checks once `token` is notified, in every run mode
+    ///
+    /// The token is looked at before each check, a check that already
+    /// started is allowed to finish. Checks that had not started result in
+    /// [`ClusterCheckError::Cancelled`]. [`run`] then ends with that error,
+    /// [`run_until_first_failure`] and [`run_with_progress`] stop after the
+    /// first cancelled check.
+    ///
+    /// [`run`]: ClusterChecker::run
+    /// [`run_until_first_failure`]: ClusterChecker::run_until_first_failure
+    /// [`run_with_progress`]: ClusterChecker::run_with_progress
+    pub fn with_cancellation_token(mut self, token: Arc<StickyEvent>) -> Self {
+        self.cancellation = Some(token);
+        self
     }
 
-    fn label(&self) -> &str {
-        "Kubernetes Service Account Permission"
+    /// Points every check, including ones added later, at `namespace`
+    ///
+    /// Without a namespace, checks keep their own defaults and the sys chart
+    /// check looks in every namespace.
+    pub fn with_namespace(mut self, namespace: impl Into<String>) -> Self {
+        let namespace = namespace.into();
+        for check in &mut self.checks {
+            check.set_namespace(&namespace);
+        }
+        self.namespace = Some(namespace);
+        self
     }
-}
 
-/// check if local cluster is running
-#[derive(Debug)]
-struct LocalClusterCheck;
+    /// Requires `count` ready Kubernetes nodes in every check, including ones added later
+    ///
+    /// Installers pass the number of SPU replicas, the [`NodeReadiness`]
+    /// check otherwise requires a single node.
+    pub fn with_min_ready_nodes(mut self, count: usize) -> Self {
+        for check in &mut self.checks {
+            check.set_min_ready_nodes(count);
+        }
+        self.min_ready_nodes = Some(count);
+        self
+    }
 
-#[async_trait]
-impl ClusterCheck for LocalClusterCheck {
-    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
-        let mut sys = System::new();
-        sys.refresh_processes(); // Only load what we need.
-        let proc_count = sys
-            .processes_by_exact_name("fluvio-run")
-            .map(|x| debug!("Found existing fluvio-run process. pid: {}", x.pid()))
-            .count();
-        if proc_count > 0 {
-            return Ok(CheckStatus::Unrecoverable(
-                UnrecoverableCheckStatus::ExistingLocalCluster,
-            ));
+    /// Points every check, including ones added later, at kubeconfig context `name`
+    ///
+    /// Checks otherwise talk to the cluster of the current context, which is
+    /// not necessarily the one an install goes to. kubectl is run with
+    /// `--context` and helm with `--kube-context`. The system chart can't be
+    /// installed or upgraded in another context, a missing or outdated one
+    /// fails with [`UnrecoverableCheckStatus::SysChartNeedsCurrentContext`]
+    /// instead of being fixed. If the kubeconfig has no context `name`, every
+    /// check fails with
+    /// [`UnrecoverableCheckStatus::KubeContextNotFound`] instead of being
+    /// performed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::ClusterChecker;
+    /// let checker = ClusterChecker::empty()
+    ///     .with_preflight_checks()
+    ///     .with_kube_context("kind-fluvio");
+    /// ```
+    pub fn with_kube_context(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        for check in &mut self.checks {
+            check.set_kube_context(&name);
         }
-        Ok(CheckStatus::pass("Local Fluvio is not installed"))
+        self.kube_context = Some(name);
+        self
     }
 
-    fn label(&self) -> &str {
-        "Fluvio Local Installation"
+    /// The context set with [`with_kube_context`], if the kubeconfig doesn't have it
+    ///
+    /// [`with_kube_context`]: ClusterChecker::with_kube_context
+    fn missing_kube_context(&self) -> Option<String> {
+        let env: &dyn CheckEnvironment = match &self.environment {
+            Some(env) => env.as_ref(),
+            None => &SystemEnvironment,
+        };
+        self.kube_context
+            .as_ref()
+            .filter(|name| !context::context_exists(env, name))
+            .cloned()
     }
-}
 
-/// Manages all cluster check operations
-///
-/// A `ClusterChecker` can be configured with different sets of checks to run.
-/// Checks are run with the [`run`] method.
-///
-/// [`run`]: ClusterChecker::run
-#[derive(Debug)]
-#[non_exhaustive]
-pub struct ClusterChecker {
-    checks: Vec<Box<dyn ClusterCheck>>,
-}
+    /// Runs every check, including ones added later, against `env`
+    ///
+    /// Checks read the kubeconfig, talk to the cluster, run kubectl and helm
+    /// and look at the local machine through their [`CheckEnvironment`],
+    /// which is the [`SystemEnvironment`] unless given another one here,
+    /// such as a fake cluster in tests. Fixes go through it as well.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use fluvio_cluster::{ClusterChecker, SystemEnvironment};
+    /// let checker = ClusterChecker::empty()
+    ///     .with_environment(Arc::new(SystemEnvironment))
+    ///     .with_preflight_checks();
+    /// ```
+    pub fn with_environment(mut self, env: Arc<dyn CheckEnvironment>) -> Self {
+        for check in &mut self.checks {
+            check.set_environment(&env);
+        }
+        self.environment = Some(env);
+        self
+    }
 
-impl ClusterChecker {
-    /// Creates an empty checker with no checks to be run.
+    /// Drops the checks added so far whose [`name`] doesn't satisfy `pred`
     ///
-    /// Be sure to use methods like [`with_check`] to add checks before
-    /// calling the `run` method, or it will do nothing.
+    /// Call this after adding checks and before running them, e.g. to skip
+    /// `helm-version` on a managed cluster without helm.
     ///
     /// # Example
     ///
     /// ```
     /// # use fluvio_cluster::ClusterChecker;
-    /// let checker: ClusterChecker = ClusterChecker::empty();
+    /// let checker = ClusterChecker::empty()
+    ///     .with_k8_checks()
+    ///     .with_check_filter(|name| name != "helm-version");
     /// ```
     ///
-    /// [`with_check`]: ClusterChecker::with_check
-    pub fn empty() -> Self {
-        ClusterChecker { checks: vec![] }
+    /// [`name`]: ClusterCheck::name
+    pub fn with_check_filter<F: Fn(&str) -> bool + 'static>(mut self, pred: F) -> Self {
+        self.checks.retain(|check| {
+            let keep = pred(check.name());
+            if !keep {
+                debug!(check = check.name(), "check filtered out");
+            }
+            keep
+        });
+        self
     }
 
-    /// Adds a check to this `ClusterChecker`
-    pub fn with_check<C: ClusterCheck>(mut self, check: impl Into<Box<C>>) -> Self {
-        self.checks.push(check.into());
-        self
+    /// Reports the checks added so far that are named in `checks` as skipped
+    ///
+    /// Skipped checks are not performed, they result in
+    /// [`CheckStatus::Skipped`] so reports still list them. This is for checks
+    /// known to fail in an environment, such as an air-gapped cluster. Returns
+    /// [`ClusterCheckError::UnknownCheck`] if a name matches none of the checks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::ClusterChecker;
+    /// let checker = ClusterChecker::empty()
+    ///     .with_preflight_checks()
+    ///     .skip(&["kubernetes-version"])
+    ///     .expect("known check");
+    /// ```
+    pub fn skip(self, checks: &[&str]) -> Result<Self, ClusterCheckError> {
+        self.ensure_known_checks(checks)?;
+        Ok(self.skip_matching(|name| checks.contains(&name), "skipped on request"))
     }
 
-    /// Adds all preflight checks to this checker.
+    /// Reports every check added so far that is not named in `checks` as skipped
     ///
-    /// Note that no checks are run until the [`run`] method is invoked.
+    /// The inverse of [`skip`]. Returns [`ClusterCheckError::UnknownCheck`] if
+    /// a name matches none of the checks.
     ///
-    /// [`run`]: ClusterChecker::run
-    pub fn with_preflight_checks(mut self) -> Self {
-        let checks: Vec<Box<(dyn ClusterCheck)>> = vec![
-            Box::new(ActiveKubernetesCluster),
-            Box::new(K8Version),
-            Box::new(HelmVersion),
-            Box::new(CreateServicePermission),
-            Box::new(CreateCrdPermission),
-            Box::new(CreateServiceAccountPermission),
-        ];
-        self.checks.extend(checks);
-        self
+    /// [`skip`]: ClusterChecker::skip
+    pub fn only(self, checks: &[&str]) -> Result<Self, ClusterCheckError> {
+        self.ensure_known_checks(checks)?;
+        Ok(self.skip_matching(|name| !checks.contains(&name), "not selected"))
+    }
+
+    fn ensure_known_checks(&self, names: &[&str]) -> Result<(), ClusterCheckError> {
+        let known: Vec<_> = self
+            .checks
+            .iter()
+            .map(|check| check.name().to_string())
+            .collect();
+        match names
+            .iter()
+            .find(|name| !known.iter().any(|known| known == *name))
+        {
+            Some(name) => Err(ClusterCheckError::UnknownCheck {
+                name: name.to_string(),
+                known,
+            }),
+            None => Ok(()),
+        }
     }
 
-    pub fn with_no_k8_checks(mut self) -> Self {
-        let checks: Vec<Box<(dyn ClusterCheck)>> = vec![Box::new(LocalClusterCheck)];
-        self.checks.extend(checks);
+    fn skip_matching(mut self, skip: impl Fn(&str) -> bool, reason: &str) -> Self {
+        self.checks = self
+            .checks
+            .into_iter()
+            .map(|check| -> Box<dyn ClusterCheck> {
+                if skip(check.name()) {
+                    debug!(check = check.name(), reason, "check skipped");
+                    Box::new(SkippedCheck::new(check, reason))
+                } else {
+                    check
+                }
+            })
+            .collect();
         self
     }
 
-    /// Adds all checks required for starting a cluster on minikube.
-    ///
-    /// Note that no checks are run until the [`run`] method is invoked.
+    /// Requires at least `version` of helm instead of the installer's default
     ///
-    /// [`run`]: ClusterChecker::run
-    pub fn with_k8_checks(mut self) -> Self {
-        let checks: Vec<Box<(dyn ClusterCheck)>> = vec![
-            Box::new(ActiveKubernetesCluster),
-            Box::new(HelmVersion),
-            Box::new(K8Version),
-        ];
-        self.checks.extend(checks);
-        self
+    /// Applies to every check, including ones added later. Returns an error
+    /// if `version` is not semver.
+    pub fn with_required_helm_version(self, version: &str) -> Result<Self, ClusterCheckError> {
+        self.with_required_version(FluvioClusterComponent::Helm, version)
     }
 
-    /// Adds all checks required for starting a local cluster.
+    /// Requires at least `version` of the Kubernetes server instead of 1.7.0
     ///
-    /// Note that no checks are run until the [`run`] method is invoked.
+    /// Applies to every check, including ones added later. Returns an error
+    /// if `version` is not semver.
     ///
-    /// [`run`]: ClusterChecker::run
-    pub fn with_local_checks(mut self) -> Self {
-        let checks: Vec<Box<(dyn ClusterCheck)>> = vec![
-            Box::new(HelmVersion),
-            Box::new(K8Version),
-            Box::new(ActiveKubernetesCluster),
-            Box::new(LocalClusterCheck),
-        ];
-        self.checks.extend(checks);
-        self
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::ClusterChecker;
+    /// let checker = ClusterChecker::empty()
+    ///     .with_k8_checks()
+    ///     .with_required_k8_version("1.21.0")
+    ///     .expect("valid version");
+    /// ```
+    pub fn with_required_k8_version(self, version: &str) -> Result<Self, ClusterCheckError> {
+        self.with_required_version(FluvioClusterComponent::K8Version, version)
     }
 
-    /// Performs checks and fixes as required.
-    pub async fn run(
-        self,
-        pb_factory: &ProgressBarFactory,
-        fix_recoverable: bool,
-    ) -> Result<bool, ClusterCheckError> {
-        macro_rules! pad_format {
-            ( $e:expr ) => {
-                format!("{:>3} {}", "", $e)
-            };
-        }
-
-        // sort checks according to dependencies
-        let mut components: HashSet<FluvioClusterComponent> = HashSet::new();
-
-        let mut sorted_checks = self.checks;
-        sorted_checks.sort_by(check_compare);
-
-        let mut failed = false;
-        for check in sorted_checks {
-            let pb = pb_factory.create()?;
-            let mut passed = false;
-            let required_components = check.required_components();
-            let component = check.component();
-            if required_components
-                .iter()
-                .filter(|component| components.contains(component))
-                .count()
-                == required_components.len()
-            {
-                pb.set_message(pad_format!(format!(
-                    "{} Checking {}",
-                    "📝".bold(),
-                    check.label()
-                )));
-                sleep(Duration::from_millis(100)).await; // dummy delay for debugging
-                match check.perform_check(&pb).await? {
-                    CheckStatus::AutoFixableError { message, fixer } => {
-                        if fix_recoverable {
-                            pb.set_message(pad_format!(format!("{} {}", "🟡️".bold(), message)));
-                            match fixer.attempt_fix(&pb).await {
-                                Ok(status) => {
-                                    pb.println(pad_format!(format!(
-                                        "{} Fixed: {}",
-                                        "✅".bold(),
-                                        status
-                                    )));
-                                    passed = true;
-                                }
-                                Err(err) => {
-                                    // If the fix failed, wrap the original failed check in Unrecoverable
-                                    pb.println(pad_format!(format!(
-                                        "{} Auto fix for {} failed {:#?}",
-                                        "❌",
-                                        check.label().italic(),
-                                        err
-                                    )));
-
-                                    failed = true;
-                                }
-                            }
-                        } else {
-                            pb.println(pad_format!(format!(
-                                "{} {} check failed and is auto-fixable but fixer is disabled. Use `--fix` to enable it.",
-                                "❌".bold(),
-                                check.label().italic(),
-                            )));
-
-                            failed = true;
-                        }
-                    }
-                    CheckStatus::Pass(status) => {
-                        passed = true;
-                        pb.println(pad_format!(format!("{} {}", "✅".bold(), status)));
-                    }
-                    CheckStatus::Unrecoverable(err) => {
-                        debug!("failed: {}", err);
-
-                        pb.println(pad_format!(format!(
-                            "{} Check {} failed {}",
-                            "❌",
-                            check.label().italic(),
-                            err.to_string().red()
-                        )));
-
-                        failed = true;
-                    }
-                }
-            } else {
-                pb.println(pad_format!(format!(
-                    "❌ skipping check: {} because required components are not met",
-                    check.label()
-                )));
-                failed = true;
-            }
-
-            if passed {
-                if let Some(component) = component {
-                    debug!(?component, "component registered");
-                    components.insert(component);
-                }
-            }
+    /// Fails the system chart check if the installed chart is older than `version`
+    ///
+    /// Charts older than that can't be upgraded in place. Returns an error
+    /// if `version` is not semver.
+    pub fn with_required_sys_chart_version(self, version: &str) -> Result<Self, ClusterCheckError> {
+        self.with_required_version(FluvioClusterComponent::SysChart, version)
+    }
 
-            pb.finish_and_clear();
+    fn with_required_version(
+        mut self,
+        component: FluvioClusterComponent,
+        version: &str,
+    ) -> Result<Self, ClusterCheckError> {
+        let version = Version::parse(version)?;
+        for check in &mut self.checks {
+            check.set_required_version(&component, &version);
         }
+        self.required_versions
+            .retain(|(required, _)| *required != component);
+        self.required_versions.push((component, version));
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::testing::*;
 
-        if failed {
-            pb_factory.println(format!("💔 {}", "Some pre-flight check failed!".bold()));
-            Err(ClusterCheckError::PreCheckFlightFailure)
-        } else {
-            pb_factory.println(format!("🎉 {}", "All checks passed!".bold()));
-            Ok(true)
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_fix_progress_steps_in_order() {
+        let (sender, receiver) = async_channel::unbounded();
+        let render = ProgressRenderer::default();
+        let reporter = FixProgressReporter::new(&render, Some(sender));
+        let fixer = ScriptedFixer(vec![
+            "adding repo",
+            "installing chart",
+            "waiting for release",
+        ]);
+
+        fixer.attempt_fix(&reporter).await.expect("fix");
+        drop(reporter);
+
+        let mut steps = vec![];
+        while let Ok(progress) = receiver.try_recv() {
+            steps.push(progress.step);
         }
+        assert_eq!(
+            steps,
+            vec!["adding repo", "installing chart", "waiting for release"]
+        );
+    }
+
+    #[test]
+    fn test_severity_order() {
+        assert!(CheckSeverity::Info < CheckSeverity::Warning);
+        assert!(CheckSeverity::Warning < CheckSeverity::Critical);
+        assert_eq!(CheckSeverity::default(), CheckSeverity::Critical);
     }
-}
 
-#[allow(clippy::borrowed_box)]
-fn check_compare(first: &Box<dyn ClusterCheck>, second: &Box<dyn ClusterCheck>) -> Ordering {
-    //  println!("dep1: {:#?}",dep1_set);
-    //  println!("dep2: {:#?}",dep2_set);
-    // check if any of dep1 is less than dep2
-    if let Some(reg) = second.component() {
-        //   println!("second component: {:#?}",reg);
-        for dep1 in first.required_components() {
-            //     println!("checking dep1: {:#?}",dep1);
-            // if first is depends on second, then seconds should be listed first
-            if dep1 == reg {
-                return Ordering::Greater;
+    #[test]
+    fn test_summary() {
+        let results: Vec<CheckResult> = vec![
+            Ok(CheckStatus::pass("ok")),
+            Ok(CheckStatus::pass("ok")),
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::NoActiveKubernetesContext,
+            )),
+            Ok(CheckStatus::skipped("not needed")),
+            Err(ClusterCheckError::PreCheckFlightFailure),
+        ];
+
+        let summary = results.summary();
+        assert_eq!(
+            summary,
+            CheckResultsSummary {
+                passed: 2,
+                failed: 1,
+                errors: 1,
+                skipped: 1,
             }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "2 passed, 1 failed, 1 errors, 1 skipped"
+        );
+        assert!(results.has_failures());
+
+        let passing: Vec<CheckResult> =
+            vec![Ok(CheckStatus::pass("ok")), Ok(CheckStatus::skipped("no"))];
+        assert!(!passing.has_failures());
+    }
+
+    #[test]
+    fn test_render_command_stderr() {
+        let err = |stderr: &str| {
+            ClusterCheckError::ServiceCreateError(CommandOutput {
+                program: "kubectl".to_string(),
+                status: Some(1),
+                stderr: stderr.to_string(),
+                ..Default::default()
+            })
+        };
+
+        assert!(render::command_stderr(&err("error: forbidden")).is_none());
+        assert_eq!(
+            render::command_stderr(&err("error: forbidden\nsee the logs")).as_deref(),
+            Some("       error: forbidden\n       see the logs")
+        );
+        assert!(render::command_stderr(&ClusterCheckError::Cancelled).is_none());
+    }
+
+    #[test]
+    fn test_check_results_accessors() {
+        let outcome = |name: &str, result| CheckOutcome {
+            name: name.to_string(),
+            result,
+        };
+        let results: CheckResults = vec![
+            outcome("pass", Ok(CheckStatus::pass("ok"))),
+            outcome(
+                "fixable",
+                Ok(CheckStatus::AutoFixableError {
+                    message: "sys chart missing".to_string(),
+                    fixer: Box::new(ScriptedFixer(vec![])),
+                }),
+            ),
+            outcome("skipped", Ok(CheckStatus::skipped("not needed"))),
+            outcome(
+                "errored",
+                Err(ClusterCheckError::Other("helm crashed".to_string())),
+            ),
+            outcome(
+                "unrecoverable",
+                Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::NoActiveKubernetesContext,
+                )),
+            ),
+        ]
+        .into();
+
+        let names = |outcomes: Vec<&CheckOutcome>| {
+            outcomes
+                .into_iter()
+                .map(|outcome| outcome.name.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(results.passed().collect()), vec!["pass"]);
+        assert_eq!(
+            names(results.failures().collect()),
+            vec!["fixable", "unrecoverable"]
+        );
+        assert_eq!(
+            names(results.recoverable_failures().collect()),
+            vec!["fixable"]
+        );
+        assert_eq!(names(results.errors().collect()), vec!["errored"]);
+        assert!(!results.all_passed());
+
+        let report = results.into_report();
+        assert_eq!(report.summary.failed, 2);
+        assert_eq!(
+            report.first_blocking.map(|outcome| outcome.name),
+            Some("errored".to_string())
+        );
+
+        let passing: CheckResults = vec![
+            outcome("pass", Ok(CheckStatus::pass("ok"))),
+            outcome("skipped", Ok(CheckStatus::skipped("not needed"))),
+        ]
+        .into();
+        assert!(passing.all_passed());
+        assert!(passing.into_report().can_proceed());
+    }
+
+    /// Remembers the namespace it was pointed at
+    #[derive(Debug, Default)]
+    struct NamespacedCheck(Arc<std::sync::Mutex<Option<String>>>);
+
+    #[async_trait]
+    impl ClusterCheck for NamespacedCheck {
+        fn name(&self) -> &str {
+            "namespaced"
+        }
+
+        fn label(&self) -> &str {
+            "Namespaced"
+        }
+
+        fn set_namespace(&mut self, namespace: &str) {
+            *self.0.lock().unwrap() = Some(namespace.to_string());
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            Ok(CheckStatus::pass("namespaced"))
         }
     }
 
-    if let Some(reg) = first.component() {
-        // println!("second component: {:#?}",reg);
-        for dep2 in second.required_components() {
-            //   println!("checking second: {:#?}",dep2);
-            // if seconds is depends on first, then first should be listed first
-            if dep2 == reg {
-                return Ordering::Less;
-            }
+    #[test]
+    fn test_with_namespace_reaches_every_check() {
+        let before = NamespacedCheck::default();
+        let after = NamespacedCheck::default();
+        let (before_ns, after_ns) = (before.0.clone(), after.0.clone());
+
+        let _checker = ClusterChecker::empty()
+            .with_check(before)
+            .with_namespace("fluvio")
+            .with_check(after);
+
+        assert_eq!(before_ns.lock().unwrap().as_deref(), Some("fluvio"));
+        assert_eq!(after_ns.lock().unwrap().as_deref(), Some("fluvio"));
+
+        let untouched = NamespacedCheck::default();
+        let untouched_ns = untouched.0.clone();
+        let _checker = ClusterChecker::empty().with_check(untouched);
+        assert_eq!(*untouched_ns.lock().unwrap(), None);
+    }
+
+    /// Remembers the kubeconfig context it was pointed at
+    #[derive(Debug, Default)]
+    struct ContextCheck(Arc<std::sync::Mutex<Option<String>>>);
+
+    #[async_trait]
+    impl ClusterCheck for ContextCheck {
+        fn name(&self) -> &str {
+            "context"
+        }
+
+        fn label(&self) -> &str {
+            "Context"
+        }
+
+        fn set_kube_context(&mut self, context: &str) {
+            *self.0.lock().unwrap() = Some(context.to_string());
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            Ok(CheckStatus::pass("context"))
         }
     }
 
-    Ordering::Equal
-}
+    #[test]
+    fn test_with_kube_context_reaches_every_check() {
+        let before = ContextCheck::default();
+        let after = ContextCheck::default();
+        let (before_context, after_context) = (before.0.clone(), after.0.clone());
+
+        let _checker = ClusterChecker::empty()
+            .with_check(before)
+            .with_kube_context("kind-fluvio")
+            .with_check(after);
+
+        assert_eq!(
+            before_context.lock().unwrap().as_deref(),
+            Some("kind-fluvio")
+        );
+        assert_eq!(
+            after_context.lock().unwrap().as_deref(),
+            Some("kind-fluvio")
+        );
+    }
 
-fn check_permission(resource: &str, _pb: &ProgressRenderer) -> CheckResult {
-    let status = check_create_permission(resource)?;
-    if !status {
-        return Ok(CheckStatus::Unrecoverable(
-            UnrecoverableCheckStatus::PermissionError {
-                resource: resource.to_string(),
-            },
+    #[test]
+    fn test_required_version_rejects_invalid() {
+        assert!(matches!(
+            ClusterChecker::empty().with_required_k8_version("1.21"),
+            Err(ClusterCheckError::VersionError(_))
+        ));
+        assert!(matches!(
+            ClusterChecker::empty().with_required_helm_version("latest"),
+            Err(ClusterCheckError::VersionError(_))
         ));
     }
-    Ok(CheckStatus::pass(format!("Can create {resource}")))
-}
 
-fn check_create_permission(resource: &str) -> Result<bool, ClusterCheckError> {
-    let check_command = Command::new("kubectl")
-        .arg("auth")
-        .arg("can-i")
-        .arg("create")
-        .arg(resource)
-        .output()
-        .map_err(ClusterCheckError::KubectlNotFoundError)?;
-    let res = String::from_utf8(check_command.stdout)
-        .map_err(|_| ClusterCheckError::FetchPermissionError)?;
-    Ok(res.trim() == "yes")
-}
+    #[test]
+    fn test_check_filter() {
+        let checker = ClusterChecker::empty()
+            .with_check(WarningCheck)
+            .with_check(SkippingCheck)
+            .with_check(ErroringCheck)
+            .with_check_filter(|name| name != "skipping");
+
+        let names: Vec<_> = checker.checks.iter().map(|check| check.name()).collect();
+        assert_eq!(names, vec!["warning", "erroring"]);
+    }
 
-#[cfg(test)]
-mod tests {
+    #[fluvio_future::test]
+    async fn test_skip_checks() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let results = ClusterChecker::empty()
+            .with_check(WarningCheck)
+            .with_check(RecordingCheck(ran.clone()))
+            .with_check(ErroringCheck)
+            .skip(&["recording", "erroring"])
+            .expect("known checks")
+            .run_parallel()
+            .await;
+
+        let names: Vec<_> = results
+            .iter()
+            .map(|outcome| outcome.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["warning", "recording", "erroring"]);
+        assert_eq!(results.summary().skipped, 2);
+        assert!(matches!(
+            results.result_for("recording"),
+            Some(Ok(CheckStatus::Skipped(reason))) if reason == "skipped on request"
+        ));
+        assert!(!ran.load(std::sync::atomic::Ordering::SeqCst));
+    }
 
-    use super::*;
+    #[fluvio_future::test]
+    async fn test_only_checks() {
+        let results = ClusterChecker::empty()
+            .with_check(WarningCheck)
+            .with_check(SkippingCheck)
+            .with_check(ErroringCheck)
+            .only(&["erroring"])
+            .expect("known check")
+            .run_parallel()
+            .await;
+
+        assert!(matches!(
+            results.result_for("warning"),
+            Some(Ok(CheckStatus::Skipped(reason))) if reason == "not selected"
+        ));
+        assert!(matches!(results.result_for("erroring"), Some(Err(_))));
+        assert_eq!(results.summary().skipped, 2);
+    }
 
     #[test]
-    fn test_check_dep() {
-        let k8: Box<dyn ClusterCheck> = Box::new(super::ActiveKubernetesCluster);
-        let perm: Box<dyn ClusterCheck> = Box::new(super::CreateCrdPermission);
-        // since per depends on k8, k8 should be less
-        assert_eq!(check_compare(&k8, &perm), Ordering::Less);
+    fn test_skip_unknown_check() {
+        let err = ClusterChecker::empty()
+            .with_check(WarningCheck)
+            .skip(&["warning", "load-balancer"])
+            .expect_err("unknown check");
+        match err {
+            ClusterCheckError::UnknownCheck { name, known } => {
+                assert_eq!(name, "load-balancer");
+                assert_eq!(known, vec!["warning"]);
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(ClusterChecker::empty().only(&["warning"]).is_err());
+    }
+
+    #[test]
+    fn test_display_status() {
+        let pass = CheckStatus::pass("Kubernetes config is loadable").to_string();
+        assert!(pass.contains('✓'));
+        assert!(pass.ends_with(" Kubernetes config is loadable"));
+
+        let failure =
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::ExistingLocalCluster).to_string();
+        let (first, suggestion) = failure.split_once('\n').expect("suggestion line");
+        assert!(first.contains('✗'));
+        assert!(first.ends_with(" A local Fluvio cluster is already running"));
+        assert!(suggestion.contains("fluvio cluster shutdown"));
+
+        let skipped = CheckStatus::skipped("not on this platform").to_string();
+        assert!(skipped.ends_with(" skipped: not on this platform"));
+
+        let errored = CheckOutcome {
+            name: "helm-version".to_string(),
+            result: Err(ClusterCheckError::Other("helm crashed".to_string())),
+        };
+        assert!(errored
+            .to_string()
+            .ends_with(" could not be performed: Other failure: helm crashed"));
+        assert!(errored.to_string().starts_with("helm-version: "));
     }
 }
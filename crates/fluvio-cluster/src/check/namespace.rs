@@ -0,0 +1,243 @@
+//! Checks that the namespace to install into exists or can be created
+//!
+//! The installer creates a missing namespace, which fails on RBAC for users
+//! that may create services and secrets but not namespaces. The permission
+//! checks don't cover it, so the install only fails after they all passed.
+//! Looking up the namespace, and asking whether it can be created when it is
+//! missing, reports it before anything is installed.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use k8_client::meta_client::MetadataClient;
+use k8_client::K8Client;
+use k8_types::{Crd, CrdNames, DefaultHeader, InputObjectMeta, Spec, Status};
+
+use crate::render::ProgressRenderer;
+use crate::DEFAULT_NAMESPACE;
+
+use super::context::k8_api_error;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::permission;
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+const NAMESPACE_API: Crd = Crd {
+    group: "core",
+    version: "v1",
+    names: CrdNames {
+        kind: "Namespace",
+        plural: "namespaces",
+        singular: "namespace",
+    },
+};
+
+/// A namespace, only looked up to know whether it exists
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct NamespaceSpec {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct NamespaceStatus {}
+
+impl Spec for NamespaceSpec {
+    type Status = NamespaceStatus;
+    type Header = DefaultHeader;
+    const NAME_SPACED: bool = false;
+
+    fn metadata() -> &'static Crd {
+        &NAMESPACE_API
+    }
+}
+
+impl Status for NamespaceStatus {}
+
+/// The namespaces of the cluster, from the Kubernetes client or a fake cluster in tests
+#[async_trait]
+pub trait NamespaceSource: Send + Sync {
+    async fn namespace_exists(&self, name: &str) -> Result<bool, ClusterCheckError>;
+}
+
+#[async_trait]
+impl NamespaceSource for K8Client {
+    async fn namespace_exists(&self, name: &str) -> Result<bool, ClusterCheckError> {
+        self.retrieve_item::<NamespaceSpec, _>(&InputObjectMeta::named(name, ""))
+            .await
+            .map(|namespace| namespace.is_some())
+            .map_err(k8_api_error)
+    }
+}
+
+/// Fails if `namespace` does not exist and the current user can't create it
+///
+/// [`ClusterChecker::with_namespace`] points it at the namespace of an install.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ClusterChecker, NamespaceCheck};
+/// let check = NamespaceCheck::new("fluvio");
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+///
+/// [`ClusterChecker::with_namespace`]: super::ClusterChecker::with_namespace
+#[derive(Debug, Clone)]
+pub struct NamespaceCheck {
+    /// Namespace Fluvio is installed into
+    pub namespace: String,
+    /// Kubeconfig context of the cluster, `None` for the current one
+    pub kube_context: Option<String>,
+    env: Environment,
+}
+
+impl NamespaceCheck {
+    /// Checks `namespace` in the current context
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+}
+
+impl Default for NamespaceCheck {
+    fn default() -> Self {
+        Self::new(DEFAULT_NAMESPACE)
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for NamespaceCheck {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let client = self
+            .env
+            .client(self.kube_context.as_deref())
+            .map_err(ClusterCheckError::K8ApiError)?;
+        let exists = match client.namespace_exists(&self.namespace).await {
+            Ok(exists) => Some(exists),
+            // namespaced users may not read namespaces, not even their own
+            Err(ClusterCheckError::K8ApiForbidden(message)) => {
+                debug!(%message, "not allowed to read namespaces");
+                None
+            }
+            Err(err) => return Err(err),
+        };
+        debug!(namespace = %self.namespace, ?exists, "namespace");
+        if exists == Some(true) {
+            return Ok(namespace_status(&self.namespace, exists, false));
+        }
+        let can_create = permission::can_create(
+            &*self.env,
+            permission::NAMESPACES,
+            None,
+            self.kube_context.as_deref(),
+        )
+        .await?;
+        Ok(namespace_status(&self.namespace, exists, can_create))
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "namespace"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes namespace"
+    }
+
+    fn description(&self) -> &str {
+        "The namespace to install into exists or can be created"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = namespace.to_string();
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// `exists` is `None` when the user may not read the namespace
+fn namespace_status(namespace: &str, exists: Option<bool>, can_create: bool) -> CheckStatus {
+    match (exists, can_create) {
+        (Some(true), _) => CheckStatus::pass(format!("Namespace {namespace} exists")),
+        (_, true) => CheckStatus::pass(format!("Namespace {namespace} will be created")),
+        // the install can still succeed in a namespace that exists but can't be read
+        (None, false) => CheckStatus::skipped(format!("not allowed to read namespace {namespace}")),
+        (Some(false), false) => {
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::NamespacePermissionError {
+                namespace: namespace.to_string(),
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::CheckSuggestion;
+
+    use super::*;
+
+    #[test]
+    fn test_namespace_exists() {
+        assert!(matches!(
+            namespace_status("fluvio", Some(true), false),
+            CheckStatus::Pass(message) if message == "Namespace fluvio exists"
+        ));
+    }
+
+    #[test]
+    fn test_namespace_will_be_created() {
+        assert!(matches!(
+            namespace_status("fluvio", Some(false), true),
+            CheckStatus::Pass(message) if message == "Namespace fluvio will be created"
+        ));
+        assert!(matches!(
+            namespace_status("fluvio", None, true),
+            CheckStatus::Pass(_)
+        ));
+    }
+
+    #[test]
+    fn test_namespace_cannot_be_created() {
+        let CheckStatus::Unrecoverable(failure) = namespace_status("fluvio", Some(false), false)
+        else {
+            panic!("fluvio can't be created");
+        };
+        assert_eq!(
+            failure.to_string(),
+            "Namespace fluvio does not exist and the current user can't create it"
+        );
+        assert!(failure
+            .suggestion()
+            .unwrap()
+            .contains("kubectl create namespace fluvio"));
+
+        assert!(!matches!(
+            namespace_status("fluvio", None, false),
+            CheckStatus::Unrecoverable(_)
+        ));
+    }
+}
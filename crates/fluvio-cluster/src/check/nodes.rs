@@ -0,0 +1,340 @@
+//! Checks that the cluster has enough nodes ready to run Fluvio
+//!
+//! When every node is `NotReady`, or there are fewer schedulable nodes than
+//! SPU replicas, the install only fails after helm, with pods stuck in
+//! `Pending`. Counting the nodes with `Ready=True` up front reports it
+//! before anything is installed, along with what is wrong with the others.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use k8_client::meta_client::{MetadataClient, NameSpace};
+use k8_client::K8Client;
+use k8_types::{Crd, CrdNames, DefaultHeader, Spec, Status};
+
+use crate::render::ProgressRenderer;
+
+use super::context::k8_api_error;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+const NODE_API: Crd = Crd {
+    group: "core",
+    version: "v1",
+    names: CrdNames {
+        kind: "Node",
+        plural: "nodes",
+        singular: "node",
+    },
+};
+
+/// The part of a node's spec that tells whether it takes new pods
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NodeSchedulingSpec {
+    /// set when the node is cordoned
+    #[serde(default)]
+    pub unschedulable: bool,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NodeConditionsStatus {
+    #[serde(default)]
+    pub conditions: Vec<NodeCondition>,
+}
+
+/// One of the conditions a kubelet reports, such as `Ready` or `MemoryPressure`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeCondition {
+    pub r#type: String,
+    /// `True`, `False` or `Unknown`
+    pub status: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl Spec for NodeSchedulingSpec {
+    type Status = NodeConditionsStatus;
+    type Header = DefaultHeader;
+    const NAME_SPACED: bool = false;
+
+    fn metadata() -> &'static Crd {
+        &NODE_API
+    }
+}
+
+impl Status for NodeConditionsStatus {}
+
+/// A node, as far as scheduling the Fluvio pods is concerned
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeState {
+    pub name: String,
+    pub unschedulable: bool,
+    pub conditions: Vec<NodeCondition>,
+}
+
+impl NodeState {
+    /// Whether new pods can be scheduled on the node
+    fn is_ready(&self) -> bool {
+        !self.unschedulable
+            && self
+                .conditions
+                .iter()
+                .any(|condition| condition.r#type == "Ready" && condition.status == "True")
+    }
+
+    /// What keeps pods off the node, such as `Ready=False (KubeletNotReady)` or `cordoned`
+    fn problems(&self) -> Vec<String> {
+        let mut problems: Vec<_> = self
+            .conditions
+            .iter()
+            .filter(|condition| {
+                // Ready is the only condition that is good when true
+                (condition.r#type == "Ready") != (condition.status == "True")
+            })
+            .map(|condition| match &condition.reason {
+                Some(reason) => format!("{}={} ({reason})", condition.r#type, condition.status),
+                None => format!("{}={}", condition.r#type, condition.status),
+            })
+            .collect();
+        if !self
+            .conditions
+            .iter()
+            .any(|condition| condition.r#type == "Ready")
+        {
+            problems.push("Ready=Unknown".to_string());
+        }
+        if self.unschedulable {
+            problems.push("cordoned".to_string());
+        }
+        problems
+    }
+}
+
+/// The nodes of the cluster, from the Kubernetes client or a fake cluster in tests
+#[async_trait]
+pub trait NodeSource: Send + Sync {
+    async fn nodes(&self) -> Result<Vec<NodeState>, ClusterCheckError>;
+}
+
+#[async_trait]
+impl NodeSource for K8Client {
+    async fn nodes(&self) -> Result<Vec<NodeState>, ClusterCheckError> {
+        let nodes = self
+            .retrieve_items::<NodeSchedulingSpec, _>(NameSpace::All)
+            .await
+            .map_err(k8_api_error)?;
+        Ok(nodes
+            .items
+            .into_iter()
+            .map(|node| NodeState {
+                name: node.metadata.name,
+                unschedulable: node.spec.unschedulable,
+                conditions: node.status.conditions,
+            })
+            .collect())
+    }
+}
+
+/// Fails if fewer than `min_ready` nodes are ready and schedulable
+///
+/// The installer requires one node per SPU replica.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ClusterChecker, NodeReadiness};
+/// let check = NodeReadiness::new(3);
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NodeReadiness {
+    /// How many nodes must be ready, at least one
+    pub min_ready: usize,
+    /// Kubeconfig context of the cluster, `None` for the current one
+    pub kube_context: Option<String>,
+    env: Environment,
+}
+
+impl NodeReadiness {
+    /// Requires `min_ready` nodes to be ready in the current context
+    pub fn new(min_ready: usize) -> Self {
+        Self {
+            min_ready,
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+}
+
+impl Default for NodeReadiness {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for NodeReadiness {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let client = self
+            .env
+            .client(self.kube_context.as_deref())
+            .map_err(ClusterCheckError::K8ApiError)?;
+        let nodes = match client.nodes().await {
+            Ok(nodes) => nodes,
+            // namespaced users may not see nodes, that is not a reason to stop the install
+            Err(ClusterCheckError::K8ApiForbidden(message)) => {
+                debug!(%message, "not allowed to list nodes");
+                return Ok(CheckStatus::skipped("not allowed to list nodes"));
+            }
+            Err(err) => return Err(err),
+        };
+        debug!(?nodes, "nodes");
+        Ok(node_readiness_status(&nodes, self.min_ready))
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "node-readiness"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes nodes"
+    }
+
+    fn description(&self) -> &str {
+        "Enough Kubernetes nodes are ready to schedule the Fluvio pods"
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_min_ready_nodes(&mut self, count: usize) {
+        self.min_ready = count;
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+fn node_readiness_status(nodes: &[NodeState], min_ready: usize) -> CheckStatus {
+    let required = min_ready.max(1);
+    let ready = nodes.iter().filter(|node| node.is_ready()).count();
+    if ready >= required {
+        return CheckStatus::pass(format!("{ready} of {} nodes are ready", nodes.len()));
+    }
+    let not_ready = nodes
+        .iter()
+        .filter(|node| !node.is_ready())
+        .map(|node| format!("{} ({})", node.name, node.problems().join(", ")))
+        .collect();
+    CheckStatus::Unrecoverable(UnrecoverableCheckStatus::NodesNotReady {
+        ready,
+        required,
+        not_ready,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::CheckSuggestion;
+
+    use super::*;
+
+    fn condition(r#type: &str, status: &str, reason: Option<&str>) -> NodeCondition {
+        NodeCondition {
+            r#type: r#type.to_string(),
+            status: status.to_string(),
+            reason: reason.map(str::to_string),
+        }
+    }
+
+    fn node(name: &str, conditions: Vec<NodeCondition>) -> NodeState {
+        NodeState {
+            name: name.to_string(),
+            unschedulable: false,
+            conditions,
+        }
+    }
+
+    #[test]
+    fn test_nodes_ready() {
+        let nodes = vec![
+            node("a", vec![condition("Ready", "True", None)]),
+            node(
+                "b",
+                vec![
+                    condition("Ready", "True", None),
+                    condition("MemoryPressure", "False", None),
+                ],
+            ),
+        ];
+
+        assert!(matches!(
+            node_readiness_status(&nodes, 2),
+            CheckStatus::Pass(message) if message == "2 of 2 nodes are ready"
+        ));
+    }
+
+    #[test]
+    fn test_no_node_ready() {
+        let nodes = vec![
+            node(
+                "a",
+                vec![
+                    condition("Ready", "False", Some("KubeletNotReady")),
+                    condition("DiskPressure", "True", None),
+                ],
+            ),
+            node("b", vec![]),
+        ];
+
+        let CheckStatus::Unrecoverable(failure) = node_readiness_status(&nodes, 0) else {
+            panic!("no node is ready");
+        };
+        assert_eq!(
+            failure.to_string(),
+            "0 Kubernetes nodes ready, 1 required. Not ready: \
+             a (Ready=False (KubeletNotReady), DiskPressure=True), b (Ready=Unknown)"
+        );
+        assert!(failure.suggestion().is_some());
+    }
+
+    #[test]
+    fn test_too_few_schedulable_nodes() {
+        let mut cordoned = node("b", vec![condition("Ready", "True", None)]);
+        cordoned.unschedulable = true;
+        let nodes = vec![node("a", vec![condition("Ready", "True", None)]), cordoned];
+
+        let CheckStatus::Unrecoverable(failure) = node_readiness_status(&nodes, 2) else {
+            panic!("b is cordoned");
+        };
+        assert!(matches!(
+            &failure,
+            UnrecoverableCheckStatus::NodesNotReady { ready: 1, required: 2, not_ready }
+                if not_ready == &vec!["b (cordoned)".to_string()]
+        ));
+    }
+}
@@ -0,0 +1,319 @@
+//! Checks that OpenShift will admit the Fluvio pods
+//!
+//! OpenShift admits pods through SecurityContextConstraints (SCCs). The
+//! default `restricted` SCC runs every pod under a random UID, while the
+//! Fluvio image runs as root, so the SC and SPU pods are rejected at
+//! admission after the install already started. [`OpenShiftScc`] looks for
+//! an SCC that lets the Fluvio service account run as any user.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::render::ProgressRenderer;
+use crate::DEFAULT_NAMESPACE;
+
+use super::command::{kubectl_output, CommandOutput};
+use super::context::kubectl_command;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::permission::can_i_answer;
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+/// API group that only OpenShift serves
+const OPENSHIFT_SECURITY_API: &str = "security.openshift.io/";
+
+/// Service account of the SC and SPU pods, see `serviceAccount.name` in the app chart
+const FLUVIO_SERVICE_ACCOUNT: &str = "fluvio";
+
+/// Fails on OpenShift if no SCC lets the Fluvio service account run as any user
+///
+/// Passes on every other Kubernetes distribution.
+#[derive(Debug, Default)]
+pub(crate) struct OpenShiftScc {
+    namespace: Option<String>,
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+#[async_trait]
+impl ClusterCheck for OpenShiftScc {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let env = &*self.env;
+        let context = self.kube_context.as_deref();
+        let api_versions = kubectl(env, context, &["api-versions"]).await?;
+        if !is_openshift(&api_versions) {
+            return Ok(CheckStatus::pass("Not an OpenShift cluster"));
+        }
+
+        let namespace = self.namespace.as_deref().unwrap_or(DEFAULT_NAMESPACE);
+        let sccs = parse_sccs(
+            &kubectl(
+                env,
+                context,
+                &["get", "securitycontextconstraints", "-o", "json"],
+            )
+            .await?,
+        )?;
+        let candidates: Vec<_> = sccs.iter().filter(|scc| scc.runs_as_any_user()).collect();
+
+        // older clusters list users and groups on the SCC, newer ones grant it through RBAC
+        let mut granted = candidates
+            .iter()
+            .find(|scc| scc.grants(namespace, FLUVIO_SERVICE_ACCOUNT))
+            .map(|scc| scc.metadata.name.clone());
+        if granted.is_none() {
+            for scc in &candidates {
+                if can_use(env, context, &scc.metadata.name, namespace).await {
+                    granted = Some(scc.metadata.name.clone());
+                    break;
+                }
+            }
+        }
+
+        match granted {
+            Some(scc) => Ok(CheckStatus::pass(format!(
+                "Service account {FLUVIO_SERVICE_ACCOUNT} can use SCC {scc}"
+            ))),
+            None => Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::MissingOpenShiftScc {
+                    service_account: FLUVIO_SERVICE_ACCOUNT.to_string(),
+                    namespace: namespace.to_string(),
+                },
+            )),
+        }
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![
+            FluvioClusterComponent::KubectlBinary,
+            FluvioClusterComponent::Kubernetes,
+        ]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::kubectl_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn name(&self) -> &str {
+        "openshift-scc"
+    }
+
+    fn label(&self) -> &str {
+        "OpenShift SCC"
+    }
+
+    fn description(&self) -> &str {
+        "On OpenShift, the Fluvio service account may run its pods as any user"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = Some(namespace.to_string());
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Runs kubectl with `args` and returns its stdout, failing if kubectl does
+async fn kubectl(
+    env: &dyn CheckEnvironment,
+    context: Option<&str>,
+    args: &[&str],
+) -> Result<Vec<u8>, ClusterCheckError> {
+    let mut command = kubectl_command(context);
+    command.args(args);
+    let description = CommandOutput::from_command(&command);
+    let output = kubectl_output(env, command).await?;
+    if !output.status.success() {
+        return Err(ClusterCheckError::KubectlFailed(
+            description.with_output(&output),
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// Whether the service account is granted `scc` through RBAC
+async fn can_use(
+    env: &dyn CheckEnvironment,
+    context: Option<&str>,
+    scc: &str,
+    namespace: &str,
+) -> bool {
+    let mut command = kubectl_command(context);
+    command
+        .arg("auth")
+        .arg("can-i")
+        .arg("use")
+        .arg(format!("securitycontextconstraints/{scc}"))
+        .arg(format!(
+            "--as=system:serviceaccount:{namespace}:{FLUVIO_SERVICE_ACCOUNT}"
+        ));
+    let description = CommandOutput::from_command(&command);
+    kubectl_output(env, command)
+        .await
+        .and_then(|output| can_i_answer(description, &output))
+        .unwrap_or_else(|err| {
+            debug!(scc, %err, "unable to check SCC access");
+            false
+        })
+}
+
+/// Whether `kubectl api-versions` lists the OpenShift security API
+fn is_openshift(api_versions: &[u8]) -> bool {
+    String::from_utf8_lossy(api_versions)
+        .lines()
+        .any(|line| line.trim().starts_with(OPENSHIFT_SECURITY_API))
+}
+
+#[derive(Debug, Deserialize)]
+struct SccList {
+    #[serde(default)]
+    items: Vec<SecurityContextConstraints>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SccMetadata {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RunAsUserStrategy {
+    #[serde(rename = "type")]
+    strategy: String,
+}
+
+/// The fields of an SCC this check looks at, they live at the top level instead of in a spec
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SecurityContextConstraints {
+    metadata: SccMetadata,
+    #[serde(default)]
+    users: Option<Vec<String>>,
+    #[serde(default)]
+    groups: Option<Vec<String>>,
+    #[serde(default)]
+    run_as_user: Option<RunAsUserStrategy>,
+}
+
+impl SecurityContextConstraints {
+    fn runs_as_any_user(&self) -> bool {
+        self.run_as_user
+            .as_ref()
+            .is_some_and(|run_as_user| run_as_user.strategy == "RunAsAny")
+    }
+
+    /// Whether the SCC lists the service account, or a group it is in
+    fn grants(&self, namespace: &str, service_account: &str) -> bool {
+        let user = format!("system:serviceaccount:{namespace}:{service_account}");
+        let groups = [
+            "system:serviceaccounts".to_string(),
+            format!("system:serviceaccounts:{namespace}"),
+            "system:authenticated".to_string(),
+        ];
+        self.users.iter().flatten().any(|listed| *listed == user)
+            || self
+                .groups
+                .iter()
+                .flatten()
+                .any(|listed| groups.contains(listed))
+    }
+}
+
+fn parse_sccs(output: &[u8]) -> Result<Vec<SecurityContextConstraints>, ClusterCheckError> {
+    let list: SccList = serde_json::from_slice(output).map_err(|err| {
+        ClusterCheckError::Other(format!("Unable to parse SecurityContextConstraints: {err}"))
+    })?;
+    Ok(list.items)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::CheckSuggestion;
+
+    use super::*;
+
+    const SCCS: &str = r#"{
+        "apiVersion": "v1",
+        "kind": "List",
+        "items": [
+            {
+                "apiVersion": "security.openshift.io/v1",
+                "kind": "SecurityContextConstraints",
+                "metadata": { "name": "anyuid" },
+                "users": [],
+                "groups": ["system:cluster-admins"],
+                "runAsUser": { "type": "RunAsAny" }
+            },
+            {
+                "apiVersion": "security.openshift.io/v1",
+                "kind": "SecurityContextConstraints",
+                "metadata": { "name": "restricted" },
+                "users": null,
+                "groups": ["system:authenticated"],
+                "runAsUser": { "type": "MustRunAsRange" }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_is_openshift() {
+        assert!(is_openshift(
+            b"apps/v1\nsecurity.openshift.io/v1\nroute.openshift.io/v1\nv1\n"
+        ));
+        assert!(!is_openshift(b"apps/v1\nnetworking.k8s.io/v1\nv1\n"));
+    }
+
+    #[test]
+    fn test_scc_grants() {
+        let sccs = parse_sccs(SCCS.as_bytes()).expect("sccs");
+        let (anyuid, restricted) = (&sccs[0], &sccs[1]);
+
+        assert!(anyuid.runs_as_any_user());
+        assert!(!restricted.runs_as_any_user());
+        assert!(!anyuid.grants("fluvio", "fluvio"));
+        assert!(restricted.grants("fluvio", "fluvio"));
+    }
+
+    #[test]
+    fn test_scc_grants_service_account() {
+        let scc = SecurityContextConstraints {
+            metadata: SccMetadata {
+                name: "anyuid".to_string(),
+            },
+            users: Some(vec!["system:serviceaccount:fluvio:fluvio".to_string()]),
+            groups: None,
+            run_as_user: None,
+        };
+
+        assert!(scc.grants("fluvio", "fluvio"));
+        assert!(!scc.grants("default", "fluvio"));
+        assert!(!scc.runs_as_any_user());
+    }
+
+    #[test]
+    fn test_missing_scc_suggestion() {
+        let failure = UnrecoverableCheckStatus::MissingOpenShiftScc {
+            service_account: "fluvio".to_string(),
+            namespace: "streaming".to_string(),
+        };
+
+        assert_eq!(
+            failure.suggestion().as_deref(),
+            Some(
+                "Allow the service account to run as any user with \
+                 'oc adm policy add-scc-to-user anyuid -z fluvio -n streaming'"
+            )
+        );
+    }
+}
@@ -0,0 +1,761 @@
+//! Permission checks backed by the Kubernetes authorization API
+//!
+//! Each check posts a `SelfSubjectAccessReview` and reads `status.allowed`,
+//! so it neither needs the kubectl binary nor depends on the language of its
+//! output. When no Kubernetes client can be built, the checks fall back to
+//! `kubectl auth can-i` if the `kubectl-checks` feature is enabled.
+
+#[cfg(feature = "kubectl-checks")]
+use std::process::{Command, Output};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures_util::future::join_all;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use k8_client::meta_client::MetadataClient;
+use k8_client::K8Client;
+use k8_types::{Crd, CrdNames, DefaultHeader, InputK8Obj, InputObjectMeta, Spec, Status};
+
+use crate::render::ProgressRenderer;
+
+#[cfg(feature = "kubectl-checks")]
+use super::command::{kubectl_output, CommandOutput};
+use super::context::k8_api_error;
+#[cfg(feature = "kubectl-checks")]
+use super::context::kubectl_command;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::{
+    CheckOutcome, CheckResult, CheckResults, CheckStatus, ClusterCheck, ClusterCheckError,
+    FluvioClusterComponent, UnrecoverableCheckStatus,
+};
+
+const ACCESS_REVIEW_API: Crd = Crd {
+    group: "authorization.k8s.io",
+    version: "v1",
+    names: CrdNames {
+        kind: "SelfSubjectAccessReview",
+        plural: "selfsubjectaccessreviews",
+        singular: "selfsubjectaccessreview",
+    },
+};
+
+/// A Kubernetes resource Fluvio needs to create
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AccessResource {
+    /// API group, empty for the core group
+    pub group: &'static str,
+    /// Plural resource name, as used by the API and by kubectl
+    pub resource: &'static str,
+}
+
+pub(crate) const SERVICES: AccessResource = AccessResource {
+    group: "",
+    resource: "services",
+};
+
+pub(crate) const CUSTOM_RESOURCE_DEFINITIONS: AccessResource = AccessResource {
+    group: "apiextensions.k8s.io",
+    resource: "customresourcedefinitions",
+};
+
+pub(crate) const SECRETS: AccessResource = AccessResource {
+    group: "",
+    resource: "secrets",
+};
+
+pub(crate) const NAMESPACES: AccessResource = AccessResource {
+    group: "",
+    resource: "namespaces",
+};
+
+/// Asks the API server whether the current user may perform an action
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfSubjectAccessReviewSpec {
+    pub resource_attributes: ResourceAttributes,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceAttributes {
+    /// empty for cluster-scoped resources or to ask about every namespace
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub namespace: String,
+    pub verb: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub group: String,
+    pub resource: String,
+}
+
+/// Answer of the API server to a [`SelfSubjectAccessReviewSpec`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubjectAccessReviewStatus {
+    pub allowed: bool,
+    #[serde(default)]
+    pub denied: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl Spec for SelfSubjectAccessReviewSpec {
+    type Status = SubjectAccessReviewStatus;
+    type Header = DefaultHeader;
+    const NAME_SPACED: bool = false;
+
+    fn metadata() -> &'static Crd {
+        &ACCESS_REVIEW_API
+    }
+}
+
+impl Status for SubjectAccessReviewStatus {}
+
+/// Something that can answer access reviews, the Kubernetes client or a fake in tests
+#[async_trait]
+pub trait AccessReviewer: Send + Sync {
+    async fn review(
+        &self,
+        spec: SelfSubjectAccessReviewSpec,
+    ) -> Result<SubjectAccessReviewStatus, ClusterCheckError>;
+}
+
+#[async_trait]
+impl AccessReviewer for K8Client {
+    async fn review(
+        &self,
+        spec: SelfSubjectAccessReviewSpec,
+    ) -> Result<SubjectAccessReviewStatus, ClusterCheckError> {
+        let review = self
+            .create_item(InputK8Obj::new(spec, InputObjectMeta::default()))
+            .await
+            .map_err(k8_api_error)?;
+        Ok(review.status)
+    }
+}
+
+/// Checks that the current user can create `resource` in `namespace`
+///
+/// Without a namespace the user must be able to create it in every namespace.
+/// `context` is the kubeconfig context to ask, `None` for the current one.
+pub(crate) async fn check_permission(
+    env: &dyn CheckEnvironment,
+    resource: AccessResource,
+    namespace: Option<&str>,
+    context: Option<&str>,
+) -> CheckResult {
+    let allowed = can_create(env, resource, namespace, context).await?;
+    Ok(permission_status(resource, allowed))
+}
+
+/// Whether the current user can create `resource` in `namespace`, see [`check_permission`]
+pub(crate) async fn can_create(
+    env: &dyn CheckEnvironment,
+    resource: AccessResource,
+    namespace: Option<&str>,
+    context: Option<&str>,
+) -> Result<bool, ClusterCheckError> {
+    match env.client(context) {
+        Ok(client) => review_create(client.as_access_reviewer(), resource, namespace).await,
+        Err(err) => can_create_without_client(env, resource, namespace, context, &err).await,
+    }
+}
+
+/// Checks that the current user can create each of `resources` in `namespace`
+///
+/// The checks run concurrently and share one Kubernetes client. There is
+/// one outcome per resource, named after the resource.
+pub(crate) async fn check_permissions(
+    env: &dyn CheckEnvironment,
+    resources: &[AccessResource],
+    namespace: Option<&str>,
+    context: Option<&str>,
+) -> CheckResults {
+    match env.client(context) {
+        Ok(client) => {
+            check_permissions_with(client.as_access_reviewer(), resources, namespace).await
+        }
+        Err(err) => {
+            let checks = resources.iter().map(|resource| {
+                let err = &err;
+                async move {
+                    CheckOutcome {
+                        name: resource.resource.to_string(),
+                        result: can_create_without_client(env, *resource, namespace, context, err)
+                            .await
+                            .map(|allowed| permission_status(*resource, allowed)),
+                    }
+                }
+            });
+            join_all(checks).await.into()
+        }
+    }
+}
+
+async fn check_permissions_with(
+    reviewer: &dyn AccessReviewer,
+    resources: &[AccessResource],
+    namespace: Option<&str>,
+) -> CheckResults {
+    let checks = resources.iter().map(|resource| async move {
+        CheckOutcome {
+            name: resource.resource.to_string(),
+            result: check_permission_with(reviewer, *resource, namespace).await,
+        }
+    });
+    join_all(checks).await.into()
+}
+
+#[cfg(feature = "kubectl-checks")]
+async fn can_create_without_client(
+    env: &dyn CheckEnvironment,
+    resource: AccessResource,
+    namespace: Option<&str>,
+    context: Option<&str>,
+    err: &str,
+) -> Result<bool, ClusterCheckError> {
+    debug!(%err, "no kubernetes client, asking kubectl instead");
+    check_create_permission(env, resource.resource, namespace, context).await
+}
+
+#[cfg(not(feature = "kubectl-checks"))]
+async fn can_create_without_client(
+    _env: &dyn CheckEnvironment,
+    _resource: AccessResource,
+    _namespace: Option<&str>,
+    _context: Option<&str>,
+    err: &str,
+) -> Result<bool, ClusterCheckError> {
+    Err(ClusterCheckError::K8ApiError(err.to_string()))
+}
+
+/// Asks `kubectl auth can-i`, used when no Kubernetes client can be built
+#[cfg(feature = "kubectl-checks")]
+async fn check_create_permission(
+    env: &dyn CheckEnvironment,
+    resource: &str,
+    namespace: Option<&str>,
+    context: Option<&str>,
+) -> Result<bool, ClusterCheckError> {
+    let command = can_i_create_command(resource, namespace, context);
+    let description = CommandOutput::from_command(&command);
+    let output = kubectl_output(env, command).await?;
+    can_i_answer(description, &output)
+}
+
+/// Reads the answer of `kubectl auth can-i`
+///
+/// kubectl exits with an error for "no" as well, so the exit status alone
+/// doesn't tell a denied permission from a wrong context or an unreachable
+/// server. Those print no answer, and fail with what kubectl wrote to stderr.
+#[cfg(feature = "kubectl-checks")]
+pub(crate) fn can_i_answer(
+    command: CommandOutput,
+    output: &Output,
+) -> Result<bool, ClusterCheckError> {
+    let answer =
+        std::str::from_utf8(&output.stdout).map_err(|_| ClusterCheckError::FetchPermissionError)?;
+    match answer.trim() {
+        "yes" => Ok(true),
+        answer if answer.starts_with("no") => Ok(false),
+        _ if !output.status.success() => Err(ClusterCheckError::KubectlFailed(
+            command.with_output(output),
+        )),
+        _ => Err(ClusterCheckError::FetchPermissionError),
+    }
+}
+
+#[cfg(feature = "kubectl-checks")]
+fn can_i_create_command(resource: &str, namespace: Option<&str>, context: Option<&str>) -> Command {
+    let mut command = kubectl_command(context);
+    command.arg("auth").arg("can-i").arg("create").arg(resource);
+    if let Some(namespace) = namespace {
+        command.arg("--namespace").arg(namespace);
+    }
+    command
+}
+
+async fn check_permission_with(
+    reviewer: &dyn AccessReviewer,
+    resource: AccessResource,
+    namespace: Option<&str>,
+) -> CheckResult {
+    let allowed = review_create(reviewer, resource, namespace).await?;
+    Ok(permission_status(resource, allowed))
+}
+
+async fn review_create(
+    reviewer: &dyn AccessReviewer,
+    resource: AccessResource,
+    namespace: Option<&str>,
+) -> Result<bool, ClusterCheckError> {
+    review_access(reviewer, "create", resource, namespace).await
+}
+
+/// Whether the current user may `verb` `resource` in `namespace`, such as `list` services
+pub(crate) async fn review_access(
+    reviewer: &dyn AccessReviewer,
+    verb: &str,
+    resource: AccessResource,
+    namespace: Option<&str>,
+) -> Result<bool, ClusterCheckError> {
+    let spec = SelfSubjectAccessReviewSpec {
+        resource_attributes: ResourceAttributes {
+            namespace: namespace.unwrap_or_default().to_string(),
+            verb: verb.to_string(),
+            group: resource.group.to_string(),
+            resource: resource.resource.to_string(),
+        },
+    };
+    let status = reviewer.review(spec).await?;
+    debug!(verb, ?resource, ?status, "access review");
+    Ok(status.allowed)
+}
+
+fn permission_status(resource: AccessResource, allowed: bool) -> CheckStatus {
+    if allowed {
+        CheckStatus::pass(format!("Can create {}", resource.resource))
+    } else {
+        CheckStatus::Unrecoverable(UnrecoverableCheckStatus::PermissionError {
+            resource: resource.resource.to_string(),
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CreateServicePermission {
+    namespace: Option<String>,
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+#[async_trait]
+impl ClusterCheck for CreateServicePermission {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        check_permission(
+            &*self.env,
+            SERVICES,
+            self.namespace.as_deref(),
+            self.kube_context.as_deref(),
+        )
+        .await
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn name(&self) -> &str {
+        "service-permission"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes Service Permission"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = Some(namespace.to_string());
+    }
+
+    fn description(&self) -> &str {
+        "The current user can create services"
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CreateCrdPermission {
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+#[async_trait]
+impl ClusterCheck for CreateCrdPermission {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        // custom resource definitions are not namespaced
+        check_permission(
+            &*self.env,
+            CUSTOM_RESOURCE_DEFINITIONS,
+            None,
+            self.kube_context.as_deref(),
+        )
+        .await
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn name(&self) -> &str {
+        "crd-permission"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes Crd Permission"
+    }
+
+    fn description(&self) -> &str {
+        "The current user can create custom resource definitions"
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Checks several create permissions at once, reporting the first one that is missing
+#[derive(Debug)]
+pub(crate) struct CreateBatchPermission {
+    resources: Vec<AccessResource>,
+    namespace: Option<String>,
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+impl CreateBatchPermission {
+    pub(crate) fn new(resources: Vec<AccessResource>) -> Self {
+        Self {
+            resources,
+            namespace: None,
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+}
+
+impl Default for CreateBatchPermission {
+    fn default() -> Self {
+        Self::new(vec![SERVICES, CUSTOM_RESOURCE_DEFINITIONS, SECRETS])
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for CreateBatchPermission {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let results = check_permissions(
+            &*self.env,
+            &self.resources,
+            self.namespace.as_deref(),
+            self.kube_context.as_deref(),
+        )
+        .await;
+        for outcome in results {
+            match outcome.result? {
+                CheckStatus::Pass(_) | CheckStatus::PassWithData(..) => {}
+                failure => return Ok(failure),
+            }
+        }
+        let resources = self
+            .resources
+            .iter()
+            .map(|resource| resource.resource)
+            .collect::<Vec<_>>()
+            .join(", ");
+        Ok(CheckStatus::pass(format!("Can create {resources}")))
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn name(&self) -> &str {
+        "batch-permission"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes Permissions"
+    }
+
+    fn description(&self) -> &str {
+        "The current user can create every resource Fluvio needs"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = Some(namespace.to_string());
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct CreateServiceAccountPermission {
+    namespace: Option<String>,
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+#[async_trait]
+impl ClusterCheck for CreateServiceAccountPermission {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        check_permission(
+            &*self.env,
+            SECRETS,
+            self.namespace.as_deref(),
+            self.kube_context.as_deref(),
+        )
+        .await
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn name(&self) -> &str {
+        "service-account-permission"
+    }
+
+    fn label(&self) -> &str {
+        "Kubernetes Service Account Permission"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = Some(namespace.to_string());
+    }
+
+    fn description(&self) -> &str {
+        "The current user can create secrets for service accounts"
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// Answers every review with the same response and remembers what it was asked
+    struct FakeReviewer {
+        response: fn() -> Result<SubjectAccessReviewStatus, ClusterCheckError>,
+        requests: Mutex<Vec<SelfSubjectAccessReviewSpec>>,
+    }
+
+    impl FakeReviewer {
+        fn new(response: fn() -> Result<SubjectAccessReviewStatus, ClusterCheckError>) -> Self {
+            Self {
+                response,
+                requests: Mutex::default(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AccessReviewer for FakeReviewer {
+        async fn review(
+            &self,
+            spec: SelfSubjectAccessReviewSpec,
+        ) -> Result<SubjectAccessReviewStatus, ClusterCheckError> {
+            self.requests.lock().unwrap().push(spec);
+            (self.response)()
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_permission_allowed() {
+        let reviewer = FakeReviewer::new(|| {
+            Ok(SubjectAccessReviewStatus {
+                allowed: true,
+                ..Default::default()
+            })
+        });
+
+        let status = check_permission_with(&reviewer, CUSTOM_RESOURCE_DEFINITIONS, None)
+            .await
+            .expect("status");
+
+        assert!(matches!(
+            status,
+            CheckStatus::Pass(message) if message == "Can create customresourcedefinitions"
+        ));
+        assert_eq!(
+            reviewer.requests.lock().unwrap()[0].resource_attributes,
+            ResourceAttributes {
+                namespace: String::new(),
+                verb: "create".to_string(),
+                group: "apiextensions.k8s.io".to_string(),
+                resource: "customresourcedefinitions".to_string(),
+            }
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_permission_denied() {
+        let reviewer = FakeReviewer::new(|| {
+            Ok(SubjectAccessReviewStatus {
+                allowed: false,
+                denied: true,
+                reason: Some("RBAC: no rule".to_string()),
+            })
+        });
+
+        let status = check_permission_with(&reviewer, SERVICES, Some("fluvio"))
+            .await
+            .expect("status");
+
+        assert_eq!(
+            reviewer.requests.lock().unwrap()[0]
+                .resource_attributes
+                .namespace,
+            "fluvio"
+        );
+        assert!(matches!(
+            status,
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::PermissionError { resource })
+                if resource == "services"
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_permission_api_error() {
+        let reviewer =
+            FakeReviewer::new(|| Err(ClusterCheckError::K8ApiError("forbidden".to_string())));
+
+        let result = check_permission_with(&reviewer, SECRETS, None).await;
+
+        assert!(matches!(
+            result,
+            Err(ClusterCheckError::K8ApiError(message)) if message == "forbidden"
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_permissions_batch() {
+        let reviewer = FakeReviewer::new(|| {
+            Ok(SubjectAccessReviewStatus {
+                allowed: true,
+                ..Default::default()
+            })
+        });
+
+        let results = check_permissions_with(&reviewer, &[SERVICES, SECRETS], Some("fluvio")).await;
+
+        let names: Vec<_> = results
+            .iter()
+            .map(|outcome| outcome.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["services", "secrets"]);
+        assert!(results
+            .iter()
+            .all(|outcome| matches!(outcome.result, Ok(CheckStatus::Pass(_)))));
+        assert_eq!(reviewer.requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_access_review_json() {
+        let spec = SelfSubjectAccessReviewSpec {
+            resource_attributes: ResourceAttributes {
+                namespace: String::new(),
+                verb: "create".to_string(),
+                group: String::new(),
+                resource: "services".to_string(),
+            },
+        };
+        assert_eq!(
+            serde_json::to_value(&spec).unwrap(),
+            serde_json::json!({
+                "resourceAttributes": { "verb": "create", "resource": "services" }
+            })
+        );
+
+        let status: SubjectAccessReviewStatus =
+            serde_json::from_str(r#"{ "allowed": false, "reason": "no RBAC policy matched" }"#)
+                .unwrap();
+        assert!(!status.allowed);
+        assert!(!status.denied);
+    }
+
+    #[cfg(all(unix, feature = "kubectl-checks"))]
+    #[test]
+    fn test_can_i_answer() {
+        use std::os::unix::process::ExitStatusExt;
+        use std::process::ExitStatus;
+
+        let output = |code: i32, stdout: &str, stderr: &str| Output {
+            status: ExitStatus::from_raw(code << 8),
+            stdout: stdout.as_bytes().to_vec(),
+            stderr: stderr.as_bytes().to_vec(),
+        };
+
+        let command = || CommandOutput::from_command(&can_i_create_command("pods", None, None));
+
+        assert!(can_i_answer(command(), &output(0, "yes\n", "")).unwrap());
+        assert!(!can_i_answer(command(), &output(1, "no\n", "")).unwrap());
+        match can_i_answer(
+            command(),
+            &output(1, "", "error: context \"kind-gone\" does not exist\n"),
+        ) {
+            Err(ClusterCheckError::KubectlFailed(failed)) => {
+                assert_eq!(failed.status, Some(1));
+                assert_eq!(failed.stderr, "error: context \"kind-gone\" does not exist");
+                assert_eq!(failed.args, vec!["auth", "can-i", "create", "pods"]);
+            }
+            other => panic!("expected kubectl failure, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "kubectl-checks")]
+    #[test]
+    fn test_can_i_create_command_namespace() {
+        let args = |namespace| {
+            can_i_create_command("services", namespace, None)
+                .get_args()
+                .map(|arg| arg.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(args(None), vec!["auth", "can-i", "create", "services"]);
+        assert_eq!(
+            args(Some("fluvio")),
+            vec![
+                "auth",
+                "can-i",
+                "create",
+                "services",
+                "--namespace",
+                "fluvio"
+            ]
+        );
+    }
+}
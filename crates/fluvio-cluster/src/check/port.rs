@@ -0,0 +1,221 @@
+//! Checks that the ports a local cluster listens on are free
+//!
+//! The SC and SPU processes fail late, after the install already started,
+//! when another process is listening on one of their ports. Binding each
+//! port up front catches that before anything is launched.
+
+use std::net::{Ipv4Addr, TcpListener};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use sysinfo::{Pid, PidExt, ProcessExt, System, SystemExt};
+use tracing::debug;
+
+use fluvio_types::defaults::{SC_PRIVATE_PORT, SC_PUBLIC_PORT};
+
+use crate::render::ProgressRenderer;
+
+use super::environment::{CheckEnvironment, Environment};
+use super::{CheckResult, CheckStatus, ClusterCheck, UnrecoverableCheckStatus};
+
+/// TLS port of the local SC, see `runtime::local::sc`
+const SC_TLS_PORT: u16 = 9005;
+/// Public and private port of the first local SPU, see `runtime::local::spu`
+const SPU_PORTS: [u16; 2] = [9010, 9011];
+
+/// How long looking up the process that holds a port may take
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Fails if one of the ports a local cluster listens on is already taken
+///
+/// The default ports are those of the SC and of the first SPU. Installers
+/// that start the cluster on other ports pass their own list.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ClusterChecker, PortAvailabilityCheck};
+/// let check = PortAvailabilityCheck::new(vec![19003, 19004]);
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PortAvailabilityCheck {
+    ports: Vec<u16>,
+    env: Environment,
+}
+
+impl PortAvailabilityCheck {
+    /// Checks `ports` instead of the default ones
+    pub fn new(ports: Vec<u16>) -> Self {
+        Self {
+            ports,
+            env: Environment::default(),
+        }
+    }
+
+    /// Ports this check tries to bind
+    pub fn ports(&self) -> &[u16] {
+        &self.ports
+    }
+}
+
+impl Default for PortAvailabilityCheck {
+    fn default() -> Self {
+        let mut ports = vec![SC_PUBLIC_PORT, SC_PRIVATE_PORT, SC_TLS_PORT];
+        ports.extend(SPU_PORTS);
+        Self::new(ports)
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for PortAvailabilityCheck {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let Some(port) = self
+            .ports
+            .iter()
+            .copied()
+            .find(|port| !self.env.is_port_free(*port))
+        else {
+            return Ok(CheckStatus::pass(format!(
+                "Ports {} are available",
+                format_ports(&self.ports)
+            )));
+        };
+        let process = listening_process(&*self.env, port).await;
+        debug!(port, ?process, "port is in use");
+        Ok(CheckStatus::Unrecoverable(
+            UnrecoverableCheckStatus::PortInUse { port, process },
+        ))
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "port-availability"
+    }
+
+    fn label(&self) -> &str {
+        "Local ports"
+    }
+
+    fn description(&self) -> &str {
+        "No other process listens on the ports of the local SC and SPU"
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Whether a listener can be bound to `port` on every interface, like the local cluster does
+pub(crate) fn is_port_free(port: u16) -> bool {
+    match TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)) {
+        Ok(_) => true,
+        Err(err) => {
+            debug!(port, %err, "unable to bind port");
+            false
+        }
+    }
+}
+
+/// Name and pid of the process listening on `port`, if `lsof` can tell
+async fn listening_process(env: &dyn CheckEnvironment, port: u16) -> Option<String> {
+    let mut command = Command::new("lsof");
+    command
+        .arg("-nP")
+        .arg(format!("-iTCP:{port}"))
+        .arg("-sTCP:LISTEN")
+        .arg("-t");
+    let output = match env.command_output(command, LOOKUP_TIMEOUT).await {
+        Ok(output) => output,
+        Err(err) => {
+            debug!(%err, "unable to run lsof");
+            return None;
+        }
+    };
+    let pid = parse_lsof_pid(&String::from_utf8_lossy(&output.stdout))?;
+
+    let mut sys = System::new();
+    sys.refresh_process(Pid::from_u32(pid));
+    let process = match sys.process(Pid::from_u32(pid)) {
+        Some(process) => format!("{} (pid {pid})", process.name()),
+        None => format!("pid {pid}"),
+    };
+    Some(process)
+}
+
+/// First pid printed by `lsof -t`
+fn parse_lsof_pid(output: &str) -> Option<u32> {
+    output.lines().find_map(|line| line.trim().parse().ok())
+}
+
+fn format_ports(ports: &[u16]) -> String {
+    ports
+        .iter()
+        .map(|port| port.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::CheckSuggestion;
+
+    use super::*;
+
+    #[fluvio_future::test]
+    async fn test_port_in_use() {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).expect("bind");
+        let port = listener.local_addr().expect("addr").port();
+
+        let status = PortAvailabilityCheck::new(vec![port])
+            .perform_check(&ProgressRenderer::default())
+            .await
+            .expect("status");
+
+        let CheckStatus::Unrecoverable(failure) = status else {
+            panic!("port {port} is in use");
+        };
+        assert!(matches!(
+            failure,
+            UnrecoverableCheckStatus::PortInUse { port: busy, .. } if busy == port
+        ));
+        assert!(failure
+            .suggestion()
+            .expect("suggestion")
+            .contains(&format!("listening on {port}")));
+    }
+
+    #[fluvio_future::test]
+    async fn test_ports_free() {
+        let port = {
+            let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).expect("bind");
+            listener.local_addr().expect("addr").port()
+        };
+
+        let status = PortAvailabilityCheck::new(vec![port])
+            .perform_check(&ProgressRenderer::default())
+            .await
+            .expect("status");
+
+        assert!(matches!(status, CheckStatus::Pass(_)), "{status:?}");
+    }
+
+    #[test]
+    fn test_default_ports() {
+        assert_eq!(
+            PortAvailabilityCheck::default().ports(),
+            &[9003, 9004, 9005, 9010, 9011]
+        );
+    }
+
+    #[test]
+    fn test_parse_lsof_pid() {
+        assert_eq!(parse_lsof_pid("4242\n4243\n"), Some(4242));
+        assert_eq!(parse_lsof_pid(""), None);
+    }
+}
@@ -0,0 +1,212 @@
+//! The sets of checks the installers run, as [`ClusterChecker`] builders
+
+use semver::Version;
+
+#[cfg(feature = "helm-checks")]
+use crate::charts::ChartConfig;
+
+#[cfg(feature = "kubectl-checks")]
+use super::openshift;
+#[cfg(feature = "kubectl-checks")]
+use super::selection::SkippedCheck;
+#[cfg(feature = "kubectl-checks")]
+use super::KubectlBinary;
+#[cfg(feature = "helm-checks")]
+use super::{HelmBinary, HelmVersion, SysChartCheck, UpgradePathCheck};
+use super::{ActiveKubernetesCluster, K8Version, LocalClusterCheck};
+use super::{CheckRegistry, CheckSelection, ClusterCheck, ClusterCheckError, ClusterChecker};
+use super::{CreateCrdPermission, CreateServiceAccountPermission, CreateServicePermission};
+use super::{InClusterPermission, LoadBalancerCheck, NamespaceCheck, NodeReadiness};
+use super::{LocalDiskSpace, PortAvailabilityCheck, ScConnectivity};
+
+impl ClusterChecker {
+    /// Adds all preflight checks to this checker.
+    ///
+    /// Note that no checks are run until the [`run`] method is invoked.
+    ///
+    /// [`run`]: ClusterChecker::run
+    pub fn with_preflight_checks(mut self) -> Self {
+        #[cfg(feature = "kubectl-checks")]
+        self.push_check(Box::new(KubectlBinary::default()));
+        #[cfg(feature = "helm-checks")]
+        self.push_check(Box::new(HelmBinary::default()));
+        self.push_check(Box::new(ActiveKubernetesCluster::default()));
+        self.push_check(Box::new(K8Version::default()));
+        self.push_check(Box::new(NodeReadiness::default()));
+        self.push_check(Box::new(NamespaceCheck::default()));
+        #[cfg(feature = "helm-checks")]
+        self.push_check(Box::new(HelmVersion::default()));
+        let checks: Vec<Box<(dyn ClusterCheck)>> = vec![
+            Box::new(CreateServicePermission::default()),
+            Box::new(CreateCrdPermission::default()),
+            Box::new(CreateServiceAccountPermission::default()),
+        ];
+        for check in checks {
+            self.push_check(check);
+        }
+        #[cfg(feature = "kubectl-checks")]
+        self.push_check(Box::new(openshift::OpenShiftScc::default()));
+        self.push_check(Box::new(LoadBalancerCheck::default()));
+        self
+    }
+
+    /// Creates a checker for running inside a pod of the Kubernetes cluster.
+    ///
+    /// The checks go through the service account mounted into the pod: access
+    /// reviews for what the install needs instead of the kubeconfig permission
+    /// checks, and a [`LoadBalancerCheck`] on the namespace. kubectl is not
+    /// used in a pod, so the kubectl checks are reported as skipped.
+    ///
+    /// Note that no checks are run until the [`run`] method is invoked.
+    ///
+    /// [`run`]: ClusterChecker::run
+    pub fn for_in_cluster() -> Self {
+        let mut checker = Self::empty();
+        #[cfg(feature = "kubectl-checks")]
+        {
+            checker.push_check(Box::new(SkippedCheck::new(
+                Box::new(KubectlBinary::default()),
+                "kubectl is not used in-cluster",
+            )));
+            checker.push_check(Box::new(SkippedCheck::new(
+                Box::new(openshift::OpenShiftScc::default()),
+                "kubectl is not used in-cluster",
+            )));
+        }
+        #[cfg(feature = "helm-checks")]
+        checker.push_check(Box::new(HelmBinary::default()));
+        checker.push_check(Box::new(ActiveKubernetesCluster::default()));
+        checker.push_check(Box::new(K8Version::default()));
+        checker.push_check(Box::new(NodeReadiness::default()));
+        checker.push_check(Box::new(NamespaceCheck::default()));
+        #[cfg(feature = "helm-checks")]
+        checker.push_check(Box::new(HelmVersion::default()));
+        checker.push_check(Box::new(InClusterPermission::default()));
+        checker.push_check(Box::new(LoadBalancerCheck::default()));
+        checker
+    }
+
+    pub fn with_no_k8_checks(mut self) -> Self {
+        self.push_check(Box::new(LocalClusterCheck::default()));
+        self.push_check(Box::new(PortAvailabilityCheck::default()));
+        self.push_check(Box::new(LocalDiskSpace::default()));
+        self
+    }
+
+    /// Adds all checks required for starting a cluster on minikube.
+    ///
+    /// Note that no checks are run until the [`run`] method is invoked.
+    ///
+    /// [`run`]: ClusterChecker::run
+    pub fn with_k8_checks(mut self) -> Self {
+        self.push_check(Box::new(ActiveKubernetesCluster::default()));
+        #[cfg(feature = "helm-checks")]
+        self.push_check(Box::new(HelmVersion::default()));
+        self.push_check(Box::new(K8Version::default()));
+        self.push_check(Box::new(NodeReadiness::default()));
+        self.push_check(Box::new(NamespaceCheck::default()));
+        self.push_check(Box::new(LoadBalancerCheck::default()));
+        self
+    }
+
+    /// Adds the checks required for upgrading an existing installation to `target_version`.
+    ///
+    /// Unlike [`with_preflight_checks`](ClusterChecker::with_preflight_checks)
+    /// these expect Fluvio to be installed already. The installation is looked
+    /// for in the namespace given to [`with_namespace`], or in the default one.
+    ///
+    /// Note that no checks are run until the [`run`] method is invoked.
+    ///
+    /// [`run`]: ClusterChecker::run
+    /// [`with_namespace`]: ClusterChecker::with_namespace
+    #[cfg_attr(not(feature = "helm-checks"), allow(unused_variables))]
+    pub fn with_upgrade_checks(mut self, target_version: Version) -> Self {
+        self.push_check(Box::new(ActiveKubernetesCluster::default()));
+        #[cfg(feature = "helm-checks")]
+        {
+            self.push_check(Box::new(HelmBinary::default()));
+            self.push_check(Box::new(HelmVersion::default()));
+            let namespace = self
+                .namespace
+                .clone()
+                .unwrap_or_else(|| crate::DEFAULT_NAMESPACE.to_string());
+            self.push_check(Box::new(
+                SysChartCheck::new(ChartConfig::sys_inline(&namespace), target_version.clone())
+                    .with_required_version(target_version.clone()),
+            ));
+            self.push_check(Box::new(UpgradePathCheck::new(namespace, target_version)));
+        }
+        self
+    }
+
+    /// Adds all checks required for starting a local cluster.
+    ///
+    /// Note that no checks are run until the [`run`] method is invoked.
+    ///
+    /// [`run`]: ClusterChecker::run
+    pub fn with_local_checks(mut self) -> Self {
+        #[cfg(feature = "helm-checks")]
+        self.push_check(Box::new(HelmVersion::default()));
+        self.push_check(Box::new(K8Version::default()));
+        self.push_check(Box::new(ActiveKubernetesCluster::default()));
+        self.push_check(Box::new(LocalClusterCheck::default()));
+        self.push_check(Box::new(PortAvailabilityCheck::default()));
+        self.push_check(Box::new(LocalDiskSpace::default()));
+        self
+    }
+
+    /// Adds the checks that validate a cluster after it was installed.
+    ///
+    /// Note that no checks are run until the [`run`] method is invoked.
+    ///
+    /// [`run`]: ClusterChecker::run
+    pub fn with_post_install_checks(mut self) -> Self {
+        self.push_check(Box::new(ScConnectivity::default()));
+        self
+    }
+
+    /// Adds the checks required for uninstalling a cluster.
+    ///
+    /// Note that no checks are run until the [`run`] method is invoked.
+    ///
+    /// [`run`]: ClusterChecker::run
+    pub fn with_uninstall_checks(mut self) -> Self {
+        self.push_check(Box::new(ActiveKubernetesCluster::default()));
+        #[cfg(feature = "helm-checks")]
+        self.push_check(Box::new(HelmVersion::default()));
+        self
+    }
+
+    /// Creates a checker from a [`CheckSelection`]
+    ///
+    /// Names in `exclude` and `include_custom` must be known to `registry`,
+    /// otherwise [`ClusterCheckError::UnknownCheck`] is returned.
+    pub fn from_selection(
+        selection: &CheckSelection,
+        registry: &CheckRegistry,
+    ) -> Result<Self, ClusterCheckError> {
+        let unknown = |name: &String| ClusterCheckError::UnknownCheck {
+            name: name.to_owned(),
+            known: registry.names(),
+        };
+
+        if let Some(name) = selection
+            .exclude
+            .iter()
+            .find(|name| !registry.contains(name))
+        {
+            return Err(unknown(name));
+        }
+
+        let mut checker = selection.preset.checker();
+        checker
+            .checks
+            .retain(|check| !selection.exclude.iter().any(|name| name == check.name()));
+
+        for name in &selection.include_custom {
+            let check = registry.create(name).ok_or_else(|| unknown(name))?;
+            checker.push_check(check);
+        }
+        Ok(checker)
+    }
+}
@@ -3,8 +3,60 @@
 use futures_util::StreamExt;
 use async_channel::Receiver;
 use crate::{
-    CheckResult, CheckResults, CheckStatus, CheckSuggestion,
+    CheckResult, CheckResults, CheckStatus, CheckSuggestion, ClusterCheckError,
     render::{ProgressRenderedText, ProgressRenderer},
 };
 
+use super::{CheckEvent, CheckOutcome, CheckProgress};
+
 const ISSUE_URL: &str = "https://github.com/infinyon/fluvio/issues/new/choose";
+
+/// Renders the events of [`ClusterChecker::run_with_progress`] until the run is over
+///
+/// The check being performed is shown as a spinner line with its position
+/// in the run, which its outcome replaces once it completes. Returns the
+/// outcomes in the order the checks completed. Aborting the run through
+/// [`CheckProgress::abort_handle`] ends the rendering once the check being
+/// performed reported its cancellation.
+///
+/// [`ClusterChecker::run_with_progress`]: super::ClusterChecker::run_with_progress
+pub async fn render_check_progress(progress: CheckProgress, pb: &ProgressRenderer) -> CheckResults {
+    let mut outcomes = vec![];
+    while let Some(event) = progress.next_event().await {
+        match event {
+            CheckEvent::Started { name, index, total } => {
+                pb.set_message(format!(
+                    "{:>3} 📝 Checking {name} ({}/{total})",
+                    "",
+                    index + 1
+                ));
+            }
+            CheckEvent::Completed { name, result, .. } => {
+                let outcome = CheckOutcome { name, result };
+                pb.println(format!("{:>3} {outcome}", ""));
+                if let Some(stderr) = outcome.result.as_ref().err().and_then(command_stderr) {
+                    pb.println(stderr);
+                }
+                outcomes.push(outcome);
+            }
+        }
+    }
+    pb.finish_and_clear();
+    outcomes.into()
+}
+
+/// Complete stderr of the command that failed with `err`, indented to go under its error line
+///
+/// `None` if the error line already shows all of it.
+pub(crate) fn command_stderr(err: &ClusterCheckError) -> Option<String> {
+    let command = err.command_output()?;
+    if !command.is_stderr_truncated() {
+        return None;
+    }
+    let lines: Vec<String> = command
+        .stderr
+        .lines()
+        .map(|line| format!("{:>7}{line}", ""))
+        .collect();
+    Some(lines.join("\n"))
+}
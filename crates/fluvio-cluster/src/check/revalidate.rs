@@ -0,0 +1,413 @@
+//! Detects cluster changes between running checks and installing
+//!
+//! Checks marked [`ClusterCheck::is_quick`] are cheap and never change the
+//! cluster. [`CheckResults::revalidate_quick`] performs them again right
+//! before the install starts and compares what they report now with the
+//! earlier results, so the installer can abort if someone else installed
+//! Fluvio or switched the Kubernetes context in the meantime. They are
+//! performed like in every other run mode, with their timeouts and retries,
+//! and all of them have to be done within [`QUICK_CHECK_BUDGET`].
+//!
+//! [`ClusterCheck::is_quick`]: super::ClusterCheck::is_quick
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use super::{CheckResult, CheckResults, CheckStatus, ClusterCheckError, ClusterChecker};
+
+/// Time allowed for running all quick checks once
+pub const QUICK_CHECK_BUDGET: Duration = Duration::from_secs(10);
+
+impl CheckResults {
+    /// Performs the quick checks of `checker` again and reports what changed
+    ///
+    /// Only checks that are part of these results are performed again, so
+    /// these can be the results of any run mode, or of
+    /// [`ClusterChecker::run_quick`].
+    pub async fn revalidate_quick(&self, checker: &ClusterChecker) -> RevalidationReport {
+        self.revalidate_quick_within(checker, QUICK_CHECK_BUDGET)
+            .await
+    }
+
+    /// Same as [`revalidate_quick`] with a custom time budget
+    ///
+    /// [`revalidate_quick`]: CheckResults::revalidate_quick
+    pub async fn revalidate_quick_within(
+        &self,
+        checker: &ClusterChecker,
+        budget: Duration,
+    ) -> RevalidationReport {
+        let current = checker
+            .run_selected_with_deadline(Instant::now() + budget, |check| {
+                check.is_quick() && self.result_for(check.name()).is_some()
+            })
+            .await;
+        self.compare(checker, &current)
+    }
+
+    fn compare(&self, checker: &ClusterChecker, current: &CheckResults) -> RevalidationReport {
+        let mut report = RevalidationReport::default();
+        for now in current {
+            let Some(before) = self.result_for(&now.name) else {
+                continue;
+            };
+            let label = checker
+                .checks
+                .iter()
+                .find(|check| check.name() == now.name)
+                .map_or_else(|| now.name.clone(), |check| check.label().to_string());
+
+            match (before, &now.result) {
+                (_, Err(ClusterCheckError::TimedOut { .. })) => report.unverified.push(label),
+                // a check out of time the first time has nothing to compare against
+                (Err(ClusterCheckError::TimedOut { .. }), _) => {}
+                (before, after) => {
+                    let (before, after) = (describe(before), describe(after));
+                    if before != after {
+                        report.drifted.push(CheckDrift {
+                            check: label,
+                            before,
+                            after,
+                        });
+                    }
+                }
+            }
+        }
+        report
+    }
+}
+
+/// A quick check that reported something different the second time
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckDrift {
+    /// Label of the check
+    pub check: String,
+    /// What the check reported when the checks ran
+    pub before: String,
+    /// What the check reports now
+    pub after: String,
+}
+
+impl fmt::Display for CheckDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} (was: {})", self.check, self.after, self.before)
+    }
+}
+
+/// Differences found by [`CheckResults::revalidate_quick`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RevalidationReport {
+    /// Checks whose outcome changed
+    pub drifted: Vec<CheckDrift>,
+    /// Labels of checks that did not finish within the time budget
+    pub unverified: Vec<String>,
+}
+
+impl RevalidationReport {
+    /// Whether every re-run check finished and none of them changed
+    ///
+    /// A check that ran out of time may have found a change, so it counts against it.
+    pub fn is_clean(&self) -> bool {
+        self.drifted.is_empty() && self.unverified.is_empty()
+    }
+}
+
+impl fmt::Display for RevalidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_clean() {
+            return write!(f, "environment unchanged since checks ran");
+        }
+        if !self.drifted.is_empty() {
+            let drifted = self
+                .drifted
+                .iter()
+                .map(|drift| drift.to_string())
+                .collect::<Vec<_>>()
+                .join("; ");
+            write!(f, "environment changed since checks ran: {drifted}")?;
+        }
+        if !self.unverified.is_empty() {
+            if !self.drifted.is_empty() {
+                write!(f, ". ")?;
+            }
+            write!(
+                f,
+                "unable to verify the environment is unchanged, out of time for: {}",
+                self.unverified.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ClusterChecker {
+    /// Performs only the quick checks, within [`QUICK_CHECK_BUDGET`]
+    ///
+    /// Auto-fixes are never attempted and nothing is printed. Call
+    /// [`CheckResults::revalidate_quick`] on the results later to find out
+    /// if the cluster changed.
+    pub async fn run_quick(&self) -> CheckResults {
+        self.run_quick_within(QUICK_CHECK_BUDGET).await
+    }
+
+    async fn run_quick_within(&self, budget: Duration) -> CheckResults {
+        self.run_selected_with_deadline(Instant::now() + budget, |check| check.is_quick())
+            .await
+    }
+}
+
+/// Details of a check result that can be compared between runs
+fn describe(result: &CheckResult) -> String {
+    match result {
+        Ok(CheckStatus::Pass(message) | CheckStatus::PassWithData(message, _)) => message.clone(),
+        Ok(CheckStatus::AutoFixableError { message, .. }) => message.clone(),
+        Ok(CheckStatus::Unrecoverable(err)) => err.to_string(),
+        Ok(CheckStatus::Skipped(reason)) => format!("skipped: {reason}"),
+        Err(err) => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+    use fluvio_future::timer::sleep;
+
+    use crate::check::ClusterCheck;
+    use crate::render::ProgressRenderer;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct SlowCheck;
+
+    #[async_trait]
+    impl ClusterCheck for SlowCheck {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn label(&self) -> &str {
+            "Slow"
+        }
+
+        fn is_quick(&self) -> bool {
+            true
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            sleep(Duration::from_secs(60)).await;
+            Ok(CheckStatus::pass("done"))
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_revalidate_out_of_time() {
+        let checker = ClusterChecker::empty().with_check(SlowCheck);
+
+        let results = checker.run_quick_within(Duration::from_millis(10)).await;
+        let report = results
+            .revalidate_quick_within(&checker, Duration::from_millis(10))
+            .await;
+
+        assert!(
+            report.drifted.is_empty(),
+            "running out of time is not drift"
+        );
+        assert_eq!(report.unverified, vec!["Slow".to_string()]);
+        assert!(
+            !report.is_clean(),
+            "a check out of time may have missed a change"
+        );
+        assert_eq!(
+            report.to_string(),
+            "unable to verify the environment is unchanged, out of time for: Slow"
+        );
+    }
+
+    /// The real [`AlreadyInstalled`] check against a mocked helm
+    #[cfg(all(unix, feature = "helm-checks"))]
+    mod already_installed {
+        use std::ffi::OsStr;
+        use std::io::Error as IoError;
+        use std::path::PathBuf;
+        use std::process::{Command, Output};
+        use std::sync::{Arc, Mutex};
+
+        use fluvio::FluvioConfig;
+        use k8_config::KubeConfig;
+
+        use crate::charts::{ChartConfig, ChartInstallError};
+        use crate::check::{
+            AlreadyInstalled, CheckEnvironment, ClusterApi, RunningProcess, SysChartInstaller,
+        };
+
+        use super::*;
+
+        /// Answers `helm list` with the Fluvio release installed so far, if any
+        #[derive(Debug, Clone, Default)]
+        struct FakeHelm(Arc<Mutex<Option<&'static str>>>);
+
+        impl FakeHelm {
+            fn install(&self, namespace: &'static str) {
+                *self.0.lock().unwrap() = Some(namespace);
+            }
+
+            fn releases(&self) -> String {
+                match *self.0.lock().unwrap() {
+                    Some(namespace) => format!(
+                        r#"[{{"name":"fluvio","namespace":"{namespace}","app_version":"0.11.0"}}]"#
+                    ),
+                    None => "[]".to_string(),
+                }
+            }
+        }
+
+        #[async_trait]
+        impl CheckEnvironment for FakeHelm {
+            fn kubeconfig(&self) -> Option<KubeConfig> {
+                None
+            }
+
+            fn client(&self, _context: Option<&str>) -> Result<Arc<dyn ClusterApi>, String> {
+                Err("no Kubernetes cluster".to_string())
+            }
+
+            async fn command_output(
+                &self,
+                command: Command,
+                _timeout: Duration,
+            ) -> Result<Output, IoError> {
+                use std::os::unix::process::ExitStatusExt;
+                use std::process::ExitStatus;
+
+                let args: Vec<_> = command.get_args().collect();
+                assert_eq!(command.get_program(), "helm");
+                assert_eq!(args.first().copied(), Some(OsStr::new("list")));
+                Ok(Output {
+                    status: ExitStatus::from_raw(0),
+                    stdout: self.releases().into_bytes(),
+                    stderr: vec![],
+                })
+            }
+
+            fn find_binary(&self, _binary: &str) -> Option<PathBuf> {
+                None
+            }
+
+            fn is_port_free(&self, _port: u16) -> bool {
+                true
+            }
+
+            fn disks(&self) -> Vec<(PathBuf, u64)> {
+                vec![]
+            }
+
+            fn processes(&self) -> Vec<RunningProcess> {
+                vec![]
+            }
+
+            fn profile(&self) -> Result<FluvioConfig, ClusterCheckError> {
+                Err(ClusterCheckError::Other("no cluster profile".to_string()))
+            }
+
+            fn sys_chart_installer(
+                &self,
+                _config: &ChartConfig,
+            ) -> Result<Box<dyn SysChartInstaller>, ChartInstallError> {
+                Err(ChartInstallError::Other("no system chart".to_string()))
+            }
+        }
+
+        /// Changes the cluster every time it runs, must never be re-run
+        #[derive(Debug, Default)]
+        struct MutatingCheck(Arc<Mutex<u32>>);
+
+        #[async_trait]
+        impl ClusterCheck for MutatingCheck {
+            fn name(&self) -> &str {
+                "mutating"
+            }
+
+            fn label(&self) -> &str {
+                "Mutating"
+            }
+
+            async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+                let mut runs = self.0.lock().unwrap();
+                *runs += 1;
+                Ok(CheckStatus::pass(format!("run {runs}")))
+            }
+        }
+
+        fn checker(helm: &FakeHelm, runs: &Arc<Mutex<u32>>) -> ClusterChecker {
+            ClusterChecker::empty()
+                .with_check(AlreadyInstalled::new("fluvio"))
+                .with_check(MutatingCheck(runs.clone()))
+                .with_environment(Arc::new(helm.clone()))
+        }
+
+        #[fluvio_future::test]
+        async fn test_revalidate_without_changes() {
+            let helm = FakeHelm::default();
+            let runs = Arc::default();
+            let checker = checker(&helm, &runs);
+
+            let results = checker.run_parallel().await;
+            let report = results.revalidate_quick(&checker).await;
+
+            assert!(report.is_clean());
+            assert_eq!(report, RevalidationReport::default());
+            assert_eq!(*runs.lock().unwrap(), 1, "mutating checks are not re-run");
+        }
+
+        #[fluvio_future::test]
+        async fn test_revalidate_detects_new_installation() {
+            let helm = FakeHelm::default();
+            let runs = Arc::default();
+            let checker = checker(&helm, &runs);
+
+            let results = checker.run_quick().await;
+            helm.install("other");
+            let report = results.revalidate_quick(&checker).await;
+
+            assert!(!report.is_clean());
+            assert_eq!(
+                report.drifted,
+                vec![CheckDrift {
+                    check: "Fluvio installation".to_string(),
+                    before: "Previous fluvio installation not found".to_string(),
+                    after: "Fluvio is already installed (0.11.0 in other), \
+                            cannot install into namespace fluvio"
+                        .to_string(),
+                }]
+            );
+            assert_eq!(
+                report.to_string(),
+                "environment changed since checks ran: Fluvio installation: Fluvio is \
+                 already installed (0.11.0 in other), cannot install into namespace fluvio \
+                 (was: Previous fluvio installation not found)"
+            );
+            assert_eq!(*runs.lock().unwrap(), 0, "mutating checks are not run");
+        }
+
+        #[fluvio_future::test]
+        async fn test_revalidate_out_of_time_and_drifted() {
+            let helm = FakeHelm::default();
+            let runs = Arc::default();
+            let checker = checker(&helm, &runs).with_check(SlowCheck);
+
+            let results = checker.run_quick_within(Duration::from_millis(50)).await;
+            helm.install("other");
+            let report = results
+                .revalidate_quick_within(&checker, Duration::from_millis(50))
+                .await;
+
+            assert!(!report.is_clean());
+            assert_eq!(report.drifted.len(), 1);
+            assert_eq!(report.unverified, vec!["Slow".to_string()]);
+            assert!(report.to_string().ends_with(
+                ". unable to verify the environment is unchanged, out of time for: Slow"
+            ));
+        }
+    }
+}
@@ -0,0 +1,1565 @@
+//! The run modes of [`ClusterChecker`]
+//!
+//! Every run mode performs its checks through a [`CheckStep`], so a check
+//! behaves the same whichever mode runs it. The modes only differ in the
+//! order they perform checks in, how many at a time, and what they do with
+//! the outcomes.
+
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_channel::Sender;
+use colored::Colorize;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use futures_util::future::{select, Either};
+use futures_util::{stream, FutureExt, Stream, StreamExt};
+use indicatif::ProgressBar;
+use tracing::{debug, error};
+
+use fluvio_types::event::StickyEvent;
+
+use crate::progress::ProgressBarFactory;
+use crate::render::ProgressRenderer;
+
+use super::{identity, render};
+use super::{
+    CheckAbortHandle, CheckEvent, CheckOutcome, CheckProgress, CheckResult, CheckResults,
+    CheckRetry, CheckSeverity, CheckStatus, CheckTimeouts, ClusterAutoFix, ClusterCheck,
+    ClusterCheckError, ClusterChecker, FixProgress, FixProgressReporter, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+impl ClusterChecker {
+    /// Performs checks and fixes as required.
+    ///
+    /// When a [`KubectlBinary`](super::KubectlBinary) or [`HelmBinary`](super::HelmBinary) check fails, the checks that
+    /// need that binary are skipped instead of failing on their own.
+    pub async fn run(
+        self,
+        pb_factory: &ProgressBarFactory,
+        fix_recoverable: bool,
+    ) -> Result<bool, ClusterCheckError> {
+        macro_rules! pad_format {
+            ( $e:expr ) => {
+                format!("{:>3} {}", "", $e)
+            };
+        }
+
+        // sort checks according to dependencies
+        let mut components: HashSet<FluvioClusterComponent> = HashSet::new();
+
+        // binaries found missing, by the component of the checks skipped because of them
+        let mut missing_binaries: HashMap<FluvioClusterComponent, &'static str> = HashMap::new();
+
+        let step = self.check_step();
+        let mut sorted_checks = self.checks;
+        sorted_checks.sort_by(check_compare);
+        let provided: HashSet<FluvioClusterComponent> = sorted_checks
+            .iter()
+            .filter_map(|check| check.component())
+            .collect();
+
+        let mut failed = false;
+        let mut identities = vec![];
+        for check in sorted_checks {
+            if step.is_cancelled() {
+                debug!(check = check.name(), "check run cancelled");
+                return Err(ClusterCheckError::Cancelled);
+            }
+            let pb = pb_factory.create()?;
+            let mut passed = false;
+            let mut check_failed = false;
+            let required_components: Vec<_> = check
+                .required_components()
+                .into_iter()
+                // a binary no check looks for is assumed to be installed
+                .filter(|required| required.binary().is_none() || provided.contains(required))
+                .collect();
+            let component = check.component();
+            if let Some(binary) = required_components
+                .iter()
+                .find_map(|required| missing_binaries.get(required).copied())
+            {
+                pb.println(pad_format!(format!(
+                    "{} Skipped {}: {} is not installed",
+                    "⏭️".bold(),
+                    check.label().italic(),
+                    binary
+                )));
+                if let Some(component) = component {
+                    missing_binaries.insert(component, binary);
+                }
+            } else if required_components
+                .iter()
+                .filter(|component| components.contains(component))
+                .count()
+                == required_components.len()
+            {
+                pb.set_message(pad_format!(format!(
+                    "{} Checking {}",
+                    "📝".bold(),
+                    check.label()
+                )));
+                sleep(Duration::from_millis(100)).await; // dummy delay for debugging
+                if let Some(identity) = check.identity() {
+                    debug!(check = check.name(), %identity, "check identity");
+                    identities.push((check.label().to_string(), identity));
+                }
+                let status = match step.perform(check.as_ref(), &pb).await.result {
+                    Err(ClusterCheckError::Cancelled) => return Err(ClusterCheckError::Cancelled),
+                    Ok(status) => Some(status),
+                    Err(err) => {
+                        // an error only fails this check, later checks still run
+                        debug!(check = check.name(), ?err, "check errored");
+                        pb.println(pad_format!(format!(
+                            "{} Check {} could not be performed: {}",
+                            "❌",
+                            check.label().italic(),
+                            err.to_string().red()
+                        )));
+                        if let Some(stderr) = render::command_stderr(&err) {
+                            pb.println(stderr);
+                        }
+                        check_failed = true;
+                        None
+                    }
+                };
+                match status {
+                    None => {}
+                    Some(CheckStatus::AutoFixableError { message, fixer }) => {
+                        if fix_recoverable {
+                            pb.set_message(pad_format!(format!("{} {}", "🟡️".bold(), message)));
+                            let progress = FixProgressReporter::new(&pb, self.fix_progress.clone());
+                            match fixer.attempt_fix(&progress).await {
+                                Ok(status) => {
+                                    pb.println(pad_format!(format!(
+                                        "{} Fixed: {}",
+                                        "✅".bold(),
+                                        status
+                                    )));
+                                    passed = true;
+                                }
+                                Err(err) => {
+                                    // If the fix failed, wrap the original failed check in Unrecoverable
+                                    pb.println(pad_format!(format!(
+                                        "{} Auto fix for {} failed {:#?}",
+                                        "❌",
+                                        check.label().italic(),
+                                        err
+                                    )));
+
+                                    check_failed = true;
+                                }
+                            }
+                        } else {
+                            pb.println(pad_format!(format!(
+                                "{} {} check failed and is auto-fixable but fixer is disabled. Use `--fix` to enable it.",
+                                "❌".bold(),
+                                check.label().italic(),
+                            )));
+
+                            check_failed = true;
+                        }
+                    }
+                    Some(CheckStatus::Pass(status) | CheckStatus::PassWithData(status, _)) => {
+                        passed = true;
+                        pb.println(pad_format!(format!("{} {}", "✅".bold(), status)));
+                    }
+                    Some(CheckStatus::Skipped(reason)) => {
+                        pb.println(pad_format!(format!(
+                            "{} Skipped {}: {}",
+                            "⏭️".bold(),
+                            check.label().italic(),
+                            reason
+                        )));
+                        // without the binary check the binary is assumed to be installed
+                        passed = component.is_some_and(|component| component.binary().is_some());
+                    }
+                    Some(CheckStatus::Unrecoverable(err)) => {
+                        debug!("failed: {}", err);
+
+                        pb.println(pad_format!(format!(
+                            "{} Check {} failed {}",
+                            "❌",
+                            check.label().italic(),
+                            err.to_string().red()
+                        )));
+                        if let Some(suggestion) = err.suggestion() {
+                            pb.println(pad_format!(format!("   💡 {suggestion}")));
+                        }
+
+                        check_failed = true;
+                    }
+                }
+            } else {
+                pb.println(pad_format!(format!(
+                    "❌ skipping check: {} because required components are not met",
+                    check.label()
+                )));
+                check_failed = true;
+            }
+
+            if check_failed {
+                if step.is_severe(check.as_ref()) {
+                    failed = true;
+                } else {
+                    pb.println(pad_format!(format!(
+                        "{} {} is a {} check, continuing",
+                        "⚠️",
+                        check.label().italic(),
+                        check.severity()
+                    )));
+                }
+            }
+
+            if check_failed {
+                if let Some(component) = component {
+                    if let Some(binary) = component.binary() {
+                        missing_binaries.insert(component, binary);
+                    }
+                }
+            }
+
+            if passed {
+                if let Some(component) = component {
+                    debug!(?component, "component registered");
+                    components.insert(component);
+                }
+            }
+
+            pb.finish_and_clear();
+        }
+
+        if let Some(warning) = identity::identity_mismatch(&identities) {
+            pb_factory.println(format!("⚠️  {}", warning.yellow()));
+        }
+
+        if failed {
+            pb_factory.println(format!("💔 {}", "Some pre-flight check failed!".bold()));
+            Err(ClusterCheckError::PreCheckFlightFailure)
+        } else {
+            pb_factory.println(format!("🎉 {}", "All checks passed!".bold()));
+            Ok(true)
+        }
+    }
+
+    /// Performs all checks concurrently and returns their results
+    ///
+    /// Results are in the order the checks were added. Unlike [`run`], nothing
+    /// is printed, no fixes are attempted and checks are not skipped when a
+    /// component they require failed. A check that panics results in an error
+    /// and does not affect the other checks. [`exclusive`] checks run one at a
+    /// time once the others are done.
+    ///
+    /// [`run`]: ClusterChecker::run
+    /// [`exclusive`]: ClusterCheck::exclusive
+    pub async fn run_parallel(&self) -> CheckResults {
+        let step = self.check_step();
+        let limit = self.concurrency_limit.unwrap_or(self.checks.len());
+        let checks = self
+            .checks
+            .iter()
+            .map(|check| (check.exclusive(), step.perform_hidden(check.as_ref())))
+            .collect();
+        run_in_order(checks, limit).await
+    }
+
+    /// Performs checks one at a time, attempting the fix of every auto-fixable failure
+    ///
+    /// Checks run in the same order as in [`run`]. Each fix attempt is an
+    /// outcome of its own, right after the failure it fixes and named after
+    /// the check with a `-fix` suffix. A fix that succeeds passes, one that
+    /// fails is an [`UnrecoverableCheckStatus::FailedRecovery`] carrying the
+    /// whole error chain, such as the stderr of a failed helm install. Unlike
+    /// [`run`], nothing is printed; fix steps are still forwarded to
+    /// [`with_fix_progress`].
+    ///
+    /// [`run`]: ClusterChecker::run
+    /// [`with_fix_progress`]: ClusterChecker::with_fix_progress
+    pub async fn run_and_fix(&self) -> CheckResults {
+        let step = self.check_step();
+        let mut checks: Vec<_> = self.checks.iter().collect();
+        checks.sort_by(|first, second| check_compare(first, second));
+
+        let mut results = vec![];
+        for check in checks {
+            let outcome = step.perform_hidden(check.as_ref()).await;
+            let fix = match &outcome.result {
+                Ok(CheckStatus::AutoFixableError { fixer, .. }) => Some(
+                    attempt_fix(check.as_ref(), fixer.as_ref(), self.fix_progress.clone()).await,
+                ),
+                _ => None,
+            };
+            results.push(outcome);
+            results.extend(fix);
+        }
+        results.into()
+    }
+
+    /// Performs checks one at a time and stops at the first failure
+    ///
+    /// Checks run in the same order as in [`run`]. The run stops after the
+    /// first check that errors or fails unrecoverably, unless that check is
+    /// less severe than [`with_minimum_severity`] asks for. Only the results
+    /// collected so far are returned. Like [`run_parallel`], nothing is
+    /// printed and no fixes are attempted.
+    ///
+    /// [`run`]: ClusterChecker::run
+    /// [`with_minimum_severity`]: ClusterChecker::with_minimum_severity
+    /// [`run_parallel`]: ClusterChecker::run_parallel
+    pub async fn run_until_first_failure(&self) -> CheckResults {
+        let mut checks: Vec<_> = self.checks.iter().collect();
+        checks.sort_by(|first, second| check_compare(first, second));
+
+        let step = self.check_step();
+        let mut results = vec![];
+        for check in checks {
+            let outcome = step.perform_hidden(check.as_ref()).await;
+            let cancelled = matches!(outcome.result, Err(ClusterCheckError::Cancelled));
+            let failed = outcome.is_blocking() && step.is_severe(check.as_ref());
+            results.push(outcome);
+            if cancelled || failed {
+                debug!(check = check.name(), "stopping at first failure");
+                break;
+            }
+        }
+        results.into()
+    }
+
+    /// Performs checks one at a time until `deadline`
+    ///
+    /// Checks run in the same order as in [`run`]. Each check is given the
+    /// time left until `deadline`, or its own timeout if that is shorter.
+    /// Once the deadline passed, every remaining check results in
+    /// [`ClusterCheckError::TimedOut`] without being performed. Like
+    /// [`run_parallel`], nothing is printed and no fixes are attempted.
+    ///
+    /// [`run`]: ClusterChecker::run
+    /// [`run_parallel`]: ClusterChecker::run_parallel
+    pub async fn run_with_deadline(&self, deadline: Instant) -> CheckResults {
+        self.run_selected_with_deadline(deadline, |_| true).await
+    }
+
+    /// Same as [`run_with_deadline`] for only the checks `selected` returns `true` for
+    ///
+    /// [`run_with_deadline`]: ClusterChecker::run_with_deadline
+    pub(super) async fn run_selected_with_deadline(
+        &self,
+        deadline: Instant,
+        selected: impl Fn(&dyn ClusterCheck) -> bool,
+    ) -> CheckResults {
+        let step = CheckStep {
+            deadline: Some(deadline),
+            ..self.check_step()
+        };
+        let mut checks: Vec<_> = self
+            .checks
+            .iter()
+            .filter(|check| selected(check.as_ref()))
+            .collect();
+        checks.sort_by(|first, second| check_compare(first, second));
+
+        let mut results = vec![];
+        for check in checks {
+            results.push(step.perform_hidden(check.as_ref()).await);
+        }
+        results.into()
+    }
+
+    /// Same as [`run_parallel`] with a [`with_concurrency_limit`] of `max_concurrent`,
+    /// on a task of its own
+    ///
+    /// [`run`] is unaffected and keeps performing checks one after the other.
+    ///
+    /// [`run_parallel`]: ClusterChecker::run_parallel
+    /// [`with_concurrency_limit`]: ClusterChecker::with_concurrency_limit
+    /// [`run`]: ClusterChecker::run
+    pub async fn run_wait_parallel(self, max_concurrent: usize) -> CheckResults {
+        let checker = self.with_concurrency_limit(max_concurrent);
+        spawn(async move { checker.run_parallel().await }).await
+    }
+
+    /// Performs checks one at a time on a spawned task, reporting each as it starts and completes
+    ///
+    /// Checks run in the same order as in [`run`]. Like [`run_parallel`],
+    /// nothing is printed and no fixes are attempted, [`render_check_progress`]
+    /// prints the events as they come. The run ends once every check
+    /// completed, or after the check that saw the run cancelled or aborted.
+    ///
+    /// [`run`]: ClusterChecker::run
+    /// [`run_parallel`]: ClusterChecker::run_parallel
+    /// [`render_check_progress`]: render::render_check_progress
+    pub fn run_with_progress(self) -> CheckProgress {
+        let (sender, events) = async_channel::unbounded();
+        let abort = CheckAbortHandle(StickyEvent::shared());
+        let step = self.check_step();
+        spawn(self.send_progress(step, sender, abort.clone()));
+        CheckProgress { events, abort }
+    }
+
+    /// Same as [`run_with_progress`], with only the result of each completed check
+    ///
+    /// [`run_with_progress`]: ClusterChecker::run_with_progress
+    pub fn run_with_results(self) -> impl Stream<Item = CheckResult> {
+        self.run_with_progress()
+            .into_stream()
+            .filter_map(|event| async move { event.into_outcome().map(|outcome| outcome.result) })
+    }
+
+    async fn send_progress(
+        mut self,
+        step: CheckStep,
+        sender: Sender<CheckEvent>,
+        abort: CheckAbortHandle,
+    ) {
+        let mut checks = std::mem::take(&mut self.checks);
+        checks.sort_by(check_compare);
+
+        let total = checks.len();
+        for (index, check) in checks.iter().enumerate() {
+            let name = check.name().to_string();
+            let mut cancelled = abort.is_aborted() || step.is_cancelled();
+            let result = if cancelled {
+                debug!(check = check.name(), "check run cancelled");
+                Err(ClusterCheckError::Cancelled)
+            } else {
+                let started = CheckEvent::Started {
+                    name: name.clone(),
+                    index,
+                    total,
+                };
+                if sender.send(started).await.is_err() {
+                    debug!("check progress receiver dropped");
+                    return;
+                }
+                let perform = step.perform_hidden(check.as_ref());
+                // dropping the check future lets the check clean up after itself
+                match select(Box::pin(perform), abort.0.listen_pinned()).await {
+                    Either::Left((outcome, _)) => outcome.result,
+                    Either::Right(_) => {
+                        debug!(check = check.name(), "check run aborted");
+                        cancelled = true;
+                        Err(ClusterCheckError::Cancelled)
+                    }
+                }
+            };
+
+            let completed = CheckEvent::Completed {
+                name,
+                index,
+                total,
+                result,
+            };
+            if sender.send(completed).await.is_err() || cancelled {
+                return;
+            }
+        }
+    }
+
+    /// What every run mode applies to each check, see [`CheckStep`]
+    fn check_step(&self) -> CheckStep {
+        CheckStep {
+            missing_context: self.missing_kube_context(),
+            cancellation: self.cancellation.clone(),
+            timeouts: self.timeouts,
+            retry: self.retry,
+            minimum_severity: self.minimum_severity,
+            deadline: None,
+        }
+    }
+}
+
+/// Performs a single check the same way in every run mode
+///
+/// The run modes only differ in the order checks run in, how many at a
+/// time, and what they do with the outcome. Everything in between, from a
+/// missing kube context to a panic, is handled here.
+#[derive(Debug, Clone)]
+struct CheckStep {
+    /// set when the kubeconfig lacks the context of [`ClusterChecker::with_kube_context`]
+    missing_context: Option<String>,
+    cancellation: Option<Arc<StickyEvent>>,
+    timeouts: CheckTimeouts,
+    retry: CheckRetry,
+    minimum_severity: CheckSeverity,
+    /// the time every check has to be done by, see [`ClusterChecker::run_with_deadline`]
+    deadline: Option<Instant>,
+}
+
+impl CheckStep {
+    fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(|token| token.is_set())
+    }
+
+    /// Whether a failure of `check` fails the run, see [`ClusterChecker::with_minimum_severity`]
+    fn is_severe(&self, check: &dyn ClusterCheck) -> bool {
+        check.severity() >= self.minimum_severity
+    }
+
+    /// Same as [`perform`] without rendering progress
+    ///
+    /// [`perform`]: CheckStep::perform
+    async fn perform_hidden(&self, check: &dyn ClusterCheck) -> CheckOutcome {
+        self.perform(check, &ProgressRenderer::from(ProgressBar::hidden()))
+            .await
+    }
+
+    /// Performs `check` unless the run was cancelled or the kube context is missing
+    ///
+    /// The check is given its timeout, or the time left until the deadline if
+    /// that is shorter, and is performed again while it errors. A panic
+    /// results in an error instead of unwinding into the run.
+    async fn perform(&self, check: &dyn ClusterCheck, pb: &ProgressRenderer) -> CheckOutcome {
+        CheckOutcome {
+            name: check.name().to_string(),
+            result: self.result(check, pb).await,
+        }
+    }
+
+    async fn result(&self, check: &dyn ClusterCheck, pb: &ProgressRenderer) -> CheckResult {
+        if self.is_cancelled() {
+            debug!(check = check.name(), "check run cancelled");
+            return Err(ClusterCheckError::Cancelled);
+        }
+        if let Some(name) = &self.missing_context {
+            return Ok(kube_context_not_found(name));
+        }
+
+        let timeout = self.timeouts.for_check(check);
+        let Some(deadline) = self.deadline else {
+            return perform_check_catching_panic(check, pb, timeout, self.retry).await;
+        };
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let timed_out = |timeout| {
+            Err(ClusterCheckError::TimedOut {
+                check: check.name().to_string(),
+                timeout,
+            })
+        };
+        if remaining.is_zero() {
+            debug!(
+                check = check.name(),
+                "deadline passed, not performing check"
+            );
+            return timed_out(Duration::ZERO);
+        }
+
+        let timeout = timeout.map_or(remaining, |timeout| timeout.min(remaining));
+        let perform = perform_check_catching_panic(check, pb, Some(timeout), self.retry);
+        // retries may take longer than a single attempt, the deadline still applies
+        match select(Box::pin(perform), Box::pin(sleep(remaining))).await {
+            Either::Left((result, _)) => result,
+            Either::Right(_) => timed_out(remaining),
+        }
+    }
+}
+
+fn kube_context_not_found(name: &str) -> CheckStatus {
+    CheckStatus::Unrecoverable(UnrecoverableCheckStatus::KubeContextNotFound {
+        name: name.to_string(),
+    })
+}
+
+/// Awaits the shared checks at most `limit` at a time, then the exclusive
+/// ones one by one, returning results in the original order
+async fn run_in_order<F>(checks: Vec<(bool, F)>, limit: usize) -> CheckResults
+where
+    F: Future<Output = CheckOutcome>,
+{
+    let mut results: Vec<Option<CheckOutcome>> = checks.iter().map(|_| None).collect();
+    let (exclusive, shared): (Vec<_>, Vec<_>) = checks
+        .into_iter()
+        .enumerate()
+        .partition(|(_, (exclusive, _))| *exclusive);
+
+    let shared: Vec<_> = shared
+        .into_iter()
+        .map(|(index, (_, check))| check.map(move |result| (index, result)))
+        .collect();
+    let finished: Vec<_> = stream::iter(shared).buffered(limit.max(1)).collect().await;
+    for (index, result) in finished {
+        results[index] = Some(result);
+    }
+
+    for (index, (_, check)) in exclusive {
+        results[index] = Some(check.await);
+    }
+
+    results.into_iter().flatten().collect()
+}
+
+/// Performs a check, giving up once `timeout` elapsed
+async fn perform_check_with_timeout(
+    check: &dyn ClusterCheck,
+    pb: &ProgressRenderer,
+    timeout: Option<Duration>,
+) -> CheckResult {
+    let Some(timeout) = timeout else {
+        return check.perform_check(pb).await;
+    };
+
+    match select(check.perform_check(pb), Box::pin(sleep(timeout))).await {
+        Either::Left((result, _)) => result,
+        Either::Right(_) => {
+            debug!(check = check.name(), ?timeout, "check timed out");
+            Err(ClusterCheckError::TimedOut {
+                check: check.name().to_string(),
+                timeout,
+            })
+        }
+    }
+}
+
+/// Performs a check again while it errors, see [`ClusterChecker::with_retry`]
+async fn perform_check_with_retry(
+    check: &dyn ClusterCheck,
+    pb: &ProgressRenderer,
+    timeout: Option<Duration>,
+    retry: CheckRetry,
+) -> CheckResult {
+    let mut attempt = 1;
+    loop {
+        match perform_check_with_timeout(check, pb, timeout).await {
+            Err(err) if attempt < retry.max_attempts => {
+                let backoff = retry.backoff * attempt;
+                debug!(check = check.name(), attempt, ?backoff, %err, "retrying check");
+                sleep(backoff).await;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Performs a check, turning a panic into an error
+async fn perform_check_catching_panic(
+    check: &dyn ClusterCheck,
+    pb: &ProgressRenderer,
+    timeout: Option<Duration>,
+    retry: CheckRetry,
+) -> CheckResult {
+    AssertUnwindSafe(perform_check_with_retry(check, pb, timeout, retry))
+        .catch_unwind()
+        .await
+        .unwrap_or_else(|_| {
+            error!(check = check.name(), "check panicked");
+            Err(ClusterCheckError::Other(format!(
+                "check {} panicked",
+                check.name()
+            )))
+        })
+}
+
+/// Renders `err` followed by each of its sources, so the output of a failed
+/// command such as helm's stderr isn't lost behind a generic message
+fn error_chain(err: &dyn std::error::Error) -> String {
+    let mut chain = err.to_string();
+    let mut source = err.source();
+    while let Some(err) = source {
+        chain.push_str(": ");
+        chain.push_str(&err.to_string());
+        source = err.source();
+    }
+    chain
+}
+
+/// Attempts to fix the failure of `check`, see [`ClusterChecker::run_and_fix`]
+async fn attempt_fix(
+    check: &dyn ClusterCheck,
+    fixer: &dyn ClusterAutoFix,
+    sender: Option<Sender<FixProgress>>,
+) -> CheckOutcome {
+    let pb = ProgressRenderer::from(ProgressBar::hidden());
+    let progress = FixProgressReporter::new(&pb, sender);
+    let status = match fixer.attempt_fix(&progress).await {
+        Ok(message) => CheckStatus::pass(message),
+        Err(err) => {
+            debug!(check = check.name(), %err, "auto fix failed");
+            CheckStatus::Unrecoverable(UnrecoverableCheckStatus::FailedRecovery {
+                check: check.name().to_string(),
+                error: error_chain(&err),
+            })
+        }
+    };
+    CheckOutcome {
+        name: format!("{}-fix", check.name()),
+        result: Ok(status),
+    }
+}
+
+#[allow(clippy::borrowed_box)]
+fn check_compare(first: &Box<dyn ClusterCheck>, second: &Box<dyn ClusterCheck>) -> Ordering {
+    //  println!("dep1: {:#?}",dep1_set);
+    //  println!("dep2: {:#?}",dep2_set);
+    // check if any of dep1 is less than dep2
+    if let Some(reg) = second.component() {
+        //   println!("second component: {:#?}",reg);
+        for dep1 in first.required_components() {
+            //     println!("checking dep1: {:#?}",dep1);
+            // if first is depends on second, then seconds should be listed first
+            if dep1 == reg {
+                return Ordering::Greater;
+            }
+        }
+    }
+
+    if let Some(reg) = first.component() {
+        // println!("second component: {:#?}",reg);
+        for dep2 in second.required_components() {
+            //   println!("checking second: {:#?}",dep2);
+            // if seconds is depends on first, then first should be listed first
+            if dep2 == reg {
+                return Ordering::Less;
+            }
+        }
+    }
+
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering::SeqCst;
+
+    use async_trait::async_trait;
+    use k8_config::ConfigError as K8ConfigError;
+
+    #[cfg(feature = "helm-checks")]
+    use crate::charts::ChartInstallError;
+    use crate::check::testing::*;
+    #[cfg(feature = "kubectl-checks")]
+    use crate::check::{ActiveKubernetesCluster, CreateCrdPermission};
+    use crate::check::{ClusterAutoFixError, CheckResultsExt, CommandOutput, HelmBinary};
+
+    use super::*;
+
+    #[cfg(feature = "kubectl-checks")]
+    #[test]
+    fn test_check_dep() {
+        let k8: Box<dyn ClusterCheck> = Box::new(ActiveKubernetesCluster::default());
+        let perm: Box<dyn ClusterCheck> = Box::new(CreateCrdPermission::default());
+        // since per depends on k8, k8 should be less
+        assert_eq!(check_compare(&k8, &perm), Ordering::Less);
+    }
+
+    #[derive(Debug)]
+    struct FailingFixer;
+
+    #[async_trait]
+    impl ClusterAutoFix for FailingFixer {
+        async fn attempt_fix(
+            &self,
+            _progress: &FixProgressReporter<'_>,
+        ) -> Result<String, ClusterAutoFixError> {
+            Err(ClusterAutoFixError::K8Config(
+                K8ConfigError::NoCurrentContext,
+            ))
+        }
+    }
+
+    /// Fails with a fixer that succeeds unless `fix_fails`
+    #[derive(Debug)]
+    struct FixableCheck {
+        name: &'static str,
+        fix_fails: bool,
+    }
+
+    #[async_trait]
+    impl ClusterCheck for FixableCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn label(&self) -> &str {
+            self.name
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            let fixer: Box<dyn ClusterAutoFix> = if self.fix_fails {
+                Box::new(FailingFixer)
+            } else {
+                Box::new(ScriptedFixer(vec!["installing chart"]))
+            };
+            Ok(CheckStatus::AutoFixableError {
+                message: "needs fixing".to_string(),
+                fixer,
+            })
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_and_fix_reports_fixes() {
+        let (sender, receiver) = async_channel::unbounded();
+        let (checker, _) = sleepy_checker(&[("fast", 0)]);
+        let checker = checker
+            .with_check(FixableCheck {
+                name: "fixed",
+                fix_fails: false,
+            })
+            .with_check(FixableCheck {
+                name: "broken",
+                fix_fails: true,
+            })
+            .with_fix_progress(sender);
+
+        let results = checker.run_and_fix().await;
+
+        let names: Vec<_> = results
+            .iter()
+            .map(|outcome| outcome.name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["fast", "fixed", "fixed-fix", "broken", "broken-fix"]
+        );
+        assert!(matches!(
+            results.result_for("fixed-fix"),
+            Some(Ok(CheckStatus::Pass(message))) if message == "fixed"
+        ));
+        assert!(matches!(
+            results.result_for("broken-fix"),
+            Some(Ok(CheckStatus::Unrecoverable(UnrecoverableCheckStatus::FailedRecovery {
+                check,
+                error,
+            }))) if check == "broken" && error.starts_with("Kubernetes config error: ")
+        ));
+        assert_eq!(results.recoverable_failures().count(), 2);
+        assert_eq!(
+            receiver.try_recv().map(|progress| progress.step),
+            Ok("installing chart".to_string())
+        );
+    }
+
+    #[cfg(feature = "helm-checks")]
+    #[test]
+    fn test_fix_error_keeps_helm_output() {
+        let err = ClusterAutoFixError::ChartInstall(ChartInstallError::Other(
+            "Error: INSTALLATION FAILED: namespaces \"fluvio\" not found".to_string(),
+        ));
+
+        assert_eq!(
+            error_chain(&err),
+            "Chart Install error: An unknown error occurred: \
+             Error: INSTALLATION FAILED: namespaces \"fluvio\" not found"
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_continues_after_check_error() {
+        let ran = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let result = ClusterChecker::empty()
+            .with_check(ErroringCheck)
+            .with_check(RecordingCheck(ran.clone()))
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ClusterCheckError::PreCheckFlightFailure)
+        ));
+        assert!(ran.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_parallel_keeps_insertion_order() {
+        let (checker, max_running) = sleepy_checker(&[("slow", 200), ("medium", 100), ("fast", 0)]);
+
+        let results = checker.run_parallel().await;
+
+        assert_eq!(passed(&results), vec!["slow", "medium", "fast"]);
+        assert_eq!(max_running.load(SeqCst), 3);
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_parallel_concurrency_limit() {
+        let (checker, max_running) =
+            sleepy_checker(&[("one", 50), ("two", 50), ("three", 50), ("four", 50)]);
+
+        let results = checker.with_concurrency_limit(2).run_parallel().await;
+
+        assert_eq!(passed(&results), vec!["one", "two", "three", "four"]);
+        assert_eq!(max_running.load(SeqCst), 2);
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_parallel_survives_panic() {
+        let (checker, _) = sleepy_checker(&[("before", 50)]);
+        let checker = checker.with_check(PanickingCheck).with_check(ErroringCheck);
+
+        let results = checker.run_parallel().await;
+
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0].result, Ok(CheckStatus::Pass(_))));
+        match &results[1].result {
+            Err(ClusterCheckError::Other(message)) => {
+                assert_eq!(message, "check panicking panicked")
+            }
+            other => panic!("expected panic error, got {other:?}"),
+        }
+        assert!(matches!(
+            results[2].result,
+            Err(ClusterCheckError::ServiceCreateError(_))
+        ));
+        assert_eq!(results[2].name, "erroring");
+        assert!(matches!(
+            results.result_for("erroring"),
+            Some(Err(ClusterCheckError::ServiceCreateError(_)))
+        ));
+        assert!(results.result_for("missing").is_none());
+    }
+
+    /// Must not overlap other checks, reports how many checks were running
+    #[derive(Debug)]
+    struct ExclusiveCheck(Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl ClusterCheck for ExclusiveCheck {
+        fn name(&self) -> &str {
+            "exclusive"
+        }
+
+        fn label(&self) -> &str {
+            "Exclusive"
+        }
+
+        fn exclusive(&self) -> bool {
+            true
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            let running = self.0.fetch_add(1, SeqCst) + 1;
+            sleep(Duration::from_millis(20)).await;
+            self.0.fetch_sub(1, SeqCst);
+            Ok(CheckStatus::pass(format!("running: {running}")))
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_wait_parallel() {
+        let running = Arc::<AtomicUsize>::default();
+        let max_running = Arc::<AtomicUsize>::default();
+        let sleepy = |name, delay_ms| SleepyCheck {
+            name,
+            delay_ms,
+            running: running.clone(),
+            max_running: max_running.clone(),
+        };
+        let checker = ClusterChecker::empty()
+            .with_check(sleepy("slow", 200))
+            .with_check(ExclusiveCheck(running.clone()))
+            .with_check(sleepy("medium", 100))
+            .with_check(PanickingCheck)
+            .with_check(sleepy("fast", 0));
+
+        let results = checker.run_wait_parallel(2).await;
+
+        assert_eq!(results.len(), 5);
+        assert!(matches!(
+            results[3].result,
+            Err(ClusterCheckError::Other(_))
+        ));
+        assert_eq!(
+            passed(&results)
+                .into_iter()
+                .enumerate()
+                .filter(|(index, _)| *index != 3)
+                .map(|(_, message)| message)
+                .collect::<Vec<_>>(),
+            vec!["slow", "running: 1", "medium", "fast"]
+        );
+        assert_eq!(max_running.load(SeqCst), 2);
+    }
+
+    /// Takes 200ms, optionally advertising its own timeout
+    #[derive(Debug)]
+    struct TimeoutCheck(Option<Duration>);
+
+    #[async_trait]
+    impl ClusterCheck for TimeoutCheck {
+        fn name(&self) -> &str {
+            "timeout"
+        }
+
+        fn label(&self) -> &str {
+            "Timeout"
+        }
+
+        fn timeout(&self) -> Option<Duration> {
+            self.0
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            sleep(Duration::from_millis(200)).await;
+            Ok(CheckStatus::pass("finished"))
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_check_timeout() {
+        let results = ClusterChecker::empty()
+            .with_check(TimeoutCheck(None))
+            .with_check(TimeoutCheck(Some(Duration::from_secs(5))))
+            .with_default_timeout(Duration::from_millis(20))
+            .run_parallel()
+            .await;
+
+        match &results[0].result {
+            Err(ClusterCheckError::TimedOut { check, timeout }) => {
+                assert_eq!(check, "timeout");
+                assert_eq!(*timeout, Duration::from_millis(20));
+            }
+            other => panic!("expected timeout, got {other:?}"),
+        }
+        assert!(matches!(results[1].result, Ok(CheckStatus::Pass(_))));
+    }
+
+    #[fluvio_future::test]
+    async fn test_timeout_caps_advertised_timeout() {
+        let results = ClusterChecker::empty()
+            .with_check(TimeoutCheck(Some(Duration::from_secs(5))))
+            .with_check(TimeoutCheck(None))
+            .with_default_timeout(Duration::from_secs(10))
+            .with_timeout(Duration::from_millis(20))
+            .run_parallel()
+            .await;
+
+        for outcome in results {
+            match outcome.result {
+                Err(ClusterCheckError::TimedOut { timeout, .. }) => {
+                    assert_eq!(timeout, Duration::from_millis(20))
+                }
+                other => panic!("expected timeout, got {other:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_timeout_for_check() {
+        let short = Duration::from_secs(1);
+        let long = Duration::from_secs(60);
+        let advertised = TimeoutCheck(Some(long));
+        let silent = TimeoutCheck(None);
+
+        let none = CheckTimeouts::default();
+        assert_eq!(none.for_check(&advertised), Some(long));
+        assert_eq!(none.for_check(&silent), None);
+
+        let default = CheckTimeouts {
+            default: Some(short),
+            limit: None,
+        };
+        assert_eq!(default.for_check(&advertised), Some(long));
+        assert_eq!(default.for_check(&silent), Some(short));
+
+        let limit = CheckTimeouts {
+            default: None,
+            limit: Some(short),
+        };
+        assert_eq!(limit.for_check(&advertised), Some(short));
+        assert_eq!(limit.for_check(&silent), Some(short));
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_continues_after_check_timeout() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let result = ClusterChecker::empty()
+            .with_check(TimeoutCheck(Some(Duration::from_millis(20))))
+            .with_check(RecordingCheck(ran.clone()))
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ClusterCheckError::PreCheckFlightFailure)
+        ));
+        assert!(ran.load(SeqCst));
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_does_not_fail_on_skipped_check() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let result = ClusterChecker::empty()
+            .with_check(SkippingCheck)
+            .with_check(RecordingCheck(ran.clone()))
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+
+        assert!(result.unwrap());
+        assert!(ran.load(SeqCst));
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_parallel_reports_skipped_checks() {
+        let (checker, _) = sleepy_checker(&[("fast", 0)]);
+        let results = checker.with_check(SkippingCheck).run_parallel().await;
+
+        let skipped: Vec<_> = results
+            .skipped()
+            .map(|outcome| outcome.name.as_str())
+            .collect();
+        assert_eq!(skipped, vec!["skipping"]);
+        assert!(matches!(
+            results.result_for("skipping"),
+            Some(Ok(CheckStatus::Skipped(reason))) if reason == "not supported on this platform"
+        ));
+        assert!(matches!(
+            results.result_for("fast"),
+            Some(Ok(CheckStatus::Pass(_)))
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_minimum_severity() {
+        let result = ClusterChecker::empty()
+            .with_check(WarningCheck)
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(ClusterCheckError::PreCheckFlightFailure)
+        ));
+
+        let result = ClusterChecker::empty()
+            .with_check(WarningCheck)
+            .with_minimum_severity(CheckSeverity::Critical)
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+        assert!(result.unwrap());
+
+        let result = ClusterChecker::empty()
+            .with_check(WarningCheck)
+            .with_check(ErroringCheck)
+            .with_minimum_severity(CheckSeverity::Critical)
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+        assert!(matches!(
+            result,
+            Err(ClusterCheckError::PreCheckFlightFailure)
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_until_first_failure() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let checker = ClusterChecker::empty()
+            .with_check(SkippingCheck)
+            .with_check(ErroringCheck)
+            .with_check(RecordingCheck(ran.clone()));
+
+        let results = checker.run_until_first_failure().await;
+
+        let names: Vec<_> = results
+            .iter()
+            .map(|outcome| outcome.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["skipping", "erroring"]);
+        assert!(!ran.load(SeqCst), "checks after the failure are not run");
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_until_first_failure_ignores_minor_checks() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let checker = ClusterChecker::empty()
+            .with_check(WarningCheck)
+            .with_check(RecordingCheck(ran.clone()))
+            .with_minimum_severity(CheckSeverity::Critical);
+
+        let results = checker.run_until_first_failure().await;
+
+        assert_eq!(results.len(), 2);
+        assert!(ran.load(SeqCst));
+    }
+
+    /// Records that it ran, needs helm
+    #[derive(Debug)]
+    struct HelmUsingCheck(Arc<std::sync::atomic::AtomicBool>);
+
+    #[async_trait]
+    impl ClusterCheck for HelmUsingCheck {
+        fn name(&self) -> &str {
+            "helm-using"
+        }
+
+        fn label(&self) -> &str {
+            "Helm using"
+        }
+
+        fn required_components(&self) -> Vec<FluvioClusterComponent> {
+            vec![FluvioClusterComponent::HelmBinary]
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            self.0.store(true, SeqCst);
+            Ok(CheckStatus::pass("helm used"))
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_skips_checks_without_binary() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let result = ClusterChecker::empty()
+            .with_check(HelmUsingCheck(ran.clone()))
+            .with_check(HelmBinary::at(dir.path().join("helm")))
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(ClusterCheckError::PreCheckFlightFailure)
+        ));
+        assert!(!ran.load(SeqCst), "helm is not installed");
+
+        // a binary no check looks for doesn't hold back the checks needing it
+        let result = ClusterChecker::empty()
+            .with_check(HelmUsingCheck(ran.clone()))
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+        assert!(result.unwrap());
+        assert!(ran.load(SeqCst));
+
+        ran.store(false, SeqCst);
+        let result = ClusterChecker::empty()
+            .with_check(HelmBinary::at(dir.path().join("helm")))
+            .with_check(HelmUsingCheck(ran.clone()))
+            .skip(&["helm-binary"])
+            .expect("known check")
+            .run(&ProgressBarFactory::new(true), false)
+            .await;
+        assert!(result.unwrap());
+        assert!(ran.load(SeqCst));
+    }
+
+    #[fluvio_future::test]
+    async fn test_check_results_summary() {
+        let (checker, _) = sleepy_checker(&[("fast", 0)]);
+        let results = checker
+            .with_check(SkippingCheck)
+            .with_check(ErroringCheck)
+            .run_parallel()
+            .await;
+
+        assert_eq!(results.summary().passed, 1);
+        assert_eq!(results.summary().skipped, 1);
+        assert_eq!(results.summary().errors, 1);
+        assert!(results.has_failures());
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_with_progress_events() {
+        let (checker, _) = sleepy_checker(&[("first", 0), ("second", 0)]);
+
+        let events: Vec<_> = checker
+            .run_with_progress()
+            .into_stream()
+            .map(|event| match event {
+                CheckEvent::Started { name, index, total } => {
+                    format!("started {name} {index}/{total}")
+                }
+                CheckEvent::Completed {
+                    name,
+                    index,
+                    total,
+                    result,
+                } => format!(
+                    "completed {name} {index}/{total} {}",
+                    matches!(result, Ok(CheckStatus::Pass(_)))
+                ),
+            })
+            .collect()
+            .await;
+
+        assert_eq!(
+            events,
+            vec![
+                "started first 0/2",
+                "completed first 0/2 true",
+                "started second 1/2",
+                "completed second 1/2 true",
+            ]
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_with_results() {
+        let (checker, _) = sleepy_checker(&[("fast", 0)]);
+
+        let results: Vec<_> = checker
+            .with_check(ErroringCheck)
+            .run_with_results()
+            .collect()
+            .await;
+
+        assert_eq!(results.len(), 2);
+        assert!(matches!(results[0], Ok(CheckStatus::Pass(_))));
+        assert!(results[1].is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_render_check_progress() {
+        let (checker, _) = sleepy_checker(&[("fast", 0)]);
+
+        let results = render::render_check_progress(
+            checker.with_check(SkippingCheck).run_with_progress(),
+            &ProgressRenderer::default(),
+        )
+        .await;
+
+        assert_eq!(results.summary().passed, 1);
+        assert_eq!(results.summary().skipped, 1);
+        assert!(results.result_for("fast").is_some());
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_with_progress_cancelled() {
+        let token = StickyEvent::shared();
+        token.notify();
+        let checker = ClusterChecker::empty()
+            .with_check(SkippingCheck)
+            .with_check(ErroringCheck)
+            .with_cancellation_token(token);
+
+        let events: Vec<_> = checker.run_with_progress().into_stream().collect().await;
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            CheckEvent::Completed {
+                result: Err(ClusterCheckError::Cancelled),
+                ..
+            }
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_with_progress_abort() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let (checker, _) = sleepy_checker(&[("slow", 60_000)]);
+        let progress = checker
+            .with_check(RecordingCheck(ran.clone()))
+            .run_with_progress();
+
+        assert!(matches!(
+            progress.next_event().await,
+            Some(CheckEvent::Started { name, .. }) if name == "slow"
+        ));
+        let started = Instant::now();
+        progress.abort_handle().abort();
+
+        assert!(matches!(
+            progress.next_event().await,
+            Some(CheckEvent::Completed {
+                name,
+                result: Err(ClusterCheckError::Cancelled),
+                ..
+            }) if name == "slow"
+        ));
+        assert!(progress.next_event().await.is_none());
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(!ran.load(SeqCst));
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_with_progress_dropped() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let (checker, _) = sleepy_checker(&[("slow", 100)]);
+        let progress = checker
+            .with_check(RecordingCheck(ran.clone()))
+            .run_with_progress();
+
+        assert!(matches!(
+            progress.next_event().await,
+            Some(CheckEvent::Started { .. })
+        ));
+        drop(progress);
+        sleep(Duration::from_millis(300)).await;
+
+        assert!(!ran.load(SeqCst));
+    }
+
+    #[fluvio_future::test]
+    async fn test_missing_kube_context_fails_every_check() {
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let checker = ClusterChecker::empty()
+            .with_check(RecordingCheck(ran.clone()))
+            .with_check(SkippingCheck)
+            .with_kube_context("fluvio-test-missing-context");
+
+        for results in [
+            checker.run_parallel().await,
+            checker.run_and_fix().await,
+            checker
+                .run_with_deadline(Instant::now() + Duration::from_secs(5))
+                .await,
+        ] {
+            assert_eq!(results.len(), 2);
+            assert!(results.iter().all(|outcome| matches!(
+                &outcome.result,
+                Ok(CheckStatus::Unrecoverable(UnrecoverableCheckStatus::KubeContextNotFound { name }))
+                    if name == "fluvio-test-missing-context"
+            )));
+        }
+        // the first check fails, so the run stops there
+        assert_eq!(checker.run_until_first_failure().await.len(), 1);
+        assert!(!ran.load(std::sync::atomic::Ordering::SeqCst));
+    }
+
+    /// Errors until it was performed `errors` times, then fails unrecoverably
+    #[derive(Debug)]
+    struct FlakyCheck {
+        errors: usize,
+        attempts: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl ClusterCheck for FlakyCheck {
+        fn name(&self) -> &str {
+            "flaky"
+        }
+
+        fn label(&self) -> &str {
+            "Flaky"
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            if self.attempts.fetch_add(1, SeqCst) < self.errors {
+                return Err(ClusterCheckError::K8ApiError(
+                    "connection reset".to_string(),
+                ));
+            }
+            Ok(CheckStatus::Unrecoverable(UnrecoverableCheckStatus::Other(
+                "flaky".to_string(),
+            )))
+        }
+    }
+
+    async fn run_flaky(errors: usize, max_attempts: u32) -> (CheckResults, usize) {
+        let attempts = Arc::<AtomicUsize>::default();
+        let results = ClusterChecker::empty()
+            .with_check(FlakyCheck {
+                errors,
+                attempts: attempts.clone(),
+            })
+            .with_retry(max_attempts, Duration::from_millis(1))
+            .run_parallel()
+            .await;
+        (results, attempts.load(SeqCst))
+    }
+
+    #[fluvio_future::test]
+    async fn test_retry_recovers_from_errors() {
+        let (results, attempts) = run_flaky(2, 3).await;
+
+        assert_eq!(attempts, 3);
+        assert!(matches!(
+            results[0].result,
+            Ok(CheckStatus::Unrecoverable(_))
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_retry_gives_up() {
+        let (results, attempts) = run_flaky(5, 3).await;
+
+        assert_eq!(attempts, 3);
+        assert!(matches!(
+            &results[0].result,
+            Err(ClusterCheckError::K8ApiError(message)) if message == "connection reset"
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_retry_not_on_failure() {
+        let (results, attempts) = run_flaky(0, 3).await;
+        assert_eq!(attempts, 1, "failures are not retried");
+        assert!(results[0].result.is_ok());
+
+        let (results, attempts) = run_flaky(1, 1).await;
+        assert_eq!(attempts, 1, "retrying is off by default");
+        assert!(results[0].result.is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_cancellation_between_checks() {
+        let token = StickyEvent::shared();
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let checker = ClusterChecker::empty()
+            .with_check(SkippingCheck)
+            .with_check(RecordingCheck(ran.clone()))
+            .with_cancellation_token(token.clone());
+
+        token.notify();
+        let results = checker.run_until_first_failure().await;
+
+        assert_eq!(results.len(), 1);
+        assert!(matches!(
+            results[0].result,
+            Err(ClusterCheckError::Cancelled)
+        ));
+        assert!(!ran.load(SeqCst));
+
+        let result = checker.run(&ProgressBarFactory::new(true), false).await;
+        assert!(matches!(result, Err(ClusterCheckError::Cancelled)));
+        assert!(!ran.load(SeqCst));
+    }
+
+    #[fluvio_future::test]
+    async fn test_cancellation_in_every_run_mode() {
+        let token = StickyEvent::shared();
+        let ran = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let checker = || {
+            ClusterChecker::empty()
+                .with_check(SkippingCheck)
+                .with_check(RecordingCheck(ran.clone()))
+                .with_cancellation_token(token.clone())
+        };
+        token.notify();
+
+        for results in [
+            checker().run_parallel().await,
+            checker().run_and_fix().await,
+            checker()
+                .run_with_deadline(Instant::now() + Duration::from_secs(5))
+                .await,
+            checker().run_wait_parallel(2).await,
+        ] {
+            assert_eq!(results.len(), 2);
+            assert!(results
+                .iter()
+                .all(|outcome| matches!(outcome.result, Err(ClusterCheckError::Cancelled))));
+        }
+        assert!(!ran.load(SeqCst));
+    }
+
+    #[fluvio_future::test]
+    async fn test_run_with_deadline() {
+        let (checker, _) = sleepy_checker(&[("fast", 0), ("slow", 60_000), ("late", 0)]);
+
+        let started = Instant::now();
+        let results = checker
+            .run_with_deadline(Instant::now() + Duration::from_millis(200))
+            .await;
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[0].result, Ok(CheckStatus::Pass(_))));
+        assert!(matches!(
+            &results[1].result,
+            Err(ClusterCheckError::TimedOut { check, timeout })
+                if check == "slow" && *timeout <= Duration::from_millis(200)
+        ));
+        assert!(matches!(
+            &results[2].result,
+            Err(ClusterCheckError::TimedOut { check, timeout })
+                if check == "late" && timeout.is_zero()
+        ));
+    }
+}
@@ -0,0 +1,362 @@
+//! Describes a set of checks as data so it can be loaded from configuration
+//!
+//! A [`CheckSelection`] starts from one of the builder presets, removes checks
+//! by name and adds checks created by a [`CheckRegistry`].
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::render::ProgressRenderer;
+
+use super::{CheckResult, CheckSeverity, CheckStatus, FluvioClusterComponent};
+use super::{ClusterCheck, ClusterChecker};
+use super::ActiveKubernetesCluster;
+use super::{LocalClusterCheck, LocalDiskSpace, PortAvailabilityCheck, ScConnectivity};
+#[cfg(feature = "helm-checks")]
+use super::HelmVersion;
+#[cfg(feature = "kubectl-checks")]
+use super::openshift::OpenShiftScc;
+use super::{K8Version, CreateServicePermission, CreateCrdPermission, CreateServiceAccountPermission};
+use super::CreateBatchPermission;
+use super::{HelmBinary, KubectlBinary, NamespaceCheck, NodeReadiness};
+use super::{InClusterPermission, LoadBalancerCheck, TlsSecrets};
+
+/// The builder preset a [`CheckSelection`] starts from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckPreset {
+    /// [`ClusterChecker::with_preflight_checks`]
+    #[default]
+    Preflight,
+    /// [`ClusterChecker::with_k8_checks`]
+    K8,
+    /// [`ClusterChecker::with_local_checks`]
+    Local,
+    /// checks needed before upgrading an existing installation
+    Upgrade,
+    /// [`ClusterChecker::with_uninstall_checks`]
+    Uninstall,
+    /// [`ClusterChecker::with_post_install_checks`]
+    PostInstall,
+    /// [`ClusterChecker::for_in_cluster`]
+    InCluster,
+}
+
+impl CheckPreset {
+    pub(crate) fn checker(&self) -> ClusterChecker {
+        let checker = ClusterChecker::empty();
+        match self {
+            Self::Preflight => checker.with_preflight_checks(),
+            Self::K8 | Self::Upgrade => checker.with_k8_checks(),
+            Self::Local => checker.with_local_checks(),
+            Self::Uninstall => checker.with_uninstall_checks(),
+            Self::PostInstall => checker.with_post_install_checks(),
+            Self::InCluster => ClusterChecker::for_in_cluster(),
+        }
+    }
+}
+
+/// A preset with checks removed or added by name
+///
+/// ```
+/// # use fluvio_cluster::{CheckPreset, CheckSelection};
+/// let selection: CheckSelection = serde_json::from_str(
+///     r#"{ "preset": "k8", "exclude": ["helm-version"] }"#,
+/// ).unwrap();
+/// assert_eq!(selection.preset, CheckPreset::K8);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct CheckSelection {
+    /// checks to start from
+    pub preset: CheckPreset,
+    /// names of preset checks to leave out
+    pub exclude: Vec<String>,
+    /// names of registered checks to add
+    pub include_custom: Vec<String>,
+}
+
+type CheckFactory = Box<dyn Fn() -> Box<dyn ClusterCheck> + Send + Sync>;
+
+/// Named check factories used to resolve a [`CheckSelection`]
+///
+/// The default registry knows every built-in check that can be created
+/// without configuration; embedders can add their own with [`register`].
+///
+/// [`register`]: CheckRegistry::register
+pub struct CheckRegistry {
+    factories: BTreeMap<String, CheckFactory>,
+}
+
+impl CheckRegistry {
+    /// A registry without any checks
+    pub fn empty() -> Self {
+        Self {
+            factories: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `factory` under `name`, replacing any previous check with that name
+    pub fn register<F, C>(&mut self, name: impl Into<String>, factory: F) -> &mut Self
+    where
+        F: Fn() -> C + Send + Sync + 'static,
+        C: ClusterCheck,
+    {
+        self.factories
+            .insert(name.into(), Box::new(move || Box::new(factory())));
+        self
+    }
+
+    /// Whether a check is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// Names of all registered checks, sorted
+    pub fn names(&self) -> Vec<String> {
+        self.factories.keys().cloned().collect()
+    }
+
+    pub(crate) fn create(&self, name: &str) -> Option<Box<dyn ClusterCheck>> {
+        self.factories.get(name).map(|factory| factory())
+    }
+}
+
+impl Default for CheckRegistry {
+    fn default() -> Self {
+        let mut registry = Self::empty();
+        registry.register("kubectl-binary", KubectlBinary::default);
+        registry.register("helm-binary", HelmBinary::default);
+        registry.register("kubernetes-cluster", ActiveKubernetesCluster::default);
+        registry.register("local-cluster", LocalClusterCheck::default);
+        registry.register("port-availability", PortAvailabilityCheck::default);
+        registry.register("local-disk-space", LocalDiskSpace::default);
+        registry.register("sc-connectivity", ScConnectivity::default);
+        #[cfg(feature = "helm-checks")]
+        registry.register("helm-version", HelmVersion::default);
+        registry.register("kubernetes-version", K8Version::default);
+        registry.register("node-readiness", NodeReadiness::default);
+        registry.register("namespace", NamespaceCheck::default);
+        registry.register("in-cluster-permission", InClusterPermission::default);
+        registry.register("load-balancer", LoadBalancerCheck::default);
+        registry.register("tls-secrets", TlsSecrets::default);
+        registry.register("service-permission", CreateServicePermission::default);
+        registry.register("crd-permission", CreateCrdPermission::default);
+        registry.register("batch-permission", CreateBatchPermission::default);
+        registry.register(
+            "service-account-permission",
+            CreateServiceAccountPermission::default,
+        );
+        #[cfg(feature = "kubectl-checks")]
+        registry.register("openshift-scc", OpenShiftScc::default);
+        registry
+    }
+}
+
+/// Stands in for a check left out with [`ClusterChecker::skip`] or [`ClusterChecker::only`]
+///
+/// Reports [`CheckStatus::Skipped`] without performing the check, so it
+/// still shows up in the results. It requires no components, a skipped check
+/// can't fail because of a check it depends on.
+#[derive(Debug)]
+pub(crate) struct SkippedCheck {
+    check: Box<dyn ClusterCheck>,
+    reason: String,
+}
+
+impl SkippedCheck {
+    pub(crate) fn new(check: Box<dyn ClusterCheck>, reason: impl Into<String>) -> Self {
+        Self {
+            check,
+            reason: reason.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for SkippedCheck {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        Ok(CheckStatus::skipped(self.reason.clone()))
+    }
+
+    fn component(&self) -> Option<FluvioClusterComponent> {
+        self.check.component()
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn severity(&self) -> CheckSeverity {
+        self.check.severity()
+    }
+
+    fn name(&self) -> &str {
+        self.check.name()
+    }
+
+    fn label(&self) -> &str {
+        self.check.label()
+    }
+
+    fn description(&self) -> &str {
+        self.check.description()
+    }
+}
+
+impl fmt::Debug for CheckRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckRegistry")
+            .field("checks", &self.names())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use crate::render::ProgressRenderer;
+    use crate::check::{CheckResult, CheckStatus, ClusterCheckError};
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct CustomCheck;
+
+    #[async_trait]
+    impl ClusterCheck for CustomCheck {
+        fn name(&self) -> &str {
+            "custom"
+        }
+
+        fn label(&self) -> &str {
+            "Custom check"
+        }
+
+        async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+            Ok(CheckStatus::pass("custom check passed"))
+        }
+    }
+
+    #[cfg(all(feature = "helm-checks", feature = "kubectl-checks"))]
+    fn check_names(checker: &ClusterChecker) -> Vec<&str> {
+        checker.checks.iter().map(|check| check.name()).collect()
+    }
+
+    #[cfg(all(feature = "helm-checks", feature = "kubectl-checks"))]
+    #[test]
+    fn test_selection_from_toml() {
+        let selection: CheckSelection = toml::from_str(
+            r#"
+            preset = "preflight"
+            exclude = ["helm-version", "crd-permission"]
+            include_custom = ["custom"]
+            "#,
+        )
+        .expect("selection");
+
+        let mut registry = CheckRegistry::default();
+        registry.register("custom", || CustomCheck);
+
+        let checker = ClusterChecker::from_selection(&selection, &registry).expect("checker");
+        assert_eq!(
+            check_names(&checker),
+            vec![
+                "kubectl-binary",
+                "helm-binary",
+                "kubernetes-cluster",
+                "kubernetes-version",
+                "node-readiness",
+                "namespace",
+                "service-permission",
+                "service-account-permission",
+                "openshift-scc",
+                "load-balancer",
+                "custom",
+            ]
+        );
+    }
+
+    #[cfg(all(feature = "helm-checks", feature = "kubectl-checks"))]
+    #[test]
+    fn test_in_cluster_selection() {
+        let selection: CheckSelection =
+            toml::from_str(r#"preset = "incluster""#).expect("selection");
+
+        let checker =
+            ClusterChecker::from_selection(&selection, &CheckRegistry::default()).expect("checker");
+        assert_eq!(
+            check_names(&checker),
+            vec![
+                "kubectl-binary",
+                "openshift-scc",
+                "helm-binary",
+                "kubernetes-cluster",
+                "kubernetes-version",
+                "node-readiness",
+                "namespace",
+                "helm-version",
+                "in-cluster-permission",
+                "load-balancer",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selection_defaults_to_preflight() {
+        let selection: CheckSelection = toml::from_str("").expect("selection");
+        assert_eq!(selection, CheckSelection::default());
+        assert_eq!(selection.preset, CheckPreset::Preflight);
+    }
+
+    #[test]
+    fn test_selection_unknown_check() {
+        let mut registry = CheckRegistry::default();
+        registry.register("custom", || CustomCheck);
+
+        let selection = CheckSelection {
+            preset: CheckPreset::Local,
+            exclude: vec![],
+            include_custom: vec!["custom".to_string(), "missing".to_string()],
+        };
+        let err = ClusterChecker::from_selection(&selection, &registry)
+            .expect_err("missing is not registered");
+        match err {
+            ClusterCheckError::UnknownCheck { name, known } => {
+                assert_eq!(name, "missing");
+                assert!(known.contains(&"custom".to_string()));
+                assert!(known.contains(&"local-cluster".to_string()));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+
+        let selection = CheckSelection {
+            exclude: vec!["missing".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            ClusterChecker::from_selection(&selection, &registry),
+            Err(ClusterCheckError::UnknownCheck { .. })
+        ));
+    }
+
+    #[test]
+    fn test_builtin_checks_describe_themselves() {
+        let registry = CheckRegistry::default();
+        for name in registry.names() {
+            let check = registry.create(&name).expect("registered");
+            assert_eq!(check.name(), name);
+            assert_ne!(
+                check.description(),
+                check.label(),
+                "{name} has no description"
+            );
+        }
+
+        assert_eq!(CustomCheck.description(), "Custom check");
+    }
+}
@@ -0,0 +1,490 @@
+//! Checks that the Fluvio system chart is installed and up to date
+//!
+//! A missing or outdated chart is auto-fixable: the fixes install or upgrade
+//! it in the current kube context.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use semver::Version;
+use tracing::debug;
+
+use crate::charts::{ChartConfig, ChartInstallError, ChartInstaller, SYS_CHART_NAME};
+use crate::render::ProgressRenderer;
+
+use super::chart;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::{
+    CheckResult, CheckSeverity, CheckStatus, ClusterAutoFix, ClusterAutoFixError, ClusterCheck,
+    FixProgress, FixProgressReporter, FluvioClusterComponent, RecoverableCheck,
+    UnrecoverableCheckStatus,
+};
+
+/// Checks that the Fluvio system chart is installed at `platform_version`
+///
+/// A missing chart is installed and an older one upgraded by the fixes of
+/// the check, in the current kube context only.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ClusterChecker, SysChartCheck};
+/// # use fluvio_cluster::charts::ChartConfig;
+/// let config = ChartConfig::sys_builder().build().expect("sys chart config");
+/// let check = SysChartCheck::new(config, semver::Version::new(0, 11, 0));
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+#[derive(Debug)]
+pub struct SysChartCheck {
+    config: ChartConfig,
+    platform_version: Version,
+    /// only look for the system chart here, `None` for every namespace
+    namespace: Option<String>,
+    /// older charts can't be upgraded in place, `None` to upgrade any version
+    min_chart_version: Option<Version>,
+    /// charts at or above this version are kept, `None` to upgrade any other version
+    required_version: Option<Version>,
+    /// helm is run against this context, `None` for the current one
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+impl SysChartCheck {
+    /// Installs the chart described by `config` at `platform_version` if it is missing
+    pub fn new(config: ChartConfig, platform_version: Version) -> Self {
+        Self {
+            config,
+            platform_version,
+            namespace: None,
+            min_chart_version: None,
+            required_version: None,
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+
+    /// Keeps an installed chart at or above `version`, older ones are upgraded
+    pub fn with_required_version(mut self, version: Version) -> Self {
+        self.required_version = Some(version);
+        self
+    }
+
+    /// Status of the system charts installed in the namespace of this check
+    async fn chart_status(&self) -> CheckResult {
+        // check installed system chart version
+        let sys_charts = match chart::installed_releases(
+            &*self.env,
+            SYS_CHART_NAME,
+            self.namespace.as_deref(),
+            self.kube_context.as_deref(),
+        )
+        .await
+        {
+            Ok(charts) => charts,
+            Err(helm_error) => {
+                debug!(?helm_error, "helm client error");
+                return Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::HelmClientError,
+                ));
+            }
+        };
+        debug!(charts = sys_charts.len(), "sys charts count");
+        self.releases_status(&sys_charts)
+    }
+
+    /// Status of the system chart releases `sys_charts`
+    fn releases_status(&self, sys_charts: &[chart::HelmRelease]) -> CheckResult {
+        match (self.fixable_status(sys_charts)?, &self.kube_context) {
+            // the chart installer has no way to target another context
+            (CheckStatus::AutoFixableError { message, .. }, Some(context)) => Ok(
+                CheckStatus::Unrecoverable(UnrecoverableCheckStatus::SysChartNeedsCurrentContext {
+                    context: context.clone(),
+                    reason: message,
+                }),
+            ),
+            (status, _) => Ok(status),
+        }
+    }
+
+    /// Status of `sys_charts`, fixed by installing or upgrading in the current context
+    fn fixable_status(&self, sys_charts: &[chart::HelmRelease]) -> CheckResult {
+        if sys_charts.is_empty() {
+            Ok(CheckStatus::AutoFixableError {
+                message: format!(
+                    "System chart not installed, installing version {}",
+                    self.platform_version
+                ),
+                fixer: Box::new(InstallSysChart {
+                    config: self.config.clone(),
+                    platform_version: self.platform_version.clone(),
+                    env: self.env.clone(),
+                }),
+            })
+        } else if sys_charts.len() > 1 {
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::MultipleSystemCharts,
+            ))
+        } else {
+            let install_chart = sys_charts.get(0).unwrap();
+            debug!(app_version = %install_chart.app_version,"Sys Chart Version");
+            self.installed_chart_status(&install_chart.app_version)
+        }
+    }
+
+    /// Status of a single installed system chart with app version `app_version`
+    fn installed_chart_status(&self, app_version: &str) -> CheckResult {
+        let existing_platform_version = Version::parse(app_version)?;
+        if let Some(min_chart_version) = &self.min_chart_version {
+            if existing_platform_version < *min_chart_version {
+                return Ok(CheckStatus::Unrecoverable(
+                    UnrecoverableCheckStatus::IncompatibleSystemChart {
+                        installed: existing_platform_version.to_string(),
+                        required: min_chart_version.to_string(),
+                    },
+                ));
+            }
+        }
+        if existing_platform_version == self.platform_version {
+            return Ok(CheckStatus::pass("Fluvio system charts are installed"));
+        }
+        let message = match &self.required_version {
+            Some(required) if existing_platform_version >= *required => {
+                return Ok(CheckStatus::pass(format!(
+                    "Fluvio system chart {existing_platform_version} is installed"
+                )));
+            }
+            Some(required) => RecoverableCheck::OutdatedSystemChart {
+                installed: existing_platform_version.to_string(),
+                required: required.to_string(),
+            }
+            .to_string(),
+            None => format!(
+                "System chart version {} installed, upgrading to version {}",
+                existing_platform_version, self.platform_version
+            ),
+        };
+        Ok(CheckStatus::AutoFixableError {
+            message,
+            fixer: Box::new(UpgradeSysChart {
+                config: self.config.clone(),
+                platform_version: self.platform_version.clone(),
+                env: self.env.clone(),
+            }),
+        })
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for SysChartCheck {
+    /// Check that the system chart is installed
+    /// This looks in every namespace unless one was set with
+    /// [`ClusterChecker::with_namespace`](super::ClusterChecker::with_namespace)
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        debug!(namespace = ?self.namespace, "performing sys chart check");
+        self.chart_status().await
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![
+            FluvioClusterComponent::Helm,
+            FluvioClusterComponent::Kubernetes,
+        ]
+    }
+
+    fn component(&self) -> Option<FluvioClusterComponent> {
+        Some(FluvioClusterComponent::SysChart)
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::kubectl_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn name(&self) -> &str {
+        "sys-chart"
+    }
+
+    fn label(&self) -> &str {
+        "Fluvio Sys Chart"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = Some(namespace.to_string());
+        self.config.namespace = namespace.to_string();
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn description(&self) -> &str {
+        "The Fluvio system chart is installed and up to date"
+    }
+
+    fn severity(&self) -> CheckSeverity {
+        CheckSeverity::Warning
+    }
+
+    fn set_required_version(&mut self, component: &FluvioClusterComponent, version: &Version) {
+        if *component == FluvioClusterComponent::SysChart {
+            self.min_chart_version = Some(version.clone());
+        }
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Installs the system chart, the helm chart installer or a fake cluster in tests
+///
+/// [`CheckEnvironment::sys_chart_installer`] hands one to the fixes of [`SysChartCheck`].
+pub trait SysChartInstaller: Send + Sync {
+    fn install(&self) -> Result<(), ChartInstallError>;
+
+    fn upgrade(&self) -> Result<(), ChartInstallError>;
+
+    fn is_installed(&self) -> Result<bool, ChartInstallError>;
+}
+
+impl SysChartInstaller for ChartInstaller {
+    fn install(&self) -> Result<(), ChartInstallError> {
+        ChartInstaller::install(self)
+    }
+
+    fn upgrade(&self) -> Result<(), ChartInstallError> {
+        ChartInstaller::upgrade(self)
+    }
+
+    fn is_installed(&self) -> Result<bool, ChartInstallError> {
+        ChartInstaller::is_installed(self)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct InstallSysChart {
+    config: ChartConfig,
+    platform_version: Version,
+    env: Environment,
+}
+
+#[async_trait]
+impl ClusterAutoFix for InstallSysChart {
+    async fn attempt_fix(
+        &self,
+        progress: &FixProgressReporter<'_>,
+    ) -> Result<String, ClusterAutoFixError> {
+        debug!(
+            "Fixing by installing Fluvio sys chart with config: {:#?}",
+            &self.config
+        );
+        progress.report(FixProgress::new("preparing helm client", Some(0)));
+        let sys_installer = self.env.sys_chart_installer(&self.config)?;
+        progress.report(FixProgress::new("installing chart", Some(20)));
+        sys_installer.install()?;
+
+        // re-run the sys chart check on the namespace just installed into
+        progress.report(FixProgress::new("checking installed chart", Some(90)));
+        let mut check = SysChartCheck::new(self.config.clone(), self.platform_version.clone());
+        check.set_namespace(&self.config.namespace);
+        check.env = self.env.clone();
+        match check.chart_status().await {
+            Ok(CheckStatus::Pass(_) | CheckStatus::PassWithData(..)) => {}
+            Ok(status) => {
+                return Err(ClusterAutoFixError::NotFixed {
+                    check: check.name().to_string(),
+                    reason: status_reason(&status),
+                })
+            }
+            Err(err) => {
+                return Err(ClusterAutoFixError::NotFixed {
+                    check: check.name().to_string(),
+                    reason: err.to_string(),
+                })
+            }
+        }
+
+        Ok(format!(
+            "Fluvio Sys chart {} is installed",
+            self.platform_version
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct UpgradeSysChart {
+    config: ChartConfig,
+    platform_version: Version,
+    env: Environment,
+}
+
+#[async_trait]
+impl ClusterAutoFix for UpgradeSysChart {
+    async fn attempt_fix(
+        &self,
+        progress: &FixProgressReporter<'_>,
+    ) -> Result<String, ClusterAutoFixError> {
+        debug!(
+            "Fixing by updating Fluvio sys chart with config: {:#?}",
+            &self.config
+        );
+
+        progress.report(FixProgress::new("preparing helm client", Some(0)));
+        let sys_installer = self.env.sys_chart_installer(&self.config)?;
+        progress.report(FixProgress::new("upgrading chart", Some(20)));
+        sys_installer.upgrade()?;
+        progress.report(FixProgress::new("waiting for release", Some(90)));
+        if !sys_installer.is_installed()? {
+            debug!("sys chart release not listed right after upgrade");
+        }
+
+        Ok(format!(
+            "Fluvio sys chart is upgraded to: {}",
+            self.platform_version
+        ))
+    }
+}
+
+/// What a failing `status` reports, without the leading mark
+fn status_reason(status: &CheckStatus) -> String {
+    match status {
+        CheckStatus::Pass(message)
+        | CheckStatus::PassWithData(message, _)
+        | CheckStatus::AutoFixableError { message, .. }
+        | CheckStatus::Skipped(message) => message.clone(),
+        CheckStatus::Unrecoverable(err) => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::ClusterChecker;
+
+    use super::*;
+
+    /// Installs the sys chart into a real cluster, so it only runs when
+    /// `FLV_TEST_SYS_CHART_FIX_NAMESPACE` names a namespace without one
+    #[fluvio_future::test]
+    async fn test_install_sys_chart_fix() {
+        let Ok(namespace) = std::env::var("FLV_TEST_SYS_CHART_FIX_NAMESPACE") else {
+            return;
+        };
+        let config = ChartConfig::sys_builder()
+            .namespace(&namespace)
+            .build()
+            .expect("chart config");
+        let version = Version::parse(include_str!("../../../../VERSION").trim()).expect("version");
+        let mut check = SysChartCheck::new(config, version);
+        check.set_namespace(&namespace);
+
+        let results = ClusterChecker::empty()
+            .with_check(check)
+            .run_and_fix()
+            .await;
+
+        assert!(matches!(
+            results.result_for("sys-chart"),
+            Some(Ok(CheckStatus::AutoFixableError { .. }))
+        ));
+        assert!(
+            matches!(
+                results.result_for("sys-chart-fix"),
+                Some(Ok(CheckStatus::Pass(_)))
+            ),
+            "{results:?}"
+        );
+    }
+
+    #[test]
+    fn test_sys_chart_min_version() {
+        let config = ChartConfig::sys_builder().build().expect("chart config");
+        let mut check = SysChartCheck::new(config, Version::new(0, 11, 0));
+        assert!(matches!(
+            check.installed_chart_status("0.9.0"),
+            Ok(CheckStatus::AutoFixableError { .. })
+        ));
+
+        check.set_required_version(&FluvioClusterComponent::SysChart, &Version::new(0, 10, 0));
+        match check.installed_chart_status("0.9.0") {
+            Ok(CheckStatus::Unrecoverable(UnrecoverableCheckStatus::IncompatibleSystemChart {
+                installed,
+                required,
+            })) => {
+                assert_eq!(installed, "0.9.0");
+                assert_eq!(required, "0.10.0");
+            }
+            other => panic!("expected incompatible chart, got {other:?}"),
+        }
+        assert!(matches!(
+            check.installed_chart_status("0.10.0"),
+            Ok(CheckStatus::AutoFixableError { .. })
+        ));
+        assert!(matches!(
+            check.installed_chart_status("0.11.0"),
+            Ok(CheckStatus::Pass(_))
+        ));
+    }
+
+    #[test]
+    fn test_sys_chart_required_version() {
+        let config = ChartConfig::sys_builder().build().expect("chart config");
+        let check = SysChartCheck::new(config, Version::new(0, 11, 0))
+            .with_required_version(Version::new(0, 11, 0));
+
+        match check.installed_chart_status("0.10.2") {
+            Ok(CheckStatus::AutoFixableError { message, .. }) => assert_eq!(
+                message,
+                "Fluvio system chart 0.10.2 is older than the required 0.11.0"
+            ),
+            other => panic!("expected outdated chart, got {other:?}"),
+        }
+        assert!(matches!(
+            check.installed_chart_status("0.11.0"),
+            Ok(CheckStatus::Pass(_))
+        ));
+        // a newer chart is not downgraded
+        assert!(matches!(
+            check.installed_chart_status("0.12.0"),
+            Ok(CheckStatus::Pass(_))
+        ));
+    }
+
+    #[test]
+    fn test_sys_chart_pinned_context() {
+        let release = |app_version: &str| chart::HelmRelease {
+            name: "fluvio-sys".to_string(),
+            namespace: "default".to_string(),
+            app_version: app_version.to_string(),
+        };
+        let config = ChartConfig::sys_builder().build().expect("chart config");
+        let mut check = SysChartCheck::new(config, Version::new(0, 11, 0));
+        assert!(matches!(
+            check.releases_status(&[]),
+            Ok(CheckStatus::AutoFixableError { .. })
+        ));
+
+        check.set_kube_context("kind-fluvio");
+        match check.releases_status(&[]) {
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::SysChartNeedsCurrentContext { context, reason },
+            )) => {
+                assert_eq!(context, "kind-fluvio");
+                assert_eq!(
+                    reason,
+                    "System chart not installed, installing version 0.11.0"
+                );
+            }
+            other => panic!("expected a chart it can't install, got {other:?}"),
+        }
+        assert!(matches!(
+            check.releases_status(&[release("0.10.0")]),
+            Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::SysChartNeedsCurrentContext { .. }
+            ))
+        ));
+        assert!(matches!(
+            check.releases_status(&[release("0.11.0")]),
+            Ok(CheckStatus::Pass(_))
+        ));
+    }
+}
@@ -0,0 +1,190 @@
+//! Checks and fixers with scripted outcomes for the unit tests of the checker
+
+use std::sync::atomic::Ordering::SeqCst;
+use std::sync::atomic::{AtomicBool, AtomicUsize};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use fluvio_future::timer::sleep;
+
+use crate::render::ProgressRenderer;
+
+use super::{
+    CheckResult, CheckResults, CheckSeverity, CheckStatus, ClusterAutoFix, ClusterAutoFixError,
+    ClusterCheck, ClusterCheckError, ClusterChecker, CommandOutput, FixProgressReporter,
+    UnrecoverableCheckStatus,
+};
+
+#[derive(Debug)]
+pub(super) struct ScriptedFixer(pub(super) Vec<&'static str>);
+
+#[async_trait]
+impl ClusterAutoFix for ScriptedFixer {
+    async fn attempt_fix(
+        &self,
+        progress: &FixProgressReporter<'_>,
+    ) -> Result<String, ClusterAutoFixError> {
+        for step in &self.0 {
+            progress.step(*step);
+        }
+        Ok("fixed".to_string())
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct ErroringCheck;
+
+#[async_trait]
+impl ClusterCheck for ErroringCheck {
+    fn name(&self) -> &str {
+        "erroring"
+    }
+
+    fn label(&self) -> &str {
+        "Erroring"
+    }
+
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        Err(ClusterCheckError::ServiceCreateError(CommandOutput {
+            program: "kubectl".to_string(),
+            status: Some(1),
+            ..Default::default()
+        }))
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct RecordingCheck(pub(super) Arc<AtomicBool>);
+
+#[async_trait]
+impl ClusterCheck for RecordingCheck {
+    fn name(&self) -> &str {
+        "recording"
+    }
+
+    fn label(&self) -> &str {
+        "Recording"
+    }
+
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        self.0.store(true, SeqCst);
+        Ok(CheckStatus::pass("recorded"))
+    }
+}
+
+/// Sleeps for `delay_ms`, tracking how many checks run at the same time
+#[derive(Debug)]
+pub(super) struct SleepyCheck {
+    pub(super) name: &'static str,
+    pub(super) delay_ms: u64,
+    pub(super) running: Arc<AtomicUsize>,
+    pub(super) max_running: Arc<AtomicUsize>,
+}
+
+#[async_trait]
+impl ClusterCheck for SleepyCheck {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn label(&self) -> &str {
+        self.name
+    }
+
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let running = self.running.fetch_add(1, SeqCst) + 1;
+        self.max_running.fetch_max(running, SeqCst);
+        sleep(Duration::from_millis(self.delay_ms)).await;
+        self.running.fetch_sub(1, SeqCst);
+        Ok(CheckStatus::pass(self.name))
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct PanickingCheck;
+
+#[async_trait]
+impl ClusterCheck for PanickingCheck {
+    fn name(&self) -> &str {
+        "panicking"
+    }
+
+    fn label(&self) -> &str {
+        "Panicking"
+    }
+
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        panic!("check blew up")
+    }
+}
+
+/// A checker of [`SleepyCheck`]s, and the most of them that ran at the same time
+pub(super) fn sleepy_checker(delays: &[(&'static str, u64)]) -> (ClusterChecker, Arc<AtomicUsize>) {
+    let running = Arc::<AtomicUsize>::default();
+    let max_running = Arc::<AtomicUsize>::default();
+    let checker = delays
+        .iter()
+        .fold(ClusterChecker::empty(), |checker, &(name, delay_ms)| {
+            checker.with_check(SleepyCheck {
+                name,
+                delay_ms,
+                running: running.clone(),
+                max_running: max_running.clone(),
+            })
+        });
+    (checker, max_running)
+}
+
+/// The message of each passed check, the debug output of any other result
+pub(super) fn passed(results: &CheckResults) -> Vec<String> {
+    results
+        .iter()
+        .map(|outcome| match &outcome.result {
+            Ok(CheckStatus::Pass(message)) => message.clone(),
+            other => format!("{other:?}"),
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub(super) struct SkippingCheck;
+
+#[async_trait]
+impl ClusterCheck for SkippingCheck {
+    fn name(&self) -> &str {
+        "skipping"
+    }
+
+    fn label(&self) -> &str {
+        "Skipping"
+    }
+
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        Ok(CheckStatus::skipped("not supported on this platform"))
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct WarningCheck;
+
+#[async_trait]
+impl ClusterCheck for WarningCheck {
+    fn name(&self) -> &str {
+        "warning"
+    }
+
+    fn label(&self) -> &str {
+        "Warning"
+    }
+
+    fn severity(&self) -> CheckSeverity {
+        CheckSeverity::Warning
+    }
+
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        Ok(CheckStatus::Unrecoverable(UnrecoverableCheckStatus::Other(
+            "not quite right".to_string(),
+        )))
+    }
+}
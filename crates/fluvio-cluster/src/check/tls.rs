@@ -0,0 +1,448 @@
+//! Checks the TLS secrets the SC is started with
+//!
+//! With TLS enabled, the SC mounts its certificate and key, and the CA
+//! certificate, from Kubernetes secrets. A missing secret or a bad certificate
+//! doesn't fail the install, the SC pod crash-loops afterwards. Reading the
+//! secrets reports it before the chart is installed.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+use x509_parser::parse_x509_certificate;
+use x509_parser::pem::parse_x509_pem;
+
+use fluvio_types::defaults::{TLS_CA_SECRET_NAME, TLS_SERVER_SECRET_NAME};
+use k8_client::meta_client::MetadataClient;
+use k8_client::K8Client;
+use k8_types::{Crd, CrdNames, Header, InputObjectMeta, Spec, Status};
+
+use crate::render::ProgressRenderer;
+use crate::DEFAULT_NAMESPACE;
+
+use super::context::k8_api_error;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, ClusterCheckError, FluvioClusterComponent,
+    UnrecoverableCheckStatus,
+};
+
+const SECRET_API: Crd = Crd {
+    group: "core",
+    version: "v1",
+    names: CrdNames {
+        kind: "Secret",
+        plural: "secrets",
+        singular: "secret",
+    },
+};
+
+const TLS_CERT: &str = "tls.crt";
+const TLS_KEY: &str = "tls.key";
+const CA_CERT: &str = "ca.crt";
+
+/// A secret, only looked up for its data
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SecretSpec {}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SecretStatus {}
+
+/// The data of a secret is next to its metadata, not in a spec
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct SecretData {
+    /// Base64 encoded value of each key
+    #[serde(default)]
+    pub data: BTreeMap<String, String>,
+}
+
+impl Spec for SecretSpec {
+    type Status = SecretStatus;
+    type Header = SecretData;
+
+    fn metadata() -> &'static Crd {
+        &SECRET_API
+    }
+}
+
+impl Status for SecretStatus {}
+
+impl Header for SecretData {}
+
+/// Something that can read secrets, the Kubernetes client or a fake in tests
+#[async_trait]
+pub trait SecretSource: Send + Sync {
+    /// Data of secret `name`, `None` if it does not exist
+    async fn secret_data(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<Option<BTreeMap<String, String>>, ClusterCheckError>;
+}
+
+#[async_trait]
+impl SecretSource for K8Client {
+    async fn secret_data(
+        &self,
+        namespace: &str,
+        name: &str,
+    ) -> Result<Option<BTreeMap<String, String>>, ClusterCheckError> {
+        let secret = self
+            .retrieve_item::<SecretSpec, _>(&InputObjectMeta::named(name, namespace))
+            .await
+            .map_err(k8_api_error)?;
+        Ok(secret.map(|secret| secret.header.data))
+    }
+}
+
+/// A secret the SC mounts and the keys it reads from it
+#[derive(Debug, Clone, Copy)]
+struct ExpectedSecret<'a> {
+    name: &'a str,
+    keys: &'static [&'static str],
+    /// key holding the PEM encoded certificate
+    certificate: &'static str,
+}
+
+/// Checks that the TLS secrets of the SC exist and hold a certificate that is currently valid
+///
+/// The server secret must have `tls.crt` and `tls.key`, the CA secret
+/// `ca.crt`. [`ClusterChecker::with_tls_checks`] only adds it when TLS is
+/// enabled.
+///
+/// # Example
+///
+/// ```
+/// # use fluvio_cluster::{ClusterChecker, TlsSecrets};
+/// let check = TlsSecrets::new("fluvio", "fluvio-tls", "fluvio-ca");
+/// let checker = ClusterChecker::empty().with_check(check);
+/// ```
+///
+/// [`ClusterChecker::with_tls_checks`]: super::ClusterChecker::with_tls_checks
+#[derive(Debug, Clone)]
+pub struct TlsSecrets {
+    namespace: String,
+    server_secret: String,
+    ca_secret: String,
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+impl TlsSecrets {
+    /// Checks secrets `server_secret` and `ca_secret` in `namespace`
+    pub fn new(
+        namespace: impl Into<String>,
+        server_secret: impl Into<String>,
+        ca_secret: impl Into<String>,
+    ) -> Self {
+        Self {
+            namespace: namespace.into(),
+            server_secret: server_secret.into(),
+            ca_secret: ca_secret.into(),
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+
+    fn expected_secrets(&self) -> [ExpectedSecret<'_>; 2] {
+        [
+            ExpectedSecret {
+                name: &self.server_secret,
+                keys: &[TLS_CERT, TLS_KEY],
+                certificate: TLS_CERT,
+            },
+            ExpectedSecret {
+                name: &self.ca_secret,
+                keys: &[CA_CERT],
+                certificate: CA_CERT,
+            },
+        ]
+    }
+}
+
+impl Default for TlsSecrets {
+    fn default() -> Self {
+        Self::new(
+            DEFAULT_NAMESPACE,
+            TLS_SERVER_SECRET_NAME,
+            TLS_CA_SECRET_NAME,
+        )
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for TlsSecrets {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let client = self
+            .env
+            .client(self.kube_context.as_deref())
+            .map_err(ClusterCheckError::K8ApiError)?;
+        let now = chrono::Utc::now().timestamp();
+        check_secrets(
+            client.as_secret_source(),
+            &self.namespace,
+            &self.expected_secrets(),
+            now,
+        )
+        .await
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![FluvioClusterComponent::Kubernetes]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::client_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "tls-secrets"
+    }
+
+    fn label(&self) -> &str {
+        "TLS secrets"
+    }
+
+    fn description(&self) -> &str {
+        "The TLS secrets of the SC exist and hold a valid certificate"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = namespace.to_string();
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+/// Reads `secrets` in order, reporting the first one that can't be used
+///
+/// `now` is a Unix timestamp the certificates must be valid at.
+async fn check_secrets(
+    source: &dyn SecretSource,
+    namespace: &str,
+    secrets: &[ExpectedSecret<'_>],
+    now: i64,
+) -> CheckResult {
+    for secret in secrets {
+        let data = match source.secret_data(namespace, secret.name).await {
+            Ok(data) => data,
+            // the installer only creates secrets, reading them may not be allowed
+            Err(ClusterCheckError::K8ApiForbidden(message)) => {
+                debug!(%message, "not allowed to read secrets");
+                return Ok(CheckStatus::skipped(format!(
+                    "not allowed to read secrets in namespace {namespace}"
+                )));
+            }
+            Err(err) => return Err(err),
+        };
+        if let Some(reason) = secret_problem(data.as_ref(), secret, now) {
+            debug!(secret = secret.name, %reason, "unusable TLS secret");
+            return Ok(CheckStatus::Unrecoverable(
+                UnrecoverableCheckStatus::InvalidTlsSecret {
+                    secret: secret.name.to_string(),
+                    namespace: namespace.to_string(),
+                    reason,
+                },
+            ));
+        }
+    }
+    let names = secrets
+        .iter()
+        .map(|secret| secret.name)
+        .collect::<Vec<_>>()
+        .join(", ");
+    Ok(CheckStatus::pass(format!("TLS secrets {names} are valid")))
+}
+
+/// Why the data of a secret can't be used by the SC, `None` if it can
+fn secret_problem(
+    data: Option<&BTreeMap<String, String>>,
+    secret: &ExpectedSecret,
+    now: i64,
+) -> Option<String> {
+    let Some(data) = data else {
+        return Some("secret not found".to_string());
+    };
+    let missing = secret
+        .keys
+        .iter()
+        .filter(|key| !data.contains_key(**key))
+        .copied()
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        return Some(format!("missing {}", missing.join(", ")));
+    }
+
+    let key = secret.certificate;
+    let Ok(pem) = base64::engine::general_purpose::STANDARD.decode(&data[key]) else {
+        return Some(format!("{key} is not base64 encoded"));
+    };
+    let Ok((_, pem)) = parse_x509_pem(&pem) else {
+        return Some(format!("{key} is not a PEM encoded certificate"));
+    };
+    let certificate = match parse_x509_certificate(&pem.contents) {
+        Ok((_, certificate)) => certificate,
+        Err(err) => return Some(format!("{key} is not a valid certificate: {err}")),
+    };
+    let validity = certificate.validity();
+    if validity.not_before.timestamp() > now {
+        Some(format!("{key} is not valid before {}", validity.not_before))
+    } else if validity.not_after.timestamp() < now {
+        Some(format!("{key} expired on {}", validity.not_after))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::CheckSuggestion;
+
+    use super::*;
+
+    const SERVER_CERT: &str = include_str!("../../test-data/tls-server.crt");
+
+    /// 2030-01-01, while the test certificate is valid
+    const NOW: i64 = 1_893_456_000;
+
+    /// Secrets by name, unless every read is forbidden
+    struct FakeSecrets {
+        secrets: BTreeMap<&'static str, BTreeMap<String, String>>,
+        forbidden: bool,
+    }
+
+    #[async_trait]
+    impl SecretSource for FakeSecrets {
+        async fn secret_data(
+            &self,
+            namespace: &str,
+            name: &str,
+        ) -> Result<Option<BTreeMap<String, String>>, ClusterCheckError> {
+            assert_eq!(namespace, "fluvio");
+            if self.forbidden {
+                return Err(ClusterCheckError::K8ApiForbidden(
+                    "secrets is forbidden".to_string(),
+                ));
+            }
+            Ok(self.secrets.get(name).cloned())
+        }
+    }
+
+    fn encoded(value: &str) -> String {
+        base64::engine::general_purpose::STANDARD.encode(value)
+    }
+
+    fn valid_secrets() -> FakeSecrets {
+        let server = BTreeMap::from([
+            (TLS_CERT.to_string(), encoded(SERVER_CERT)),
+            (TLS_KEY.to_string(), encoded("key")),
+        ]);
+        let ca = BTreeMap::from([(CA_CERT.to_string(), encoded(SERVER_CERT))]);
+        FakeSecrets {
+            secrets: BTreeMap::from([("fluvio-tls", server), ("fluvio-ca", ca)]),
+            forbidden: false,
+        }
+    }
+
+    async fn status(source: &FakeSecrets, now: i64) -> CheckStatus {
+        let check = TlsSecrets::new("fluvio", "fluvio-tls", "fluvio-ca");
+        check_secrets(source, "fluvio", &check.expected_secrets(), now)
+            .await
+            .expect("status")
+    }
+
+    fn failure(status: CheckStatus) -> UnrecoverableCheckStatus {
+        match status {
+            CheckStatus::Unrecoverable(failure) => failure,
+            other => panic!("expected a failure, got {other:?}"),
+        }
+    }
+
+    #[fluvio_future::test]
+    async fn test_valid_secrets() {
+        assert!(matches!(
+            status(&valid_secrets(), NOW).await,
+            CheckStatus::Pass(message) if message == "TLS secrets fluvio-tls, fluvio-ca are valid"
+        ));
+    }
+
+    #[fluvio_future::test]
+    async fn test_missing_secret_and_key() {
+        let mut source = valid_secrets();
+        source.secrets.remove("fluvio-ca");
+        let missing = failure(status(&source, NOW).await);
+        assert_eq!(
+            missing.to_string(),
+            "TLS secret fluvio-ca is invalid: secret not found"
+        );
+        assert!(missing.suggestion().unwrap().contains("-n fluvio"));
+
+        let mut source = valid_secrets();
+        source
+            .secrets
+            .get_mut("fluvio-tls")
+            .unwrap()
+            .remove(TLS_KEY);
+        assert_eq!(
+            failure(status(&source, NOW).await).to_string(),
+            "TLS secret fluvio-tls is invalid: missing tls.key"
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_bad_certificate() {
+        let mut source = valid_secrets();
+        source
+            .secrets
+            .get_mut("fluvio-ca")
+            .unwrap()
+            .insert(CA_CERT.to_string(), encoded("not a certificate"));
+        assert_eq!(
+            failure(status(&source, NOW).await).to_string(),
+            "TLS secret fluvio-ca is invalid: ca.crt is not a PEM encoded certificate"
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_certificate_validity() {
+        // 2128, after the test certificate expired
+        let expired = failure(status(&valid_secrets(), 5_000_000_000).await).to_string();
+        assert!(
+            expired.starts_with("TLS secret fluvio-tls is invalid: tls.crt expired on"),
+            "{expired}"
+        );
+
+        // 2020-01-01, before it was issued
+        let not_yet_valid = failure(status(&valid_secrets(), 1_577_836_800).await).to_string();
+        assert!(
+            not_yet_valid.contains("tls.crt is not valid before"),
+            "{not_yet_valid}"
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_forbidden_is_skipped() {
+        let source = FakeSecrets {
+            secrets: BTreeMap::new(),
+            forbidden: true,
+        };
+        assert!(matches!(
+            status(&source, NOW).await,
+            CheckStatus::Skipped(_)
+        ));
+    }
+}
@@ -0,0 +1,268 @@
+//! Checks that an existing installation can be upgraded to the target version
+//!
+//! The preflight checks expect nothing to be installed yet, an upgrade
+//! expects the opposite. [`UpgradePathCheck`] makes sure there is a release
+//! to upgrade, that the target is not older than it, and that the jump does
+//! not cross a version that can't be upgraded to in place.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use semver::Version;
+use tracing::debug;
+
+use crate::charts::APP_CHART_NAME;
+use crate::render::ProgressRenderer;
+
+use super::chart;
+use super::environment::{CheckEnvironment, Environment};
+use super::identity::{self, ClusterIdentity};
+use super::{
+    CheckResult, CheckStatus, ClusterCheck, FluvioClusterComponent, FluvioInstallation,
+    UnrecoverableCheckStatus,
+};
+
+/// Versions that can only be installed fresh, with what changed in them
+///
+/// An upgrade from a release older than one of these to it, or past it, fails.
+const BREAKING_VERSIONS: &[(&str, &str)] = &[(
+    "0.10.0",
+    "clusters from 0.9 and earlier are not compatible with it",
+)];
+
+/// Fails unless the release in the namespace can be upgraded to `target`
+#[derive(Debug)]
+pub(crate) struct UpgradePathCheck {
+    namespace: String,
+    target: Version,
+    /// helm is run against this context, `None` for the current one
+    kube_context: Option<String>,
+    env: Environment,
+}
+
+impl UpgradePathCheck {
+    /// check that the installation in `namespace` can be upgraded to `target`
+    pub(crate) fn new(namespace: impl Into<String>, target: Version) -> Self {
+        Self {
+            namespace: namespace.into(),
+            target,
+            kube_context: None,
+            env: Environment::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ClusterCheck for UpgradePathCheck {
+    async fn perform_check(&self, _pb: &ProgressRenderer) -> CheckResult {
+        let installations: Vec<_> = chart::installed_releases(
+            &*self.env,
+            APP_CHART_NAME,
+            Some(&self.namespace),
+            self.kube_context.as_deref(),
+        )
+        .await?
+        .into_iter()
+        .map(|chart| FluvioInstallation {
+            name: chart.name,
+            namespace: chart.namespace,
+            version: chart.app_version,
+        })
+        .collect();
+        debug!(?installations, "installations to upgrade");
+        upgrade_path_status(&self.namespace, installations.first(), &self.target)
+    }
+
+    fn required_components(&self) -> Vec<FluvioClusterComponent> {
+        vec![
+            FluvioClusterComponent::Helm,
+            FluvioClusterComponent::Kubernetes,
+        ]
+    }
+
+    fn identity(&self) -> Option<ClusterIdentity> {
+        identity::kubectl_identity(&*self.env, self.kube_context.as_deref())
+    }
+
+    fn is_quick(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        "upgrade-path"
+    }
+
+    fn label(&self) -> &str {
+        "Upgrade path"
+    }
+
+    fn description(&self) -> &str {
+        "The installed Fluvio release can be upgraded to the target version"
+    }
+
+    fn set_namespace(&mut self, namespace: &str) {
+        self.namespace = namespace.to_string();
+    }
+
+    fn set_kube_context(&mut self, context: &str) {
+        self.kube_context = Some(context.to_string());
+    }
+
+    fn set_environment(&mut self, env: &Arc<dyn CheckEnvironment>) {
+        self.env = env.into();
+    }
+}
+
+fn upgrade_path_status(
+    namespace: &str,
+    installation: Option<&FluvioInstallation>,
+    target: &Version,
+) -> CheckResult {
+    let Some(installation) = installation else {
+        return Ok(CheckStatus::Unrecoverable(
+            UnrecoverableCheckStatus::NoInstallationToUpgrade {
+                namespace: namespace.to_string(),
+            },
+        ));
+    };
+    let installed = Version::parse(&installation.version)?;
+    if installed > *target {
+        return Ok(CheckStatus::Unrecoverable(
+            UnrecoverableCheckStatus::UpgradeIsDowngrade {
+                installed: installed.to_string(),
+                target: target.to_string(),
+            },
+        ));
+    }
+    if let Some((boundary, reason)) = breaking_version_between(&installed, target) {
+        return Ok(CheckStatus::Unrecoverable(
+            UnrecoverableCheckStatus::BreakingUpgrade {
+                installed: installed.to_string(),
+                target: target.to_string(),
+                breaking: boundary.to_string(),
+                reason: reason.to_string(),
+            },
+        ));
+    }
+    if installed == *target {
+        return Ok(CheckStatus::pass(format!(
+            "Fluvio {installed} is installed, it is already at the target version"
+        )));
+    }
+    Ok(CheckStatus::pass(format!(
+        "Fluvio {installed} in namespace {} can be upgraded to {target}",
+        installation.namespace
+    )))
+}
+
+/// The first entry of [`BREAKING_VERSIONS`] after `installed` and at or before `target`
+fn breaking_version_between(
+    installed: &Version,
+    target: &Version,
+) -> Option<(Version, &'static str)> {
+    BREAKING_VERSIONS
+        .iter()
+        .map(|(version, reason)| {
+            (
+                Version::parse(version).expect("breaking versions are semver"),
+                *reason,
+            )
+        })
+        .find(|(version, _)| installed < version && version <= target)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::check::CheckSuggestion;
+
+    use super::*;
+
+    fn installation(version: &str) -> FluvioInstallation {
+        FluvioInstallation {
+            name: "fluvio".to_string(),
+            namespace: "default".to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    fn failure(status: CheckResult) -> UnrecoverableCheckStatus {
+        match status {
+            Ok(CheckStatus::Unrecoverable(failure)) => failure,
+            other => panic!("expected failure, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_breaking_versions_are_semver() {
+        for (version, _) in BREAKING_VERSIONS {
+            assert!(Version::parse(version).is_ok(), "{version}");
+        }
+    }
+
+    #[test]
+    fn test_upgrade_path() {
+        let target = Version::new(0, 11, 2);
+
+        assert!(matches!(
+            upgrade_path_status("default", Some(&installation("0.11.0")), &target),
+            Ok(CheckStatus::Pass(_))
+        ));
+        assert!(matches!(
+            upgrade_path_status("default", Some(&installation("0.11.2")), &target),
+            Ok(CheckStatus::Pass(_))
+        ));
+    }
+
+    #[test]
+    fn test_nothing_to_upgrade() {
+        let failure = failure(upgrade_path_status("dev", None, &Version::new(0, 11, 2)));
+
+        assert_eq!(
+            failure.to_string(),
+            "There is no Fluvio installation to upgrade in namespace dev"
+        );
+        assert!(failure.suggestion().is_some());
+    }
+
+    #[test]
+    fn test_downgrade() {
+        let failure = failure(upgrade_path_status(
+            "default",
+            Some(&installation("0.11.3")),
+            &Version::new(0, 11, 2),
+        ));
+
+        assert!(matches!(
+            &failure,
+            UnrecoverableCheckStatus::UpgradeIsDowngrade { installed, target }
+                if installed == "0.11.3" && target == "0.11.2"
+        ));
+        assert!(failure
+            .suggestion()
+            .unwrap()
+            .contains("fluvio cluster delete"));
+    }
+
+    #[test]
+    fn test_breaking_upgrade() {
+        let failure = failure(upgrade_path_status(
+            "default",
+            Some(&installation("0.9.33")),
+            &Version::new(0, 11, 2),
+        ));
+
+        assert!(matches!(
+            &failure,
+            UnrecoverableCheckStatus::BreakingUpgrade { breaking, .. } if breaking == "0.10.0"
+        ));
+
+        assert!(matches!(
+            upgrade_path_status(
+                "default",
+                Some(&installation("0.10.0")),
+                &Version::new(0, 11, 2)
+            ),
+            Ok(CheckStatus::Pass(_))
+        ));
+    }
+}
@@ -115,6 +115,10 @@ pub struct K8Install {
     #[arg(long)]
     use_cluster_ip: bool,
 
+    /// Allow installing alongside Fluvio clusters in other namespaces
+    #[arg(long)]
+    allow_multiple_installs: bool,
+
     /// TLS: Client secret name while adding to Kubernetes
     #[arg(long, default_value = TLS_CLIENT_SECRET_NAME)]
     tls_client_secret_name: String,
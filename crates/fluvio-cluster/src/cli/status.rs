@@ -58,7 +58,7 @@ impl StatusOpt {
     }
 
     async fn check_k8s_cluster(pb: &ProgressRenderer) -> Result<(), ClusterCliError> {
-        let k8s_cluster_check = Box::new(ActiveKubernetesCluster);
+        let k8s_cluster_check = Box::new(ActiveKubernetesCluster::default());
 
         pb.set_message(pad_format!(format!(
             "{} Checking {}",
@@ -67,10 +67,14 @@ impl StatusOpt {
         )));
 
         match k8s_cluster_check.perform_check(pb).await? {
-            CheckStatus::Pass(status) => {
+            CheckStatus::Pass(status) | CheckStatus::PassWithData(status, _) => {
                 pb.println(pad_format!(format!("{} {}", "✅".bold(), status)));
                 Ok(())
             }
+            CheckStatus::Skipped(reason) => {
+                pb.println(pad_format!(format!("{} {}", "⏭️".bold(), reason)));
+                Ok(())
+            }
             CheckStatus::Unrecoverable(err) => {
                 debug!("failed: {}", err);
 
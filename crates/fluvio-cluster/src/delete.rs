@@ -9,6 +9,7 @@ use sysinfo::{ProcessExt, System, SystemExt};
 use fluvio_command::CommandExt;
 use fluvio_types::defaults::SPU_MONITORING_UNIX_SOCKET;
 
+#[cfg(feature = "helm-checks")]
 use crate::helm::HelmClient;
 use crate::charts::{APP_CHART_NAME, SYS_CHART_NAME};
 use crate::progress::ProgressBarFactory;
@@ -61,12 +62,14 @@ pub struct ClusterUninstaller {
     /// Configuration options for this process
     config: ClusterUninstallConfig,
     /// Helm client for performing uninstalls
+    #[cfg(feature = "helm-checks")]
     helm_client: Option<HelmClient>,
     pb_factory: ProgressBarFactory,
 }
 
 impl ClusterUninstaller {
     fn from_config(config: ClusterUninstallConfig) -> Result<Self, ClusterError> {
+        #[cfg(feature = "helm-checks")]
         let helm_client = if config.uninstall_k8 || config.uninstall_sys {
             Some(HelmClient::new().map_err(UninstallError::HelmError)?)
         } else {
@@ -74,6 +77,7 @@ impl ClusterUninstaller {
         };
 
         Ok(ClusterUninstaller {
+            #[cfg(feature = "helm-checks")]
             helm_client,
             pb_factory: ProgressBarFactory::new(config.hide_spinner),
             config,
@@ -101,18 +105,9 @@ impl ClusterUninstaller {
 
     #[instrument(skip(self))]
     async fn uninstall_k8(&self) -> Result<(), ClusterError> {
-        use fluvio_helm::UninstallArg;
-
         let pb = self.pb_factory.create()?;
         pb.set_message("Uninstalling fluvio kubernetes components");
-        let uninstall = UninstallArg::new(self.config.app_chart_name.to_owned())
-            .namespace(self.config.namespace.to_owned())
-            .ignore_not_found();
-        self.helm_client
-            .as_ref()
-            .ok_or(UninstallError::Other("helm client undefined".into()))?
-            .uninstall(uninstall)
-            .map_err(UninstallError::HelmError)?;
+        self.uninstall_chart(&self.config.app_chart_name)?;
 
         pb.println("Uninstalled fluvio kubernetes components");
         pb.finish_and_clear();
@@ -122,27 +117,41 @@ impl ClusterUninstaller {
 
     #[instrument(skip(self))]
     async fn uninstall_sys(&self) -> Result<(), ClusterError> {
-        use fluvio_helm::UninstallArg;
-
         let pb = self.pb_factory.create()?;
         pb.set_message("Uninstalling Fluvio sys chart");
+        self.uninstall_chart(&self.config.sys_chart_name)?;
+        debug!("fluvio sys chart has been uninstalled");
+
+        pb.set_message("Fluvio System chart has been uninstalled");
+        pb.finish_and_clear();
+
+        Ok(())
+    }
+
+    /// Uninstalls the helm release `chart` from the namespace, if it is installed
+    #[cfg(feature = "helm-checks")]
+    fn uninstall_chart(&self, chart: &str) -> Result<(), UninstallError> {
+        use fluvio_helm::UninstallArg;
+
         self.helm_client
             .as_ref()
             .ok_or(UninstallError::Other("helm client undefined".into()))?
             .uninstall(
-                UninstallArg::new(self.config.sys_chart_name.to_owned())
+                UninstallArg::new(chart.to_owned())
                     .namespace(self.config.namespace.to_owned())
                     .ignore_not_found(),
             )
             .map_err(UninstallError::HelmError)?;
-        debug!("fluvio sys chart has been uninstalled");
-
-        pb.set_message("Fluvio System chart has been uninstalled");
-        pb.finish_and_clear();
-
         Ok(())
     }
 
+    #[cfg(not(feature = "helm-checks"))]
+    fn uninstall_chart(&self, chart: &str) -> Result<(), UninstallError> {
+        Err(UninstallError::Other(format!(
+            "unable to uninstall {chart}, helm support requires the helm-checks feature"
+        )))
+    }
+
     async fn uninstall_local(&self) -> Result<(), ClusterError> {
         let pb = self.pb_factory.create()?;
         pb.set_message("Uninstalling fluvio local components");
@@ -6,10 +6,12 @@ use indicatif::style::TemplateError;
 
 use fluvio::FluvioError;
 use k8_config::ConfigError as K8ConfigError;
+#[cfg(feature = "helm-checks")]
 use fluvio_helm::HelmError;
 use fluvio_command::CommandError;
 
 use crate::check::{CheckResults, CheckStatuses, ClusterCheckError};
+#[cfg(feature = "helm-checks")]
 use crate::charts::ChartInstallError;
 use crate::runtime::local::LocalRuntimeError;
 
@@ -23,6 +25,7 @@ pub enum ClusterError {
     #[error("Failed to install Fluvio locally: {0}")]
     InstallLocal(#[from] LocalInstallError),
     /// An error occurred while trying to install Fluvio system charts
+    #[cfg(feature = "helm-checks")]
     #[error("Failed to install Fluvio system charts: {0}")]
     InstallSys(#[from] ChartInstallError),
     /// An error occurred while trying to uninstall Fluvio
@@ -39,9 +42,11 @@ pub enum K8InstallError {
     #[error("Kubernetes config error: {0}")]
     K8ConfigError(#[from] K8ConfigError),
     /// An error occurred while running helm.
+    #[cfg(feature = "helm-checks")]
     #[error("Helm client error: {0}")]
     HelmError(#[from] HelmError),
     /// An error occurred while running helm.
+    #[cfg(feature = "helm-checks")]
     #[error("Helm Chart error: {0}")]
     ChartError(#[from] ChartInstallError),
     /// Failed to execute a command
@@ -112,6 +117,7 @@ pub enum LocalInstallError {
     #[error("Kubernetes config error: {0}")]
     K8ConfigError(#[from] K8ConfigError),
     /// An error occurred while running helm.
+    #[cfg(feature = "helm-checks")]
     #[error("Helm client error: {0}")]
     HelmError(#[from] HelmError),
     /// Failed to execute a command
@@ -173,6 +179,7 @@ pub enum UninstallError {
     #[error("Kubernetes config error: {0}")]
     K8ConfigError(#[from] K8ConfigError),
     /// An error occurred while running helm.
+    #[cfg(feature = "helm-checks")]
     #[error("Helm client error: {0}")]
     HelmError(#[from] HelmError),
     /// Timed out when waiting for SC service.
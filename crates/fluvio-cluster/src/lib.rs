@@ -38,14 +38,37 @@ pub mod runtime;
 /// extensions
 #[cfg(feature = "cli")]
 pub mod cli;
+#[cfg(feature = "helm-checks")]
 use fluvio_helm as helm;
 
+#[cfg(feature = "helm-checks")]
 pub use start::k8::{ClusterInstaller, ClusterConfig, ClusterConfigBuilder};
 pub use start::local::{LocalInstaller, LocalConfig, LocalConfigBuilder};
 pub use error::{ClusterError, K8InstallError, LocalInstallError, UninstallError};
+#[cfg(feature = "helm-checks")]
 pub use helm::HelmError;
-pub use check::{ClusterChecker, CheckStatus, CheckStatuses, CheckResult, CheckResults};
-pub use check::{RecoverableCheck, UnrecoverableCheckStatus, CheckSuggestion};
+pub use check::{ClusterChecker, CheckStatus, CheckStatuses, CheckResult, CheckResults, CheckData};
+pub use check::{CheckEvent, CheckOutcome, CheckReport, CheckResultsExt, CheckResultsSummary};
+pub use check::{CheckAbortHandle, CheckProgress};
+pub use check::render::render_check_progress;
+pub use check::{RecoverableCheck, UnrecoverableCheckStatus, CheckSuggestion, ExecPluginError};
+pub use check::{FluvioInstallation, ClusterIdentity, PortAvailabilityCheck, ScConnectivity};
+pub use check::{LocalDiskSpace, KubectlBinary, HelmBinary, NodeReadiness, NamespaceCheck};
+pub use check::{LoadBalancerCheck, TlsSecrets};
+#[cfg(feature = "helm-checks")]
+pub use check::{ChartAvailability, SysChartCheck, SysChartInstaller};
+pub use check::{CheckEnvironment, SystemEnvironment, ClusterApi, RunningProcess};
+pub use check::{AccessReviewer, SelfSubjectAccessReviewSpec, ResourceAttributes};
+pub use check::{SubjectAccessReviewStatus, ServiceSource, LoadBalancer, SecretSource};
+pub use check::{NodeSource, NodeState, NodeCondition, NamespaceSource, ServerVersionSource};
+pub use check::{CheckDrift, RevalidationReport, InfraDetector, KubernetesInfra};
+pub use check::{ClusterCheck, CheckSeverity, CheckPreset, CheckRegistry, CheckSelection};
+pub use check::{ClusterCheckError, CommandOutput, FluvioClusterComponent, K8VersionRange};
+#[cfg(feature = "helm-checks")]
+pub use check::check_helm_version;
+pub use check::{ClusterAutoFix, ClusterAutoFixError, FixProgress, FixProgressReporter};
+pub use progress::ProgressBarFactory;
+pub use render::ProgressRenderer;
 pub use delete::*;
 pub use fluvio::config as fluvio_config;
 pub use fluvio_extension_common::installation::InstallationType;
@@ -24,6 +24,7 @@ use fluvio_controlplane_metadata::spg::SpuConfig;
 use fluvio_sc_schema::objects::CommonCreateRequest;
 use fluvio_types::defaults::TLS_CLIENT_SECRET_NAME;
 use fluvio_types::defaults::TLS_SERVER_SECRET_NAME;
+use fluvio_types::defaults::TLS_CA_SECRET_NAME;
 use k8_client::SharedK8Client;
 use k8_client::load_and_share;
 use k8_types::K8Obj;
@@ -40,8 +41,8 @@ use k8_types::core::node::{NodeSpec, NodeAddress};
 use fluvio_command::CommandExt;
 
 use crate::InstallationType;
-use crate::check::ClusterCheckError;
-use crate::check::{AlreadyInstalled, SysChartCheck};
+use crate::check::{ClusterCheckError, TlsSecrets};
+use crate::check::{AlreadyInstalled, ChartAvailability, SysChartCheck, UpgradePathCheck};
 use crate::error::K8InstallError;
 use crate::progress::ProgressBarFactory;
 use crate::render::ProgressRenderedText;
@@ -49,7 +50,7 @@ use crate::render::ProgressRenderer;
 use crate::start::common::check_crd;
 use crate::tls_config_to_cert_paths;
 use crate::{ClusterError, StartStatus, DEFAULT_NAMESPACE, ClusterChecker};
-use crate::charts::{ChartConfig, ChartInstaller};
+use crate::charts::{ChartConfig, ChartInstaller, APP_CHART_NAME};
 use crate::UserChartLocation;
 use crate::progress::InstallProgressMessage;
 
@@ -297,6 +298,10 @@ pub struct ClusterConfig {
     #[builder(default = "false")]
     use_k8_port_forwarding: bool,
 
+    /// Whether to install even if Fluvio already runs in other namespaces. Defaults to `false`.
+    #[builder(default = "false")]
+    allow_multiple_installs: bool,
+
     /// Use NodePort instead of load balancer for SC and SPU
     #[builder(setter(into), default = "DEFAULT_SERVICE_TYPE.to_string()")]
     service_type: String,
@@ -606,7 +611,33 @@ impl ClusterInstaller {
             env::set_var(DISPATCHER_WAIT, "300");
         }
 
-        let mut checker = ClusterChecker::empty().with_k8_checks();
+        let checker = self.preflight_checker();
+
+        self.pb_factory
+            .println(InstallProgressMessage::PreFlightCheck.msg());
+
+        checker.run(&self.pb_factory, fix).await?;
+
+        Ok(())
+    }
+
+    /// Checks run by [`preflight_check`](ClusterInstaller::preflight_check)
+    fn preflight_checker(&self) -> ClusterChecker {
+        let mut checker = ClusterChecker::empty()
+            .with_k8_checks()
+            .with_min_ready_nodes(self.config.spu_replicas as usize)
+            .with_namespace(&self.config.namespace);
+
+        // only a LoadBalancer service needs the cluster to hand out addresses
+        if self.config.use_cluster_ip || self.config.service_type != "LoadBalancer" {
+            checker = checker.with_check_filter(|name| name != "load-balancer");
+        }
+
+        // secrets uploaded from certificate files are checked once they are uploaded
+        if !self.uploads_tls_secrets() {
+            checker =
+                checker.with_tls_checks(&self.config.server_tls_policy, self.tls_secrets_check());
+        }
 
         if self.config.install_sys {
             let mut sys_config: ChartConfig = ChartConfig::sys_builder()
@@ -618,22 +649,35 @@ impl ClusterInstaller {
                 sys_config.location = location.to_owned().into();
             }
 
-            checker = checker.with_check(SysChartCheck::new(
-                sys_config,
-                self.config.platform_version.clone(),
-            ));
+            // a newer system chart has the CRDs of this version, only older ones are upgraded
+            checker = checker.with_check(
+                SysChartCheck::new(sys_config, self.config.platform_version.clone())
+                    .with_required_version(self.config.platform_version.clone()),
+            );
         }
 
-        if !self.config.upgrade {
-            checker = checker.with_check(AlreadyInstalled);
+        if let (Some(UserChartLocation::Remote(url)), Some(version)) =
+            (&self.config.chart_location, &self.config.chart_version)
+        {
+            checker =
+                checker.with_check(ChartAvailability::new(APP_CHART_NAME, url, version.clone()));
         }
 
-        self.pb_factory
-            .println(InstallProgressMessage::PreFlightCheck.msg());
+        if self.config.upgrade {
+            checker = checker.with_check(UpgradePathCheck::new(
+                &self.config.namespace,
+                self.config.platform_version.clone(),
+            ));
+        }
 
-        checker.run(&self.pb_factory, fix).await?;
+        checker = checker.with_check(
+            AlreadyInstalled::new(&self.config.namespace)
+                .with_platform_version(self.config.platform_version.clone())
+                .with_reuse(self.config.upgrade)
+                .with_allow_multiple(self.config.allow_multiple_installs),
+        );
 
-        Ok(())
+        checker
     }
 
     /// Installs Fluvio according to the installer's configuration
@@ -645,7 +689,13 @@ impl ClusterInstaller {
     )]
     pub async fn install_fluvio(&self) -> Result<StartStatus> {
         if !self.config.skip_checks {
+            // fixes may take a while, make sure nobody changed the cluster meanwhile
+            let quick = self.preflight_checker().run_quick().await;
             self.preflight_check(true).await?;
+            let report = quick.revalidate_quick(&self.preflight_checker()).await;
+            if !report.is_clean() {
+                return Err(ClusterCheckError::EnvironmentChanged(report).into());
+            }
         }
 
         self.install_app().await?;
@@ -742,6 +792,10 @@ impl ClusterInstaller {
             &self.config.client_tls_policy,
         ) {
             self.upload_tls_secrets(server_tls, client_tls)?;
+            ClusterChecker::empty()
+                .with_tls_checks(&self.config.server_tls_policy, self.tls_secrets_check())
+                .run(&self.pb_factory, false)
+                .await?;
             install_settings.push(("cert.domain", Cow::Borrowed(server_tls.domain())));
         }
 
@@ -865,6 +919,26 @@ impl ClusterInstaller {
         Ok(())
     }
 
+    /// Whether [`install_app`](ClusterInstaller::install_app) uploads the TLS certificate files
+    fn uploads_tls_secrets(&self) -> bool {
+        matches!(
+            (
+                &self.config.server_tls_policy,
+                &self.config.client_tls_policy
+            ),
+            (TlsPolicy::Verified(_), TlsPolicy::Verified(_))
+        )
+    }
+
+    /// Check of the TLS secrets the SC is started with
+    fn tls_secrets_check(&self) -> TlsSecrets {
+        TlsSecrets::new(
+            &self.config.namespace,
+            &self.config.tls_server_secret_name,
+            TLS_CA_SECRET_NAME,
+        )
+    }
+
     /// Uploads TLS secrets to Kubernetes
     fn upload_tls_secrets(&self, server_tls: &TlsConfig, client_tls: &TlsConfig) -> Result<()> {
         let server_paths: Cow<TlsPaths> = tls_config_to_cert_paths(server_tls)?;
@@ -1199,7 +1273,12 @@ impl ClusterInstaller {
 
         // Try uninstalling secrets first to prevent duplication error
         Command::new("kubectl")
-            .args(["delete", "secret", "fluvio-ca", "--ignore-not-found=true"])
+            .args([
+                "delete",
+                "secret",
+                TLS_CA_SECRET_NAME,
+                "--ignore-not-found=true",
+            ])
             .args(["--namespace", &self.config.namespace])
             .inherit()
             .result()?;
@@ -1227,7 +1306,7 @@ impl ClusterInstaller {
             .result()?;
 
         Command::new("kubectl")
-            .args(["create", "secret", "generic", "fluvio-ca"])
+            .args(["create", "secret", "generic", TLS_CA_SECRET_NAME])
             .args(["--from-file", ca_cert])
             .args(["--namespace", &self.config.namespace])
             .inherit()
@@ -19,8 +19,11 @@ use k8_client::SharedK8Client;
 
 use crate::render::{ProgressRenderedText, ProgressRenderer};
 use crate::{ClusterChecker, LocalInstallError, StartStatus, UserChartLocation, InstallationType};
+#[cfg(feature = "helm-checks")]
 use crate::charts::ChartConfig;
-use crate::check::{SysChartCheck, ClusterCheckError};
+use crate::check::ClusterCheckError;
+#[cfg(feature = "helm-checks")]
+use crate::check::SysChartCheck;
 use crate::runtime::local::{LocalSpuProcessClusterManager, ScProcess, ScMode};
 use crate::progress::{InstallProgressMessage, ProgressBarFactory};
 
@@ -368,25 +371,28 @@ impl LocalInstaller {
                 Ok(())
             }
             InstallationType::LocalK8 => {
-                let mut sys_config: ChartConfig = ChartConfig::sys_builder()
-                    .version(self.config.chart_version.clone())
-                    .build()
-                    .expect("should build config since all required arguments are given");
+                let checker = ClusterChecker::empty().with_local_checks();
 
-                if let Some(location) = &self.config.chart_location {
-                    sys_config.location = location.to_owned().into();
-                }
+                #[cfg(feature = "helm-checks")]
+                let checker = {
+                    let mut sys_config: ChartConfig = ChartConfig::sys_builder()
+                        .version(self.config.chart_version.clone())
+                        .build()
+                        .expect("should build config since all required arguments are given");
 
-                self.pb_factory
-                    .println(InstallProgressMessage::PreFlightCheck.msg());
-                ClusterChecker::empty()
-                    .with_local_checks()
-                    .with_check(SysChartCheck::new(
+                    if let Some(location) = &self.config.chart_location {
+                        sys_config.location = location.to_owned().into();
+                    }
+
+                    checker.with_check(SysChartCheck::new(
                         sys_config,
                         self.config.platform_version.clone(),
                     ))
-                    .run(&self.pb_factory, fix)
-                    .await?;
+                };
+
+                self.pb_factory
+                    .println(InstallProgressMessage::PreFlightCheck.msg());
+                checker.run(&self.pb_factory, fix).await?;
                 Ok(())
             }
             InstallationType::K8 => Err(ClusterCheckError::Other(
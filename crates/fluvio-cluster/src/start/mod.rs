@@ -1,3 +1,4 @@
+#[cfg(feature = "helm-checks")]
 pub mod k8;
 pub mod local;
 mod common;
@@ -0,0 +1,1291 @@
+//! The check presets run against a fake cluster
+//!
+//! [`Harness`] is a [`CheckEnvironment`] that answers the checks from a
+//! [`FakeCluster`] instead of the kubeconfig, the Kubernetes client, kubectl,
+//! helm and the local machine. That way the real presets of
+//! [`ClusterChecker`] can be run in every mode against a cluster described
+//! in a few lines, such as "helm 3.2 installed, sys chart missing, the load
+//! balancer never gets an address, can't create CRDs".
+
+#![cfg(all(feature = "helm-checks", feature = "kubectl-checks"))]
+
+use std::collections::BTreeMap;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitStatus, Output};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use fluvio::config::TlsPolicy;
+use fluvio::FluvioConfig;
+use futures_util::stream::{self, BoxStream, StreamExt};
+use k8_config::KubeConfig;
+use semver::Version;
+use serde_json::json;
+
+use fluvio_cluster::charts::{ChartConfig, ChartInstallError};
+use fluvio_cluster::{
+    AccessReviewer, CheckEnvironment, CheckEvent, CheckResult, CheckResults, CheckStatus,
+    ClusterApi, ClusterCheckError, ClusterChecker, LoadBalancer, LoadBalancerCheck, LocalDiskSpace,
+    NamespaceSource, NodeCondition, NodeSource, NodeState, ProgressBarFactory, RunningProcess,
+    SecretSource, SelfSubjectAccessReviewSpec, ServerVersionSource, ServiceSource,
+    SubjectAccessReviewStatus, SysChartCheck, SysChartInstaller, TlsSecrets,
+};
+
+/// A helm release as `helm list` reports it
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FakeRelease {
+    chart: String,
+    namespace: String,
+    version: String,
+}
+
+impl FakeRelease {
+    fn new(chart: &str, namespace: &str, version: &str) -> Self {
+        Self {
+            chart: chart.to_string(),
+            namespace: namespace.to_string(),
+            version: version.to_string(),
+        }
+    }
+}
+
+/// Everything the checks can find out about the machine and the cluster
+#[derive(Debug, Clone)]
+struct FakeCluster {
+    /// current kubeconfig context, `None` without a kubeconfig
+    context: Option<&'static str>,
+    server: &'static str,
+    /// binaries found in `PATH`
+    binaries: Vec<&'static str>,
+    /// as `helm version --short` prints it, without the leading `v`
+    helm_version: &'static str,
+    /// `gitVersion` of the Kubernetes server
+    server_version: &'static str,
+    /// name of each node and whether it is ready
+    nodes: Vec<(&'static str, bool)>,
+    namespaces: Vec<&'static str>,
+    /// resources the current user, or the service account, may not create
+    denied: Vec<&'static str>,
+    /// lists the OpenShift security API, with no SCC the fluvio account may use
+    openshift: bool,
+    releases: Vec<FakeRelease>,
+    /// version of the system chart bundled with the installer
+    chart_version: &'static str,
+    /// what helm prints when installing the system chart fails
+    chart_install_error: Option<&'static str>,
+    /// name and address of each LoadBalancer service
+    load_balancers: Vec<(&'static str, Option<&'static str>)>,
+    /// address the first load balancer is given once the check watches it
+    assigned_address: Option<&'static str>,
+    /// name and keys of each secret
+    secrets: Vec<(&'static str, Vec<&'static str>)>,
+    /// command line of each local process
+    processes: Vec<Vec<&'static str>>,
+    taken_ports: Vec<u16>,
+    /// bytes available on the disk mounted at `/`
+    available_disk: u64,
+    /// SC address of the current profile, `None` without a profile
+    sc_endpoint: Option<&'static str>,
+}
+
+impl FakeCluster {
+    /// A cloud cluster Fluvio 0.11.0 is installed on, with nothing wrong with it
+    fn healthy() -> Self {
+        Self {
+            context: Some("fluvio-test"),
+            server: "https://10.0.0.1:6443",
+            binaries: vec!["kubectl", "helm"],
+            helm_version: "3.12.0",
+            server_version: "v1.27.3",
+            nodes: vec![("node-1", true), ("node-2", true)],
+            namespaces: vec!["default", "kube-system"],
+            denied: vec![],
+            openshift: false,
+            releases: vec![
+                FakeRelease::new("fluvio-sys", "default", "0.11.0"),
+                FakeRelease::new("fluvio", "default", "0.11.0"),
+            ],
+            chart_version: "0.11.0",
+            chart_install_error: None,
+            load_balancers: vec![("fluvio-sc-public", Some("34.120.10.7"))],
+            assigned_address: None,
+            secrets: vec![],
+            processes: vec![],
+            taken_ports: vec![],
+            available_disk: 50 * 1024 * 1024 * 1024,
+            sc_endpoint: None,
+        }
+    }
+}
+
+/// A [`CheckEnvironment`] answering from a [`FakeCluster`], recording the commands run
+///
+/// Clones share the cluster, so fixes change what later checks see.
+#[derive(Debug, Clone)]
+struct Harness {
+    cluster: Arc<Mutex<FakeCluster>>,
+    commands: Arc<Mutex<Vec<String>>>,
+}
+
+impl Harness {
+    fn new(cluster: FakeCluster) -> Self {
+        Self {
+            cluster: Arc::new(Mutex::new(cluster)),
+            commands: Arc::default(),
+        }
+    }
+
+    fn cluster(&self) -> MutexGuard<'_, FakeCluster> {
+        self.cluster.lock().unwrap()
+    }
+
+    /// Command lines run so far, such as `helm version --short`
+    fn commands(&self) -> Vec<String> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    fn env(&self) -> Arc<dyn CheckEnvironment> {
+        Arc::new(self.clone())
+    }
+
+    /// An empty checker whose checks run against the fake cluster
+    fn checker(&self) -> ClusterChecker {
+        ClusterChecker::empty().with_environment(self.env())
+    }
+
+    fn record(&self, command: String) {
+        self.commands.lock().unwrap().push(command);
+    }
+
+    /// Runs kubectl or helm against the fake cluster, other binaries are not found
+    fn run_command(&self, command: &Command) -> Result<Output, IoError> {
+        let program = command.get_program().to_string_lossy().to_string();
+        let args: Vec<String> = command
+            .get_args()
+            .map(|arg| arg.to_string_lossy().to_string())
+            .collect();
+        self.record(
+            std::iter::once(program.clone())
+                .chain(args.iter().cloned())
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let installed = self.cluster().binaries.contains(&program.as_str());
+        match program.as_str() {
+            "kubectl" if installed => Ok(self.kubectl(&args)),
+            "helm" if installed => Ok(self.helm(&args)),
+            _ => Err(IoError::new(
+                ErrorKind::NotFound,
+                format!("{program}: command not found"),
+            )),
+        }
+    }
+
+    fn kubectl(&self, args: &[&str]) -> Output {
+        let mut cluster = self.cluster();
+        let args = match args {
+            ["--context", _, args @ ..] => args,
+            args => args,
+        };
+        if cluster.context.is_none() {
+            // kubectl still prints its own version
+            let stdout = match args {
+                ["version", ..] => {
+                    json!({ "clientVersion": { "gitVersion": "v1.27.3" } }).to_string()
+                }
+                _ => String::new(),
+            };
+            return output(
+                1,
+                stdout,
+                "error: no configuration has been provided, try setting KUBERNETES_MASTER \
+                 environment variable",
+            );
+        }
+        match args {
+            ["api-versions"] => {
+                let mut apis = vec!["apps/v1", "v1"];
+                if cluster.openshift {
+                    apis.push("security.openshift.io/v1");
+                }
+                output(0, apis.join("\n"), "")
+            }
+            ["get", "securitycontextconstraints", "-o", "json"] => {
+                let sccs = json!({
+                    "items": [
+                        {
+                            "metadata": { "name": "anyuid" },
+                            "groups": ["system:cluster-admins"],
+                            "runAsUser": { "type": "RunAsAny" },
+                        },
+                        {
+                            "metadata": { "name": "restricted" },
+                            "groups": ["system:authenticated"],
+                            "runAsUser": { "type": "MustRunAsRange" },
+                        },
+                    ]
+                });
+                output(0, sccs.to_string(), "")
+            }
+            ["auth", "can-i", "use", ..] => output(1, "no", ""),
+            ["auth", "can-i", "create", resource, ..] if cluster.denied.contains(resource) => {
+                output(1, "no", "")
+            }
+            ["auth", "can-i", "create", ..] => output(0, "yes", ""),
+            ["create", "service", "loadbalancer", "fluvio-dummy-service", ..] => {
+                cluster.load_balancers.push(("fluvio-dummy-service", None));
+                output(0, "service/fluvio-dummy-service created", "")
+            }
+            ["delete", "service", "fluvio-dummy-service", ..] => {
+                cluster
+                    .load_balancers
+                    .retain(|(name, _)| *name != "fluvio-dummy-service");
+                output(0, "service \"fluvio-dummy-service\" deleted", "")
+            }
+            ["version", "-o=json", ..] => {
+                let versions = json!({
+                    "clientVersion": { "gitVersion": "v1.27.3" },
+                    "serverVersion": { "gitVersion": cluster.server_version },
+                });
+                output(0, versions.to_string(), "")
+            }
+            args => output(
+                1,
+                String::new(),
+                &format!("error: unknown command \"{}\"", args.join(" ")),
+            ),
+        }
+    }
+
+    fn helm(&self, args: &[&str]) -> Output {
+        let cluster = self.cluster();
+        match args {
+            ["version", "--short"] => output(0, format!("v{}+g1234567", cluster.helm_version), ""),
+            ["list", "--filter", filter, "--output", "json", scope @ ..] => {
+                if cluster.context.is_none() {
+                    return output(1, String::new(), "Error: Kubernetes cluster unreachable");
+                }
+                let chart = filter.trim_start_matches('^').trim_end_matches('$');
+                let namespace = match scope {
+                    ["--namespace", namespace, ..] => Some(*namespace),
+                    _ => None,
+                };
+                let releases: Vec<_> = cluster
+                    .releases
+                    .iter()
+                    .filter(|release| release.chart == chart)
+                    .filter(|release| match namespace {
+                        Some(namespace) => release.namespace == namespace,
+                        None => true,
+                    })
+                    .map(|release| {
+                        json!({
+                            "name": release.chart,
+                            "namespace": release.namespace,
+                            "app_version": release.version,
+                        })
+                    })
+                    .collect();
+                output(0, json!(releases).to_string(), "")
+            }
+            args => output(
+                1,
+                String::new(),
+                &format!("Error: unknown command \"{}\" for \"helm\"", args.join(" ")),
+            ),
+        }
+    }
+}
+
+fn output(code: i32, stdout: impl Into<String>, stderr: &str) -> Output {
+    Output {
+        status: exit_status(code),
+        stdout: stdout.into().into_bytes(),
+        stderr: stderr.as_bytes().to_vec(),
+    }
+}
+
+#[cfg(unix)]
+fn exit_status(code: i32) -> ExitStatus {
+    use std::os::unix::process::ExitStatusExt;
+
+    ExitStatus::from_raw(code << 8)
+}
+
+#[cfg(windows)]
+fn exit_status(code: i32) -> ExitStatus {
+    use std::os::windows::process::ExitStatusExt;
+
+    ExitStatus::from_raw(code as u32)
+}
+
+#[async_trait]
+impl CheckEnvironment for Harness {
+    fn kubeconfig(&self) -> Option<KubeConfig> {
+        let cluster = self.cluster();
+        let context = cluster.context?;
+        let kubeconfig = format!(
+            r#"
+apiVersion: v1
+kind: Config
+current-context: {context}
+clusters:
+- name: {context}
+  cluster:
+    server: {server}
+contexts:
+- name: {context}
+  context:
+    cluster: {context}
+    user: {context}
+users:
+- name: {context}
+  user:
+    token: fake
+"#,
+            server = cluster.server
+        );
+        Some(serde_yaml::from_str(&kubeconfig).expect("fake kubeconfig"))
+    }
+
+    fn client(&self, context: Option<&str>) -> Result<Arc<dyn ClusterApi>, String> {
+        if let Some(name) = context {
+            if self.cluster().context != Some(name) {
+                return Err(format!("Kubernetes context {name} not found"));
+            }
+        }
+        self.default_config().map_err(|err| err.to_string())?;
+        Ok(Arc::new(self.clone()))
+    }
+
+    async fn command_output(
+        &self,
+        command: Command,
+        _timeout: Duration,
+    ) -> Result<Output, IoError> {
+        self.run_command(&command)
+    }
+
+    fn find_binary(&self, binary: &str) -> Option<PathBuf> {
+        self.cluster()
+            .binaries
+            .contains(&binary)
+            .then(|| Path::new("/usr/local/bin").join(binary))
+    }
+
+    fn is_port_free(&self, port: u16) -> bool {
+        !self.cluster().taken_ports.contains(&port)
+    }
+
+    fn disks(&self) -> Vec<(PathBuf, u64)> {
+        vec![(PathBuf::from("/"), self.cluster().available_disk)]
+    }
+
+    fn processes(&self) -> Vec<RunningProcess> {
+        self.cluster()
+            .processes
+            .iter()
+            .enumerate()
+            .map(|(index, cmd)| RunningProcess {
+                pid: (1000 + index).to_string(),
+                name: Path::new(cmd[0])
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                cmd: cmd.iter().map(|arg| arg.to_string()).collect(),
+            })
+            .collect()
+    }
+
+    fn profile(&self) -> Result<FluvioConfig, ClusterCheckError> {
+        match self.cluster().sc_endpoint {
+            Some(endpoint) => Ok(FluvioConfig::new(endpoint)),
+            None => Err(ClusterCheckError::Other(
+                "no cluster profile: Config has no active profile".to_string(),
+            )),
+        }
+    }
+
+    fn sys_chart_installer(
+        &self,
+        config: &ChartConfig,
+    ) -> Result<Box<dyn SysChartInstaller>, ChartInstallError> {
+        Ok(Box::new(FakeChartInstaller::new(self.clone(), config)))
+    }
+}
+
+#[async_trait]
+impl AccessReviewer for Harness {
+    async fn review(
+        &self,
+        spec: SelfSubjectAccessReviewSpec,
+    ) -> Result<SubjectAccessReviewStatus, ClusterCheckError> {
+        let resource = spec.resource_attributes.resource;
+        let allowed = !self.cluster().denied.contains(&resource.as_str());
+        Ok(SubjectAccessReviewStatus {
+            allowed,
+            denied: !allowed,
+            reason: None,
+        })
+    }
+}
+
+#[async_trait]
+impl ServiceSource for Harness {
+    async fn load_balancers(
+        &self,
+        _namespace: &str,
+    ) -> Result<Vec<LoadBalancer>, ClusterCheckError> {
+        Ok(self
+            .cluster()
+            .load_balancers
+            .iter()
+            .map(|(name, address)| LoadBalancer {
+                name: name.to_string(),
+                address: address.map(str::to_owned),
+            })
+            .collect())
+    }
+
+    fn watch_load_balancers(
+        &self,
+        _namespace: &str,
+    ) -> BoxStream<'_, Result<LoadBalancer, ClusterCheckError>> {
+        let cluster = self.cluster();
+        let assigned = cluster.assigned_address.and_then(|address| {
+            let (name, _) = cluster.load_balancers.first()?;
+            Some(Ok(LoadBalancer {
+                name: name.to_string(),
+                address: Some(address.to_string()),
+            }))
+        });
+        // a watch stays open until the check gives up
+        stream::iter(assigned).chain(stream::pending()).boxed()
+    }
+}
+
+#[async_trait]
+impl SecretSource for Harness {
+    async fn secret_data(
+        &self,
+        _namespace: &str,
+        name: &str,
+    ) -> Result<Option<BTreeMap<String, String>>, ClusterCheckError> {
+        Ok(self
+            .cluster()
+            .secrets
+            .iter()
+            .find(|(secret, _)| *secret == name)
+            .map(|(_, keys)| {
+                keys.iter()
+                    .map(|key| (key.to_string(), String::new()))
+                    .collect()
+            }))
+    }
+}
+
+#[async_trait]
+impl NodeSource for Harness {
+    async fn nodes(&self) -> Result<Vec<NodeState>, ClusterCheckError> {
+        Ok(self
+            .cluster()
+            .nodes
+            .iter()
+            .map(|(name, ready)| NodeState {
+                name: name.to_string(),
+                unschedulable: false,
+                conditions: vec![NodeCondition {
+                    r#type: "Ready".to_string(),
+                    status: if *ready { "True" } else { "False" }.to_string(),
+                    reason: (!ready).then(|| "KubeletNotReady".to_string()),
+                }],
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl NamespaceSource for Harness {
+    async fn namespace_exists(&self, name: &str) -> Result<bool, ClusterCheckError> {
+        Ok(self.cluster().namespaces.contains(&name))
+    }
+}
+
+#[async_trait]
+impl ServerVersionSource for Harness {
+    async fn server_git_version(&self) -> Result<String, ClusterCheckError> {
+        Ok(self.cluster().server_version.to_string())
+    }
+}
+
+/// Installs the system chart into the fake cluster, as `helm install` would
+#[derive(Debug)]
+struct FakeChartInstaller {
+    harness: Harness,
+    chart: String,
+    namespace: String,
+    version: Option<Version>,
+}
+
+impl FakeChartInstaller {
+    fn new(harness: Harness, config: &ChartConfig) -> Self {
+        Self {
+            harness,
+            chart: config.name.clone(),
+            namespace: config.namespace.clone(),
+            version: config.version.clone(),
+        }
+    }
+
+    fn release(&self, verb: &str) -> Result<(), ChartInstallError> {
+        self.harness.record(format!(
+            "helm {verb} {} --namespace {}",
+            self.chart, self.namespace
+        ));
+        let mut cluster = self.harness.cluster();
+        if let Some(error) = cluster.chart_install_error {
+            return Err(ChartInstallError::Other(error.to_string()));
+        }
+        let version = match &self.version {
+            Some(version) => version.to_string(),
+            None => cluster.chart_version.to_string(),
+        };
+        cluster
+            .releases
+            .retain(|release| release.chart != self.chart || release.namespace != self.namespace);
+        cluster
+            .releases
+            .push(FakeRelease::new(&self.chart, &self.namespace, &version));
+        Ok(())
+    }
+}
+
+impl SysChartInstaller for FakeChartInstaller {
+    fn install(&self) -> Result<(), ChartInstallError> {
+        self.release("install")
+    }
+
+    fn upgrade(&self) -> Result<(), ChartInstallError> {
+        self.release("upgrade")
+    }
+
+    fn is_installed(&self) -> Result<bool, ChartInstallError> {
+        Ok(self
+            .harness
+            .cluster()
+            .releases
+            .iter()
+            .any(|release| release.chart == self.chart))
+    }
+}
+
+/// Version of the platform the CLI checks the system chart against
+const PLATFORM: Version = Version::new(0, 11, 0);
+
+fn render(results: CheckResults) -> Vec<String> {
+    results
+        .into_iter()
+        .map(|outcome| render_outcome(&outcome.name, &outcome.result))
+        .collect()
+}
+
+fn render_outcome(name: &str, result: &CheckResult) -> String {
+    let status = match result {
+        Ok(CheckStatus::Pass(message)) => format!("pass: {message}"),
+        Ok(CheckStatus::PassWithData(message, data)) => format!("pass: {message} ({data:?})"),
+        Ok(CheckStatus::AutoFixableError { message, .. }) => format!("fixable: {message}"),
+        Ok(CheckStatus::Unrecoverable(failure)) => format!("fail: {failure}"),
+        Ok(CheckStatus::Skipped(reason)) => format!("skipped: {reason}"),
+        Err(err) => format!("error: {err}"),
+    };
+    format!("{name}: {status}")
+}
+
+fn render_event(event: CheckEvent) -> String {
+    match event {
+        CheckEvent::Started { name, index, total } => {
+            format!("started {name} {}/{total}", index + 1)
+        }
+        CheckEvent::Completed { name, result, .. } => render_outcome(&name, &result),
+    }
+}
+
+/// `expected` with the outcome of check `name` replaced by `status`
+fn with_status(mut expected: Vec<String>, name: &str, status: &str) -> Vec<String> {
+    let prefix = format!("{name}: ");
+    let line = expected
+        .iter_mut()
+        .find(|line| line.starts_with(&prefix))
+        .expect("check in expected outcomes");
+    *line = format!("{prefix}{status}");
+    expected
+}
+
+/// The checks of `fluvio cluster check`, with a load balancer check that gives up early
+fn cli_checker(harness: &Harness) -> ClusterChecker {
+    let sys_config = ChartConfig::sys_builder()
+        .build()
+        .expect("sys chart config");
+    harness
+        .checker()
+        .with_preflight_checks()
+        .with_check_filter(|name| name != "load-balancer")
+        .with_check(short_load_balancer_check())
+        .with_check(SysChartCheck::new(sys_config, PLATFORM))
+}
+
+/// The load balancer check, giving up after a fraction of a second
+fn short_load_balancer_check() -> LoadBalancerCheck {
+    LoadBalancerCheck::new(
+        Duration::from_millis(200),
+        Duration::from_millis(50),
+        Duration::from_millis(100),
+    )
+}
+
+fn in_cluster_checker(harness: &Harness) -> ClusterChecker {
+    ClusterChecker::for_in_cluster()
+        .with_environment(harness.env())
+        .with_check_filter(|name| name != "load-balancer")
+        .with_check(short_load_balancer_check())
+}
+
+fn cli_passes() -> Vec<String> {
+    [
+        "kubectl-binary: pass: kubectl found at /usr/local/bin/kubectl",
+        "helm-binary: pass: helm found at /usr/local/bin/helm",
+        "kubernetes-cluster: pass: Kubectl active cluster fluvio-test at: https://10.0.0.1:6443 found",
+        "kubernetes-version: pass: Supported Kubernetes server 1.27.3 found",
+        "node-readiness: pass: 2 of 2 nodes are ready",
+        "namespace: pass: Namespace default exists",
+        "helm-version: pass: Supported helm version 3.12.0+g1234567 is installed",
+        "service-permission: pass: Can create services",
+        "crd-permission: pass: Can create customresourcedefinitions",
+        "service-account-permission: pass: Can create secrets",
+        "openshift-scc: pass: Not an OpenShift cluster",
+        "load-balancer: pass: Load balancer fluvio-sc-public has address 34.120.10.7 \
+         (LoadBalancerAddress(\"34.120.10.7\"))",
+        "sys-chart: pass: Fluvio system charts are installed",
+    ]
+    .into_iter()
+    .map(String::from)
+    .collect()
+}
+
+#[fluvio_future::test]
+async fn test_healthy_cluster_in_every_run_mode() {
+    let harness = Harness::new(FakeCluster::healthy());
+    let expected = cli_passes();
+
+    assert_eq!(render(cli_checker(&harness).run_parallel().await), expected);
+    assert_eq!(
+        render(cli_checker(&harness).run_wait_parallel(4).await),
+        expected
+    );
+    assert_eq!(render(cli_checker(&harness).run_and_fix().await), expected);
+    let deadline = Instant::now() + Duration::from_secs(60);
+    assert_eq!(
+        render(cli_checker(&harness).run_with_deadline(deadline).await),
+        expected
+    );
+
+    // the checks are spawned onto other tasks, taking their environment along
+    let events: Vec<String> = cli_checker(&harness)
+        .run_with_progress()
+        .into_stream()
+        .map(render_event)
+        .collect()
+        .await;
+    let total = expected.len();
+    let expected_events: Vec<String> = expected
+        .iter()
+        .enumerate()
+        .flat_map(|(index, line)| {
+            let name = line.split(':').next().unwrap_or_default();
+            [
+                format!("started {name} {}/{total}", index + 1),
+                line.clone(),
+            ]
+        })
+        .collect();
+    assert_eq!(events, expected_events);
+
+    let harness = Harness::new(FakeCluster::healthy());
+    assert_eq!(
+        render(cli_checker(&harness).run_until_first_failure().await),
+        expected
+    );
+    assert_eq!(
+        harness.commands(),
+        vec![
+            "helm version --short",
+            "kubectl api-versions",
+            "helm list --filter ^fluvio-sys$ --output json --all-namespaces",
+        ]
+    );
+
+    let pb_factory = ProgressBarFactory::new(true);
+    assert!(cli_checker(&harness)
+        .run(&pb_factory, false)
+        .await
+        .expect("checks pass"));
+}
+
+#[fluvio_future::test]
+async fn test_healthy_cluster_in_every_preset() {
+    let harness = Harness::new(FakeCluster::healthy());
+    let passes = cli_passes();
+    let pass = |name: &str| {
+        passes
+            .iter()
+            .find(|line| line.starts_with(&format!("{name}: ")))
+            .expect("cli check")
+            .clone()
+    };
+
+    assert_eq!(
+        render(harness.checker().with_k8_checks().run_parallel().await),
+        vec![
+            pass("kubernetes-cluster"),
+            pass("helm-version"),
+            pass("kubernetes-version"),
+            pass("node-readiness"),
+            pass("namespace"),
+            pass("load-balancer"),
+        ]
+    );
+
+    assert_eq!(
+        render(
+            harness
+                .checker()
+                .with_upgrade_checks(PLATFORM)
+                .run_and_fix()
+                .await
+        ),
+        vec![
+            pass("kubernetes-cluster"),
+            pass("helm-binary"),
+            pass("helm-version"),
+            pass("sys-chart"),
+            "upgrade-path: pass: Fluvio 0.11.0 is installed, it is already at the target version"
+                .to_string(),
+        ]
+    );
+
+    assert_eq!(
+        render(in_cluster_checker(&harness).run_and_fix().await),
+        vec![
+            "kubectl-binary: skipped: kubectl is not used in-cluster".to_string(),
+            "openshift-scc: skipped: kubectl is not used in-cluster".to_string(),
+            pass("helm-binary"),
+            pass("kubernetes-cluster"),
+            pass("kubernetes-version"),
+            pass("node-readiness"),
+            pass("namespace"),
+            pass("helm-version"),
+            "in-cluster-permission: pass: Service account can list services, create services, \
+             create secrets, create customresourcedefinitions"
+                .to_string(),
+            pass("load-balancer"),
+        ]
+    );
+
+    // in the order the preset adds them, sorted runs check the cluster before its version
+    let disk = LocalDiskSpace::default();
+    assert_eq!(
+        render(harness.checker().with_local_checks().run_parallel().await),
+        vec![
+            pass("helm-version"),
+            pass("kubernetes-version"),
+            pass("kubernetes-cluster"),
+            "local-cluster: pass: Local Fluvio is not installed".to_string(),
+            "port-availability: pass: Ports 9003, 9004, 9005, 9010, 9011 are available".to_string(),
+            format!(
+                "local-disk-space: pass: 51200 MiB available for {}",
+                disk.path.display()
+            ),
+        ]
+    );
+
+    assert_eq!(
+        render(
+            harness
+                .checker()
+                .with_uninstall_checks()
+                .run_parallel()
+                .await
+        ),
+        vec![pass("kubernetes-cluster"), pass("helm-version")]
+    );
+}
+
+#[fluvio_future::test]
+async fn test_missing_sys_chart_is_installed() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.releases.clear();
+    let harness = Harness::new(cluster);
+    let missing = with_status(
+        cli_passes(),
+        "sys-chart",
+        "fixable: System chart not installed, installing version 0.11.0",
+    );
+
+    assert_eq!(render(cli_checker(&harness).run_parallel().await), missing);
+    let pb_factory = ProgressBarFactory::new(true);
+    assert!(matches!(
+        cli_checker(&harness).run(&pb_factory, false).await,
+        Err(ClusterCheckError::PreCheckFlightFailure)
+    ));
+    assert!(harness.cluster().releases.is_empty());
+
+    let mut fixed = missing;
+    fixed.push("sys-chart-fix: pass: Fluvio Sys chart 0.11.0 is installed".to_string());
+    assert_eq!(render(cli_checker(&harness).run_and_fix().await), fixed);
+    assert_eq!(
+        harness.cluster().releases,
+        vec![FakeRelease::new("fluvio-sys", "default", "0.11.0")]
+    );
+    // the fix checks the namespace it installed into
+    assert!(harness.commands().ends_with(&[
+        "helm install fluvio-sys --namespace default".to_string(),
+        "helm list --filter ^fluvio-sys$ --output json --namespace default".to_string(),
+    ]));
+
+    let mut cluster = FakeCluster::healthy();
+    cluster.releases.clear();
+    let harness = Harness::new(cluster);
+    assert!(cli_checker(&harness)
+        .run(&pb_factory, true)
+        .await
+        .expect("sys chart is installed"));
+    assert_eq!(
+        render(cli_checker(&harness).run_parallel().await),
+        cli_passes()
+    );
+}
+
+#[fluvio_future::test]
+async fn test_failed_sys_chart_install() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.releases.clear();
+    cluster.chart_install_error =
+        Some("Error: INSTALLATION FAILED: timed out waiting for the condition");
+    let harness = Harness::new(cluster);
+
+    let mut expected = with_status(
+        cli_passes(),
+        "sys-chart",
+        "fixable: System chart not installed, installing version 0.11.0",
+    );
+    expected.push(
+        "sys-chart-fix: fail: Auto fix for sys-chart failed: Chart Install error: \
+         An unknown error occurred: Error: INSTALLATION FAILED: timed out waiting for the \
+         condition"
+            .to_string(),
+    );
+    assert_eq!(render(cli_checker(&harness).run_and_fix().await), expected);
+
+    let pb_factory = ProgressBarFactory::new(true);
+    assert!(matches!(
+        cli_checker(&harness).run(&pb_factory, true).await,
+        Err(ClusterCheckError::PreCheckFlightFailure)
+    ));
+    assert!(harness.cluster().releases.is_empty());
+}
+
+#[fluvio_future::test]
+async fn test_outdated_sys_chart_is_upgraded() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.chart_version = "0.11.1";
+    let harness = Harness::new(cluster);
+    let upgrade_checker = || {
+        harness
+            .checker()
+            .with_upgrade_checks(Version::new(0, 11, 1))
+    };
+
+    let outdated = vec![
+        "kubernetes-cluster: pass: Kubectl active cluster fluvio-test at: https://10.0.0.1:6443 found",
+        "helm-binary: pass: helm found at /usr/local/bin/helm",
+        "helm-version: pass: Supported helm version 3.12.0+g1234567 is installed",
+        "sys-chart: fixable: Fluvio system chart 0.11.0 is older than the required 0.11.1",
+        "upgrade-path: pass: Fluvio 0.11.0 in namespace default can be upgraded to 0.11.1",
+    ];
+    assert_eq!(render(upgrade_checker().run_parallel().await), outdated);
+
+    let mut upgraded = outdated.clone();
+    upgraded.insert(
+        4,
+        "sys-chart-fix: pass: Fluvio sys chart is upgraded to: 0.11.1",
+    );
+    assert_eq!(render(upgrade_checker().run_and_fix().await), upgraded);
+    assert!(harness.cluster().releases.contains(&FakeRelease::new(
+        "fluvio-sys",
+        "default",
+        "0.11.1"
+    )));
+    assert_eq!(
+        render(upgrade_checker().run_parallel().await)[3],
+        "sys-chart: pass: Fluvio system charts are installed"
+    );
+}
+
+#[fluvio_future::test]
+async fn test_upgrade_checks_look_in_the_checker_namespace() {
+    let harness = Harness::new(FakeCluster::healthy());
+    let results = harness
+        .checker()
+        .with_namespace("staging")
+        .with_upgrade_checks(Version::new(0, 11, 1))
+        .run_parallel()
+        .await;
+
+    assert_eq!(
+        render(results).last().map(String::as_str),
+        Some("upgrade-path: fail: There is no Fluvio installation to upgrade in namespace staging")
+    );
+    assert!(harness
+        .commands()
+        .contains(&"helm list --filter ^fluvio$ --output json --namespace staging".to_string()));
+}
+
+/// helm 3.2 installed, sys chart missing, the load balancer never gets an
+/// address and CRDs can't be created
+fn broken_cluster() -> FakeCluster {
+    let mut cluster = FakeCluster::healthy();
+    cluster.helm_version = "3.2.0";
+    cluster.releases.clear();
+    cluster.load_balancers = vec![("fluvio-sc-public", None)];
+    cluster.denied = vec!["customresourcedefinitions"];
+    cluster
+}
+
+#[fluvio_future::test]
+async fn test_broken_cluster_fails_fast() {
+    let harness = Harness::new(broken_cluster());
+    let old_helm = "fail: Must have helm version >=3.3.4. You have 3.2.0+g1234567";
+    let expected = with_status(
+        with_status(
+            with_status(
+                with_status(cli_passes(), "helm-version", old_helm),
+                "crd-permission",
+                "fail: Permissions to create customresourcedefinitions denied",
+            ),
+            "load-balancer",
+            "fail: Load balancer service is not available",
+        ),
+        "sys-chart",
+        "fixable: System chart not installed, installing version 0.11.0",
+    );
+
+    assert_eq!(render(cli_checker(&harness).run_parallel().await), expected);
+    assert_eq!(
+        render(cli_checker(&harness).run_until_first_failure().await),
+        expected[..7]
+    );
+
+    // the sys chart check needs a supported helm, so nothing is fixed
+    let pb_factory = ProgressBarFactory::new(true);
+    assert!(matches!(
+        cli_checker(&harness).run(&pb_factory, true).await,
+        Err(ClusterCheckError::PreCheckFlightFailure)
+    ));
+    assert!(harness.cluster().releases.is_empty());
+
+    let mut fixed = expected;
+    fixed.push("sys-chart-fix: pass: Fluvio Sys chart 0.11.0 is installed".to_string());
+    assert_eq!(render(cli_checker(&harness).run_and_fix().await), fixed);
+
+    let in_cluster = render(in_cluster_checker(&harness).run_parallel().await);
+    assert_eq!(
+        in_cluster[7..],
+        [
+            format!("helm-version: {old_helm}"),
+            "in-cluster-permission: fail: The service account of the pod is not allowed \
+             to create customresourcedefinitions"
+                .to_string(),
+            "load-balancer: fail: Load balancer service is not available".to_string(),
+        ]
+    );
+}
+
+#[fluvio_future::test]
+async fn test_load_balancer_by_infra() {
+    let load_balancer = |context: &'static str, assigned_address| async move {
+        let mut cluster = FakeCluster::healthy();
+        cluster.context = Some(context);
+        cluster.load_balancers = vec![("fluvio-sc-public", None)];
+        cluster.assigned_address = assigned_address;
+        let harness = Harness::new(cluster);
+        let checker = harness.checker().with_check(short_load_balancer_check());
+        render(checker.run_parallel().await)
+    };
+
+    assert_eq!(
+        load_balancer("kind-fluvio", None).await,
+        vec![
+            "load-balancer: pass: kind gives LoadBalancer services no address without \
+             MetalLB, use NodePort services instead (NodePortRecommended)"
+        ]
+    );
+    assert_eq!(
+        load_balancer("docker-desktop", None).await,
+        vec!["load-balancer: fail: Docker Desktop gave no address to the load balancer service"]
+    );
+    assert_eq!(
+        load_balancer("minikube", None).await,
+        vec![
+            "load-balancer: fail: minikube gave no address to the load balancer service, \
+             'minikube tunnel' is not running"
+        ]
+    );
+    assert_eq!(
+        load_balancer("fluvio-test", Some("34.120.10.8")).await,
+        vec![
+            "load-balancer: pass: Load balancer fluvio-sc-public has address 34.120.10.8 \
+             (LoadBalancerAddress(\"34.120.10.8\"))"
+        ]
+    );
+}
+
+#[fluvio_future::test]
+async fn test_load_balancer_probe() {
+    let probe = |assigned_address| async move {
+        let mut cluster = FakeCluster::healthy();
+        cluster.load_balancers.clear();
+        cluster.assigned_address = assigned_address;
+        let harness = Harness::new(cluster);
+        let checker = harness
+            .checker()
+            .with_check(short_load_balancer_check())
+            .with_namespace("fluvio");
+        (render(checker.run_parallel().await), harness)
+    };
+    let create = "kubectl create service loadbalancer fluvio-dummy-service --tcp=5555:5555 \
+                  --namespace default";
+    let delete = "kubectl delete service fluvio-dummy-service --namespace default \
+                  --ignore-not-found";
+
+    // the namespace of the install does not exist yet
+    let (results, harness) = probe(Some("34.120.10.9")).await;
+    assert_eq!(
+        results,
+        vec![
+            "load-balancer: pass: Load balancer fluvio-dummy-service has address 34.120.10.9 \
+             (LoadBalancerAddress(\"34.120.10.9\"))"
+        ]
+    );
+    assert_eq!(harness.commands(), vec![create, delete]);
+    assert!(harness.cluster().load_balancers.is_empty());
+
+    let (results, harness) = probe(None).await;
+    assert_eq!(
+        results,
+        vec!["load-balancer: fail: Load balancer service is not available"]
+    );
+    assert_eq!(harness.commands()[..2], [create, delete]);
+    assert!(harness.cluster().load_balancers.is_empty());
+}
+
+#[fluvio_future::test]
+async fn test_no_kubeconfig() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.context = None;
+    let harness = Harness::new(cluster);
+
+    assert_eq!(
+        render(cli_checker(&harness).run_until_first_failure().await),
+        vec![
+            "kubectl-binary: pass: kubectl found at /usr/local/bin/kubectl",
+            "helm-binary: pass: helm found at /usr/local/bin/helm",
+            "kubernetes-cluster: fail: There is no active Kubernetes context",
+        ]
+    );
+
+    // only the helm version is checked without a cluster
+    let harness = Harness::new(harness.cluster().clone());
+    let pb_factory = ProgressBarFactory::new(true);
+    assert!(matches!(
+        cli_checker(&harness).run(&pb_factory, true).await,
+        Err(ClusterCheckError::PreCheckFlightFailure)
+    ));
+    assert_eq!(harness.commands(), vec!["helm version --short"]);
+}
+
+#[fluvio_future::test]
+async fn test_missing_helm() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.binaries = vec!["kubectl"];
+    let harness = Harness::new(cluster);
+
+    let expected = with_status(
+        with_status(
+            with_status(
+                cli_passes(),
+                "helm-binary",
+                "fail: helm was not found in PATH",
+            ),
+            "helm-version",
+            "fail: No Helm client: Unable to find helm: Unable to run `helm version --short`: \
+             helm: command not found",
+        ),
+        "sys-chart",
+        "fail: Helm client error",
+    );
+    assert_eq!(render(cli_checker(&harness).run_parallel().await), expected);
+
+    // checks needing helm are skipped
+    let harness = Harness::new(harness.cluster().clone());
+    let pb_factory = ProgressBarFactory::new(true);
+    assert!(matches!(
+        cli_checker(&harness).run(&pb_factory, true).await,
+        Err(ClusterCheckError::PreCheckFlightFailure)
+    ));
+    assert_eq!(harness.commands(), vec!["kubectl api-versions"]);
+}
+
+#[fluvio_future::test]
+async fn test_openshift_without_scc() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.openshift = true;
+    let harness = Harness::new(cluster);
+
+    let mut expected = with_status(
+        cli_passes(),
+        "openshift-scc",
+        "fail: Service account fluvio in namespace default may not run as any user",
+    );
+    let scc = expected
+        .iter()
+        .position(|line| line.starts_with("openshift-scc: "))
+        .expect("openshift check");
+    expected.truncate(scc + 1);
+    assert_eq!(
+        render(cli_checker(&harness).run_until_first_failure().await),
+        expected
+    );
+    assert_eq!(
+        harness.commands(),
+        vec![
+            "helm version --short",
+            "kubectl api-versions",
+            "kubectl get securitycontextconstraints -o json",
+            "kubectl auth can-i use securitycontextconstraints/anyuid \
+             --as=system:serviceaccount:default:fluvio",
+        ]
+    );
+}
+
+#[fluvio_future::test]
+async fn test_invalid_tls_secret() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.secrets = vec![
+        ("fluvio-tls", vec!["tls.crt"]),
+        ("fluvio-ca", vec!["ca.crt"]),
+    ];
+    let harness = Harness::new(cluster);
+
+    assert_eq!(
+        render(
+            cli_checker(&harness)
+                .with_tls_checks(&TlsPolicy::Disabled, TlsSecrets::default())
+                .run_parallel()
+                .await
+        ),
+        cli_passes()
+    );
+
+    let mut expected = cli_passes();
+    expected
+        .push("tls-secrets: fail: TLS secret fluvio-tls is invalid: missing tls.key".to_string());
+    assert_eq!(
+        render(
+            cli_checker(&harness)
+                .with_tls_checks(&TlsPolicy::Anonymous, TlsSecrets::default())
+                .run_parallel()
+                .await
+        ),
+        expected
+    );
+}
+
+#[fluvio_future::test]
+async fn test_local_cluster_already_running() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.processes = vec![vec!["/usr/local/bin/fluvio-run", "sc", "--local"]];
+    cluster.taken_ports = vec![9004];
+    cluster.available_disk = 100 * 1024 * 1024;
+    let harness = Harness::new(cluster);
+
+    assert_eq!(
+        render(harness.checker().with_no_k8_checks().run_parallel().await),
+        vec![
+            "local-cluster: fail: A local Fluvio cluster is already running",
+            "port-availability: fail: Port 9004 is already in use",
+            "local-disk-space: fail: Only 100 MiB of disk space available, 1024 MiB required",
+        ]
+    );
+    assert_eq!(
+        render(
+            harness
+                .checker()
+                .with_no_k8_checks()
+                .run_until_first_failure()
+                .await
+        ),
+        vec!["local-cluster: fail: A local Fluvio cluster is already running"]
+    );
+}
+
+#[fluvio_future::test]
+async fn test_unreachable_sc() {
+    let mut cluster = FakeCluster::healthy();
+    let harness = Harness::new(cluster.clone());
+    assert_eq!(
+        render(
+            harness.checker().with_post_install_checks().run_parallel().await
+        ),
+        vec!["sc-connectivity: error: Other failure: no cluster profile: Config has no active profile"]
+    );
+
+    cluster.sc_endpoint = Some("127.0.0.1:1");
+    let harness = Harness::new(cluster);
+    assert_eq!(
+        render(
+            harness
+                .checker()
+                .with_post_install_checks()
+                .run_parallel()
+                .await
+        ),
+        vec!["sc-connectivity: fail: Could not connect to SC at 127.0.0.1:1"]
+    );
+}
+
+#[fluvio_future::test]
+async fn test_nodes_not_ready_and_namespace_denied() {
+    let mut cluster = FakeCluster::healthy();
+    cluster.nodes = vec![("node-1", false)];
+    cluster.namespaces = vec!["kube-system"];
+    cluster.denied = vec!["namespaces"];
+    let harness = Harness::new(cluster);
+    let checker = || harness.checker().with_k8_checks();
+
+    assert_eq!(
+        render(checker().run_parallel().await),
+        vec![
+            "kubernetes-cluster: pass: Kubectl active cluster fluvio-test at: https://10.0.0.1:6443 found",
+            "helm-version: pass: Supported helm version 3.12.0+g1234567 is installed",
+            "kubernetes-version: pass: Supported Kubernetes server 1.27.3 found",
+            "node-readiness: fail: 0 Kubernetes nodes ready, 1 required. Not ready: node-1 (Ready=False (KubeletNotReady))",
+            "namespace: fail: Namespace default does not exist and the current user can't create it",
+            "load-balancer: pass: Load balancer fluvio-sc-public has address 34.120.10.7 \
+             (LoadBalancerAddress(\"34.120.10.7\"))",
+        ]
+    );
+    assert_eq!(render(checker().run_until_first_failure().await).len(), 4);
+}
@@ -147,6 +147,33 @@ fn generate_request_trait_impl(name: &Ident, attrs: &[Attribute]) -> TokenStream
             quote! {}
         };
 
+    let retry_count = find_int_name_value(&version_meta, "retry_count");
+    let retry_backoff_ms = find_int_name_value(&version_meta, "retry_backoff_ms");
+    let retry = match (retry_count, retry_backoff_ms) {
+        (Some(count), _) if count > u32::MAX as u64 => {
+            syn::Error::new(version_meta.span(), "retry_count must fit in a u32").to_compile_error()
+        }
+        (Some(count), backoff_ms) => {
+            let backoff_ms = backoff_ms.map(|backoff_ms| {
+                quote! {
+                    const RETRY_BACKOFF_MS: Option<u64> = Some(#backoff_ms);
+                }
+            });
+            let count = count as u32;
+            quote! {
+                const RETRY_COUNT: Option<u32> = Some(#count);
+
+                #backoff_ms
+            }
+        }
+        (None, Some(_)) => syn::Error::new(
+            version_meta.span(),
+            "retry_backoff_ms needs retry_count, it is the wait between retries",
+        )
+        .to_compile_error(),
+        (None, None) => quote! {},
+    };
+
     quote! {
 
         impl Request for #name {
@@ -157,6 +184,8 @@ fn generate_request_trait_impl(name: &Ident, attrs: &[Attribute]) -> TokenStream
 
             #max_version
 
+            #retry
+
             type Response = #response_type;
 
         }
@@ -39,6 +39,10 @@ impl ContainerAttributes {
                                 if let Lit::Int(lit_int) = &name_value.lit {
                                     cont_attr.api_key = Some(lit_int.base10_parse::<u8>()?);
                                 }
+                            } else if name_value.path.is_ident("retry_count")
+                                || name_value.path.is_ident("retry_backoff_ms")
+                            {
+                                // parsed along with the api version, see `api::generate_request_trait_impl`
                             } else if name_value.path.is_ident("response") {
                                 if let Lit::Str(lit_str) = &name_value.lit {
                                     cont_attr.response = Some(lit_str.value());
@@ -41,6 +41,8 @@ pub(crate) enum FluvioBound {
     Encoder,
     Decoder,
     Default,
+    Debug,
+    Schema,
 }
 
 pub(crate) fn add_bounds(
@@ -64,6 +66,14 @@ pub(crate) fn add_bounds(
                 FluvioBound::Default => {
                     type_param.bounds.push(parse_quote!(Default));
                 }
+                FluvioBound::Debug => {
+                    type_param.bounds.push(parse_quote!(std::fmt::Debug));
+                }
+                FluvioBound::Schema => {
+                    type_param
+                        .bounds
+                        .push(parse_quote!(fluvio_protocol::schema::FluvioSchemaProvider));
+                }
             }
             if attr.trace {
                 type_param.bounds.push(parse_quote!(std::fmt::Debug));
@@ -1,7 +1,12 @@
-use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
-use syn::{Attribute, Error, Field, Lit, Meta, NestedMeta, Type};
+use syn::{Attribute, Error, Field, Lit, Meta, MetaNameValue, NestedMeta, Path, Type};
+
+use crate::util::{find_all_attrs, get_lit_str_list, AttrItem};
 
 #[derive(Clone)]
 pub(crate) struct NamedProp {
@@ -17,6 +22,18 @@ pub(crate) struct UnnamedProp {
 }
 
 impl NamedProp {
+    /// Name of the field in debug output and trace messages
+    pub fn wire_name(&self) -> &str {
+        self.attrs.rename.as_deref().unwrap_or(&self.field_name)
+    }
+
+    pub fn is_fixed_width(&self) -> bool {
+        !self.attrs.varint
+            && !self.attrs.varint_zigzag
+            && !self.attrs.length_delimited
+            && is_fixed_width_primitive(&self.field_type)
+    }
+
     pub fn from_ast(field: &Field) -> syn::Result<Self> {
         let field_ident = if let Some(ident) = &field.ident {
             ident.clone()
@@ -29,17 +46,20 @@ impl NamedProp {
         let field_name = field_ident.to_string();
         let field_type = field.ty.clone();
         let attrs = PropAttrs::from_ast(&field.attrs)?;
+        attrs.check_varint_zigzag_type(&field.ty)?;
+        if attrs.flatten && is_fixed_width_primitive(&field.ty) {
+            return Err(Error::new(
+                field.ty.span(),
+                "flatten needs a struct deriving Encoder and Decoder, not a primitive.",
+            ));
+        }
         let prop = NamedProp {
             field_name,
             field_type,
             attrs,
         };
 
-        let result = validate_versions(
-            prop.attrs.min_version,
-            prop.attrs.max_version,
-            Some(&prop.field_name),
-        );
+        let result = prop.attrs.validate_literal_versions(Some(&prop.field_name));
 
         if let Some(err) = result {
             Err(syn::Error::new(field.span(), err))
@@ -53,10 +73,16 @@ impl NamedProp {
         field_stream: TokenStream,
         trace: bool,
     ) -> TokenStream {
-        let min = self.attrs.min_version;
-        let field_name = &self.field_name;
+        let min = &self.attrs.min_version;
+        let field_name = self.wire_name();
+        let field_stream = if trace {
+            self.attrs
+                .deprecation_trace(quote! { stringify!(#field_name) }, field_stream)
+        } else {
+            field_stream
+        };
 
-        if let Some(max) = self.attrs.max_version {
+        if let Some(max) = &self.attrs.max_version {
             let trace = if trace {
                 quote! {
                     else {
@@ -93,12 +119,32 @@ impl NamedProp {
 }
 
 impl UnnamedProp {
+    pub fn is_fixed_width(&self) -> bool {
+        !self.attrs.varint
+            && !self.attrs.varint_zigzag
+            && !self.attrs.length_delimited
+            && is_fixed_width_primitive(&self.field_type)
+    }
+
     pub fn from_ast(field: &Field) -> syn::Result<Self> {
         let attrs = PropAttrs::from_ast(&field.attrs)?;
+        attrs.check_varint_zigzag_type(&field.ty)?;
+        if attrs.rename.is_some() {
+            return Err(Error::new(
+                field.span(),
+                "Only named fields can be renamed.",
+            ));
+        }
+        if attrs.flatten {
+            return Err(Error::new(
+                field.span(),
+                "Only named fields can be flattened.",
+            ));
+        }
         let field_type = field.ty.clone();
         let prop = UnnamedProp { field_type, attrs };
 
-        let result = validate_versions(prop.attrs.min_version, prop.attrs.max_version, None);
+        let result = prop.attrs.validate_literal_versions(None);
 
         if let Some(err) = result {
             Err(syn::Error::new(field.span(), err))
@@ -112,9 +158,15 @@ impl UnnamedProp {
         field_stream: TokenStream,
         trace: bool,
     ) -> TokenStream {
-        let min = self.attrs.min_version;
+        let min = &self.attrs.min_version;
+        let field_stream = if trace {
+            self.attrs
+                .deprecation_trace(quote! { "from tuple struct" }, field_stream)
+        } else {
+            field_stream
+        };
 
-        if let Some(max) = self.attrs.max_version {
+        if let Some(max) = &self.attrs.max_version {
             let trace = if trace {
                 quote! {
                     else {
@@ -152,8 +204,29 @@ impl UnnamedProp {
     }
 }
 
+/// Whether values of `ty` always encode to the same number of bytes
+pub(crate) fn is_fixed_width_primitive(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+    if type_path.qself.is_some() {
+        return false;
+    }
+    type_path.path.get_ident().is_some_and(|ident| {
+        matches!(
+            ident.to_string().as_str(),
+            "bool" | "i8" | "u8" | "i16" | "u16" | "i32" | "u32" | "i64" | "u64"
+        )
+    })
+}
+
 pub fn validate_versions(min: i16, max: Option<i16>, field: Option<&str>) -> Option<String> {
     match (max, field) {
+        // Checked first, a max version doesn't make a negative min valid
+        (_, Some(field)) if min < 0 => {
+            Some(format!("field `{field}`: min_version {min} must be ≥ 0"))
+        }
+        (_, None) if min < 0 => Some(format!("min_version {min} must be ≥ 0")),
         // Print name in named fields
         (Some(max), Some(field)) if min > max => Some(format!(
             "On {field}, max version({max}) is less than min({min})."
@@ -162,65 +235,749 @@ pub fn validate_versions(min: i16, max: Option<i16>, field: Option<&str>) -> Opt
         (Some(max), None) if min > max => {
             Some(format!("Max version({max}) is less than min({min})."))
         }
-        (None, Some(field)) if min < 0 => {
-            Some(format!("On {field} min version({min}) must be positive."))
-        }
-        (None, None) if min < 0 => Some(format!("Min version({min}) must be positive.")),
         _ => None,
     }
 }
 
+pub fn validate_deprecated_since(
+    min: i16,
+    deprecated_since: Option<i16>,
+    field: Option<&str>,
+) -> Option<String> {
+    match (deprecated_since, field) {
+        (Some(since), Some(field)) if min > since => Some(format!(
+            "On {field}, deprecated_since({since}) is less than min version({min})."
+        )),
+        (Some(since), None) if min > since => Some(format!(
+            "Deprecated_since({since}) is less than min version({min})."
+        )),
+        _ => None,
+    }
+}
+
+/// Value of a version attribute, such as `min_version`
+#[derive(Clone)]
+pub(crate) enum PropAttrsType {
+    /// `min_version = 3`
+    Int(i16),
+    /// `min_version = MIN_VERSION` or `min_version = crate::protocol::MIN_VERSION`,
+    /// a constant that is only known once the generated code is compiled
+    Path(Path),
+    /// `min_version = "min_version()"` or `min_version = crate::protocol::min_version()`,
+    /// a `const fn` without arguments, also only known once the generated code is compiled
+    ConstFn(Path),
+}
+
+impl PropAttrsType {
+    /// The version if it is an integer literal
+    pub fn as_int(&self) -> Option<i16> {
+        match self {
+            Self::Int(version) => Some(*version),
+            Self::Path(_) | Self::ConstFn(_) => None,
+        }
+    }
+
+    /// The version as written in the attribute, for messages
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Int(version) => version.to_string(),
+            Self::Path(path) => path.to_token_stream().to_string().replace(' ', ""),
+            Self::ConstFn(path) => format!("{}()", path.to_token_stream()).replace(' ', ""),
+        }
+    }
+}
+
+impl Default for PropAttrsType {
+    fn default() -> Self {
+        Self::Int(0)
+    }
+}
+
+impl ToTokens for PropAttrsType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Self::Int(version) => version.to_tokens(tokens),
+            Self::Path(path) => path.to_tokens(tokens),
+            Self::ConstFn(path) => quote! { #path() }.to_tokens(tokens),
+        }
+    }
+}
+
 #[derive(Default, Clone)]
 pub(crate) struct PropAttrs {
     pub varint: bool,
     /// Will default to 0 if not specified.
     /// Note: `None` is encoded as "-1" so it's i16.
-    pub min_version: i16,
+    pub min_version: PropAttrsType,
     /// Optional max version.
     /// The field won't be decoded from the buffer if it has a larger version than what is specified here.
     /// Note: `None` is encoded as "-1" so it's i16.
-    pub max_version: Option<i16>,
+    pub max_version: Option<PropAttrsType>,
     /// Sets this value to the field when it isn't present in the buffer.
     /// Example: `#[fluvio(default = "-1")]`
     pub default_value: Option<String>,
+    /// Function called for the value of the field when it isn't present in the buffer,
+    /// for defaults that can't be written as an expression in `default`.
+    /// Example: `#[fluvio(default_fn = "default_names")]` with `fn default_names() -> Vec<String>`
+    pub default_fn: Option<Path>,
+    /// Keeps the value out of `FluvioDebug` output and trace messages.
+    /// Example: `#[fluvio(sensitive)]`
+    pub sensitive: bool,
+    /// Version from which the field is deprecated.
+    /// It is still encoded and decoded within its version range, so peers on older versions keep working.
+    /// Deriving `Encoder` warns about it, unless the field is marked `#[allow(deprecated)]`.
+    /// Example: `#[fluvio(deprecated_since = 5)]`
+    pub deprecated_since: Option<PropAttrsType>,
+    /// Where `deprecated_since` is set, for the deprecation warning
+    pub deprecated_since_span: Option<Span>,
+    /// Lint attributes of the field, such as `#[allow(deprecated)]`
+    pub lints: Vec<Attribute>,
+    /// Labels listed with the field in its `FluvioSchema`, such as the features it belongs to.
+    /// Example: `#[fluvio(tags = "kafka-v2", "internal")]`
+    pub tags: Vec<String>,
+    /// Name of the field in debug output and trace messages, the Rust field name is unchanged.
+    /// Example: `#[fluvio(rename = "throttleTimeMs")]`
+    pub rename: Option<String>,
+    /// Leaves the field out of the encoded bytes, it is still decoded.
+    /// Example: `#[fluvio(skip_encoding)]`
+    pub skip_encoding: bool,
+    /// Fills the field from its default instead of the buffer, it is still encoded.
+    /// Example: `#[fluvio(skip_decoding, default = "-1")]`
+    pub skip_decoding: bool,
+    /// Function called with the decoded value, decoding fails with the `String` it returns as error.
+    /// Example: `#[fluvio(validate = "non_negative")]` with `fn non_negative(value: &i32) -> Result<(), String>`
+    pub validate: Option<Path>,
+    /// Integer type of the length prefix of a `Vec` field, one of `i16`, `u16`, `i32` or `u32`.
+    /// Collections are prefixed with an `i32` length when it isn't set.
+    /// Example: `#[fluvio(array_length_encoding = "u16")]`
+    pub array_length_encoding: Option<String>,
+    /// Encodes the field, a struct deriving `Encoder` and `Decoder`, through its own impls
+    /// at the version of the container, without a length prefix.
+    /// Example: `#[fluvio(flatten)]`
+    pub flatten: bool,
+    /// Encodes a signed integer field as a zigzag varint, so small negative values take one byte.
+    /// Works for `i8`, `i16` and `i32`, `#[varint]` covers `i64`.
+    /// Example: `#[fluvio(varint_zigzag)]`
+    pub varint_zigzag: bool,
+    /// Prefixes the encoded field with its size as an `i32`, so readers can skip a message they
+    /// don't know. Bytes the field's decoder leaves within that size are skipped.
+    /// Example: `#[fluvio(length_delimited)]`
+    pub length_delimited: bool,
+}
+
+/// Parses the version of `min_version`, `max_version` or `deprecated_since`
+///
+/// A string ending in `()`, such as `"min_version()"`, calls that `const fn`.
+/// Constants and calls are written as paths instead of literals in the attribute.
+fn version_value(name_value: &MetaNameValue) -> syn::Result<PropAttrsType> {
+    let name = name_value.path.to_token_stream().to_string();
+    let not_a_version = |span| {
+        Error::new(
+            span,
+            format!(
+                "{name} expects an integer literal, the path of a constant or a const fn call, such as `{name} = 3` or `{name} = \"{name}()\"`."
+            ),
+        )
+    };
+    match &name_value.lit {
+        Lit::Int(lit_int) => lit_int.base10_parse::<i16>().map(PropAttrsType::Int),
+        Lit::Str(lit_str) => lit_str
+            .value()
+            .strip_suffix("()")
+            .and_then(|const_fn| syn::parse_str::<Path>(const_fn).ok())
+            .map(PropAttrsType::ConstFn)
+            .ok_or_else(|| not_a_version(lit_str.span())),
+        lit => Err(not_a_version(lit.span())),
+    }
+}
+
+/// Errors if the attribute `name` was already seen on the field
+fn check_unique(names: &mut HashSet<String>, name: &str, span: Span) -> syn::Result<()> {
+    if names.insert(name.to_string()) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            span,
+            format!("{name} is set more than once, keep only one of them."),
+        ))
+    }
 }
 
+/// Integer types a collection length can be encoded as
+const ARRAY_LENGTH_TYPES: [&str; 4] = ["i16", "u16", "i32", "u32"];
+
+/// Integer types `varint_zigzag` can encode
+const ZIGZAG_TYPES: [&str; 3] = ["i8", "i16", "i32"];
+
 impl PropAttrs {
+    /// Versions the field is encoded in, `i16::MAX` as upper bound without `max_version`
+    ///
+    /// `None` when a bound is the path of a constant, which can't be evaluated at derive time.
+    pub fn effective_version_range(&self) -> Option<(i16, i16)> {
+        let min = self.min_version.as_int()?;
+        let max = match &self.max_version {
+            Some(max) => max.as_int()?,
+            None => i16::MAX,
+        };
+        Some((min, max))
+    }
+
+    /// Checks the version range where it is made of integer literals
+    ///
+    /// Bounds given as paths are left to [`version_assertions`](Self::version_assertions).
+    pub fn validate_literal_versions(&self, field: Option<&str>) -> Option<String> {
+        let min = self.min_version.as_int()?;
+        let max = match &self.max_version {
+            Some(max) => Some(max.as_int()?),
+            None => None,
+        };
+        let deprecated_since = match &self.deprecated_since {
+            Some(since) => Some(since.as_int()?),
+            None => None,
+        };
+        validate_versions(min, max, field)
+            .or_else(|| validate_deprecated_since(min, deprecated_since, field))
+    }
+
+    /// The checks of [`validate_literal_versions`](Self::validate_literal_versions) as const
+    /// assertions, when a bound is a constant or a `const fn` call only the compiler can evaluate
+    pub fn version_assertions(&self, field: Option<&str>) -> TokenStream {
+        let mut bounds = std::iter::once(&self.min_version)
+            .chain(&self.max_version)
+            .chain(&self.deprecated_since);
+        if bounds.all(|bound| bound.as_int().is_some()) {
+            return quote! {};
+        }
+        let min = &self.min_version;
+        let prefix = field
+            .map(|field| format!("On {field}, "))
+            .unwrap_or_default();
+        let message = format!("{prefix}min_version({}) must be ≥ 0.", min.describe());
+        let mut assertions = vec![quote! { assert!(#min >= 0, #message); }];
+        if let Some(max) = &self.max_version {
+            let message = format!(
+                "{prefix}max version({}) is less than min({}).",
+                max.describe(),
+                min.describe()
+            );
+            assertions.push(quote! { assert!(#min <= #max, #message); });
+        }
+        if let Some(since) = &self.deprecated_since {
+            let message = format!(
+                "{prefix}deprecated_since({}) is less than min version({}).",
+                since.describe(),
+                min.describe()
+            );
+            assertions.push(quote! { assert!(#min <= #since, #message); });
+        }
+        quote! {
+            const _: () = {
+                #(#assertions)*
+            };
+        }
+    }
+
+    /// Errors if the field is `varint_zigzag` but not one of the signed integers it can encode
+    pub fn check_varint_zigzag_type(&self, ty: &Type) -> syn::Result<()> {
+        if !self.varint_zigzag {
+            return Ok(());
+        }
+        let is_zigzag_type = match ty {
+            Type::Path(type_path) => type_path
+                .path
+                .get_ident()
+                .is_some_and(|ident| ZIGZAG_TYPES.contains(&ident.to_string().as_str())),
+            _ => false,
+        };
+        if is_zigzag_type {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ty.span(),
+                format!(
+                    "varint_zigzag needs a signed integer field, one of: {}.",
+                    ZIGZAG_TYPES.join(", ")
+                ),
+            ))
+        }
+    }
+
+    /// Value to print in trace messages, hiding sensitive fields
+    pub fn trace_value(&self, value: TokenStream) -> TokenStream {
+        if self.sensitive {
+            quote! { format_args!("<redacted>") }
+        } else {
+            value
+        }
+    }
+
+    /// Value of the field when it is not read from the buffer, `default`, `default_fn()` or `Default::default()`
+    pub fn default_token_stream(&self) -> TokenStream {
+        if let Some(default_fn) = &self.default_fn {
+            return quote! { #default_fn() };
+        }
+        self.default_value
+            .as_deref()
+            .and_then(|def| TokenStream::from_str(def).ok())
+            .unwrap_or_else(|| quote! { std::default::Default::default() })
+    }
+
+    /// Runs the `validate` function on the decoded `value`, failing the decode with its error
+    pub fn validation_token_stream(&self, value: TokenStream, field: TokenStream) -> TokenStream {
+        match &self.validate {
+            Some(validate) => quote! {
+                if let Err(err) = #validate(#value) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("Invalid value for field <{}>: {}", #field, err),
+                    ));
+                }
+            },
+            None => quote! {},
+        }
+    }
+
+    /// Expression encoding `value` into `dest`, writing the `array_length_encoding` prefix if set
+    pub fn encode_token_stream(&self, value: TokenStream) -> TokenStream {
+        if self.flatten {
+            return quote! { fluvio_protocol::Encoder::encode(&#value, dest, version) };
+        }
+        if self.varint_zigzag {
+            return quote! {
+                fluvio_protocol::core::EncoderVarInt::encode_varint(&i64::from(#value), dest)
+            };
+        }
+        if self.length_delimited {
+            return quote! {
+                ({
+                    let mut message: Vec<u8> =
+                        Vec::with_capacity(fluvio_protocol::Encoder::write_size(&#value, version));
+                    fluvio_protocol::Encoder::encode(&#value, &mut message, version).and_then(|_| {
+                        let len = <i32 as std::convert::TryFrom<usize>>::try_from(message.len()).map_err(|_| {
+                            std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                format!("{} bytes don't fit in an i32 length", message.len()),
+                            )
+                        })?;
+                        fluvio_protocol::Encoder::encode(&len, dest, version)?;
+                        if fluvio_protocol::bytes::BufMut::remaining_mut(dest) < message.len() {
+                            return Err(std::io::Error::new(
+                                std::io::ErrorKind::UnexpectedEof,
+                                "not enough capacity for length delimited message",
+                            ));
+                        }
+                        fluvio_protocol::bytes::BufMut::put_slice(dest, &message);
+                        Ok(())
+                    })
+                })
+            };
+        }
+        match &self.array_length_encoding {
+            Some(len_type) => {
+                let len_type = format_ident!("{}", len_type);
+                quote! {
+                    <#len_type as std::convert::TryFrom<usize>>::try_from(#value.len())
+                        .map_err(|_| {
+                            std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                format!("{} items don't fit in a {} length", #value.len(), stringify!(#len_type)),
+                            )
+                        })
+                        .and_then(|len| len.encode(dest, version))
+                        .and_then(|_| #value.iter().try_for_each(|item| item.encode(dest, version)))
+                }
+            }
+            None => quote! { #value.encode(dest, version) },
+        }
+    }
+
+    /// Expression for the encoded size of `value`, counting the `array_length_encoding` prefix if set
+    pub fn write_size_token_stream(&self, value: TokenStream) -> TokenStream {
+        if self.flatten {
+            return quote! { fluvio_protocol::Encoder::write_size(&#value, version) };
+        }
+        if self.varint_zigzag {
+            return quote! {
+                fluvio_protocol::core::EncoderVarInt::var_write_size(&i64::from(#value))
+            };
+        }
+        if self.length_delimited {
+            return quote! {
+                (std::mem::size_of::<i32>() + fluvio_protocol::Encoder::write_size(&#value, version))
+            };
+        }
+        match &self.array_length_encoding {
+            Some(len_type) => {
+                let len_type = format_ident!("{}", len_type);
+                quote! {
+                    (std::mem::size_of::<#len_type>()
+                        + #value.iter().map(|item| item.write_size(version)).sum::<usize>())
+                }
+            }
+            None => quote! { #value.write_size(version) },
+        }
+    }
+
+    /// Expression decoding `value` from `src`, reading the `array_length_encoding` prefix if set
+    pub fn decode_token_stream(&self, value: TokenStream) -> TokenStream {
+        if self.flatten {
+            return quote! { fluvio_protocol::Decoder::decode(&mut #value, src, version) };
+        }
+        if self.length_delimited {
+            return quote! {
+                ({
+                    let mut len: i32 = 0;
+                    fluvio_protocol::Decoder::decode(&mut len, src, version).and_then(|_| {
+                        let available = fluvio_protocol::bytes::Buf::remaining(src);
+                        let size = <usize as std::convert::TryFrom<i32>>::try_from(len)
+                            .ok()
+                            .filter(|size| *size <= available)
+                            .ok_or_else(|| {
+                                std::io::Error::new(
+                                    std::io::ErrorKind::InvalidData,
+                                    fluvio_protocol::DecodeError::LengthDelimitedOverflow {
+                                        length: len,
+                                        available,
+                                    },
+                                )
+                            })?;
+                        let mut message = fluvio_protocol::bytes::Buf::take(&mut *src, size);
+                        fluvio_protocol::Decoder::decode(&mut #value, &mut message, version)?;
+                        // skip what this version doesn't know about
+                        let unread = fluvio_protocol::bytes::Buf::remaining(&message);
+                        fluvio_protocol::bytes::Buf::advance(&mut message, unread);
+                        Ok(())
+                    })
+                })
+            };
+        }
+        if self.varint_zigzag {
+            return quote! {
+                ({
+                    let mut wide: i64 = 0;
+                    fluvio_protocol::core::DecoderVarInt::decode_varint(&mut wide, src).and_then(|_| {
+                        #value = std::convert::TryFrom::try_from(wide).map_err(|_| {
+                            std::io::Error::new(
+                                std::io::ErrorKind::InvalidData,
+                                format!("zigzag varint {} is out of range", wide),
+                            )
+                        })?;
+                        Ok(())
+                    })
+                })
+            };
+        }
+        match &self.array_length_encoding {
+            Some(len_type) => {
+                let len_type = format_ident!("{}", len_type);
+                quote! {
+                    ({
+                        let mut len: #len_type = 0;
+                        len.decode(src, version).and_then(|_| {
+                            // negative lengths are null arrays, left empty as with the default i32 length
+                            let len = usize::try_from(len).unwrap_or_default();
+                            (0..len).try_for_each(|_| -> ::std::result::Result<(), std::io::Error> {
+                                let mut item = std::default::Default::default();
+                                fluvio_protocol::Decoder::decode(&mut item, src, version)?;
+                                #value.push(item);
+                                Ok(())
+                            })
+                        })
+                    })
+                }
+            }
+            None => quote! { #value.decode(src, version) },
+        }
+    }
+
+    /// Traces that the field is deprecated before running `field_stream`
+    pub fn deprecation_trace(&self, field: TokenStream, field_stream: TokenStream) -> TokenStream {
+        match &self.deprecated_since {
+            Some(since) => quote! {
+                if version >= #since {
+                    tracing::trace!("Field: <{}> is deprecated since version: {}",#field,#since);
+                }
+                #field_stream
+            },
+            None => field_stream,
+        }
+    }
+
+    /// Statement using a `#[deprecated]` marker when the field has `deprecated_since`,
+    /// so the compiler warns about the field where it is declared
+    pub fn deprecation_marker(&self, field: &str) -> TokenStream {
+        let (Some(since), Some(span)) = (&self.deprecated_since, self.deprecated_since_span) else {
+            return quote! {};
+        };
+        let since = since.describe();
+        let note =
+            format!("field `{field}` is deprecated since protocol version {since}, it is kept for older peers");
+        let marker = format_ident!("{}", field, span = span);
+        let lints = &self.lints;
+        quote_spanned! {span=>
+            {
+                #[deprecated(since = #since, note = #note)]
+                #[allow(non_camel_case_types)]
+                struct #marker;
+                #(#lints)*
+                let _ = #marker;
+            }
+        }
+    }
+
+    /// The flag named `name`, such as `sensitive`, which is set by `#[fluvio(sensitive)]`
+    /// as well as `#[fluvio(sensitive = true)]`
+    fn flag(&mut self, name: &str) -> Option<&mut bool> {
+        let flag = match name {
+            "sensitive" => &mut self.sensitive,
+            "skip_encoding" => &mut self.skip_encoding,
+            "skip_decoding" => &mut self.skip_decoding,
+            "flatten" => &mut self.flatten,
+            "varint_zigzag" => &mut self.varint_zigzag,
+            "length_delimited" => &mut self.length_delimited,
+            _ => return None,
+        };
+        Some(flag)
+    }
+
     pub fn from_ast(attrs: &[Attribute]) -> syn::Result<Self> {
         let mut prop_attrs = Self::default();
+        let mut has_min_version = false;
+        let mut max_version_span = None;
+        let mut array_length_span = None;
+        let mut default_fn_span = None;
+        let mut ignorable_span = None;
+        // flags that are set, by name
+        let mut flag_spans = HashMap::new();
 
-        // Find all supported field level attributes in one go.
-        for attribute in attrs.iter() {
-            if attribute.path.is_ident("varint") {
-                prop_attrs.varint = true;
-            } else if attribute.path.is_ident("fluvio") {
-                if let Ok(Meta::List(list)) = attribute.parse_meta() {
-                    for kf_attr in list.nested {
-                        if let NestedMeta::Meta(Meta::NameValue(name_value)) = kf_attr {
-                            if name_value.path.is_ident("min_version") {
-                                if let Lit::Int(lit_int) = name_value.lit {
-                                    prop_attrs.min_version = lit_int.base10_parse::<i16>()?;
-                                }
-                            } else if name_value.path.is_ident("max_version") {
-                                if let Lit::Int(lit_int) = name_value.lit {
-                                    prop_attrs.max_version = Some(lit_int.base10_parse::<i16>()?);
-                                }
-                            } else if name_value.path.is_ident("default") {
-                                if let Lit::Str(lit_str) = name_value.lit {
-                                    prop_attrs.default_value = Some(lit_str.value());
-                                }
-                            } else {
-                                tracing::warn!(
-                                    "#[fluvio({})] does nothing here.",
-                                    name_value.to_token_stream().to_string(),
+        let mut names = HashSet::new();
+
+        prop_attrs.varint = attrs
+            .iter()
+            .any(|attribute| attribute.path.is_ident("varint"));
+        prop_attrs.lints = attrs
+            .iter()
+            .filter(|attribute| {
+                ["allow", "expect", "warn", "deny", "forbid"]
+                    .iter()
+                    .any(|lint| attribute.path.is_ident(lint))
+            })
+            .cloned()
+            .collect();
+
+        // Find all supported field level attributes in one go, a field may have several #[fluvio(...)]
+        let mut items = find_all_attrs(attrs, "fluvio")?.into_iter().peekable();
+        while let Some(item) = items.next() {
+            let kf_attr = match item {
+                AttrItem::Meta(kf_attr) => kf_attr,
+                AttrItem::PathValue {
+                    name: path,
+                    value,
+                    call,
+                } => {
+                    let name = path.to_token_stream().to_string();
+                    check_unique(&mut names, &name, path.span())?;
+                    let value_span = value.span();
+                    let version = if call {
+                        PropAttrsType::ConstFn(value)
+                    } else {
+                        PropAttrsType::Path(value)
+                    };
+                    if path.is_ident("min_version") {
+                        has_min_version = true;
+                        prop_attrs.min_version = version;
+                    } else if path.is_ident("max_version") {
+                        max_version_span = Some(path.span());
+                        prop_attrs.max_version = Some(version);
+                    } else if path.is_ident("deprecated_since") {
+                        prop_attrs.deprecated_since_span = Some(path.span());
+                        prop_attrs.deprecated_since = Some(version);
+                    } else {
+                        return Err(Error::new(
+                            value_span,
+                            format!("{name} expects a literal, only versions can be the path of a constant or a const fn call."),
+                        ));
+                    }
+                    continue;
+                }
+            };
+            if let NestedMeta::Meta(Meta::NameValue(name_value)) = kf_attr {
+                let name = name_value.path.to_token_stream().to_string();
+                check_unique(&mut names, &name, name_value.span())?;
+                if let Some(flag) = prop_attrs.flag(&name) {
+                    let Lit::Bool(lit_bool) = &name_value.lit else {
+                        return Err(Error::new(
+                            name_value.lit.span(),
+                            format!("{name} is a flag, write `{name}` or `{name} = false`."),
+                        ));
+                    };
+                    *flag = lit_bool.value;
+                    if lit_bool.value {
+                        flag_spans.insert(name, name_value.span());
+                    }
+                } else if name_value.path.is_ident("min_version") {
+                    has_min_version = true;
+                    prop_attrs.min_version = version_value(&name_value)?;
+                } else if name_value.path.is_ident("max_version") {
+                    max_version_span = Some(name_value.span());
+                    prop_attrs.max_version = Some(version_value(&name_value)?);
+                } else if name_value.path.is_ident("deprecated_since") {
+                    prop_attrs.deprecated_since_span = Some(name_value.span());
+                    prop_attrs.deprecated_since = Some(version_value(&name_value)?);
+                } else if name_value.path.is_ident("default") {
+                    if let Lit::Str(lit_str) = name_value.lit {
+                        prop_attrs.default_value = Some(lit_str.value());
+                    }
+                } else if name_value.path.is_ident("default_fn") {
+                    default_fn_span = Some(name_value.span());
+                    if let Lit::Str(lit_str) = name_value.lit {
+                        let default_fn = lit_str.parse::<Path>().map_err(|_| {
+                                Error::new(
+                                    lit_str.span(),
+                                    "default_fn expects the path of a function, such as `default_fn = \"make_default\"`.",
+                                )
+                            })?;
+                        prop_attrs.default_fn = Some(default_fn);
+                    }
+                } else if name_value.path.is_ident("validate") {
+                    if let Lit::Str(lit_str) = name_value.lit {
+                        let validate = lit_str.parse::<Path>().map_err(|_| {
+                                Error::new(
+                                    lit_str.span(),
+                                    "validate expects the path of a function, such as `validate = \"check_value\"`.",
                                 )
-                            }
+                            })?;
+                        prop_attrs.validate = Some(validate);
+                    }
+                } else if name_value.path.is_ident("array_length_encoding") {
+                    array_length_span = Some(name_value.span());
+                    if let Lit::Str(lit_str) = name_value.lit {
+                        let len_type = lit_str.value();
+                        if !ARRAY_LENGTH_TYPES.contains(&len_type.as_str()) {
+                            return Err(Error::new(
+                                    lit_str.span(),
+                                    format!(
+                                        "Unsupported array_length_encoding \"{len_type}\", expected one of: {}.",
+                                        ARRAY_LENGTH_TYPES.join(", ")
+                                    ),
+                                ));
                         }
+                        prop_attrs.array_length_encoding = Some(len_type);
+                    }
+                } else if name_value.path.is_ident("tags") {
+                    prop_attrs.tags = get_lit_str_list(&name_value, &mut items)?;
+                } else if name_value.path.is_ident("rename") {
+                    if let Lit::Str(lit_str) = name_value.lit {
+                        prop_attrs.rename = Some(lit_str.value());
                     }
+                } else {
+                    tracing::warn!(
+                        "#[fluvio({})] does nothing here.",
+                        name_value.to_token_stream().to_string(),
+                    )
                 }
+            } else if let NestedMeta::Meta(Meta::Path(path)) = kf_attr {
+                let name = path.to_token_stream().to_string();
+                if let Some(flag) = prop_attrs.flag(&name) {
+                    *flag = true;
+                    flag_spans.insert(name, path.span());
+                } else if path.is_ident("ignorable") {
+                    // only checked here, ignorable doesn't change the generated code
+                    ignorable_span = Some(path.span());
+                } else {
+                    tracing::warn!(
+                        "#[fluvio({})] does nothing here.",
+                        path.to_token_stream().to_string(),
+                    )
+                }
+            } else if let NestedMeta::Lit(lit) = kf_attr {
+                // the second value of `name = "a", "b"` for an attribute that takes a single one,
+                // which would be dropped otherwise
+                return Err(Error::new(
+                    lit.span(),
+                    "unexpected value, only tags takes a list such as `tags = \"a\", \"b\"`.",
+                ));
+            }
+        }
+
+        let flatten_span = flag_spans.get("flatten").copied();
+        let varint_zigzag_span = flag_spans.get("varint_zigzag").copied();
+        let length_delimited_span = flag_spans.get("length_delimited").copied();
+
+        if let (Some(span), true) = (default_fn_span, prop_attrs.default_value.is_some()) {
+            return Err(Error::new(
+                span,
+                "default_fn can't be used together with default, keep only one of them.",
+            ));
+        }
+
+        if let (Some(span), true) = (array_length_span, prop_attrs.varint) {
+            return Err(Error::new(
+                span,
+                "array_length_encoding can't be used on a varint field.",
+            ));
+        }
+
+        if let Some(span) = flatten_span {
+            if prop_attrs.varint {
+                return Err(Error::new(span, "flatten can't be used on a varint field."));
+            }
+            if prop_attrs.array_length_encoding.is_some() {
+                return Err(Error::new(
+                    span,
+                    "flatten can't be used together with array_length_encoding, a flattened field has no length prefix.",
+                ));
+            }
+        }
+
+        if let Some(span) = varint_zigzag_span {
+            if prop_attrs.varint {
+                return Err(Error::new(
+                    span,
+                    "varint_zigzag can't be used together with varint, keep only one of them.",
+                ));
+            }
+            if prop_attrs.array_length_encoding.is_some() || prop_attrs.flatten {
+                return Err(Error::new(
+                    span,
+                    "varint_zigzag can't be used together with array_length_encoding or flatten.",
+                ));
+            }
+        }
+
+        if let Some(span) = length_delimited_span {
+            if prop_attrs.varint || prop_attrs.varint_zigzag {
+                return Err(Error::new(
+                    span,
+                    "length_delimited can't be used on a varint field.",
+                ));
+            }
+            if prop_attrs.flatten || prop_attrs.array_length_encoding.is_some() {
+                return Err(Error::new(
+                    span,
+                    "length_delimited can't be used together with flatten or array_length_encoding.",
+                ));
             }
         }
 
+        // min_version defaults to 0, but a forgotten one is easy to miss next to a max_version
+        if let (Some(span), false) = (max_version_span, has_min_version) {
+            return Err(Error::new(
+                span,
+                "max_version requires an explicit min_version, use `min_version = 0` to start from the first version.",
+            ));
+        }
+
+        // without a version range there is nothing that makes the field absent
+        if let (Some(span), false, None) =
+            (ignorable_span, has_min_version, &prop_attrs.max_version)
+        {
+            return Err(Error::new(
+                span,
+                "ignorable field must specify min_version or max_version",
+            ));
+        }
+
         Ok(prop_attrs)
     }
 }
@@ -1,4 +1,6 @@
-use crate::ast::prop::{NamedProp, UnnamedProp};
+use crate::ast::prop::{NamedProp, PropAttrs, UnnamedProp};
+use proc_macro2::TokenStream;
+use quote::quote;
 use syn::{Fields, Generics, Ident, ItemStruct};
 
 pub(crate) enum FluvioStruct {
@@ -79,6 +81,67 @@ pub(crate) enum FluvioStructProps {
     Unnamed(Vec<UnnamedProp>),
 }
 
+impl FluvioStructProps {
+    /// True if there are fields and all of them are fixed-width primitives
+    pub fn is_fixed_width(&self) -> bool {
+        match self {
+            Self::Named(props) => !props.is_empty() && props.iter().all(NamedProp::is_fixed_width),
+            Self::Unnamed(props) => {
+                !props.is_empty() && props.iter().all(UnnamedProp::is_fixed_width)
+            }
+        }
+    }
+
+    /// Deprecation warnings for the fields with `deprecated_since`
+    pub fn deprecation_markers(&self) -> TokenStream {
+        let markers: Vec<TokenStream> = match self {
+            Self::Named(props) => props
+                .iter()
+                .map(|prop| prop.attrs.deprecation_marker(&prop.field_name))
+                .collect(),
+            Self::Unnamed(props) => props
+                .iter()
+                .enumerate()
+                .map(|(idx, prop)| prop.attrs.deprecation_marker(&format!("field_{idx}")))
+                .collect(),
+        };
+        quote! { #(#markers)* }
+    }
+
+    /// Const assertions for the version ranges that use constants or `const fn` calls
+    pub fn version_assertions(&self) -> TokenStream {
+        let assertions: Vec<TokenStream> = match self {
+            Self::Named(props) => props
+                .iter()
+                .map(|prop| prop.attrs.version_assertions(Some(&prop.field_name)))
+                .collect(),
+            Self::Unnamed(props) => props
+                .iter()
+                .map(|prop| prop.attrs.version_assertions(None))
+                .collect(),
+        };
+        quote! { #(#assertions)* }
+    }
+
+    /// Lowest `min_version` and highest `max_version` of the fields
+    ///
+    /// A field without `max_version` has no upper bound, nor does a struct without fields.
+    /// Fields without an [`effective_version_range`](PropAttrs::effective_version_range) are left out.
+    pub fn version_bounds(&self) -> (i16, i16) {
+        let attrs: Vec<&PropAttrs> = match self {
+            Self::Named(props) => props.iter().map(|prop| &prop.attrs).collect(),
+            Self::Unnamed(props) => props.iter().map(|prop| &prop.attrs).collect(),
+        };
+        let ranges: Vec<(i16, i16)> = attrs
+            .iter()
+            .filter_map(|attrs| attrs.effective_version_range())
+            .collect();
+        let min = ranges.iter().map(|(min, _)| *min).min();
+        let max = ranges.iter().map(|(_, max)| *max).max();
+        (min.unwrap_or(0), max.unwrap_or(i16::MAX))
+    }
+}
+
 pub(crate) struct FluvioTupleStruct {
     pub struct_ident: Ident,
     pub props: Vec<UnnamedProp>,
@@ -25,6 +25,7 @@ pub(crate) fn generate_decode_trait_impls(input: &DeriveItem) -> TokenStream {
             let ident = &kf_struct.struct_ident();
             let generics = add_bounds(kf_struct.generics().clone(), attrs, FluvioBound::Decoder);
             let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            let version_assertions = kf_struct.props().version_assertions();
             quote! {
                 impl #impl_generics fluvio_protocol::Decoder for #ident #ty_generics #where_clause {
                     fn decode<T>(&mut self, src: &mut T,version: fluvio_protocol::Version) -> ::std::result::Result<(),std::io::Error> where T: fluvio_protocol::bytes::Buf {
@@ -33,6 +34,8 @@ pub(crate) fn generate_decode_trait_impls(input: &DeriveItem) -> TokenStream {
                         Ok(())
                     }
                 }
+
+                #version_assertions
             }
         }
         DeriveItem::Enum(kf_enum, attrs) => {
@@ -83,38 +86,53 @@ pub(crate) fn generate_struct_named_fields(
 ) -> TokenStream {
     let recurse = props.iter().map(|prop| {
         let fname = format_ident!("{}", prop.field_name);
-        if prop.attrs.varint {
+        let wire_name = prop.wire_name();
+        let traced = prop.attrs.trace_value(quote! { &self.#fname });
+        let validation = prop
+            .attrs
+            .validation_token_stream(quote! { &self.#fname }, quote! { #wire_name });
+        if prop.attrs.skip_decoding {
+            let default = prop.attrs.default_token_stream();
+            quote! {
+                self.#fname = #default;
+            }
+        } else if prop.attrs.varint {
             if attr.trace {
                 quote! {
-                    tracing::trace!("start decoding varint field <{}>", stringify!(#fname));
+                    tracing::trace!("start decoding varint field <{}>", #wire_name);
                     let result = self.#fname.decode_varint(src);
                     if result.is_ok() {
-                        tracing::trace!("decoding ok varint <{}> => {:?}",stringify!(#fname),&self.#fname);
+                        tracing::trace!("decoding ok varint <{}> => {:?}",#wire_name,#traced);
                     } else {
-                        tracing::trace!("decoding varint error <{}> ==> {}",stringify!(#fname),result.as_ref().unwrap_err());
+                        tracing::trace!("decoding varint error <{}> ==> {}",#wire_name,result.as_ref().unwrap_err());
                         return result;
                     }
+                    #validation
                 }
             } else {
                 quote! {
                     self.#fname.decode_varint(src)?;
+                    #validation
                 }
             }
         } else {
+            let decode = prop.attrs.decode_token_stream(quote! { self.#fname });
             let base = if attr.trace {
                 quote! {
-                    tracing::trace!("start decoding struct: <{}> field: <{}>",stringify!(#struct_ident),stringify!(#fname));
-                    let result = self.#fname.decode(src,version);
+                    tracing::trace!("start decoding struct: <{}> field: <{}>",stringify!(#struct_ident),#wire_name);
+                    let result = #decode;
                     if result.is_ok() {
-                        tracing::trace!("decoding struct: <{}> field: <{}> => {:#?}",stringify!(#struct_ident),stringify!(#fname),&self.#fname);
+                        tracing::trace!("decoding struct: <{}> field: <{}> => {:#?}",stringify!(#struct_ident),#wire_name,#traced);
                     } else {
-                        tracing::trace!("error decoding <{}> ==> {}",stringify!(#fname),result.as_ref().unwrap_err());
+                        tracing::trace!("error decoding <{}> ==> {}",#wire_name,result.as_ref().unwrap_err());
                         return result;
                     }
+                    #validation
                 }
              } else {
                     quote! {
-                        self.#fname.decode(src,version)?;
+                        #decode?;
+                        #validation
                     }
             };
 
@@ -133,38 +151,52 @@ pub(crate) fn generate_struct_unnamed_fields(
 ) -> TokenStream {
     let recurse = props.iter().enumerate().map(|(idx, prop)| {
         let field_idx = syn::Index::from(idx);
-        if prop.attrs.varint {
+        let traced = prop.attrs.trace_value(quote! { &self.#field_idx });
+        let validation = prop
+            .attrs
+            .validation_token_stream(quote! { &self.#field_idx }, quote! { stringify!(#field_idx) });
+        if prop.attrs.skip_decoding {
+            let default = prop.attrs.default_token_stream();
+            quote! {
+                self.#field_idx = #default;
+            }
+        } else if prop.attrs.varint {
             if attrs.trace {
                 quote! {
                     tracing::trace!("start decoding varint field <{}>", stringify!(#idx));
                     let result = self.#field_idx.decode_varint(src);
                     if result.is_ok() {
-                        tracing::trace!("decoding ok varint <{}> => {:?}",stringify!(#idx),&self.#field_idx);
+                        tracing::trace!("decoding ok varint <{}> => {:?}",stringify!(#idx),#traced);
                     } else {
                         tracing::trace!("decoding varint error <{}> ==> {}",stringify!(#idx),result.as_ref().unwrap_err());
                         return result;
                     }
+                    #validation
                 }
             } else {
                 quote! {
                     self.#field_idx.decode_varint(src)?;
+                    #validation
                 }
             }
         } else {
+            let decode = prop.attrs.decode_token_stream(quote! { self.#field_idx });
             let base = if attrs.trace {
                 quote! {
                     tracing::trace!("start decoding struct: <{}> field: <{}>",stringify!(#struct_ident),stringify!(#idx));
-                    let result = self.#field_idx.decode(src,version);
+                    let result = #decode;
                     if result.is_ok() {
-                        tracing::trace!("decoding struct: <{}> field: <{}> => {:#?}",stringify!(#struct_ident),stringify!(#idx),&self.#field_idx);
+                        tracing::trace!("decoding struct: <{}> field: <{}> => {:#?}",stringify!(#struct_ident),stringify!(#idx),#traced);
                     } else {
                         tracing::trace!("error decoding <{}> ==> {}",stringify!(#idx),result.as_ref().unwrap_err());
                         return result;
                     }
+                    #validation
                 }
             }else {
                 quote! {
-                    self.#field_idx.decode(src,version)?;
+                    #decode?;
+                    #validation
                 }
             };
 
@@ -413,20 +445,9 @@ pub(crate) fn generate_default_impls(props: &FluvioStructProps) -> TokenStream {
 pub(crate) fn generate_default_impls_named_fields(props: &[NamedProp]) -> TokenStream {
     let recurse = props.iter().map(|prop| {
         let fname = format_ident!("{}", prop.field_name);
-        if let Some(def) = &prop.attrs.default_value {
-            if let Ok(liter) = TokenStream::from_str(def) {
-                quote! {
-                    #fname: #liter,
-                }
-            } else {
-                quote! {
-                    #fname: std::default::Default::default(),
-                }
-            }
-        } else {
-            quote! {
-                #fname: std::default::Default::default(),
-            }
+        let default = prop.attrs.default_token_stream();
+        quote! {
+            #fname: #default,
         }
     });
     quote! {
@@ -437,21 +458,9 @@ pub(crate) fn generate_default_impls_named_fields(props: &[NamedProp]) -> TokenS
 pub(crate) fn generate_default_impls_unnamed_fields(props: &[UnnamedProp]) -> TokenStream {
     let recurse = props.iter().enumerate().map(|(idx, prop)| {
         let field_idx = syn::Index::from(idx);
-
-        if let Some(def) = &prop.attrs.default_value {
-            if let Ok(liter) = TokenStream::from_str(def) {
-                quote! {
-                    #field_idx: #liter,
-                }
-            } else {
-                quote! {
-                    #field_idx: std::default::Default::default(),
-                }
-            }
-        } else {
-            quote! {
-                #field_idx: std::default::Default::default(),
-            }
+        let default = prop.attrs.default_token_stream();
+        quote! {
+            #field_idx: #default,
         }
     });
     quote! {
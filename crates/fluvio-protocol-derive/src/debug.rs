@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Ident, Index};
+
+use crate::ast::prop::{NamedProp, PropAttrs, UnnamedProp};
+use crate::ast::r#struct::FluvioStructProps;
+use crate::ast::{add_bounds, DeriveItem, FluvioBound};
+
+pub(crate) fn generate_debug_trait_impls(input: &DeriveItem) -> TokenStream {
+    match &input {
+        DeriveItem::Struct(kf_struct, attrs) => {
+            let ident = kf_struct.struct_ident();
+            let generics = add_bounds(kf_struct.generics().clone(), attrs, FluvioBound::Debug);
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            let debug_tokens = match kf_struct.props() {
+                FluvioStructProps::Named(props) => generate_named_fields_debug(ident, &props),
+                FluvioStructProps::Unnamed(props) => generate_unnamed_fields_debug(ident, &props),
+            };
+            quote! {
+                impl #impl_generics ::std::fmt::Debug for #ident #ty_generics #where_clause {
+                    fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                        #debug_tokens
+                    }
+                }
+            }
+        }
+        DeriveItem::Enum(kf_enum, _) => syn::Error::new(
+            kf_enum.enum_ident.span(),
+            "FluvioDebug only supports structs",
+        )
+        .to_compile_error(),
+    }
+}
+
+/// Sensitive fields only show their encoded length
+fn field_value(attrs: &PropAttrs, value: TokenStream) -> TokenStream {
+    if attrs.sensitive {
+        quote! {
+            &format_args!("<redacted len={}>", fluvio_protocol::Encoder::write_size(#value, 0))
+        }
+    } else {
+        value
+    }
+}
+
+fn generate_named_fields_debug(ident: &Ident, props: &[NamedProp]) -> TokenStream {
+    let fields = props.iter().map(|prop| {
+        let fname = format_ident!("{}", prop.field_name);
+        let wire_name = prop.wire_name();
+        let value = field_value(&prop.attrs, quote! { &self.#fname });
+        quote! {
+            .field(#wire_name, #value)
+        }
+    });
+
+    quote! {
+        f.debug_struct(stringify!(#ident))
+            #(#fields)*
+            .finish()
+    }
+}
+
+fn generate_unnamed_fields_debug(ident: &Ident, props: &[UnnamedProp]) -> TokenStream {
+    let fields = props.iter().enumerate().map(|(idx, prop)| {
+        let field_idx = Index::from(idx);
+        let value = field_value(&prop.attrs, quote! { &self.#field_idx });
+        quote! {
+            .field(#value)
+        }
+    });
+
+    quote! {
+        f.debug_tuple(stringify!(#ident))
+            #(#fields)*
+            .finish()
+    }
+}
@@ -3,6 +3,8 @@ extern crate proc_macro;
 mod api;
 mod ast;
 mod de;
+mod debug;
+mod schema;
 mod ser;
 mod util;
 
@@ -10,6 +12,8 @@ use self::api::generate_request_traits;
 use self::api::parse_and_generate_api;
 use self::de::generate_decode_trait_impls;
 use self::de::generate_default_trait_impls;
+use self::debug::generate_debug_trait_impls;
+use self::schema::generate_schema_trait_impls;
 use self::ser::generate_encode_trait_impls;
 
 use proc_macro::TokenStream;
@@ -54,3 +58,19 @@ pub fn fluvio_default(tokens: TokenStream) -> TokenStream {
 
     expanded.into()
 }
+
+#[proc_macro_derive(FluvioDebug, attributes(fluvio))]
+pub fn fluvio_debug(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input![tokens as ast::DeriveItem];
+    let expanded = generate_debug_trait_impls(&input);
+
+    expanded.into()
+}
+
+#[proc_macro_derive(FluvioSchema, attributes(varint, fluvio))]
+pub fn fluvio_schema(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input![tokens as ast::DeriveItem];
+    let expanded = generate_schema_trait_impls(&input);
+
+    expanded.into()
+}
@@ -0,0 +1,174 @@
+use std::str::FromStr;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote, ToTokens};
+use syn::{Ident, LitInt, Type};
+
+use crate::ast::container::ContainerAttributes;
+use crate::ast::prop::{NamedProp, PropAttrs, UnnamedProp};
+use crate::ast::r#enum::{EnumProp, FieldKind};
+use crate::ast::r#struct::FluvioStructProps;
+use crate::ast::{add_bounds, DeriveItem, FluvioBound};
+
+pub(crate) fn generate_schema_trait_impls(input: &DeriveItem) -> TokenStream {
+    let (ident, generics, schema) = match &input {
+        DeriveItem::Struct(kf_struct, attrs) => {
+            let fields_schema = match kf_struct.props() {
+                FluvioStructProps::Named(props) => generate_named_fields_schema(&props),
+                FluvioStructProps::Unnamed(props) => generate_unnamed_fields_schema(&props),
+            };
+            let ident = kf_struct.struct_ident();
+            let schema = quote! {
+                let mut schema = #fields_schema;
+                schema["title"] = fluvio_protocol::schema::serde_json::json!(stringify!(#ident));
+                schema
+            };
+            let generics = add_bounds(kf_struct.generics().clone(), attrs, FluvioBound::Schema);
+            (ident, generics, schema)
+        }
+        DeriveItem::Enum(kf_enum, attrs) => {
+            let ident = &kf_enum.enum_ident;
+            let schema = generate_enum_schema(ident, &kf_enum.props, attrs);
+            let generics = add_bounds(kf_enum.generics.clone(), attrs, FluvioBound::Schema);
+            (ident, generics, schema)
+        }
+    };
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics fluvio_protocol::schema::FluvioSchemaProvider for #ident #ty_generics #where_clause {
+            fn schema() -> fluvio_protocol::schema::serde_json::Value {
+                #schema
+            }
+        }
+    }
+}
+
+/// Versions the field is encoded in, as the arguments of `with_versions`
+fn field_versions(attrs: &PropAttrs) -> TokenStream {
+    let min = &attrs.min_version;
+    let max = match &attrs.max_version {
+        Some(max) => quote! { Some(#max) },
+        None => quote! { None },
+    };
+    quote! { #min, #max }
+}
+
+/// Schema of the field as it is encoded, with the versions it is encoded in
+fn field_schema(attrs: &PropAttrs, ty: &Type) -> TokenStream {
+    let mut schema = if attrs.varint || attrs.varint_zigzag {
+        quote! { fluvio_protocol::schema::varint_schema() }
+    } else {
+        quote! { <#ty as fluvio_protocol::schema::FluvioSchemaProvider>::schema() }
+    };
+    if let Some(len_type) = &attrs.array_length_encoding {
+        let len_type = format_ident!("{}", len_type);
+        schema = quote! { fluvio_protocol::schema::with_length_format::<#len_type>(#schema) };
+    }
+    if attrs.length_delimited {
+        schema = quote! { fluvio_protocol::schema::with_size_prefix(#schema) };
+    }
+    if !attrs.tags.is_empty() {
+        let tags = &attrs.tags;
+        schema = quote! { fluvio_protocol::schema::with_tags(#schema, &[#(#tags),*]) };
+    }
+    let versions = field_versions(attrs);
+    quote! {
+        fluvio_protocol::schema::with_versions(#schema, #versions)
+    }
+}
+
+fn generate_named_fields_schema(props: &[NamedProp]) -> TokenStream {
+    let fields = props
+        .iter()
+        .filter(|prop| !prop.attrs.skip_encoding)
+        .map(|prop| {
+            let ty = &prop.field_type;
+            if prop.attrs.flatten {
+                let versions = field_versions(&prop.attrs);
+                return quote! {
+                    fields.flatten(
+                        <#ty as fluvio_protocol::schema::FluvioSchemaProvider>::schema(),
+                        #versions,
+                    );
+                };
+            }
+            let name = prop.wire_name();
+            let schema = field_schema(&prop.attrs, ty);
+            quote! {
+                fields.push(
+                    #name,
+                    #schema,
+                    <#ty as fluvio_protocol::schema::FluvioSchemaProvider>::is_optional(),
+                );
+            }
+        });
+
+    quote! {{
+        let mut fields = fluvio_protocol::schema::StructFields::default();
+        #(#fields)*
+        fields.into_schema()
+    }}
+}
+
+fn generate_unnamed_fields_schema(props: &[UnnamedProp]) -> TokenStream {
+    let fields = props
+        .iter()
+        .filter(|prop| !prop.attrs.skip_encoding)
+        .map(|prop| field_schema(&prop.attrs, &prop.field_type));
+
+    quote! {{
+        let items: Vec<fluvio_protocol::schema::serde_json::Value> = vec![#(#fields),*];
+        fluvio_protocol::schema::serde_json::json!({
+            "type": "array",
+            "prefixItems": items,
+            "items": false,
+        })
+    }}
+}
+
+/// One schema per variant, with the tag it is encoded as
+fn generate_enum_schema(
+    enum_ident: &Ident,
+    props: &[EnumProp],
+    attrs: &ContainerAttributes,
+) -> TokenStream {
+    let int_type = match &attrs.repr_type_name {
+        Some(int_type_name) => format_ident!("{}", int_type_name),
+        _ => Ident::new("u8", Span::call_site()),
+    };
+    let variants = props.iter().enumerate().map(|(idx, prop)| {
+        let name = &prop.variant_name;
+        // same tag as the Encoder derive
+        let tag = if let Some(tag) = &prop.tag {
+            match TokenStream::from_str(tag) {
+                Ok(literal) => literal,
+                _ => LitInt::new(&idx.to_string(), Span::call_site()).to_token_stream(),
+            }
+        } else {
+            match &prop.discriminant {
+                Some(dsc) => dsc.as_token_stream(),
+                _ => LitInt::new(&idx.to_string(), Span::call_site()).to_token_stream(),
+            }
+        };
+        let fields_schema = match &prop.kind {
+            FieldKind::Named(_, props) => generate_named_fields_schema(props),
+            FieldKind::Unnamed(_, props) => generate_unnamed_fields_schema(props),
+            FieldKind::Unit => quote! { fluvio_protocol::schema::serde_json::json!({}) },
+        };
+        quote! {{
+            let mut variant = #fields_schema;
+            variant["title"] = fluvio_protocol::schema::serde_json::json!(#name);
+            variant["tag"] = fluvio_protocol::schema::serde_json::json!(#tag);
+            variant
+        }}
+    });
+
+    quote! {
+        let variants: Vec<fluvio_protocol::schema::serde_json::Value> = vec![#(#variants),*];
+        fluvio_protocol::schema::serde_json::json!({
+            "title": stringify!(#enum_ident),
+            "tagType": <#int_type as fluvio_protocol::schema::FluvioSchemaProvider>::schema(),
+            "oneOf": variants,
+        })
+    }
+}
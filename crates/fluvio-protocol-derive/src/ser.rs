@@ -30,9 +30,19 @@ pub(crate) fn generate_encode_trait_impls(input: &DeriveItem) -> TokenStream {
                 quote! {}
             };
 
+            let encode_into = if kf_struct.props().is_fixed_width() {
+                generate_encode_into(ident, &generics)
+            } else {
+                quote! {}
+            };
+            let version_bounds = generate_version_bounds(ident, &generics, &kf_struct.props());
+            let deprecation_markers = kf_struct.props().deprecation_markers();
+            let version_assertions = kf_struct.props().version_assertions();
+
             quote! {
                 impl #impl_generics fluvio_protocol::Encoder for #ident #ty_generics #where_clause {
                     fn encode<T>(&self, dest: &mut T, version: fluvio_protocol::Version) ->  ::std::result::Result<(),std::io::Error> where T: fluvio_protocol::bytes::BufMut {
+                        #deprecation_markers
                         #trace_encode
                         #encoded_field_tokens
                         Ok(())
@@ -45,6 +55,12 @@ pub(crate) fn generate_encode_trait_impls(input: &DeriveItem) -> TokenStream {
                         len
                     }
                 }
+
+                #encode_into
+
+                #version_bounds
+
+                #version_assertions
             }
         }
         DeriveItem::Enum(kf_enum, attrs) => {
@@ -84,6 +100,31 @@ pub(crate) fn generate_encode_trait_impls(input: &DeriveItem) -> TokenStream {
     }
 }
 
+/// Structs made only of fixed-width primitives can be encoded straight into a slice
+fn generate_encode_into(ident: &Ident, generics: &syn::Generics) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    quote! {
+        impl #impl_generics fluvio_protocol::EncodeInto for #ident #ty_generics #where_clause {}
+    }
+}
+
+/// Versions the struct spans, from the lowest `min_version` to the highest `max_version` of its fields
+fn generate_version_bounds(
+    ident: &Ident,
+    generics: &syn::Generics,
+    props: &FluvioStructProps,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let (min, max) = props.version_bounds();
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Lowest and highest version any field is encoded in, `i16::MAX` if a field has no `max_version`
+            #[allow(dead_code)]
+            pub const VERSION_BOUNDS: (fluvio_protocol::Version, fluvio_protocol::Version) = (#min, #max);
+        }
+    }
+}
+
 fn parse_struct_props_encoding(
     props: &FluvioStructProps,
     struct_ident: &Ident,
@@ -104,15 +145,17 @@ fn parse_struct_named_props_encoding(
     struct_ident: &Ident,
     attr: &ContainerAttributes,
 ) -> TokenStream {
-    let recurse = props.iter().map(|prop| {
+    let recurse = props.iter().filter(|prop| !prop.attrs.skip_encoding).map(|prop| {
         let fname = format_ident!("{}", prop.field_name);
+        let wire_name = prop.wire_name();
+        let traced = prop.attrs.trace_value(quote! { &self.#fname });
         if prop.attrs.varint {
             if attr.trace {
                 quote! {
-                    tracing::trace!("encoding varint struct: <{}> field <{}> => {:?}",stringify!(#struct_ident),stringify!(#fname),&self.#fname);
+                    tracing::trace!("encoding varint struct: <{}> field <{}> => {:?}",stringify!(#struct_ident),#wire_name,#traced);
                     let result = self.#fname.encode_varint(dest);
                     if result.is_err() {
-                        tracing::error!("error varint encoding <{}> ==> {}",stringify!(#fname),result.as_ref().unwrap_err());
+                        tracing::error!("error varint encoding <{}> ==> {}",#wire_name,result.as_ref().unwrap_err());
                         return result;
                     }
                 }
@@ -122,19 +165,19 @@ fn parse_struct_named_props_encoding(
                 }
             }
         } else {
-
+            let encode = prop.attrs.encode_token_stream(quote! { self.#fname });
             let base = if attr.trace {
                 quote! {
-                    tracing::trace!("encoding struct: <{}>, field <{}> => {:?}",stringify!(#struct_ident),stringify!(#fname),&self.#fname);
-                    let result = self.#fname.encode(dest,version);
+                    tracing::trace!("encoding struct: <{}>, field <{}> => {:?}",stringify!(#struct_ident),#wire_name,#traced);
+                    let result = #encode;
                     if result.is_err() {
-                        tracing::error!("Error Encoding <{}> ==> {}",stringify!(#fname),result.as_ref().unwrap_err());
+                        tracing::error!("Error Encoding <{}> ==> {}",#wire_name,result.as_ref().unwrap_err());
                         return result;
                     }
                 }
             } else {
                 quote! {
-                    self.#fname.encode(dest,version)?;
+                    #encode?;
                 }
             };
 
@@ -152,13 +195,14 @@ fn parse_struct_unnamed_props_encoding(
     struct_ident: &Ident,
     attr: &ContainerAttributes,
 ) -> TokenStream {
-    let recurse = props.iter().enumerate().map(|(idx, prop)| {
+    let recurse = props.iter().enumerate().filter(|(_, prop)| !prop.attrs.skip_encoding).map(|(idx, prop)| {
 
         let field_idx = syn::Index::from(idx);
+        let traced = prop.attrs.trace_value(quote! { &self.#field_idx });
         if prop.attrs.varint {
             if attr.trace {
                 quote! {
-                    tracing::trace!("encoding varint struct: <{}> field <{}> => {:?}",stringify!(#struct_ident),stringify!(#idx),&self.#field_idx);
+                    tracing::trace!("encoding varint struct: <{}> field <{}> => {:?}",stringify!(#struct_ident),stringify!(#idx),#traced);
                     let result = self.#field_idx.encode_varint(dest);
                     if result.is_err() {
                         !("error varint encoding <{}> ==> {}",stringify!(#idx),result.as_ref().unwrap_err());
@@ -171,10 +215,11 @@ fn parse_struct_unnamed_props_encoding(
                 }
             }
         } else {
+            let encode = prop.attrs.encode_token_stream(quote! { self.#field_idx });
             let base = if attr.trace {
                 quote! {
-                    tracing::trace!("encoding struct: <{}>, field <{}> => {:?}",stringify!(#struct_ident),stringify!(#idx),&self.#field_idx);
-                    let result = self.#field_idx.encode(dest,version);
+                    tracing::trace!("encoding struct: <{}>, field <{}> => {:?}",stringify!(#struct_ident),stringify!(#idx),#traced);
+                    let result = #encode;
                     if result.is_err() {
                         tracing::error!("Error Encoding <{}> ==> {}",stringify!(#idx),result.as_ref().unwrap_err());
                         return result;
@@ -182,7 +227,7 @@ fn parse_struct_unnamed_props_encoding(
                 }
             } else {
                 quote! {
-                    self.#field_idx.encode(dest,version)?;
+                    #encode?;
                 }
             };
 
@@ -215,13 +260,14 @@ fn parse_struct_named_props_size(
     struct_ident: &Ident,
     attr: &ContainerAttributes,
 ) -> TokenStream {
-    let recurse = props.iter().map(|prop| {
+    let recurse = props.iter().filter(|prop| !prop.attrs.skip_encoding).map(|prop| {
         let fname = format_ident!("{}", prop.field_name);
+        let wire_name = prop.wire_name();
         if prop.attrs.varint {
             if attr.trace {
                 quote! {
                     let write_size = self.#fname.var_write_size();
-                    tracing::trace!("varint write size: <{}>, field: <{}> is: {}",stringify!(#struct_ident),stringify!(#fname),write_size);
+                    tracing::trace!("varint write size: <{}>, field: <{}> is: {}",stringify!(#struct_ident),#wire_name,write_size);
                     len += write_size;
                 }
             } else {
@@ -230,16 +276,16 @@ fn parse_struct_named_props_size(
                 }
             }
         } else {
-
+            let write_size = prop.attrs.write_size_token_stream(quote! { self.#fname });
             let base = if attr.trace {
                 quote! {
-                    let write_size = self.#fname.write_size(version);
-                    tracing::trace!("write size: <{}> field: <{}> => {}",stringify!(#struct_ident),stringify!(#fname),write_size);
+                    let write_size = #write_size;
+                    tracing::trace!("write size: <{}> field: <{}> => {}",stringify!(#struct_ident),#wire_name,write_size);
                     len += write_size;
                 }
             } else {
                 quote! {
-                    len += self.#fname.write_size(version);
+                    len += #write_size;
                 }
             };
             prop.version_check_token_stream(base,attr.trace)
@@ -255,7 +301,7 @@ fn parse_struct_unnamed_props_size(
     struct_ident: &Ident,
     attr: &ContainerAttributes,
 ) -> TokenStream {
-    let recurse = props.iter().enumerate().map(|(idx, prop)| {
+    let recurse = props.iter().enumerate().filter(|(_, prop)| !prop.attrs.skip_encoding).map(|(idx, prop)| {
         let field_idx = syn::Index::from(idx);
         if prop.attrs.varint {
             if attr.trace {
@@ -270,15 +316,16 @@ fn parse_struct_unnamed_props_size(
                 }
             }
         } else {
+            let write_size = prop.attrs.write_size_token_stream(quote! { self.#field_idx });
             let base = if attr.trace {
                 quote! {
-                    let write_size = self.#field_idx.write_size(version);
+                    let write_size = #write_size;
                     tracing::trace!("write size: <{}> field: <{}> => {}",stringify!(#struct_ident),stringify!(#idx),write_size);
                     len += write_size;
                 }
             } else {
                 quote! {
-                    len += self.#field_idx.write_size(version);
+                    len += #write_size;
                 }
             };
             prop.version_check_token_stream(base,attr.trace)
@@ -1,4 +1,13 @@
-use syn::{Attribute, Lit, LitStr, Meta, MetaNameValue, NestedMeta};
+use std::iter::Peekable;
+
+use quote::ToTokens;
+use syn::parse::discouraged::Speculative;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{
+    parenthesized, token, Attribute, Error, Lit, LitStr, Meta, MetaNameValue, NestedMeta, Path,
+    Token,
+};
 
 pub(crate) fn find_attr(attrs: &[Attribute], name: &str) -> Option<Meta> {
     attrs.iter().find_map(|a| {
@@ -16,6 +25,78 @@ pub(crate) fn find_attr(attrs: &[Attribute], name: &str) -> Option<Meta> {
     })
 }
 
+/// One item of an attribute list
+///
+/// Like [`NestedMeta`], except that the value of `name = value` may also be a
+/// path, such as `min_version = crate::protocol::MIN_VERSION`, or a call of a
+/// function without arguments, such as `min_version = crate::protocol::min_version()`.
+pub(crate) enum AttrItem {
+    Meta(NestedMeta),
+    PathValue { name: Path, value: Path, call: bool },
+}
+
+impl Parse for AttrItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let fork = input.fork();
+        if let Ok(nested) = fork.parse::<NestedMeta>() {
+            input.advance_to(&fork);
+            return Ok(Self::Meta(nested));
+        }
+        let name = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let value = input.parse()?;
+        let call = input.peek(token::Paren);
+        if call {
+            let args;
+            parenthesized!(args in input);
+            if !args.is_empty() {
+                return Err(args.error("only functions without arguments can be called here"));
+            }
+        }
+        Ok(Self::PathValue { name, value, call })
+    }
+}
+
+/// Items of every attribute named `name`, such as the `#[fluvio(...)]` of a field split over several lines
+pub(crate) fn find_all_attrs(attrs: &[Attribute], name: &str) -> syn::Result<Vec<AttrItem>> {
+    let mut items = vec![];
+    for attribute in attrs
+        .iter()
+        .filter(|attribute| attribute.path.is_ident(name))
+    {
+        items.extend(
+            attribute.parse_args_with(Punctuated::<AttrItem, Token![,]>::parse_terminated)?,
+        );
+    }
+    Ok(items)
+}
+
+/// Values of a list attribute such as `tags = "a", "b"`
+///
+/// The list parses as `tags = "a"` followed by bare literals, so the first value
+/// comes from `name_value` and the others are taken from `items` while they are strings.
+pub(crate) fn get_lit_str_list<I>(
+    name_value: &MetaNameValue,
+    items: &mut Peekable<I>,
+) -> syn::Result<Vec<String>>
+where
+    I: Iterator<Item = AttrItem>,
+{
+    let Lit::Str(first) = &name_value.lit else {
+        let name = name_value.path.to_token_stream().to_string();
+        return Err(Error::new(
+            name_value.lit.span(),
+            format!("{name} expects string literals, such as `{name} = \"a\", \"b\"`."),
+        ));
+    };
+    let mut values = vec![first.value()];
+    while let Some(AttrItem::Meta(NestedMeta::Lit(Lit::Str(value)))) = items.peek() {
+        values.push(value.value());
+        items.next();
+    }
+    Ok(values)
+}
+
 pub(crate) fn find_name_attribute<'a>(meta: &'a Meta, name: &str) -> Option<&'a MetaNameValue> {
     find_meta(meta, name).map(|meta| match meta {
         Meta::NameValue(name_value) => name_value,
@@ -22,6 +22,18 @@ mod test {
         pub value3: i8,
     }
 
+    #[derive(Encoder, Decoder, FluvioDefault, RequestApi, Debug)]
+    #[fluvio(
+        api_min_version = 0,
+        api_key = 11,
+        response = "TestResponse",
+        retry_count = 3,
+        retry_backoff_ms = 250
+    )]
+    pub struct RetriedRequest {
+        pub value: i8,
+    }
+
     #[derive(Encoder, Decoder, FluvioDefault, Debug)]
     pub struct TestResponse {
         pub value: i8,
@@ -52,7 +64,7 @@ mod test {
         pub value: i8,
 
         // only works up to version 4
-        #[fluvio(max_version = 4)]
+        #[fluvio(min_version = 0, max_version = 4)]
         pub addr: i8,
 
         // works from version 2
@@ -108,6 +120,28 @@ mod test {
         assert_eq!(TestRequest::MAX_API_VERSION, 6);
     }
 
+    #[test]
+    fn test_effective_version() {
+        let request = TestRequest::default();
+        assert_eq!(request.effective_version(Some(6)), 6);
+        assert_eq!(request.effective_version(Some(9)), 6);
+        assert_eq!(request.effective_version(Some(1)), 5);
+        // the default version 0 is below the min version
+        assert_eq!(request.effective_version(None), 5);
+
+        let request = RetriedRequest::default();
+        assert_eq!(request.effective_version(None), 0);
+        assert_eq!(request.effective_version(Some(3)), 0);
+    }
+
+    #[test]
+    fn test_api_request_retry() {
+        assert_eq!(TestRequest::RETRY_COUNT, None);
+        assert_eq!(TestRequest::RETRY_BACKOFF_MS, None);
+        assert_eq!(RetriedRequest::RETRY_COUNT, Some(3));
+        assert_eq!(RetriedRequest::RETRY_BACKOFF_MS, Some(250));
+    }
+
     #[test]
     fn test_api_getter() {
         let record = TestRequest {
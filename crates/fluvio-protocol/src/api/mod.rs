@@ -46,7 +46,28 @@ mod common {
         const MIN_API_VERSION: i16 = max(Self::DEFAULT_API_VERSION - 1, 0); // by default, only suport last version
         const MAX_API_VERSION: i16 = Self::DEFAULT_API_VERSION;
 
+        /// How many times a client may resend the request after it failed, `None` to not retry
+        const RETRY_COUNT: Option<u32> = None;
+        /// How long a client waits before resending the request, in milliseconds
+        const RETRY_BACKOFF_MS: Option<u64> = None;
+
         type Response: Encoder + Decoder + Debug;
+
+        /// Version to encode the request with: the version `negotiated` with
+        /// the server, or [`DEFAULT_API_VERSION`], clamped to the versions
+        /// the request supports
+        ///
+        /// [`MIN_API_VERSION`] wins if it is above [`MAX_API_VERSION`].
+        ///
+        /// [`DEFAULT_API_VERSION`]: Request::DEFAULT_API_VERSION
+        /// [`MIN_API_VERSION`]: Request::MIN_API_VERSION
+        /// [`MAX_API_VERSION`]: Request::MAX_API_VERSION
+        fn effective_version(&self, negotiated: Option<i16>) -> i16 {
+            negotiated
+                .unwrap_or(Self::DEFAULT_API_VERSION)
+                .min(Self::MAX_API_VERSION)
+                .max(Self::MIN_API_VERSION)
+        }
     }
 
     pub trait ApiMessage: Sized + Default {
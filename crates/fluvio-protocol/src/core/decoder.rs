@@ -1,5 +1,6 @@
 use std::cmp::Ord;
 use std::collections::BTreeMap;
+use std::fmt;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::io::Read;
@@ -25,11 +26,86 @@ pub trait Decoder: Sized + Default {
         Ok(decoder)
     }
 
+    /// decode value from buf, failing if any bytes are left over
+    ///
+    /// Leftover bytes are reported as an `InvalidData` error wrapping [`TrailingBytes`].
+    /// Use `decode_from` for values embedded inside a larger structure.
+    fn decode_exact<T>(src: &mut T, version: Version) -> Result<Self, Error>
+    where
+        T: Buf,
+    {
+        let actual = src.remaining();
+        let (decoder, expected) = Self::decode_with_consumed(src, version)?;
+        if expected != actual {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                TrailingBytes { expected, actual },
+            ));
+        }
+        Ok(decoder)
+    }
+
+    /// decode value from buf and return number of bytes read
+    fn decode_with_consumed<T>(src: &mut T, version: Version) -> Result<(Self, usize), Error>
+    where
+        T: Buf,
+    {
+        let before = src.remaining();
+        let decoder = Self::decode_from(src, version)?;
+        Ok((decoder, before - src.remaining()))
+    }
+
     fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), Error>
     where
         T: Buf;
 }
 
+/// Buffer contained more bytes than the decoded value accounts for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrailingBytes {
+    /// bytes consumed by the decoded value
+    pub expected: usize,
+    /// bytes available in the buffer
+    pub actual: usize,
+}
+
+impl fmt::Display for TrailingBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "trailing bytes after decoding: expected {} bytes, found {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for TrailingBytes {}
+
+/// Decoding failure reported as an `InvalidData` error wrapping this value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The length prefix of a `length_delimited` field is negative or exceeds the bytes left
+    LengthDelimitedOverflow {
+        /// the decoded length prefix
+        length: i32,
+        /// bytes left in the buffer after the prefix
+        available: usize,
+    },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::LengthDelimitedOverflow { length, available } => write!(
+                f,
+                "length delimited message of {length} bytes doesn't fit in the {available} bytes left"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
 pub trait DecoderVarInt {
     fn decode_varint<T>(&mut self, src: &mut T) -> Result<(), Error>
     where
@@ -130,6 +206,33 @@ where
     }
 }
 
+/// Tuples are decoded element by element, without any prefix
+macro_rules! impl_decoder_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> Decoder for ($($name,)+)
+        where
+            $($name: Decoder,)+
+        {
+            fn decode<T>(&mut self, src: &mut T, version: Version) -> Result<(), Error>
+            where
+                T: Buf,
+            {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                $($name.decode(src, version)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_decoder_for_tuple!(A);
+impl_decoder_for_tuple!(A, B);
+impl_decoder_for_tuple!(A, B, C);
+impl_decoder_for_tuple!(A, B, C, D);
+impl_decoder_for_tuple!(A, B, C, D, E);
+impl_decoder_for_tuple!(A, B, C, D, E, F);
+
 impl Decoder for bool {
     fn decode<T>(&mut self, src: &mut T, _version: Version) -> Result<(), Error>
     where
@@ -1,5 +1,6 @@
 // decode values
 use std::collections::BTreeMap;
+use std::fmt;
 use std::io::Error;
 use std::io::ErrorKind;
 use std::io::Write;
@@ -37,6 +38,59 @@ pub trait Encoder {
     }
 }
 
+/// Encoding into a caller-provided slice, derived for structs made only of fixed-width primitives
+pub trait EncodeInto: Encoder {
+    /// Encodes into the start of `buf` without allocating, returning the number of bytes written
+    fn encode_into(&self, buf: &mut [u8], version: Version) -> Result<usize, EncodeError> {
+        let needed = self.write_size(version);
+        if buf.len() < needed {
+            return Err(EncodeError::InsufficientSpace {
+                needed,
+                available: buf.len(),
+            });
+        }
+        let mut dest = &mut buf[..needed];
+        self.encode(&mut dest, version)?;
+        Ok(needed)
+    }
+}
+
+/// Error from [`EncodeInto::encode_into`]
+#[derive(Debug)]
+pub enum EncodeError {
+    /// The destination slice is too small for the encoded value
+    InsufficientSpace { needed: usize, available: usize },
+    /// Encoding a field failed
+    Io(Error),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InsufficientSpace { needed, available } => write!(
+                f,
+                "not enough space to encode: needed {needed} bytes, available {available}"
+            ),
+            Self::Io(err) => write!(f, "encoding failed: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for EncodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InsufficientSpace { .. } => None,
+            Self::Io(err) => Some(err),
+        }
+    }
+}
+
+impl From<Error> for EncodeError {
+    fn from(err: Error) -> Self {
+        Self::Io(err)
+    }
+}
+
 pub trait EncoderVarInt {
     fn var_write_size(&self) -> usize;
 
@@ -149,6 +203,39 @@ where
     }
 }
 
+/// Tuples are encoded element by element, without any prefix
+macro_rules! impl_encoder_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> Encoder for ($($name,)+)
+        where
+            $($name: Encoder,)+
+        {
+            fn write_size(&self, version: Version) -> usize {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                0 $(+ $name.write_size(version))+
+            }
+
+            fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
+            where
+                T: BufMut,
+            {
+                #[allow(non_snake_case)]
+                let ($($name,)+) = self;
+                $($name.encode(dest, version)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_encoder_for_tuple!(A);
+impl_encoder_for_tuple!(A, B);
+impl_encoder_for_tuple!(A, B, C);
+impl_encoder_for_tuple!(A, B, C, D);
+impl_encoder_for_tuple!(A, B, C, D, E);
+impl_encoder_for_tuple!(A, B, C, D, E, F);
+
 impl Encoder for bool {
     fn write_size(&self, _version: Version) -> usize {
         1
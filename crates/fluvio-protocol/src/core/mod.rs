@@ -5,8 +5,12 @@ mod varint;
 mod zerocopy;
 
 pub use self::bytebuf::ByteBuf;
+pub use self::decoder::DecodeError;
 pub use self::decoder::Decoder;
 pub use self::decoder::DecoderVarInt;
+pub use self::decoder::TrailingBytes;
+pub use self::encoder::EncodeError;
+pub use self::encoder::EncodeInto;
 pub use self::encoder::Encoder;
 pub use self::encoder::EncoderVarInt;
 
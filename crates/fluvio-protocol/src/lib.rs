@@ -33,9 +33,16 @@ pub mod fixture;
 #[cfg(all(unix, feature = "store"))]
 pub mod store;
 
+#[cfg(feature = "schema")]
+pub mod schema;
+
 pub use self::core::ByteBuf;
+pub use self::core::DecodeError;
 pub use self::core::Decoder;
 pub use self::core::DecoderVarInt;
+pub use self::core::TrailingBytes;
+pub use self::core::EncodeError;
+pub use self::core::EncodeInto;
 pub use self::core::Encoder;
 pub use self::core::EncoderVarInt;
 pub use self::core::Version;
@@ -69,6 +76,23 @@ mod inner_deriver {
     /// Encoder applies to either Struct of Enum.  
     ///
     /// Encoder respects version attributes.  See Decoder derive.
+    ///
+    /// For a struct, it also adds `VERSION_BOUNDS`, the lowest `min_version` and the highest
+    /// `max_version` of its fields, with `i16::MAX` when a field has no `max_version`.
+    ///
+    /// ```
+    /// use fluvio_protocol::Encoder;
+    ///
+    /// #[derive(Encoder)]
+    /// pub struct FetchRequest {
+    ///     #[fluvio(min_version = 1)]
+    ///     isolation: u8,
+    ///     #[fluvio(min_version = 2, max_version = 5)]
+    ///     session: u32,
+    /// }
+    ///
+    /// assert_eq!(FetchRequest::VERSION_BOUNDS, (1, i16::MAX));
+    /// ```
     pub use fluvio_protocol_derive::Encoder;
 
     /// Custom derive for decoding structure or enum from bytes using fluvio protocol format.
@@ -137,8 +161,39 @@ mod inner_deriver {
     /// For field attributes
     /// * `#[varint]` force decode using varint format.
     /// * `#[trace]` print out debug information during decoding
-    /// * `#fluvio(min_version = <version>)]` decodes only if version is equal or greater than min_version
-    /// * `#fluvio(max_version = <version>)]`decodes only if version is less or equal than max_version
+    /// * `#fluvio(min_version = <version>)]` decodes only if version is equal or greater than min_version,
+    ///   which can't be negative. Versions are integer literals or the path of an `i16` constant,
+    ///   such as `crate::protocol::MIN_VERSION`, or a call of a `const fn` without arguments,
+    ///   written `"min_version()"` or `crate::protocol::min_version()`. Constants and calls are
+    ///   checked by const assertions, and fields using them are left out of `VERSION_BOUNDS`.
+    /// * `#fluvio(max_version = <version>)]`decodes only if version is less or equal than max_version,
+    ///   it must come with a `min_version`
+    /// * `#fluvio(deprecated_since = <version>)]` marks the field as deprecated from that version on.
+    ///   It is still decoded within its version range, and `#[fluvio(trace)]` reports it when it is.
+    ///   Deriving `Encoder` raises a `deprecated` warning at the attribute, which `#[allow(deprecated)]`
+    ///   on the field silences.
+    /// * `#fluvio(ignorable)]` marks a field that is only present in some versions.
+    ///   It must come with a `min_version` or `max_version`.
+    /// * `#fluvio(rename = "<name>")]` names the field in trace messages and `FluvioDebug` output,
+    ///   the Rust field name and the encoding are unchanged.
+    /// * `#fluvio(skip_decoding)]` never reads the field, it is set to its `default` value instead.
+    ///   The field is still encoded, for values that are only read back from legacy messages.
+    /// * `#fluvio(skip_encoding)]` never writes the field, it is still decoded.
+    /// * `#fluvio(validate = "<function>")]` calls `function(&value) -> Result<(), String>` once the
+    ///   field is decoded, an `Err` fails the decode with `InvalidData` and its message.
+    /// * `#fluvio(array_length_encoding = "<type>")]` prefixes a `Vec` field with a length of type
+    ///   `i16`, `u16`, `i32` or `u32` instead of the default `i32`, for both encoding and decoding.
+    /// * `#fluvio(flatten)]` encodes a named struct field inline with its own `Encoder` and `Decoder`
+    ///   at the version of the container, without a length prefix.
+    /// * `#fluvio(varint_zigzag)]` encodes an `i8`, `i16` or `i32` field as a zigzag varint, so
+    ///   small negative values such as a `-1` error code take a single byte.
+    /// * `#fluvio(length_delimited)]` prefixes the encoded field with its size as an `i32`. Decoding
+    ///   reads exactly that many bytes and skips what the field doesn't use, a prefix larger than
+    ///   the buffer fails with [`crate::DecodeError::LengthDelimitedOverflow`].
+    /// * `#fluvio(tags = "<tag>", "<tag>")]` labels the field in its `FluvioSchema`, the encoding is unchanged.
+    ///
+    /// Flags such as `skip_decoding` or `flatten` can also be written `skip_decoding = true` or
+    /// `skip_decoding = false`, for instance when the attribute is generated by another macro.
     ///
     pub use fluvio_protocol_derive::Decoder;
 
@@ -171,6 +226,8 @@ mod inner_deriver {
     /// * `api_max_version`:  max version that API supports.  This is optional.
     /// * `api_key`:  API number.  This is required
     /// * `response`:  Response struct.  This is required
+    /// * `retry_count`:  how many times a client may resend a failed request.  This is optional
+    /// * `retry_backoff_ms`:  milliseconds to wait between retries.  This is optional and needs `retry_count`
     ///
     ///
     #[cfg(feature = "api")]
@@ -195,5 +252,67 @@ mod inner_deriver {
     /// ```
     ///
     /// `default` assignment can be any Rust expression.
+    /// `#[fluvio(default_fn = "<function>")]` calls `function()` instead, for defaults that are
+    /// easier to build in a function. It is also used for fields marked `skip_decoding`.
     pub use fluvio_protocol_derive::FluvioDefault;
+
+    /// Custom derive for `Debug` that keeps sensitive fields out of the output
+    ///
+    /// Fields marked `#[fluvio(sensitive)]` print only their encoded length.
+    /// The same fields are also left out of `#[fluvio(trace)]` messages
+    /// generated by the `Encoder` and `Decoder` derives.
+    ///
+    /// ```
+    /// use fluvio_protocol::FluvioDebug;
+    ///
+    /// #[derive(FluvioDebug)]
+    /// pub struct Credentials {
+    ///     user: String,
+    ///     #[fluvio(sensitive)]
+    ///     password: String,
+    /// }
+    ///
+    /// let credentials = Credentials {
+    ///     user: "admin".to_owned(),
+    ///     password: "secret".to_owned(),
+    /// };
+    /// assert_eq!(
+    ///     format!("{credentials:?}"),
+    ///     r#"Credentials { user: "admin", password: <redacted len=8> }"#
+    /// );
+    /// ```
+    pub use fluvio_protocol_derive::FluvioDebug;
+
+    /// Custom derive for [`FluvioSchemaProvider`](crate::schema::FluvioSchemaProvider),
+    /// which describes the wire format as a JSON Schema
+    ///
+    /// Each field is listed with its type, the versions it is encoded in and whether
+    /// it is optional, an `Option`. Field types are described through their own
+    /// `FluvioSchemaProvider`, so nested messages derive it as well.
+    /// `#[fluvio(rename = "<name>")]` names the field in the schema, and
+    /// `#[fluvio(tags = "<tag>", "<tag>")]` lists labels such as feature names with it.
+    ///
+    /// The schema follows the encoding attributes: `skip_encoding` fields are left
+    /// out, the fields of a `flatten` field are listed inline, and `varint`,
+    /// `varint_zigzag`, `array_length_encoding` and `length_delimited` fields are
+    /// described as they are encoded, see [`schema`](crate::schema).
+    ///
+    /// ```
+    /// use fluvio_protocol::FluvioSchema;
+    /// use fluvio_protocol::schema::FluvioSchemaProvider;
+    ///
+    /// #[derive(FluvioSchema)]
+    /// pub struct FetchRequest {
+    ///     topic: String,
+    ///     #[fluvio(min_version = 2, max_version = 5)]
+    ///     session: Option<u32>,
+    /// }
+    ///
+    /// let schema = FetchRequest::schema();
+    /// assert_eq!(schema["properties"]["session"]["minVersion"], 2);
+    /// assert_eq!(schema["required"][0], "topic");
+    /// assert_eq!(schema["required"].as_array().unwrap().len(), 1);
+    /// ```
+    #[cfg(feature = "schema")]
+    pub use fluvio_protocol_derive::FluvioSchema;
 }
@@ -391,8 +391,9 @@ impl<R> Encoder for Batch<R>
 where
     R: BatchRecords,
 {
-    fn write_size(&self, version: Version) -> usize {
-        BATCH_FILE_HEADER_SIZE + self.records.write_size(version)
+    fn write_size(&self, _version: Version) -> usize {
+        // the schema id is only encoded when the header says it's present
+        BATCH_PREAMBLE_SIZE + self.calc_batch_len() as usize
     }
 
     fn encode<T>(&self, dest: &mut T, version: Version) -> Result<(), Error>
@@ -618,6 +619,7 @@ mod test {
             bytes.len(),
             bytes.as_ref()
         );
+        assert_eq!(batch.write_size(0), bytes.len());
 
         let batch = Batch::<MemoryRecords>::decode_from(&mut Cursor::new(bytes), 0)?;
         println!("batch: {batch:#?}");
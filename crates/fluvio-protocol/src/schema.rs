@@ -0,0 +1,357 @@
+//! JSON Schema descriptions of wire formats
+//!
+//! [`FluvioSchemaProvider`] describes a type as a JSON Schema document. The
+//! `FluvioSchema` derive implements it for structs and enums, nesting the
+//! schema of each field's type, so `T::schema()` describes a whole message.
+//!
+//! Besides the standard keywords, the schemas use:
+//! * `minVersion` and `maxVersion`, the versions a field is encoded in
+//! * `fieldOrder`, the fields of a struct in the order they are encoded
+//! * `tag`, the value an enum variant is encoded as
+//! * `lengthFormat`, the integer format of the item count before an array
+//!   when it isn't the default `int32`
+//! * `sizePrefix`, the integer format of the byte size written before a
+//!   length delimited field
+//!
+//! Integers encoded as zigzag varints have the `varint` format. Fields that
+//! are not encoded are left out, and flattened fields are listed inline.
+
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+pub use serde_json;
+use serde_json::{json, Value};
+
+use crate::{ByteBuf, Version};
+
+/// Describes the wire format of a type as a JSON Schema
+///
+/// ```
+/// use fluvio_protocol::schema::FluvioSchemaProvider;
+///
+/// assert_eq!(i16::schema()["format"], "int16");
+/// assert!(Option::<String>::is_optional());
+/// ```
+pub trait FluvioSchemaProvider {
+    /// JSON Schema of the type
+    fn schema() -> Value;
+
+    /// Whether a value may be absent, as with `Option`
+    fn is_optional() -> bool {
+        false
+    }
+}
+
+/// Schema of a field of type `T`, with the versions it is encoded in
+///
+/// `max_version` is left out when the field has no upper bound.
+pub fn field_schema<T>(min_version: Version, max_version: Option<Version>) -> Value
+where
+    T: FluvioSchemaProvider + ?Sized,
+{
+    with_versions(T::schema(), min_version, max_version)
+}
+
+/// `schema` with the versions a field is encoded in, see [`field_schema`]
+pub fn with_versions(
+    mut schema: Value,
+    min_version: Version,
+    max_version: Option<Version>,
+) -> Value {
+    if let Value::Object(keywords) = &mut schema {
+        keywords.insert("minVersion".to_owned(), json!(min_version));
+        if let Some(max_version) = max_version {
+            keywords.insert("maxVersion".to_owned(), json!(max_version));
+        }
+    }
+    schema
+}
+
+/// Schema of an integer encoded as a zigzag varint
+pub fn varint_schema() -> Value {
+    json!({ "type": "integer", "format": "varint" })
+}
+
+/// Schema of an array whose item count is encoded as an `L` instead of an `i32`
+pub fn with_length_format<L>(mut schema: Value) -> Value
+where
+    L: FluvioSchemaProvider,
+{
+    if let Value::Object(keywords) = &mut schema {
+        keywords.insert("lengthFormat".to_owned(), L::schema()["format"].clone());
+    }
+    schema
+}
+
+/// Schema of a field prefixed with its encoded size as an `i32`
+pub fn with_size_prefix(mut schema: Value) -> Value {
+    if let Value::Object(keywords) = &mut schema {
+        keywords.insert("sizePrefix".to_owned(), i32::schema()["format"].clone());
+    }
+    schema
+}
+
+/// Schema of a field labelled with `#[fluvio(tags = ...)]`
+pub fn with_tags(mut schema: Value, tags: &[&str]) -> Value {
+    if let Value::Object(keywords) = &mut schema {
+        keywords.insert("tags".to_owned(), json!(tags));
+    }
+    schema
+}
+
+/// Fields of a struct schema being built by the `FluvioSchema` derive
+#[derive(Debug, Default)]
+pub struct StructFields {
+    properties: serde_json::Map<String, Value>,
+    required: Vec<String>,
+    field_order: Vec<String>,
+}
+
+impl StructFields {
+    /// Adds field `name` with schema `schema`, required unless `optional`
+    pub fn push(&mut self, name: &str, schema: Value, optional: bool) {
+        if !optional {
+            self.required.push(name.to_owned());
+        }
+        self.field_order.push(name.to_owned());
+        self.properties.insert(name.to_owned(), schema);
+    }
+
+    /// Adds the fields of `schema`, a struct encoded inline, within the versions of the field
+    pub fn flatten(&mut self, schema: Value, min_version: Version, max_version: Option<Version>) {
+        let Value::Object(mut keywords) = schema else {
+            return;
+        };
+        let mut properties = match keywords.remove("properties") {
+            Some(Value::Object(properties)) => properties,
+            _ => serde_json::Map::new(),
+        };
+        let required = keywords.remove("required").unwrap_or_default();
+        let field_order = match keywords.remove("fieldOrder") {
+            Some(Value::Array(field_order)) => field_order,
+            _ => vec![],
+        };
+        for name in field_order.iter().filter_map(Value::as_str) {
+            let Some(mut property) = properties.remove(name) else {
+                continue;
+            };
+            // the inner field is only encoded when the flattened field is
+            let min = property["minVersion"].as_i64().map_or(min_version, |min| {
+                min.max(i64::from(min_version)) as Version
+            });
+            let max = match (property["maxVersion"].as_i64(), max_version) {
+                (Some(inner), Some(outer)) => Some(inner.min(i64::from(outer)) as Version),
+                (Some(inner), None) => Some(inner as Version),
+                (None, outer) => outer,
+            };
+            property = with_versions(property, min, max);
+            let optional = !required
+                .as_array()
+                .is_some_and(|required| required.iter().any(|field| field == name));
+            self.push(name, property, optional);
+        }
+    }
+
+    /// Schema of the struct, an object with the fields in encoding order
+    pub fn into_schema(self) -> Value {
+        json!({
+            "type": "object",
+            "properties": self.properties,
+            "required": self.required,
+            "fieldOrder": self.field_order,
+        })
+    }
+}
+
+macro_rules! impl_schema_for_integer {
+    ($($ty:ty => $format:literal),+) => {
+        $(
+            impl FluvioSchemaProvider for $ty {
+                fn schema() -> Value {
+                    json!({ "type": "integer", "format": $format })
+                }
+            }
+        )+
+    };
+}
+
+impl_schema_for_integer!(
+    i8 => "int8",
+    u8 => "uint8",
+    i16 => "int16",
+    u16 => "uint16",
+    i32 => "int32",
+    u32 => "uint32",
+    i64 => "int64",
+    u64 => "uint64"
+);
+
+impl FluvioSchemaProvider for bool {
+    fn schema() -> Value {
+        json!({ "type": "boolean" })
+    }
+}
+
+impl FluvioSchemaProvider for String {
+    fn schema() -> Value {
+        json!({ "type": "string" })
+    }
+}
+
+impl FluvioSchemaProvider for ByteBuf {
+    fn schema() -> Value {
+        json!({ "type": "string", "format": "binary" })
+    }
+}
+
+/// Encoded as seconds followed by nanoseconds
+impl FluvioSchemaProvider for Duration {
+    fn schema() -> Value {
+        json!({
+            "title": "Duration",
+            "type": "object",
+            "properties": {
+                "secs": u64::schema(),
+                "nanos": u32::schema(),
+            },
+            "required": ["secs", "nanos"],
+            "fieldOrder": ["secs", "nanos"],
+        })
+    }
+}
+
+impl<M> FluvioSchemaProvider for Vec<M>
+where
+    M: FluvioSchemaProvider,
+{
+    fn schema() -> Value {
+        json!({ "type": "array", "items": M::schema() })
+    }
+}
+
+impl<M> FluvioSchemaProvider for Option<M>
+where
+    M: FluvioSchemaProvider,
+{
+    fn schema() -> Value {
+        json!({ "anyOf": [M::schema(), { "type": "null" }] })
+    }
+
+    fn is_optional() -> bool {
+        true
+    }
+}
+
+/// Nothing is encoded
+impl<M> FluvioSchemaProvider for PhantomData<M> {
+    fn schema() -> Value {
+        json!({ "type": "null" })
+    }
+}
+
+/// Encoded as a list of key and value pairs
+impl<K, V> FluvioSchemaProvider for BTreeMap<K, V>
+where
+    K: FluvioSchemaProvider,
+    V: FluvioSchemaProvider,
+{
+    fn schema() -> Value {
+        json!({
+            "type": "array",
+            "items": {
+                "type": "array",
+                "prefixItems": [K::schema(), V::schema()],
+                "items": false,
+            },
+        })
+    }
+}
+
+impl<M> FluvioSchemaProvider for &M
+where
+    M: FluvioSchemaProvider,
+{
+    fn schema() -> Value {
+        M::schema()
+    }
+
+    fn is_optional() -> bool {
+        M::is_optional()
+    }
+}
+
+/// Tuples are encoded element by element, without any prefix
+macro_rules! impl_schema_for_tuple {
+    ($($name:ident),+) => {
+        impl<$($name),+> FluvioSchemaProvider for ($($name,)+)
+        where
+            $($name: FluvioSchemaProvider,)+
+        {
+            fn schema() -> Value {
+                json!({
+                    "type": "array",
+                    "prefixItems": [$($name::schema()),+],
+                    "items": false,
+                })
+            }
+        }
+    };
+}
+
+impl_schema_for_tuple!(A);
+impl_schema_for_tuple!(A, B);
+impl_schema_for_tuple!(A, B, C);
+impl_schema_for_tuple!(A, B, C, D);
+impl_schema_for_tuple!(A, B, C, D, E);
+impl_schema_for_tuple!(A, B, C, D, E, F);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_schema() {
+        assert_eq!(
+            field_schema::<u8>(2, Some(4)),
+            json!({ "type": "integer", "format": "uint8", "minVersion": 2, "maxVersion": 4 })
+        );
+        assert_eq!(
+            field_schema::<Vec<String>>(0, None),
+            json!({ "type": "array", "items": { "type": "string" }, "minVersion": 0 })
+        );
+    }
+
+    #[test]
+    fn test_flatten() {
+        let mut inner = StructFields::default();
+        inner.push("id", with_versions(i32::schema(), 0, None), false);
+        inner.push(
+            "leader",
+            with_versions(Option::<i32>::schema(), 3, Some(5)),
+            true,
+        );
+
+        let mut fields = StructFields::default();
+        fields.push("name", with_versions(String::schema(), 0, None), false);
+        fields.flatten(inner.into_schema(), 2, Some(4));
+        let schema = fields.into_schema();
+
+        assert_eq!(schema["fieldOrder"], json!(["name", "id", "leader"]));
+        assert_eq!(schema["required"], json!(["name", "id"]));
+        assert_eq!(schema["properties"]["id"]["minVersion"], 2);
+        assert_eq!(schema["properties"]["id"]["maxVersion"], 4);
+        assert_eq!(schema["properties"]["leader"]["minVersion"], 3);
+        assert_eq!(schema["properties"]["leader"]["maxVersion"], 4);
+    }
+
+    #[test]
+    fn test_optional() {
+        assert!(Option::<i32>::is_optional());
+        assert!(!i32::is_optional());
+        assert_eq!(
+            Option::<i32>::schema(),
+            json!({ "anyOf": [{ "type": "integer", "format": "int32" }, { "type": "null" }] })
+        );
+    }
+}
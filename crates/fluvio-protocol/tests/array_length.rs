@@ -0,0 +1,77 @@
+use std::io::Cursor;
+
+use fluvio_protocol::{Decoder, Encoder};
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct ShortArrays {
+    #[fluvio(array_length_encoding = "u16")]
+    names: Vec<String>,
+    #[fluvio(array_length_encoding = "i16")]
+    ids: Vec<i8>,
+    #[fluvio(array_length_encoding = "u32")]
+    flags: Vec<u8>,
+    default_len: Vec<u8>,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct ShortArrayTuple(i8, #[fluvio(array_length_encoding = "i16")] Vec<i8>);
+
+#[test]
+fn test_array_length_encoding() {
+    let arrays = ShortArrays {
+        names: vec!["a".to_owned()],
+        ids: vec![1, 2],
+        flags: vec![3],
+        default_len: vec![4],
+    };
+
+    let mut dest = vec![];
+    arrays.encode(&mut dest, 0).expect("encode");
+
+    assert_eq!(
+        dest,
+        vec![
+            0, 1, 0, 1, b'a', // names
+            0, 2, 1, 2, // ids
+            0, 0, 0, 1, 3, // flags
+            0, 0, 0, 1, 4, // default_len
+        ]
+    );
+    assert_eq!(arrays.write_size(0), dest.len());
+
+    let decoded = ShortArrays::decode_from(&mut Cursor::new(&dest), 0).expect("decode");
+    assert_eq!(decoded, arrays);
+}
+
+#[test]
+fn test_array_length_encoding_tuple() {
+    let arrays = ShortArrayTuple(5, vec![6, 7]);
+
+    let mut dest = vec![];
+    arrays.encode(&mut dest, 0).expect("encode");
+
+    assert_eq!(dest, vec![5, 0, 2, 6, 7]);
+    assert_eq!(arrays.write_size(0), dest.len());
+
+    let decoded = ShortArrayTuple::decode_from(&mut Cursor::new(&dest), 0).expect("decode");
+    assert_eq!(decoded, arrays);
+}
+
+#[test]
+fn test_array_length_encoding_negative_length() {
+    let data = [5, 0xff, 0xff];
+
+    let decoded = ShortArrayTuple::decode_from(&mut Cursor::new(&data), 0).expect("decode");
+
+    assert_eq!(decoded, ShortArrayTuple(5, vec![]));
+}
+
+#[test]
+fn test_array_length_encoding_overflow() {
+    let arrays = ShortArrayTuple(0, vec![0; i16::MAX as usize + 1]);
+
+    let mut dest = vec![];
+    let err = arrays.encode(&mut dest, 0).expect_err("too many items");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,84 @@
+use std::io::Cursor;
+
+use fluvio_protocol::{Decoder, Encoder, FluvioDebug};
+
+const SECRET: &str = "hunter2-super-secret";
+
+#[derive(Encoder, Decoder, Default, FluvioDebug, PartialEq)]
+pub struct SaslAuthenticate {
+    mechanism: String,
+    #[fluvio(sensitive)]
+    auth_bytes: Vec<u8>,
+    #[fluvio(sensitive)]
+    token: String,
+}
+
+#[derive(Encoder, Decoder, Default, FluvioDebug, PartialEq)]
+pub struct DelegationToken(i32, #[fluvio(sensitive)] String);
+
+#[test]
+fn test_sensitive_fields_are_redacted() {
+    let request = SaslAuthenticate {
+        mechanism: "PLAIN".to_owned(),
+        auth_bytes: SECRET.as_bytes().to_vec(),
+        token: SECRET.to_owned(),
+    };
+
+    let debug = format!("{request:?}");
+    assert!(!debug.contains(SECRET));
+    assert!(!debug.contains("104, 117")); // "hu" as bytes
+    assert_eq!(
+        debug,
+        r#"SaslAuthenticate { mechanism: "PLAIN", auth_bytes: <redacted len=24>, token: <redacted len=22> }"#
+    );
+
+    let pretty = format!("{request:#?}");
+    assert!(!pretty.contains(SECRET));
+
+    let mut src = vec![];
+    request.encode(&mut src, 0).expect("encode");
+    let decoded = SaslAuthenticate::decode_from(&mut Cursor::new(&src), 0).expect("decode");
+    assert!(decoded == request);
+}
+
+#[test]
+fn test_sensitive_tuple_field_is_redacted() {
+    let token = DelegationToken(7, SECRET.to_owned());
+
+    let debug = format!("{token:?}");
+    assert!(!debug.contains(SECRET));
+    assert_eq!(debug, "DelegationToken(7, <redacted len=22>)");
+
+    let mut src = vec![];
+    token.encode(&mut src, 0).expect("encode");
+    let decoded = DelegationToken::decode_from(&mut Cursor::new(&src), 0).expect("decode");
+    assert!(decoded == token);
+}
+
+#[derive(Encoder, Decoder, Default, FluvioDebug, PartialEq)]
+#[fluvio(trace)]
+pub struct FetchResponse {
+    #[fluvio(rename = "throttleTimeMs")]
+    throttle_time_ms: i32,
+    #[fluvio(rename = "sessionId", min_version = 7)]
+    session_id: i32,
+}
+
+#[test]
+fn test_renamed_fields() {
+    let response = FetchResponse {
+        throttle_time_ms: 100,
+        session_id: 3,
+    };
+
+    assert_eq!(
+        format!("{response:?}"),
+        "FetchResponse { throttleTimeMs: 100, sessionId: 3 }"
+    );
+
+    let mut src = vec![];
+    response.encode(&mut src, 7).expect("encode");
+    assert_eq!(src.len(), 8);
+    let decoded = FetchResponse::decode_from(&mut Cursor::new(&src), 7).expect("decode");
+    assert!(decoded == response);
+}
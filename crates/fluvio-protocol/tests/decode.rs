@@ -1,6 +1,7 @@
 use std::io::Cursor;
+use std::io::ErrorKind;
 
-use fluvio_protocol::{Decoder, DecoderVarInt};
+use fluvio_protocol::{Decoder, DecoderVarInt, TrailingBytes};
 
 #[derive(Decoder, Default, Debug)]
 pub struct SimpleRecord {
@@ -46,3 +47,58 @@ fn test_decode_recordset() {
     assert_eq!(record.len, 10);
     assert_eq!(record.attributes, 4);
 }
+
+#[test]
+fn test_decode_exact_recordset() {
+    let data = [
+        0x00, 0x00, 0x00, 0x01, // record count
+        0x14, // record length of 7
+        0x04, // attributes
+    ];
+    let recordset = RecordSet::decode_exact(&mut Cursor::new(&data), 0).expect("decode");
+    assert_eq!(recordset.records.len(), 1);
+
+    let (_, consumed) =
+        RecordSet::decode_with_consumed(&mut Cursor::new(&data), 0).expect("decode");
+    assert_eq!(consumed, data.len());
+}
+
+#[test]
+fn test_decode_exact_short_payload() {
+    let data = [
+        0x00, 0x00, 0x00, 0x01, // record count
+        0x14, // record length of 7
+    ];
+    let err = RecordSet::decode_exact(&mut Cursor::new(&data), 0).expect_err("short payload");
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn test_decode_exact_trailing_bytes() {
+    let data = [
+        0x00, 0x00, 0x00, 0x01, // record count
+        0x14, // record length of 7
+        0x04, // attributes
+        0xff, 0xff, // garbage
+    ];
+
+    // lenient decoding ignores the leftover bytes
+    let (recordset, consumed) =
+        RecordSet::decode_with_consumed(&mut Cursor::new(&data), 0).expect("decode");
+    assert_eq!(recordset.records.len(), 1);
+    assert_eq!(consumed, 6);
+
+    let err = RecordSet::decode_exact(&mut Cursor::new(&data), 0).expect_err("trailing bytes");
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let trailing = err
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<TrailingBytes>())
+        .expect("trailing bytes error");
+    assert_eq!(
+        *trailing,
+        TrailingBytes {
+            expected: 6,
+            actual: 8
+        }
+    );
+}
@@ -1,4 +1,6 @@
-use fluvio_protocol::FluvioDefault;
+use std::io::Cursor;
+
+use fluvio_protocol::{Decoder, FluvioDefault};
 
 #[derive(FluvioDefault, Debug)]
 struct TestRecord {
@@ -16,3 +18,40 @@ fn test_default() {
     assert_eq!(record.value3, 4);
     assert_eq!(record.value4, -1);
 }
+
+fn default_names() -> Vec<String> {
+    vec!["first".to_owned(), "second".to_owned()]
+}
+
+mod defaults {
+    pub fn replicas() -> i32 {
+        3
+    }
+}
+
+#[derive(FluvioDefault, Decoder, Debug)]
+struct FnDefaultRecord {
+    #[fluvio(default_fn = "default_names")]
+    names: Vec<String>,
+    #[fluvio(skip_decoding, default_fn = "defaults::replicas")]
+    replicas: i32,
+    value: i8,
+}
+
+#[test]
+fn test_default_fn() {
+    let record = FnDefaultRecord::default();
+    assert_eq!(record.names, vec!["first", "second"]);
+    assert_eq!(record.replicas, 3);
+    assert_eq!(record.value, 0);
+}
+
+#[test]
+fn test_default_fn_skip_decoding() {
+    let data = [0, 0, 0, 0, 7];
+
+    let record = FnDefaultRecord::decode_from(&mut Cursor::new(&data), 0).expect("decode");
+
+    assert_eq!(record.replicas, 3);
+    assert_eq!(record.value, 7);
+}
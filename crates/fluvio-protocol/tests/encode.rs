@@ -1,4 +1,4 @@
-use fluvio_protocol::{Encoder, EncoderVarInt};
+use fluvio_protocol::{EncodeError, EncodeInto, Encoder, EncoderVarInt};
 
 #[derive(Encoder)]
 pub struct ByteRecord {
@@ -48,3 +48,75 @@ fn test_encode_recordset() {
     assert_eq!(src[5], 0x0a);
     assert_eq!(recordset.write_size(0), src.len());
 }
+
+#[derive(Encoder, Default, Debug)]
+pub struct RequestHeader {
+    api_key: u16,
+    api_version: i16,
+    correlation_id: i32,
+    #[fluvio(min_version = 1)]
+    flags: u8,
+}
+
+#[test]
+fn test_encode_into_exact_buffer() {
+    let header = RequestHeader {
+        api_key: 1003,
+        api_version: 2,
+        correlation_id: 77,
+        flags: 1,
+    };
+
+    let mut expected = vec![];
+    header.encode(&mut expected, 1).expect("encode");
+
+    let mut buf = [0u8; 9];
+    let written = header.encode_into(&mut buf, 1).expect("encode into");
+    assert_eq!(written, 9);
+    assert_eq!(&buf[..], &expected[..]);
+
+    // version 0 leaves out the flags
+    let mut buf = [0xffu8; 9];
+    assert_eq!(header.encode_into(&mut buf, 0).expect("encode into"), 8);
+    assert_eq!(&buf[..8], &expected[..8]);
+    assert_eq!(buf[8], 0xff);
+}
+
+#[test]
+fn test_encode_into_small_buffer() {
+    let header = RequestHeader::default();
+    let mut buf = [0u8; 4];
+    let err = header.encode_into(&mut buf, 1).expect_err("too small");
+    assert!(matches!(
+        err,
+        EncodeError::InsufficientSpace {
+            needed: 9,
+            available: 4
+        }
+    ));
+}
+
+#[derive(Encoder, Default, Debug)]
+pub struct Checksum {
+    crc: u32,
+}
+
+impl Checksum {
+    // an inherent method of the same name is not shadowed by the derive
+    fn encode_into(&self) -> String {
+        format!("{:08x}", self.crc)
+    }
+}
+
+#[test]
+fn test_encode_into_beside_inherent_method() {
+    let checksum = Checksum { crc: 0xcafe };
+    assert_eq!(checksum.encode_into(), "0000cafe");
+
+    let mut buf = [0u8; 4];
+    assert_eq!(
+        EncodeInto::encode_into(&checksum, &mut buf, 0).expect("encode into"),
+        4
+    );
+    assert_eq!(buf, [0, 0, 0xca, 0xfe]);
+}
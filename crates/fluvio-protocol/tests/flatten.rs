@@ -0,0 +1,63 @@
+use std::io::Cursor;
+
+use fluvio_protocol::{Decoder, Encoder};
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct Partition {
+    index: i32,
+    #[fluvio(min_version = 1)]
+    leader_epoch: i16,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct Topic {
+    name: String,
+    #[fluvio(flatten)]
+    partition: Partition,
+    max_bytes: i8,
+}
+
+#[test]
+fn test_flatten() {
+    let topic = Topic {
+        name: "t".to_owned(),
+        partition: Partition {
+            index: 2,
+            leader_epoch: 3,
+        },
+        max_bytes: 4,
+    };
+
+    let mut dest = vec![];
+    topic.encode(&mut dest, 1).expect("encode");
+
+    assert_eq!(dest, vec![0, 1, b't', 0, 0, 0, 2, 0, 3, 4]);
+    assert_eq!(topic.write_size(1), dest.len());
+
+    let decoded = Topic::decode_from(&mut Cursor::new(&dest), 1).expect("decode");
+    assert_eq!(decoded, topic);
+}
+
+#[test]
+fn test_flatten_uses_container_version() {
+    let topic = Topic {
+        name: "t".to_owned(),
+        partition: Partition {
+            index: 2,
+            leader_epoch: 3,
+        },
+        max_bytes: 4,
+    };
+
+    let mut dest = vec![];
+    topic.encode(&mut dest, 0).expect("encode");
+
+    // leader_epoch is only in version 1 and later
+    assert_eq!(dest, vec![0, 1, b't', 0, 0, 0, 2, 4]);
+    assert_eq!(topic.write_size(0), dest.len());
+
+    let decoded = Topic::decode_from(&mut Cursor::new(&dest), 0).expect("decode");
+    assert_eq!(decoded.partition.index, 2);
+    assert_eq!(decoded.partition.leader_epoch, 0);
+    assert_eq!(decoded.max_bytes, 4);
+}
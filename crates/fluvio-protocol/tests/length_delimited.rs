@@ -0,0 +1,96 @@
+use std::io::{Cursor, ErrorKind};
+
+use fluvio_protocol::{DecodeError, Decoder, Encoder};
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct Header {
+    kind: i8,
+    flags: i16,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct HeaderV2 {
+    kind: i8,
+    flags: i16,
+    extension: i32,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct Message {
+    id: i8,
+    #[fluvio(length_delimited)]
+    header: Header,
+    tail: i8,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct MessageV2 {
+    id: i8,
+    #[fluvio(length_delimited)]
+    header: HeaderV2,
+    tail: i8,
+}
+
+#[test]
+fn test_length_delimited() {
+    let message = Message {
+        id: 1,
+        header: Header { kind: 2, flags: 3 },
+        tail: 4,
+    };
+
+    let mut dest = vec![];
+    message.encode(&mut dest, 0).expect("encode");
+
+    assert_eq!(dest, vec![1, 0, 0, 0, 3, 2, 0, 3, 4]);
+    assert_eq!(message.write_size(0), dest.len());
+
+    let decoded = Message::decode_from(&mut Cursor::new(&dest), 0).expect("decode");
+    assert_eq!(decoded, message);
+}
+
+#[test]
+fn test_length_delimited_skips_unknown_bytes() {
+    let newer = MessageV2 {
+        id: 1,
+        header: HeaderV2 {
+            kind: 2,
+            flags: 3,
+            extension: 5,
+        },
+        tail: 4,
+    };
+    let mut dest = vec![];
+    newer.encode(&mut dest, 0).expect("encode");
+
+    let decoded = Message::decode_from(&mut Cursor::new(&dest), 0).expect("decode");
+
+    assert_eq!(
+        decoded,
+        Message {
+            id: 1,
+            header: Header { kind: 2, flags: 3 },
+            tail: 4,
+        }
+    );
+}
+
+#[test]
+fn test_length_delimited_overflow() {
+    for (data, length) in [
+        (vec![1, 0, 0, 0, 100, 2, 0, 3, 4], 100),
+        (vec![1, 255, 255, 255, 255, 2, 0, 3, 4], -1),
+    ] {
+        let err = Message::decode_from(&mut Cursor::new(&data), 0).expect_err("overflow");
+
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert_eq!(
+            err.get_ref()
+                .and_then(|inner| inner.downcast_ref::<DecodeError>()),
+            Some(&DecodeError::LengthDelimitedOverflow {
+                length,
+                available: 4
+            })
+        );
+    }
+}
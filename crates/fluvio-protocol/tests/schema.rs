@@ -0,0 +1,196 @@
+use fluvio_protocol::schema::serde_json::json;
+use fluvio_protocol::core::EncoderVarInt;
+use fluvio_protocol::schema::FluvioSchemaProvider;
+use fluvio_protocol::{Encoder, FluvioSchema};
+
+#[derive(Encoder, FluvioSchema)]
+struct Partition {
+    id: i32,
+    #[fluvio(min_version = 1)]
+    leader: Option<i32>,
+}
+
+#[derive(Encoder, FluvioSchema)]
+struct Topic {
+    #[fluvio(rename = "topicName")]
+    name: String,
+    #[fluvio(min_version = 2, max_version = 4)]
+    partitions: Vec<Partition>,
+}
+
+#[test]
+fn test_struct_schema() {
+    let schema = Topic::schema();
+
+    assert_eq!(schema["title"], "Topic");
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["fieldOrder"], json!(["topicName", "partitions"]));
+    assert_eq!(schema["required"], json!(["topicName", "partitions"]));
+    assert_eq!(
+        schema["properties"]["topicName"],
+        json!({ "type": "string", "minVersion": 0 })
+    );
+
+    let partitions = &schema["properties"]["partitions"];
+    assert_eq!(partitions["minVersion"], 2);
+    assert_eq!(partitions["maxVersion"], 4);
+    assert_eq!(partitions["items"], Partition::schema());
+}
+
+#[test]
+fn test_optional_field() {
+    let schema = Partition::schema();
+
+    assert_eq!(schema["required"], json!(["id"]));
+    assert_eq!(
+        schema["properties"]["leader"],
+        json!({
+            "anyOf": [{ "type": "integer", "format": "int32" }, { "type": "null" }],
+            "minVersion": 1,
+        })
+    );
+    assert!(schema["properties"]["leader"].get("maxVersion").is_none());
+}
+
+#[derive(Encoder, FluvioSchema)]
+struct Offset(i64, #[fluvio(min_version = 3)] u8);
+
+#[derive(Encoder, FluvioSchema)]
+struct Wrapper<M> {
+    inner: M,
+}
+
+#[test]
+fn test_tuple_and_generic_schema() {
+    assert_eq!(
+        Offset::schema(),
+        json!({
+            "title": "Offset",
+            "type": "array",
+            "prefixItems": [
+                { "type": "integer", "format": "int64", "minVersion": 0 },
+                { "type": "integer", "format": "uint8", "minVersion": 3 },
+            ],
+            "items": false,
+        })
+    );
+
+    let schema = Wrapper::<Offset>::schema();
+    assert_eq!(schema["properties"]["inner"]["title"], "Offset");
+}
+
+#[derive(Encoder, FluvioSchema)]
+#[repr(u16)]
+enum Compression {
+    #[fluvio(tag = 0)]
+    None,
+    #[fluvio(tag = 1)]
+    Gzip { level: u8 },
+}
+
+#[derive(Encoder, FluvioSchema)]
+#[fluvio(encode_discriminant)]
+#[repr(i8)]
+enum Isolation {
+    ReadUncommitted = 0,
+    ReadCommitted = -1,
+}
+
+#[test]
+fn test_enum_schema() {
+    let schema = Compression::schema();
+
+    assert_eq!(schema["title"], "Compression");
+    assert_eq!(schema["tagType"], u16::schema());
+    assert_eq!(schema["oneOf"][0], json!({ "title": "None", "tag": 0 }));
+    assert_eq!(schema["oneOf"][1]["tag"], 1);
+    assert_eq!(schema["oneOf"][1]["required"], json!(["level"]));
+
+    // the tag is encoded as the tagType
+    assert_eq!(Compression::None.write_size(0), 2);
+    assert_eq!(Compression::Gzip { level: 9 }.write_size(0), 3);
+
+    let schema = Isolation::schema();
+    assert_eq!(schema["tagType"], i8::schema());
+    assert_eq!(
+        schema["oneOf"][1],
+        json!({ "title": "ReadCommitted", "tag": -1 })
+    );
+    assert_eq!(Isolation::ReadCommitted.write_size(0), 1);
+    assert_eq!(Isolation::ReadUncommitted.write_size(0), 1);
+}
+
+#[derive(Encoder, Default, FluvioSchema)]
+struct Header {
+    id: i32,
+    #[fluvio(min_version = 3)]
+    trace: Option<String>,
+}
+
+#[derive(Encoder, FluvioSchema)]
+struct Message {
+    #[fluvio(min_version = 1)]
+    #[fluvio(flatten)]
+    header: Header,
+    #[varint]
+    timestamp: i64,
+    #[fluvio(varint_zigzag)]
+    error_code: i16,
+    #[fluvio(array_length_encoding = "u16")]
+    keys: Vec<String>,
+    #[fluvio(length_delimited)]
+    value: String,
+    // only ever decoded
+    #[allow(dead_code)]
+    #[fluvio(skip_encoding)]
+    cached_size: u32,
+}
+
+#[test]
+fn test_wire_attributes() {
+    let schema = Message::schema();
+
+    assert_eq!(
+        schema["fieldOrder"],
+        json!(["id", "trace", "timestamp", "error_code", "keys", "value"])
+    );
+    assert_eq!(
+        schema["required"],
+        json!(["id", "timestamp", "error_code", "keys", "value"])
+    );
+    let properties = &schema["properties"];
+    assert!(properties.get("cached_size").is_none());
+    assert!(properties.get("header").is_none());
+    assert_eq!(properties["id"]["minVersion"], 1);
+    assert_eq!(properties["trace"]["minVersion"], 3);
+    assert_eq!(
+        properties["timestamp"],
+        json!({ "type": "integer", "format": "varint", "minVersion": 0 })
+    );
+    assert_eq!(properties["error_code"]["format"], "varint");
+    assert_eq!(properties["keys"]["lengthFormat"], "uint16");
+    assert_eq!(properties["keys"]["items"], String::schema());
+    assert_eq!(
+        properties["value"],
+        json!({ "type": "string", "sizePrefix": "int32", "minVersion": 0 })
+    );
+}
+
+#[derive(Encoder, FluvioSchema)]
+struct Feature {
+    #[fluvio(tags = "kafka-v2", "internal")]
+    name: String,
+    #[fluvio(min_version = 1, tags = "preview")]
+    preview: bool,
+}
+
+#[test]
+fn test_field_tags() {
+    let schema = Feature::schema();
+
+    assert_eq!(
+        schema["properties"]["name"],
+        json!({ "type": "string", "tags": ["kafka-v2", "internal"], "minVersion": 0 })
+    );
+    assert_eq!(schema["properties"]["preview"]["tags"], json!(["preview"]));
+}
@@ -0,0 +1,80 @@
+use std::io::Cursor;
+
+use fluvio_protocol::{Decoder, Encoder};
+
+#[derive(Encoder, Decoder, Default, Debug)]
+struct LegacyRecord {
+    value: i8,
+    #[fluvio(skip_decoding, default = "-1")]
+    legacy: i16,
+    #[fluvio(skip_encoding)]
+    write_only: i8,
+    #[fluvio(skip_decoding)]
+    flags: u8,
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+struct LegacyTuple(i8, #[fluvio(skip_encoding)] i8, i8);
+
+#[test]
+fn test_skip_encoding() {
+    let record = LegacyRecord {
+        value: 3,
+        legacy: 7,
+        write_only: 9,
+        flags: 5,
+    };
+
+    let mut dest = vec![];
+    record.encode(&mut dest, 0).expect("encode");
+
+    assert_eq!(dest, vec![3, 0, 7, 5]);
+    assert_eq!(record.write_size(0), dest.len());
+}
+
+#[test]
+fn test_skip_decoding() {
+    let data = [3, 4, 5];
+
+    let record = LegacyRecord::decode_from(&mut Cursor::new(&data), 0).expect("decode");
+
+    assert_eq!(record.value, 3);
+    assert_eq!(record.legacy, -1);
+    assert_eq!(record.write_only, 4);
+    assert_eq!(record.flags, 0);
+}
+
+#[test]
+fn test_skip_encoding_tuple() {
+    let record = LegacyTuple(1, 2, 3);
+
+    let mut dest = vec![];
+    record.encode(&mut dest, 0).expect("encode");
+
+    assert_eq!(dest, vec![1, 3]);
+    assert_eq!(record.write_size(0), 2);
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+struct ExplicitFlags {
+    #[fluvio(skip_encoding = true)]
+    legacy: i8,
+    #[fluvio(skip_decoding = false)]
+    value: i8,
+}
+
+#[test]
+fn test_skip_flag_values() {
+    let record = ExplicitFlags {
+        legacy: 1,
+        value: 2,
+    };
+
+    let mut dest = vec![];
+    record.encode(&mut dest, 0).expect("encode");
+    assert_eq!(dest, vec![2]);
+
+    let record = ExplicitFlags::decode_from(&mut Cursor::new(&[3, 4]), 0).expect("decode");
+    assert_eq!(record.legacy, 3);
+    assert_eq!(record.value, 4);
+}
@@ -0,0 +1,64 @@
+use std::io::Cursor;
+use std::io::Error;
+
+use fluvio_protocol::{Decoder, Encoder};
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+pub struct OffsetEpochs {
+    latest: (i64, i32),
+    history: Vec<(String, i64)>,
+    error: Option<(i16, String)>,
+    #[fluvio(min_version = 1)]
+    since_v1: (u8, u8, u8),
+}
+
+#[test]
+fn test_tuple_fields_round_trip() -> Result<(), Error> {
+    let value = OffsetEpochs {
+        latest: (100, 3),
+        history: vec![("epoch-1".to_owned(), 10), ("epoch-2".to_owned(), 90)],
+        error: Some((6, "not leader".to_owned())),
+        since_v1: (1, 2, 3),
+    };
+
+    let mut src = vec![];
+    value.encode(&mut src, 1)?;
+    assert_eq!(src.len(), value.write_size(1));
+    // 12 (i64, i32) + 4 + 2 * (2 + 7 + 8) + 1 + 2 + 2 + 10 + 3
+    assert_eq!(src.len(), 68);
+
+    let decoded = OffsetEpochs::decode_from(&mut Cursor::new(&src), 1)?;
+    assert_eq!(decoded, value);
+
+    Ok(())
+}
+
+#[test]
+fn test_tuple_field_version() -> Result<(), Error> {
+    let value = OffsetEpochs {
+        since_v1: (1, 2, 3),
+        ..Default::default()
+    };
+
+    let mut src = vec![];
+    value.encode(&mut src, 0)?;
+    assert_eq!(src.len(), value.write_size(0));
+    assert_eq!(src.len(), 12 + 4 + 1);
+
+    let decoded = OffsetEpochs::decode_from(&mut Cursor::new(&src), 0)?;
+    assert_eq!(decoded.since_v1, (0, 0, 0));
+
+    Ok(())
+}
+
+#[test]
+fn test_tuple_elements_in_order() -> Result<(), Error> {
+    let mut src = vec![];
+    (1_i8, 2_i16, 3_i32, 4_i64, true, 5_u8).encode(&mut src, 0)?;
+    assert_eq!(src, [1, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 4, 1, 5]);
+
+    let decoded = <(i8, i16, i32, i64, bool, u8)>::decode_from(&mut Cursor::new(&src), 0)?;
+    assert_eq!(decoded, (1, 2, 3, 4, true, 5));
+
+    Ok(())
+}
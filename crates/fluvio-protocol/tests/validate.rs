@@ -0,0 +1,84 @@
+use std::io::{Cursor, ErrorKind};
+
+use fluvio_protocol::{Decoder, DecoderVarInt, Encoder, EncoderVarInt};
+
+fn non_negative(value: &i32) -> Result<(), String> {
+    if *value < 0 {
+        Err(format!("{value} is negative"))
+    } else {
+        Ok(())
+    }
+}
+
+mod limits {
+    pub fn small(value: &i64) -> Result<(), String> {
+        if *value > 10 {
+            Err("too large".to_string())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+struct Partition {
+    #[fluvio(validate = "non_negative")]
+    index: i32,
+    #[varint]
+    #[fluvio(validate = "limits::small")]
+    replicas: i64,
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+struct Offset(#[fluvio(validate = "non_negative")] i32);
+
+fn encode(index: i32, replicas: i64) -> Vec<u8> {
+    let mut data = vec![];
+    Partition { index, replicas }
+        .encode(&mut data, 0)
+        .expect("encode");
+    data
+}
+
+#[test]
+fn test_validate_passes() {
+    let data = encode(3, 2);
+
+    let partition = Partition::decode_from(&mut Cursor::new(&data), 0).expect("decode");
+
+    assert_eq!(partition.index, 3);
+    assert_eq!(partition.replicas, 2);
+}
+
+#[test]
+fn test_validate_fails() {
+    let data = encode(-1, 2);
+
+    let err = Partition::decode_from(&mut Cursor::new(&data), 0).expect_err("invalid index");
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert_eq!(
+        err.to_string(),
+        "Invalid value for field <index>: -1 is negative"
+    );
+
+    let data = encode(1, 11);
+    let err = Partition::decode_from(&mut Cursor::new(&data), 0).expect_err("invalid replicas");
+    assert_eq!(
+        err.to_string(),
+        "Invalid value for field <replicas>: too large"
+    );
+}
+
+#[test]
+fn test_validate_tuple_field() {
+    let mut data = vec![];
+    Offset(-5).encode(&mut data, 0).expect("encode");
+
+    let err = Offset::decode_from(&mut Cursor::new(&data), 0).expect_err("invalid offset");
+
+    assert_eq!(
+        err.to_string(),
+        "Invalid value for field <0>: -5 is negative"
+    );
+}
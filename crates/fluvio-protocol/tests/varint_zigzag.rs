@@ -0,0 +1,69 @@
+use std::io::{Cursor, ErrorKind};
+
+use fluvio_protocol::{Decoder, Encoder};
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct ZigzagRecord {
+    #[fluvio(varint_zigzag)]
+    error_code: i16,
+    #[fluvio(varint_zigzag)]
+    offset_delta: i32,
+    #[fluvio(varint_zigzag)]
+    flags: i8,
+    fixed: i16,
+}
+
+#[derive(Encoder, Decoder, Default, Debug, PartialEq)]
+struct ZigzagTuple(#[fluvio(varint_zigzag)] i32, i8);
+
+#[test]
+fn test_varint_zigzag() {
+    let record = ZigzagRecord {
+        error_code: -1,
+        offset_delta: 300,
+        flags: 1,
+        fixed: -1,
+    };
+
+    let mut dest = vec![];
+    record.encode(&mut dest, 0).expect("encode");
+
+    assert_eq!(
+        dest,
+        vec![
+            0x01, // error_code
+            0xD8, 0x04, // offset_delta
+            0x02, // flags
+            0xFF, 0xFF, // fixed
+        ]
+    );
+    assert_eq!(record.write_size(0), dest.len());
+
+    let decoded = ZigzagRecord::decode_from(&mut Cursor::new(&dest), 0).expect("decode");
+    assert_eq!(decoded, record);
+}
+
+#[test]
+fn test_varint_zigzag_tuple() {
+    let record = ZigzagTuple(-2, 7);
+
+    let mut dest = vec![];
+    record.encode(&mut dest, 0).expect("encode");
+
+    assert_eq!(dest, vec![0x03, 7]);
+    assert_eq!(record.write_size(0), 2);
+    assert_eq!(
+        ZigzagTuple::decode_from(&mut Cursor::new(&dest), 0).expect("decode"),
+        record
+    );
+}
+
+#[test]
+fn test_varint_zigzag_out_of_range() {
+    // 1000 doesn't fit in the i8 flags
+    let data = [0x01, 0x02, 0xD0, 0x0F, 0x00, 0x00];
+
+    let err = ZigzagRecord::decode_from(&mut Cursor::new(&data), 0).expect_err("out of range");
+
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
@@ -66,3 +66,114 @@ fn test_decode_version() {
     assert_eq!(record.value2, 0);
     assert_eq!(record.value3, 1); // default, didn't consume
 }
+
+#[derive(Encoder, Decoder, Default, Debug)]
+#[fluvio(trace)]
+struct DeprecatedRecord {
+    value: i8,
+    #[fluvio(min_version = 1, deprecated_since = 2)]
+    #[allow(deprecated)]
+    legacy: i8,
+}
+
+#[test]
+fn test_deprecated_field_still_decoded() {
+    let record = DeprecatedRecord {
+        value: 8,
+        legacy: 3,
+    };
+
+    for version in [1, 2, 3] {
+        let mut dest = vec![];
+        record.encode(&mut dest, version).expect("encode");
+        assert_eq!(dest, [8, 3], "version {version}");
+
+        let decoded =
+            DeprecatedRecord::decode_from(&mut Cursor::new(&dest), version).expect("decode");
+        assert_eq!(decoded.legacy, 3);
+    }
+}
+
+#[derive(Encoder, Default)]
+struct BoundedRecord {
+    #[fluvio(min_version = 2, max_version = 4)]
+    value: i8,
+    #[fluvio(min_version = 3, max_version = 7)]
+    value2: i8,
+}
+
+#[derive(Encoder, Default)]
+struct EmptyRecord {}
+
+#[test]
+fn test_version_bounds() {
+    assert_eq!(TestRecord::VERSION_BOUNDS, (0, i16::MAX));
+    assert_eq!(BoundedRecord::VERSION_BOUNDS, (2, 7));
+    assert_eq!(EmptyRecord::VERSION_BOUNDS, (0, i16::MAX));
+}
+
+mod protocol {
+    pub const MIN_VERSION: i16 = 2;
+}
+
+const MAX_VERSION: i16 = 3;
+
+#[derive(Encoder, Decoder, Default, Debug)]
+struct ConstVersionRecord {
+    value: i8,
+    #[fluvio(min_version = crate::protocol::MIN_VERSION, max_version = MAX_VERSION)]
+    value2: i8,
+}
+
+#[test]
+fn test_const_path_versions() {
+    let record = ConstVersionRecord {
+        value: 1,
+        value2: 2,
+    };
+    assert_eq!(record.write_size(1), 1);
+    assert_eq!(record.write_size(2), 2);
+    assert_eq!(record.write_size(3), 2);
+    assert_eq!(record.write_size(4), 1);
+
+    let data = [0x08, 0x05];
+    let decoded = ConstVersionRecord::decode_from(&mut Cursor::new(&data), 2).expect("decode");
+    assert_eq!(decoded.value2, 5);
+
+    // bounds given as paths are not known to the derive, the field is left out
+    assert_eq!(ConstVersionRecord::VERSION_BOUNDS, (0, i16::MAX));
+}
+
+mod versions {
+    pub const fn max_version() -> i16 {
+        super::MAX_VERSION + 1
+    }
+}
+
+const fn min_version() -> i16 {
+    protocol::MIN_VERSION
+}
+
+#[derive(Encoder, Decoder, Default, Debug)]
+struct ConstFnVersionRecord {
+    value: i8,
+    #[fluvio(min_version = "min_version()", max_version = crate::versions::max_version())]
+    value2: i8,
+}
+
+#[test]
+fn test_const_fn_versions() {
+    let record = ConstFnVersionRecord {
+        value: 1,
+        value2: 2,
+    };
+    assert_eq!(record.write_size(1), 1);
+    assert_eq!(record.write_size(2), 2);
+    assert_eq!(record.write_size(4), 2);
+    assert_eq!(record.write_size(5), 1);
+
+    let data = [0x08, 0x05];
+    let decoded = ConstFnVersionRecord::decode_from(&mut Cursor::new(&data), 4).expect("decode");
+    assert_eq!(decoded.value2, 5);
+    assert_eq!(ConstFnVersionRecord::VERSION_BOUNDS, (0, i16::MAX));
+}
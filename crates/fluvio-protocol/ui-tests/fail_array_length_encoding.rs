@@ -0,0 +1,9 @@
+use fluvio_protocol::{Decoder, Encoder};
+
+fn main() {}
+
+#[derive(Encoder, Decoder, Default)]
+struct FailArrayLengthEncoding {
+    #[fluvio(array_length_encoding = "u64")]
+    values: Vec<u8>,
+}
@@ -0,0 +1,9 @@
+use fluvio_protocol::Encoder;
+
+fn main() {}
+
+#[derive(Encoder, Default)]
+struct FailAttributeValueList {
+    #[fluvio(rename = "topic", "name")]
+    topic: String,
+}
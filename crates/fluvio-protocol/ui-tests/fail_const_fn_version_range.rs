@@ -0,0 +1,13 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+const fn min_version() -> i16 {
+    5
+}
+
+#[derive(Decoder, Default)]
+struct FailConstFnVersionRange {
+    #[fluvio(min_version = "min_version()", max_version = 3)]
+    added: i8,
+}
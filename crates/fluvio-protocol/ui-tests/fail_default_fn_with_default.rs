@@ -0,0 +1,13 @@
+use fluvio_protocol::FluvioDefault;
+
+fn main() {}
+
+fn make_default() -> i32 {
+    1
+}
+
+#[derive(FluvioDefault)]
+struct FailDefaultFnWithDefault {
+    #[fluvio(default = "2", default_fn = "make_default")]
+    value: i32,
+}
@@ -0,0 +1,9 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailDeprecatedBeforeMinVersion {
+    #[fluvio(min_version = 3, deprecated_since = 2)]
+    legacy: i8,
+}
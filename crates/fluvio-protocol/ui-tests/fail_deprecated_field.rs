@@ -0,0 +1,12 @@
+#![deny(deprecated)]
+
+use fluvio_protocol::Encoder;
+
+fn main() {}
+
+#[derive(Encoder, Default)]
+struct FailDeprecatedField {
+    value: i8,
+    #[fluvio(min_version = 1, deprecated_since = 2)]
+    legacy: i8,
+}
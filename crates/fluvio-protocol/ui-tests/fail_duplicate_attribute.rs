@@ -0,0 +1,10 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailDuplicateAttribute {
+    #[fluvio(min_version = 1)]
+    #[fluvio(min_version = 2, max_version = 5)]
+    added: i8,
+}
@@ -0,0 +1,9 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailFlattenPrimitive {
+    #[fluvio(flatten)]
+    count: i32,
+}
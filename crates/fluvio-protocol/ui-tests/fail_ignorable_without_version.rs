@@ -0,0 +1,9 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailIgnorableWithoutVersion {
+    #[fluvio(ignorable)]
+    value: u16,
+}
@@ -0,0 +1,10 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailLengthDelimitedVarint {
+    #[varint]
+    #[fluvio(length_delimited)]
+    offset: i64,
+}
@@ -0,0 +1,9 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailMaxVersionWithoutMinVersion {
+    #[fluvio(max_version = 4)]
+    legacy: i8,
+}
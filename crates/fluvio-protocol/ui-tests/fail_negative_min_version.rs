@@ -0,0 +1,9 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailNegativeMinVersion {
+    #[fluvio(min_version = -1, max_version = 5)]
+    legacy: i8,
+}
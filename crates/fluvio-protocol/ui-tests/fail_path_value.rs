@@ -0,0 +1,11 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+const NAME: &str = "throttleTimeMs";
+
+#[derive(Decoder, Default)]
+struct FailPathValue {
+    #[fluvio(rename = NAME)]
+    throttle_time_ms: i32,
+}
@@ -0,0 +1,6 @@
+use fluvio_protocol::Encoder;
+
+fn main() {}
+
+#[derive(Encoder)]
+struct FailRenameTupleField(#[fluvio(rename = "value")] u16);
@@ -0,0 +1,9 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailValidateNotAPath {
+    #[fluvio(validate = "|value| value > 0")]
+    value: u16,
+}
@@ -0,0 +1,9 @@
+use fluvio_protocol::Encoder;
+
+fn main() {}
+
+#[derive(Encoder, Default)]
+struct FailVarintZigzagUnsigned {
+    #[fluvio(varint_zigzag)]
+    count: u32,
+}
@@ -0,0 +1,9 @@
+use fluvio_protocol::Decoder;
+
+fn main() {}
+
+#[derive(Decoder, Default)]
+struct FailVersionNotLiteral {
+    #[fluvio(min_version = "latest")]
+    added: i8,
+}
@@ -86,7 +86,7 @@ pub struct SmartModuleInput {
     raw_bytes: Vec<u8>,
     /// This is deprecrated, extra parameters should not be passed, they will be removed in the future
     #[deprecated]
-    #[fluvio(max_version = 22)]
+    #[fluvio(min_version = 0, max_version = 22)]
     params: SmartModuleExtraParams,
     #[fluvio(min_version = 16, max_version = 22)]
     join_record: Vec<u8>,
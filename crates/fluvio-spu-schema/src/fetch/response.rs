@@ -22,6 +22,16 @@ pub struct FetchResponse<R> {
     pub topics: Vec<FetchableTopicResponse<R>>,
 }
 
+impl<R: RecordsSize> FetchResponse<R> {
+    /// Record bytes returned for each topic, in response order
+    pub fn size_by_topic(&self) -> Vec<(String, usize)> {
+        self.topics
+            .iter()
+            .map(|topic| (topic.name.clone(), topic.total_records_size()))
+            .collect()
+    }
+}
+
 impl<R> FetchResponse<R> {
     pub fn find_partition(
         self,
@@ -52,6 +62,16 @@ pub struct FetchableTopicResponse<R> {
     pub data: PhantomData<R>,
 }
 
+impl<R: RecordsSize> FetchableTopicResponse<R> {
+    /// Record bytes returned for all partitions of this topic
+    pub fn total_records_size(&self) -> usize {
+        self.partitions
+            .iter()
+            .map(|partition| partition.records_size())
+            .sum()
+    }
+}
+
 #[derive(Encoder, Decoder, FluvioDefault, Debug)]
 pub struct FetchablePartitionResponse<R> {
     /// The partition index.
@@ -78,6 +98,13 @@ pub struct FetchablePartitionResponse<R> {
     pub records: R,
 }
 
+impl<R: RecordsSize> FetchablePartitionResponse<R> {
+    /// Encoded size of the record batches in this partition
+    pub fn records_size(&self) -> usize {
+        self.records.records_size()
+    }
+}
+
 impl<R: BatchRecords> FetchablePartitionResponse<RecordSet<R>> {
     /// offset that will be use for fetching rest of offsets
     /// this will be 1 greater than last offset of previous query
@@ -91,6 +118,18 @@ impl<R: BatchRecords> FetchablePartitionResponse<RecordSet<R>> {
     }
 }
 
+/// Record data whose encoded size is known without encoding it
+pub trait RecordsSize {
+    /// Size in bytes of the encoded record batches, excluding the length prefix
+    fn records_size(&self) -> usize;
+}
+
+impl<R: BatchRecords> RecordsSize for RecordSet<R> {
+    fn records_size(&self) -> usize {
+        self.batches.iter().map(|batch| batch.write_size(0)).sum()
+    }
+}
+
 #[derive(Encoder, Decoder, FluvioDefault, Debug)]
 pub struct AbortedTransaction {
     pub producer_id: i64,
@@ -108,6 +147,23 @@ impl<R> FetchResponse<R> {
     {
         self.topics.iter().find(|&r_topic| r_topic.name == *topic)
     }
+
+    /// Topic name, partition index and error code of every partition that returned an error
+    pub fn partition_errors(&self) -> impl Iterator<Item = (&str, PartitionId, &ErrorCode)> {
+        self.topics.iter().flat_map(|topic| {
+            topic
+                .partitions
+                .iter()
+                .filter(|partition| partition.error_code.is_error())
+                .map(|partition| {
+                    (
+                        topic.name.as_str(),
+                        partition.partition_index,
+                        &partition.error_code,
+                    )
+                })
+        })
+    }
 }
 
 #[cfg(feature = "file")]
@@ -134,6 +190,12 @@ mod file {
     pub type FileTopicResponse = FetchableTopicResponse<FileRecordSet>;
     pub type FilePartitionResponse = FetchablePartitionResponse<FileRecordSet>;
 
+    impl RecordsSize for FileRecordSet {
+        fn records_size(&self) -> usize {
+            self.len()
+        }
+    }
+
     impl FileWrite for FilePartitionResponse {
         fn file_encode(
             &self,
@@ -191,3 +253,112 @@ mod file {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use fluvio_protocol::record::{Batch, RawRecords, Record};
+
+    use super::*;
+
+    fn records(count: usize) -> RecordSet {
+        let mut batch = Batch::default();
+        for i in 0..count {
+            batch.add_record(Record::new(format!("value {i}")));
+        }
+        RecordSet::default().add(batch)
+    }
+
+    fn partition(
+        partition_index: PartitionId,
+        count: usize,
+    ) -> FetchablePartitionResponse<RecordSet> {
+        FetchablePartitionResponse {
+            partition_index,
+            records: records(count),
+            ..Default::default()
+        }
+    }
+
+    /// three partitions of different sizes spread over two topics
+    fn response() -> DefaultFetchResponse {
+        DefaultFetchResponse {
+            topics: vec![
+                FetchableTopicResponse {
+                    name: "one".to_string(),
+                    partitions: vec![partition(0, 1), partition(1, 5)],
+                    ..Default::default()
+                },
+                FetchableTopicResponse {
+                    name: "two".to_string(),
+                    partitions: vec![partition(0, 20)],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    /// record bytes actually written for a partition, without the length prefix
+    fn encoded_records_size(partition: &FetchablePartitionResponse<RecordSet>) -> usize {
+        let mut out = vec![];
+        partition.records.encode(&mut out, 0).expect("encode");
+        out.len() - 4
+    }
+
+    #[test]
+    fn test_records_size_matches_encoding() {
+        let response = response();
+
+        let mut sizes = vec![];
+        for topic in &response.topics {
+            let mut total = 0;
+            for partition in &topic.partitions {
+                let encoded = encoded_records_size(partition);
+                assert_eq!(partition.records_size(), encoded);
+                total += encoded;
+            }
+            assert_eq!(topic.total_records_size(), total);
+            sizes.push((topic.name.clone(), total));
+        }
+
+        assert!(sizes[0].1 > 0 && sizes[0].1 < sizes[1].1);
+        assert_eq!(response.size_by_topic(), sizes);
+    }
+
+    #[test]
+    fn test_partition_errors() {
+        let mut response = response();
+        assert_eq!(response.partition_errors().count(), 0);
+
+        response.topics[0].partitions[1].error_code = ErrorCode::OffsetOutOfRange;
+        response.topics[1].partitions[0].error_code = ErrorCode::NotLeaderForPartition;
+
+        let errors: Vec<_> = response.partition_errors().collect();
+        assert_eq!(
+            errors,
+            vec![
+                ("one", 1, &ErrorCode::OffsetOutOfRange),
+                ("two", 0, &ErrorCode::NotLeaderForPartition),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_raw_records_size_matches_decoded() {
+        let response = response();
+        let mut out = vec![];
+        response.encode(&mut out, 11).expect("encode");
+
+        let raw = FetchResponse::<RecordSet<RawRecords>>::decode_from(&mut Cursor::new(out), 11)
+            .expect("decode");
+
+        assert_eq!(raw.size_by_topic(), response.size_by_topic());
+        let raw_partition = &raw.topics[0].partitions[1];
+        assert_eq!(
+            raw_partition.records_size(),
+            encoded_records_size(&response.topics[0].partitions[1])
+        );
+    }
+}
@@ -59,6 +59,7 @@ pub const CLI_DEFAULT_PROFILE: &str = "default";
 pub const CLI_CONFIG_PATH: &str = ".fluvio";
 pub const TLS_CLIENT_SECRET_NAME: &str = "fluvio-client-tls";
 pub const TLS_SERVER_SECRET_NAME: &str = "fluvio-tls";
+pub const TLS_CA_SECRET_NAME: &str = "fluvio-ca";
 
 // Env
 pub const FLV_FLUVIO_HOME: &str = "FLUVIO_HOME";
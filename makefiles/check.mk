@@ -42,6 +42,7 @@ run-all-unit-test: install_rustup_target
 	cargo test -p fluvio-connector-common --all-features $(BUILD_FLAGS) 
 	cargo test -p fluvio-connector-package $(BUILD_FLAGS) 
 	cargo test -p fluvio-controlplane-metadata --features=smartmodule $(BUILD_FLAGS)
+	cargo test -p fluvio-cluster --lib --no-default-features $(BUILD_FLAGS)
 	make test-all -C crates/fluvio-protocol
 
 run-integration-test: build_smartmodules install_rustup_target